@@ -1,14 +1,31 @@
 use clap::Parser;
 use lib::{
-    dag_creator::create_dag_set_from_dir,
-    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    admission_control::OnlineAdmission,
+    analysis::calculate_response_time_bounds,
+    cause_effect_chain::load_cause_effect_chains,
+    dag_creator::{create_dag_set_from_dir, dump_annotated_dag_set_yamls, get_experiment_seed},
+    dag_set_scheduler::{DAGSetSchedulerBase, DeadlineMissPolicy, PreemptiveType},
+    energy_accounting::{IdleEnergyPolicy, PowerModel},
+    error::Result,
+    event_script::EventScript,
     global_edf_scheduler::GlobalEDFScheduler,
-    graph_extension::GraphExtension,
     homogeneous::HomogeneousProcessor,
-    log::dump_dag_set_scheduler_result_to_yaml,
+    log::{
+        dump_dag_set_scheduler_result_to_yaml, dump_run_time_to_yaml,
+        dump_schedulability_verdicts_to_yaml, print_result_summary, OutputFormat, RunTimeLog,
+    },
+    logical_execution_time::CommunicationSemantics,
+    network::Network,
     processor::ProcessorBase,
-    util::{adjust_to_implicit_deadline, load_yaml},
+    run_metadata::RunMetadata,
+    schedulability_test::test_all,
+    simulation_horizon::{HorizonEnd, SimulationHorizon},
+    sporadic_release::ReleaseJitter,
+    tardiness_analysis::calculate_tardiness_bounds,
+    util::adjust_to_implicit_deadline,
+    visualization::dump_gantt_svg,
 };
+use log::warn;
 
 #[derive(Parser)]
 #[clap(
@@ -32,16 +49,180 @@ struct ArgParser {
     ///Enable or disable preemptive scheduling.
     #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
     enable_preemption: bool,
+    ///Path to event script file for what-if scenarios.
+    #[clap(short = 'e', long = "event_script_path")]
+    event_script_path: Option<String>,
+    ///Seed for drawing the release jitters and the execution times of nodes with bcet, acet or execution time samples.
+    #[clap(short = 's', long = "execution_time_seed")]
+    execution_time_seed: Option<u64>,
+    ///Repetition index of the run in a sweep. The seed is derived from it and the DAG file names unless given.
+    #[clap(short = 'r', long = "repetition_index")]
+    repetition_index: Option<u64>,
+    ///Action on a deadline miss: continue, abort, kill_job or skip_next_release.
+    #[clap(short = 'm', long = "deadline_miss_policy", default_value = "continue")]
+    deadline_miss_policy: String,
+    ///Communication semantics of the nodes: implicit, or let for the logical execution time of each node.
+    #[clap(
+        long = "communication_semantics",
+        alias = "communication-semantics",
+        default_value = "implicit"
+    )]
+    communication_semantics: String,
+    ///Number of hyper periods to simulate.
+    #[clap(short = 'n', long = "num_hyper_periods", default_value = "1")]
+    num_hyper_periods: i64,
+    ///Time before which released jobs are excluded from the response time statistics.
+    #[clap(short = 'w', long = "warm_up_time", default_value = "0")]
+    warm_up_time: i64,
+    ///Path to the network of processors for distributed scheduling. Its total number of cores must equal number_of_cores.
+    #[clap(long = "network_file_path", alias = "network-file-path")]
+    network_file_path: Option<String>,
+    ///Path to the online DAG arrivals whose admissions are decided by the admission test in the file.
+    #[clap(
+        long = "online_admission_file_path",
+        alias = "online-admission-file-path"
+    )]
+    online_admission_file_path: Option<String>,
+    ///Decompose the end-to-end deadlines into the sub-deadlines of the nodes assigned to the processors of the network.
+    #[clap(
+        long = "decompose_deadlines",
+        alias = "decompose-deadlines",
+        default_value = "false"
+    )]
+    decompose_deadlines: bool,
+    ///Path to the cause-effect chains whose maximum data age and reaction time are logged.
+    #[clap(long = "chain_file_path")]
+    chain_file_path: Option<String>,
+    ///Record the busy and idle intervals of each core in the log.
+    #[clap(
+        long = "core_timeline",
+        alias = "core-timeline",
+        default_value = "false"
+    )]
+    core_timeline: bool,
+    ///Non-negative clock drift of each core in ppm, separated by commas. Cores beyond the list have no drift.
+    #[clap(
+        long = "clock_drift_ppm",
+        alias = "clock-drift-ppm",
+        value_delimiter = ',',
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    clock_drift_ppm: Vec<i64>,
+    ///Write the analytical response time bounds of each DAG into the log.
+    #[clap(
+        long = "response_time_bounds",
+        alias = "response-time-bounds",
+        default_value = "false"
+    )]
+    response_time_bounds: bool,
+    ///Also dump the verdicts of the analytic schedulability tests.
+    #[clap(
+        long = "schedulability_tests",
+        alias = "schedulability-tests",
+        default_value = "false"
+    )]
+    schedulability_tests: bool,
+    ///Power of a core while it executes a job. Enables the energy accounting of each DAG and job.
+    #[clap(long = "active_power", alias = "active-power")]
+    active_power: Option<f64>,
+    ///Power of a core while it is idle.
+    #[clap(long = "idle_power", alias = "idle-power", default_value = "0.0")]
+    idle_power: f64,
+    ///Attribution of the idle energy to the DAGs: unattributed, proportional or equal.
+    #[clap(
+        long = "idle_energy_policy",
+        alias = "idle-energy-policy",
+        default_value = "unattributed"
+    )]
+    idle_energy_policy: String,
+    ///Render the schedule as an SVG Gantt chart next to the log.
+    #[clap(long = "render_gantt", alias = "render-gantt", default_value = "false")]
+    render_gantt: bool,
+    ///Directory to which the input DAGs annotated with the schedule of their first job are written.
+    #[clap(long = "annotated_dag_dir_path", alias = "annotated-dag-dir-path")]
+    annotated_dag_dir_path: Option<String>,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
 }
 
-fn main() {
+fn main() -> Result<()> {
     let arg: ArgParser = ArgParser::parse();
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
 
-    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
-    adjust_to_implicit_deadline(&mut dag_set);
+    let mut run_time_log = RunTimeLog::default();
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path)?;
+    run_time_log.measure_preprocessing(|| adjust_to_implicit_deadline(&mut dag_set));
+    let network = arg
+        .network_file_path
+        .as_ref()
+        .map(|network_file_path| -> Result<Network> {
+            let network = Network::from_yaml_file(network_file_path)?;
+            if network.get_number_of_cores() != arg.number_of_cores {
+                panic!(
+                    "The network has {} cores, but number_of_cores is {}.",
+                    network.get_number_of_cores(),
+                    arg.number_of_cores
+                );
+            }
+            Ok(network)
+        })
+        .transpose()?;
+    if arg.decompose_deadlines {
+        let network = network
+            .as_ref()
+            .expect("decompose_deadlines requires network_file_path.");
+        run_time_log.measure_preprocessing(|| {
+            for dag in dag_set.iter_mut() {
+                network.decompose_end_to_end_deadline(dag);
+            }
+        });
+    }
 
-    let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
+    let mut homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
+    let seed = match (arg.execution_time_seed, arg.repetition_index) {
+        (Some(seed), _) => Some(seed),
+        (None, Some(repetition_index)) => {
+            Some(get_experiment_seed(&arg.dag_dir_path, repetition_index)?)
+        }
+        (None, None) => None,
+    };
+    if let Some(seed) = seed {
+        homogeneous_processor.set_execution_time_seed(seed);
+    }
+    for (core_id, clock_drift_ppm) in arg.clock_drift_ppm.iter().enumerate() {
+        homogeneous_processor.set_clock_drift(core_id, *clock_drift_ppm);
+    }
     let mut gedf_scheduler = GlobalEDFScheduler::new(&dag_set, &homogeneous_processor);
+    if let Some(seed) = seed {
+        gedf_scheduler.set_release_jitter(ReleaseJitter::from_seed(seed));
+    }
+    if let Some(event_script_path) = &arg.event_script_path {
+        gedf_scheduler.set_event_script(EventScript::from_yaml_file(event_script_path)?);
+    }
+    gedf_scheduler
+        .set_deadline_miss_policy(DeadlineMissPolicy::from_name(&arg.deadline_miss_policy)?);
+    gedf_scheduler.set_communication_semantics(CommunicationSemantics::from_name(
+        &arg.communication_semantics,
+    )?);
+    gedf_scheduler.set_simulation_horizon(SimulationHorizon::new(
+        HorizonEnd::HyperPeriods(arg.num_hyper_periods),
+        arg.warm_up_time,
+    ));
+    if let Some(network) = network {
+        gedf_scheduler.set_network(network);
+    }
+    if let Some(online_admission_file_path) = &arg.online_admission_file_path {
+        gedf_scheduler
+            .set_online_admission(OnlineAdmission::from_yaml_file(online_admission_file_path)?)?;
+    }
+    if arg.core_timeline {
+        gedf_scheduler.get_log_mut().enable_core_timeline();
+    }
 
     // Change whether it is preemptive or not depending on the argument.
     let (preemptive_type, file_name) = if arg.enable_preemption {
@@ -56,23 +237,89 @@ fn main() {
     };
 
     // To make it preemptive, rename the second argument of dump_log.
-    gedf_scheduler.schedule(preemptive_type);
+    let schedule_outcome =
+        run_time_log.measure_simulation(|| gedf_scheduler.schedule(preemptive_type))?;
+    // The tardiness bound is derived for preemptive G-EDF only.
+    if arg.enable_preemption {
+        match calculate_tardiness_bounds(&dag_set, arg.number_of_cores) {
+            Some(tardiness_bounds) => gedf_scheduler
+                .get_log_mut()
+                .write_tardiness_bounds(&tardiness_bounds),
+            None => warn!("The tardiness is unbounded because the total utilization exceeds the number of cores."),
+        }
+    }
+    if arg.response_time_bounds {
+        gedf_scheduler
+            .get_log_mut()
+            .write_response_time_bounds(&calculate_response_time_bounds(
+                &dag_set,
+                arg.number_of_cores,
+            ));
+    }
+    if let Some(chain_file_path) = &arg.chain_file_path {
+        for chain in load_cause_effect_chains(chain_file_path) {
+            chain.validate(&dag_set);
+            let (max_data_age, max_reaction_time) =
+                chain.calculate_latencies(&schedule_outcome.log);
+            gedf_scheduler.get_log_mut().write_chain_latency(
+                &chain.name,
+                max_data_age,
+                max_reaction_time,
+            );
+        }
+    }
+    if let Some(active_power) = arg.active_power {
+        let energy_log = PowerModel::new(active_power, arg.idle_power).calculate_energy(
+            &schedule_outcome.log,
+            schedule_outcome.schedule_length,
+            IdleEnergyPolicy::from_name(&arg.idle_energy_policy)?,
+        );
+        gedf_scheduler.get_log_mut().write_energy_log(energy_log);
+    }
+    let mut run_metadata = RunMetadata::new("global_edf", arg.number_of_cores);
+    run_metadata.execution_time_seed = seed;
+    run_metadata.add_input_dir(&arg.dag_dir_path)?;
+    for input_file_path in [
+        &arg.event_script_path,
+        &arg.network_file_path,
+        &arg.online_admission_file_path,
+        &arg.chain_file_path,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        run_metadata.add_input_file(input_file_path)?;
+    }
+    gedf_scheduler
+        .get_log_mut()
+        .write_run_metadata(run_metadata);
     let file_path = gedf_scheduler.dump_log(&arg.output_dir_path, file_name);
+    if arg.render_gantt {
+        dump_gantt_svg(&schedule_outcome.log, &file_path.replace(".yaml", ".svg"));
+    }
+    if let Some(annotated_dag_dir_path) = &arg.annotated_dag_dir_path {
+        dump_annotated_dag_set_yamls(
+            &arg.dag_dir_path,
+            &schedule_outcome.log,
+            0,
+            annotated_dag_dir_path,
+        )?;
+    }
 
     // Check the result
-    let yaml_doc = &load_yaml(&file_path)[0];
-    let dag_set_log = &yaml_doc["dag_set_log"];
-    let mut result = true;
-    for dag in dag_set {
-        if dag_set_log[dag.get_dag_param("dag_id") as usize]["worst_response_time"]
-            .as_i64()
-            .unwrap()
-            > dag.get_head_period().unwrap() as i64
-        {
-            result = false;
-            break;
-        }
-    }
+    let result = !schedule_outcome.has_deadline_miss();
 
     dump_dag_set_scheduler_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    if arg.schedulability_tests {
+        dump_schedulability_verdicts_to_yaml(&file_path, &test_all(&dag_set, arg.number_of_cores));
+    }
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_outcome.schedule_length),
+        &schedule_outcome.worst_response_times,
+    );
+    Ok(())
 }
@@ -0,0 +1,70 @@
+//! Runs of federated scheduling from the arguments of the command line,
+//! which are shared by the binary of this crate and the sched_sim binary.
+use crate::{
+    federated::{self, FederateResult},
+    outputs_result::{
+        dump_dag_set_info_to_yaml, dump_federated_result_to_yaml, dump_processor_info_to_yaml,
+    },
+};
+use lib::{
+    dag_set_scheduler::DAGSetSchedulerBase,
+    error::Result,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    log::{dump_run_time_to_yaml, print_result_summary, OutputFormat, RunTimeLog},
+    processor::ProcessorBase,
+    run_metadata::RunMetadata,
+    util::create_scheduler_log_yaml,
+};
+use petgraph::Graph;
+
+/// Analyze the DAG set by federated scheduling, and simulate a schedulable result if `simulate` is set.
+/// Returns whether the DAG set is schedulable, or whether no deadline is missed in the simulation.
+pub fn run_federated(
+    mut dag_set: Vec<Graph<NodeData, i64>>,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    output_format: OutputFormat,
+    run_metadata: RunMetadata,
+    enable_task_splitting: bool,
+    simulate: bool,
+) -> Result<bool> {
+    // Federated scheduling is an analysis, so the whole algorithm is counted as pre-processing.
+    let mut run_time_log = RunTimeLog::default();
+    let result = run_time_log.measure_preprocessing(|| {
+        if enable_task_splitting {
+            federated::semi_partitioned_federated(&mut dag_set, number_of_cores)
+        } else {
+            federated::federated(&mut dag_set, number_of_cores)
+        }
+    });
+    let is_schedulable = matches!(result, FederateResult::Schedulable { .. });
+    if simulate && is_schedulable {
+        let (mut scheduler, schedule_outcome) = run_time_log.measure_simulation(|| {
+            federated::simulate_federated(&mut dag_set, number_of_cores, &result)
+        })?;
+        scheduler.get_log_mut().write_run_metadata(run_metadata);
+        let file_path = scheduler.dump_log(output_dir_path, "federated");
+        dump_federated_result_to_yaml(&file_path, result);
+        dump_run_time_to_yaml(&file_path, &run_time_log);
+        let result = !schedule_outcome.has_deadline_miss();
+        print_result_summary(
+            output_format,
+            &file_path,
+            result,
+            Some(schedule_outcome.schedule_length),
+            &schedule_outcome.worst_response_times,
+        );
+        return Ok(result);
+    }
+    let file_path = create_scheduler_log_yaml(output_dir_path, "federated");
+    run_metadata.dump_to_yaml(&file_path);
+    let homogeneous_processor = HomogeneousProcessor::new(number_of_cores);
+    dump_dag_set_info_to_yaml(&file_path, dag_set);
+    dump_processor_info_to_yaml(&file_path, &homogeneous_processor);
+    dump_federated_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    // Without the simulation, there is no schedule to summarize.
+    print_result_summary(output_format, &file_path, is_schedulable, None, &[]);
+    Ok(is_schedulable)
+}
@@ -1,6 +1,27 @@
 //! This module implements the federated scheduling algorithm.
-use lib::graph_extension::{GraphExtension, NodeData};
-use petgraph::graph::Graph;
+//! Each high-utilization DAG is assigned its own dedicated cores.
+//! Each low-utilization DAG is stretched to a sequential task with its volume as the WCET,
+//! and the sequential tasks are scheduled by partitioned EDF on the remaining cores.
+//!
+//! With task splitting, a sequential task that fits on no core is split into two parts in topological order,
+//! and the second part migrates to another core as a semi-partitioned task.
+//!
+//! The core assignment can also be simulated: the nodes are pinned to their cores by `allowed_cores`
+//! and scheduled by preemptive EDF, which is global on the dedicated cores of a high-utilization DAG
+//! and partitioned on the remaining cores.
+use lib::{
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType, ScheduleOutcome},
+    error::{Result, SchedSimError},
+    global_edf_scheduler::GlobalEDFScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    processor::ProcessorBase,
+    util::get_hyper_period,
+};
+use petgraph::{
+    algo::toposort,
+    graph::{Graph, NodeIndex},
+};
 use serde_derive::{Deserialize, Serialize};
 use FederateResult::{Schedulable, Unschedulable};
 
@@ -10,6 +31,17 @@ pub enum FederateResult {
     Schedulable {
         high_dedicated_cores: usize,
         low_dedicated_cores: usize,
+        /// The high-utilization DAGs and their numbers of dedicated cores, in the order of the DAG ids.
+        #[serde(default)]
+        heavy_dags: Vec<HeavyDAG>,
+        /// The ids of the low-utilization DAGs, which are stretched to sequential tasks.
+        #[serde(default)]
+        light_dag_ids: Vec<usize>,
+        /// The DAG ids of the stretched low-utilization DAGs on each of the remaining cores in use.
+        low_core_partition: Vec<Vec<usize>>,
+        /// The DAGs split over two of the remaining cores, only with task splitting.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        split_dags: Vec<SplitDAG>,
     },
     Unschedulable {
         reason: String,
@@ -17,6 +49,175 @@ pub enum FederateResult {
     },
 }
 
+/// A high-utilization DAG executed on its own dedicated cores.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct HeavyDAG {
+    pub dag_id: usize,
+    pub dedicated_cores: usize,
+}
+
+/// A stretched DAG whose jobs start on `body_core` and migrate to `tail_core` for the remaining nodes.
+/// Each part is accounted for by its share of the utilization on its core. As with EDF-os,
+/// the split DAG has bounded tardiness rather than a hard guarantee since the tail waits for the body.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SplitDAG {
+    pub dag_id: usize,
+    pub body_core: usize,
+    pub tail_core: usize,
+    pub migrated_node_ids: Vec<usize>,
+    pub migrations_per_hyper_period: i64,
+}
+
+/// A low-utilization DAG executed sequentially on a single core.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct StretchedTask {
+    dag_id: usize,
+    execution_time: i64,
+    period: i64,
+}
+
+impl StretchedTask {
+    fn get_utilization(&self) -> f64 {
+        self.execution_time as f64 / self.period as f64
+    }
+}
+
+/// Stretch the DAG to a sequential task, which is feasible since its volume does not exceed the period.
+fn stretch_dag(dag_id: usize, dag: &Graph<NodeData, i64>) -> StretchedTask {
+    StretchedTask {
+        dag_id,
+        execution_time: dag.get_volume(),
+        period: dag.get_head_period().unwrap(),
+    }
+}
+
+fn sort_by_decreasing_utilization(tasks: &[StretchedTask]) -> Vec<StretchedTask> {
+    let mut sorted_tasks = tasks.to_vec();
+    sorted_tasks.sort_by(|a, b| {
+        b.get_utilization()
+            .partial_cmp(&a.get_utilization())
+            .unwrap()
+            .then(a.dag_id.cmp(&b.dag_id))
+    });
+    sorted_tasks
+}
+
+/// Partition the tasks onto cores by first-fit decreasing utilization.
+/// EDF schedules the tasks on a core if their total utilization does not exceed 1.
+fn partition_by_first_fit_decreasing(tasks: &[StretchedTask]) -> Vec<Vec<usize>> {
+    let mut core_utilizations: Vec<f64> = Vec::new();
+    let mut partition: Vec<Vec<usize>> = Vec::new();
+    for task in sort_by_decreasing_utilization(tasks) {
+        let utilization = task.get_utilization();
+        match core_utilizations
+            .iter()
+            .position(|core_utilization| core_utilization + utilization <= 1.0)
+        {
+            Some(core_i) => {
+                core_utilizations[core_i] += utilization;
+                partition[core_i].push(task.dag_id);
+            }
+            None => {
+                core_utilizations.push(utilization);
+                partition.push(vec![task.dag_id]);
+            }
+        }
+    }
+    partition
+}
+
+/// Split the task in topological order so that the body fits on the core with the most remaining utilization
+/// and the tail fits on another core. The body is kept as long as possible to minimize the migrated work.
+/// Returns the body core, the tail core, the migrated nodes and the utilizations of the body and the tail.
+fn split_task(
+    task: &StretchedTask,
+    dag: &Graph<NodeData, i64>,
+    core_utilizations: &[f64],
+) -> Option<(usize, usize, Vec<NodeIndex>, f64, f64)> {
+    let (body_core, body_core_utilization) = core_utilizations
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())?;
+    let mut sorted_nodes = toposort(dag, None).unwrap();
+    // Skip the dummy nodes added for the critical path.
+    sorted_nodes.retain(|node_i| !dag[*node_i].params.contains_key("dummy"));
+
+    let body_len = (1..sorted_nodes.len()).rev().find(|body_len| {
+        let body_utilization =
+            dag.get_total_wcet_from_nodes(&sorted_nodes[..*body_len]) as f64 / task.period as f64;
+        body_core_utilization + body_utilization <= 1.0
+    })?;
+    let body_utilization =
+        dag.get_total_wcet_from_nodes(&sorted_nodes[..body_len]) as f64 / task.period as f64;
+    let tail_nodes = sorted_nodes[body_len..].to_vec();
+    let tail_utilization =
+        dag.extract_subgraph(&tail_nodes).get_volume() as f64 / task.period as f64;
+    let tail_core =
+        core_utilizations
+            .iter()
+            .enumerate()
+            .position(|(core_i, core_utilization)| {
+                core_i != body_core && core_utilization + tail_utilization <= 1.0
+            })?;
+    Some((
+        body_core,
+        tail_core,
+        tail_nodes,
+        body_utilization,
+        tail_utilization,
+    ))
+}
+
+/// Partition the tasks by first-fit decreasing utilization onto at most `number_of_cores` cores,
+/// splitting the tasks that fit on no core once all the cores are in use.
+/// A task that can be neither placed nor split opens another core, which makes the partition infeasible.
+fn partition_with_task_splitting(
+    tasks: &[StretchedTask],
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> (Vec<Vec<usize>>, Vec<SplitDAG>) {
+    let hyper_period = get_hyper_period(dag_set).unwrap_or_else(|err| panic!("{}", err));
+    let mut core_utilizations: Vec<f64> = Vec::new();
+    let mut partition: Vec<Vec<usize>> = Vec::new();
+    let mut split_dags = Vec::new();
+    for task in sort_by_decreasing_utilization(tasks) {
+        let utilization = task.get_utilization();
+        if let Some(core_i) = core_utilizations
+            .iter()
+            .position(|core_utilization| core_utilization + utilization <= 1.0)
+        {
+            core_utilizations[core_i] += utilization;
+            partition[core_i].push(task.dag_id);
+            continue;
+        }
+        if partition.len() >= number_of_cores {
+            if let Some((body_core, tail_core, tail_nodes, body_utilization, tail_utilization)) =
+                split_task(&task, &dag_set[task.dag_id], &core_utilizations)
+            {
+                core_utilizations[body_core] += body_utilization;
+                core_utilizations[tail_core] += tail_utilization;
+                partition[body_core].push(task.dag_id);
+                partition[tail_core].push(task.dag_id);
+                split_dags.push(SplitDAG {
+                    dag_id: task.dag_id,
+                    body_core,
+                    tail_core,
+                    migrated_node_ids: tail_nodes
+                        .iter()
+                        .map(|node_i| dag_set[task.dag_id][*node_i].id as usize)
+                        .collect(),
+                    migrations_per_hyper_period: hyper_period / task.period,
+                });
+                continue;
+            }
+        }
+        core_utilizations.push(utilization);
+        partition.push(vec![task.dag_id]);
+    }
+    (partition, split_dags)
+}
+
 /// This function attempts to apply federated scheduling to a set of directed acyclic graphs
 /// (DAGs), each representing a task with a certain period and a worst-case
 /// execution time (WCET). It also considers a given number of available processing cores.
@@ -24,45 +225,65 @@ pub enum FederateResult {
 /// # Arguments
 ///
 /// * `dag_set` - A vector of Graphs. Each Graph represents a task with nodes of type `NodeData`
-///   and edges of type `i32`. Each task has an "period" parameter and a WCET.
+///   and edges of type `i64`. Each task has an "period" parameter and a WCET.
 /// * `number_of_cores` - The total number of available processing cores.
 ///
 /// # Returns
 ///
-/// * `bool` - Returns `true` if the tasks can be scheduled according to the federated
-///   scheduling algorithm with the given number of cores, and `false` otherwise.
+/// * `FederateResult` - `Schedulable` with the core assignment if the tasks can be scheduled
+///   according to the federated scheduling algorithm with the given number of cores,
+///   and `Unschedulable` with the reason otherwise.
 ///
 /// # Example
 ///
 /// ```
-/// use algorithm::federated::federated;
+/// use ecrts_federated::federated::federated;
 /// use petgraph::graph::Graph;
 /// use lib::graph_extension::NodeData;
-/// use std::collections::HashMap;
-/// fn create_node(id: i32, key: &str, value: i32) -> NodeData {
-///  let mut params = HashMap::new();
+/// use std::collections::BTreeMap;
+/// fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+///  let mut params = BTreeMap::new();
 ///  params.insert(key.to_string(), value);
 ///  NodeData { id, params }
 /// }
-/// let mut dag = Graph::<NodeData, i32>::new();
-/// let mut params = HashMap::new();
+/// let mut dag = Graph::<NodeData, i64>::new();
+/// let mut params = BTreeMap::new();
 /// params.insert("execution_time".to_owned(), 2);
 /// params.insert("period".to_owned(), 143);
-/// let n0 = dag.add_node(NodeData { id: 2, params });
-/// let n1 = dag.add_node(create_node(0, "execution_time", 3));
-/// let n2 = dag.add_node(create_node(1, "execution_time", 6));
+/// let n0 = dag.add_node(NodeData { id: 0, params });
+/// let n1 = dag.add_node(create_node(1, "execution_time", 3));
+/// let n2 = dag.add_node(create_node(2, "execution_time", 6));
 /// dag.add_edge(n0, n1, 1);
 /// dag.add_edge(n1, n2, 1);
-/// let dag_set = vec![dag];
+/// let mut dag_set = vec![dag];
 /// let number_of_cores = 4;
-/// let can_schedule = federated(dag_set, number_of_cores);
+/// let can_schedule = federated(&mut dag_set, number_of_cores);
 /// ```
 ///
-pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -> FederateResult {
+pub fn federated(dag_set: &mut [Graph<NodeData, i64>], number_of_cores: usize) -> FederateResult {
+    schedule_federated(dag_set, number_of_cores, false)
+}
+
+/// Federated scheduling with semi-partitioned low-utilization DAGs.
+/// Unlike `federated`, a stretched DAG that fits on no remaining core is split over two cores,
+/// which is a middle ground between the partitioned and the global scheduling of the low-utilization DAGs.
+pub fn semi_partitioned_federated(
+    dag_set: &mut [Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> FederateResult {
+    schedule_federated(dag_set, number_of_cores, true)
+}
+
+fn schedule_federated(
+    dag_set: &mut [Graph<NodeData, i64>],
+    number_of_cores: usize,
+    allows_task_splitting: bool,
+) -> FederateResult {
     let mut remaining_cores = number_of_cores;
-    let mut low_utilizations = 0.0;
+    let mut heavy_dags = Vec::new();
+    let mut stretched_tasks = Vec::new();
 
-    for dag in dag_set {
+    for (dag_id, dag) in dag_set.iter_mut().enumerate() {
         let period = dag.get_head_period().unwrap();
 
         // Conforms to the definition in the original paper
@@ -91,38 +312,158 @@ pub fn federated(dag_set: &mut [Graph<NodeData, i32>], number_of_cores: usize) -
                 };
             } else {
                 remaining_cores -= high_dedicated_cores;
+                heavy_dags.push(HeavyDAG {
+                    dag_id,
+                    dedicated_cores: high_dedicated_cores,
+                });
             }
         } else {
-            low_utilizations += utilization;
+            stretched_tasks.push(stretch_dag(dag_id, dag));
         }
     }
-    if remaining_cores as f32 > 2.0 * low_utilizations {
+
+    let (low_core_partition, split_dags) = if allows_task_splitting {
+        partition_with_task_splitting(&stretched_tasks, dag_set, remaining_cores)
+    } else {
+        (
+            partition_by_first_fit_decreasing(&stretched_tasks),
+            Vec::new(),
+        )
+    };
+    if low_core_partition.len() <= remaining_cores {
         Schedulable {
             high_dedicated_cores: number_of_cores - remaining_cores,
             low_dedicated_cores: remaining_cores,
+            heavy_dags,
+            light_dag_ids: stretched_tasks.iter().map(|task| task.dag_id).collect(),
+            low_core_partition,
+            split_dags,
         }
     } else {
         Unschedulable {
             reason: "Insufficient number of cores for low-utilization tasks.".to_string(),
-            insufficient_cores: (2.0 * low_utilizations - remaining_cores as f32).ceil() as usize,
+            insufficient_cores: low_core_partition.len() - remaining_cores,
         }
     }
 }
 
-#[cfg(test)]
+/// The bitmask of the consecutive cores from `first_core`.
+fn get_core_mask(first_core: usize, number_of_cores: usize) -> i64 {
+    (first_core..first_core + number_of_cores).fold(0, |core_mask, core_i| core_mask | 1 << core_i)
+}
+
+fn set_allowed_cores(dag: &mut Graph<NodeData, i64>, node_i: NodeIndex, core_mask: i64) {
+    dag[node_i]
+        .params
+        .insert("allowed_cores".to_string(), core_mask);
+}
+
+/// Pin the nodes to the cores of the schedulable result by `allowed_cores`.
+/// The high-utilization DAGs have the dedicated cores from core 0 in the order of `heavy_dags`,
+/// and the cores of `low_core_partition` follow them.
+/// The migrated nodes of a split DAG are pinned to its tail core and the other nodes to its body core.
+/// Returns a validation error if the result is unschedulable or uses a core beyond the bitmask.
+pub fn assign_federated_cores(
+    dag_set: &mut [Graph<NodeData, i64>],
+    result: &FederateResult,
+) -> Result<()> {
+    let Schedulable {
+        high_dedicated_cores,
+        heavy_dags,
+        low_core_partition,
+        split_dags,
+        ..
+    } = result
+    else {
+        return Err(SchedSimError::Validation(
+            "An unschedulable federated result has no core assignment.".to_string(),
+        ));
+    };
+    if high_dedicated_cores + low_core_partition.len() > i64::BITS as usize - 1 {
+        return Err(SchedSimError::Validation(format!(
+            "The federated core assignment uses {} cores, but allowed_cores covers at most {}.",
+            high_dedicated_cores + low_core_partition.len(),
+            i64::BITS - 1
+        )));
+    }
+
+    let mut first_core = 0;
+    for heavy_dag in heavy_dags.iter() {
+        let dag = &mut dag_set[heavy_dag.dag_id];
+        let core_mask = get_core_mask(first_core, heavy_dag.dedicated_cores);
+        for node_i in dag.node_indices() {
+            set_allowed_cores(dag, node_i, core_mask);
+        }
+        first_core += heavy_dag.dedicated_cores;
+    }
+    for (core_i, dag_ids) in low_core_partition.iter().enumerate() {
+        for dag_id in dag_ids.iter() {
+            let dag = &mut dag_set[*dag_id];
+            let split_dag = split_dags
+                .iter()
+                .find(|split_dag| split_dag.dag_id == *dag_id);
+            for node_i in dag.node_indices() {
+                // The nodes of a split DAG are pinned once by the body core.
+                let low_core_i = match split_dag {
+                    Some(split_dag) if split_dag.body_core != core_i => continue,
+                    Some(split_dag)
+                        if split_dag
+                            .migrated_node_ids
+                            .contains(&(dag[node_i].id as usize)) =>
+                    {
+                        split_dag.tail_core
+                    }
+                    _ => core_i,
+                };
+                set_allowed_cores(dag, node_i, get_core_mask(first_core + low_core_i, 1));
+            }
+        }
+    }
+    Ok(())
+}
 
+/// Simulate the schedulable result for the periods of the DAG set with the job logs of all the DAGs.
+/// A sink without an end-to-end deadline gets the implicit one, as in the analysis.
+/// The scheduler is returned with the outcome to dump its log.
+pub fn simulate_federated(
+    dag_set: &mut [Graph<NodeData, i64>],
+    number_of_cores: usize,
+    result: &FederateResult,
+) -> Result<(GlobalEDFScheduler, ScheduleOutcome)> {
+    assign_federated_cores(dag_set, result)?;
+    for (dag_id, dag) in dag_set.iter_mut().enumerate() {
+        dag.set_dag_param("dag_id", dag_id as i64);
+        // The analysis assumes implicit deadlines.
+        let period = dag.get_head_period().unwrap();
+        for sink_i in dag.get_sink_nodes() {
+            dag[sink_i]
+                .params
+                .entry("end_to_end_deadline".to_string())
+                .or_insert(period);
+        }
+    }
+    let mut scheduler =
+        GlobalEDFScheduler::new(dag_set, &HomogeneousProcessor::new(number_of_cores));
+    let schedule_outcome = scheduler.schedule(PreemptiveType::Preemptive {
+        key: "node_absolute_deadline".to_string(),
+    })?;
+    Ok((scheduler, schedule_outcome))
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use lib::dag_creator::create_dag_from_yaml_str;
     use std::collections::BTreeMap;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
-    fn create_high_utilization_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_high_utilization_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = {
             let mut params = BTreeMap::new();
             params.insert("execution_time".to_owned(), 4);
@@ -139,8 +480,8 @@ mod tests {
         dag
     }
 
-    fn create_low_utilization_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_low_utilization_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = {
             let mut params = BTreeMap::new();
             params.insert("execution_time".to_owned(), 3);
@@ -155,8 +496,20 @@ mod tests {
         dag
     }
 
-    fn create_period_exceeding_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_chain_dag(num_nodes: i64, period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let mut pre_node = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(pre_node, "period", period);
+        for id in 1..num_nodes {
+            let node = dag.add_node(create_node(id, "execution_time", 1));
+            dag.add_edge(pre_node, node, 1);
+            pre_node = node;
+        }
+        dag
+    }
+
+    fn create_period_exceeding_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 20);
         params.insert("period".to_owned(), 10);
@@ -164,8 +517,8 @@ mod tests {
         dag
     }
 
-    fn create_no_has_period_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_no_has_period_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 3);
         dag.add_node(NodeData { id: 0, params });
@@ -184,7 +537,20 @@ mod tests {
             federated(&mut dag_set, 40),
             Schedulable {
                 high_dedicated_cores: 6,
-                low_dedicated_cores: 34
+                low_dedicated_cores: 34,
+                heavy_dags: vec![
+                    HeavyDAG {
+                        dag_id: 0,
+                        dedicated_cores: 3
+                    },
+                    HeavyDAG {
+                        dag_id: 1,
+                        dedicated_cores: 3
+                    }
+                ],
+                light_dag_ids: vec![2],
+                low_core_partition: vec![vec![2]],
+                split_dags: vec![]
             }
         );
     }
@@ -218,11 +584,182 @@ mod tests {
             federated(&mut dag_set, 3),
             Unschedulable {
                 reason: (String::from("Insufficient number of cores for low-utilization tasks.")),
-                insufficient_cores: 2
+                insufficient_cores: 1
             }
         );
     }
 
+    #[test]
+    fn test_federated_stretched_low_tasks_share_core() {
+        let mut dag_set = vec![
+            create_low_utilization_dag(),
+            create_low_utilization_dag(),
+            create_low_utilization_dag(),
+        ];
+
+        assert_eq!(
+            federated(&mut dag_set, 1),
+            Schedulable {
+                high_dedicated_cores: 0,
+                low_dedicated_cores: 1,
+                heavy_dags: vec![],
+                light_dag_ids: vec![0, 1, 2],
+                low_core_partition: vec![vec![0, 1, 2]],
+                split_dags: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_partition_by_first_fit_decreasing_normal() {
+        let tasks: Vec<StretchedTask> = [(0, 5), (1, 7), (2, 3), (3, 5)]
+            .into_iter()
+            .map(|(dag_id, execution_time)| StretchedTask {
+                dag_id,
+                execution_time,
+                period: 10,
+            })
+            .collect();
+
+        assert_eq!(
+            partition_by_first_fit_decreasing(&tasks),
+            vec![vec![1, 2], vec![0, 3]]
+        );
+    }
+
+    #[test]
+    fn test_semi_partitioned_federated_split_low_task() {
+        let mut dag_set = vec![
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+        ];
+
+        assert_eq!(
+            semi_partitioned_federated(&mut dag_set, 2),
+            Schedulable {
+                high_dedicated_cores: 0,
+                low_dedicated_cores: 2,
+                heavy_dags: vec![],
+                light_dag_ids: vec![0, 1, 2],
+                low_core_partition: vec![vec![0, 2], vec![1, 2]],
+                split_dags: vec![SplitDAG {
+                    dag_id: 2,
+                    body_core: 0,
+                    tail_core: 1,
+                    migrated_node_ids: vec![4, 5],
+                    migrations_per_hyper_period: 1,
+                }]
+            }
+        );
+        // Without task splitting, the third DAG needs another core.
+        assert_eq!(
+            federated(&mut dag_set, 2),
+            Unschedulable {
+                reason: (String::from("Insufficient number of cores for low-utilization tasks.")),
+                insufficient_cores: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_semi_partitioned_federated_no_split_with_enough_cores() {
+        let mut dag_set = vec![
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+        ];
+
+        assert_eq!(
+            semi_partitioned_federated(&mut dag_set, 3),
+            federated(&mut dag_set, 3)
+        );
+    }
+
+    fn get_allowed_cores(dag: &Graph<NodeData, i64>) -> Vec<i64> {
+        dag.node_weights()
+            .map(|node| node.get_params_value("allowed_cores"))
+            .collect()
+    }
+
+    #[test]
+    fn test_assign_federated_cores_heavy_and_light() {
+        let mut dag_set = vec![
+            create_high_utilization_dag(),
+            create_high_utilization_dag(),
+            create_low_utilization_dag(),
+        ];
+        let result = federated(&mut dag_set, 40);
+        assign_federated_cores(&mut dag_set, &result).unwrap();
+
+        assert_eq!(get_allowed_cores(&dag_set[0]), vec![0b111; 4]);
+        assert_eq!(get_allowed_cores(&dag_set[1]), vec![0b111000; 4]);
+        // The light DAG follows the dedicated cores of the heavy DAGs.
+        assert_eq!(get_allowed_cores(&dag_set[2]), vec![0b1000000; 3]);
+    }
+
+    #[test]
+    fn test_assign_federated_cores_unschedulable() {
+        let mut dag_set = vec![create_high_utilization_dag()];
+        let result = federated(&mut dag_set, 1);
+        assert!(matches!(
+            assign_federated_cores(&mut dag_set, &result),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_simulate_federated_heavy_and_light() {
+        // A fork of volume 14 and critical path 8 needs 3 cores for the period 10.
+        let heavy_dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 4, period: 10}, {id: 1, execution_time: 4}, {id: 2, execution_time: 3}, {id: 3, execution_time: 3}]\nlinks: [{source: 0, target: 1}, {source: 0, target: 2}, {source: 0, target: 3}]",
+            false,
+        )
+        .unwrap();
+        let mut dag_set = vec![heavy_dag, create_chain_dag(6, 10)];
+        let result = federated(&mut dag_set, 4);
+        assert!(matches!(
+            &result,
+            Schedulable { heavy_dags, light_dag_ids, .. }
+                if heavy_dags == &vec![HeavyDAG { dag_id: 0, dedicated_cores: 3 }] && light_dag_ids == &vec![1]
+        ));
+        let (_, schedule_outcome) = simulate_federated(&mut dag_set, 4, &result).unwrap();
+
+        let log = schedule_outcome.log;
+        for slice in log.get_execution_slices() {
+            match slice.dag_id {
+                0 => assert!(slice.core_id < 3),
+                _ => assert_eq!(slice.core_id, 3),
+            }
+        }
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![false, false]);
+    }
+
+    #[test]
+    fn test_simulate_semi_partitioned_federated_migrated_nodes() {
+        let mut dag_set = vec![
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+            create_chain_dag(6, 10),
+        ];
+        let result = semi_partitioned_federated(&mut dag_set, 2);
+        let (_, schedule_outcome) = simulate_federated(&mut dag_set, 2, &result).unwrap();
+
+        for slice in schedule_outcome.log.get_execution_slices() {
+            let expected_core_id = match (slice.dag_id, slice.node_id) {
+                (0, _) => 0,
+                (1, _) => 1,
+                (2, node_id) if node_id >= 4 => 1,
+                _ => 0,
+            };
+            assert_eq!(slice.core_id, expected_core_id);
+        }
+        assert_eq!(
+            schedule_outcome.deadline_miss_flags,
+            vec![false, false, false]
+        );
+    }
+
     #[test]
     fn test_federated_unsuited_tasks() {
         assert_eq!(
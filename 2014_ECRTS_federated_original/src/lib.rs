@@ -0,0 +1,3 @@
+pub mod driver;
+pub mod federated;
+pub mod outputs_result;
@@ -1,20 +1,13 @@
-mod federated;
-mod outputs_result;
-
 use clap::Parser;
+use ecrts_federated::driver::run_federated;
 use lib::{
-    dag_creator::create_dag_set_from_dir, homogeneous, processor::ProcessorBase,
-    util::create_scheduler_log_yaml,
-};
-use outputs_result::{
-    dump_dag_set_info_to_yaml, dump_federated_result_to_yaml, dump_processor_info_to_yaml,
+    dag_creator::create_dag_set_from_dir, error::Result, log::OutputFormat,
+    run_metadata::RunMetadata,
 };
 
-/// Application description and arguments definition using clap crate
+/// Application arguments definition using clap crate
 #[derive(Parser)]
 #[clap()]
-
-/// Application arguments definition using clap crate
 struct AppArg {
     #[clap(short = 'd', long = "dag_dir_path", required = true)]
     dag_dir_path: String,
@@ -22,18 +15,36 @@ struct AppArg {
     number_of_cores: usize,
     #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
     output_dir_path: String,
+    ///Split the low-utilization DAGs that fit on no remaining core over two cores.
+    #[clap(short = 's', long = "enable_task_splitting", default_value = "false")]
+    enable_task_splitting: bool,
+    ///Simulate a schedulable result by preemptive EDF on its core assignment, with the job logs of all the DAGs.
+    #[clap(long = "simulate", default_value = "false")]
+    simulate: bool,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
 }
 
 /// Application main function
-fn main() {
+fn main() -> Result<()> {
     let arg: AppArg = AppArg::parse();
-    let dag_dir_path = arg.dag_dir_path;
-    let number_of_cores = arg.number_of_cores;
-    let mut dag_set = create_dag_set_from_dir(&dag_dir_path);
-    let result = federated::federated(&mut dag_set, number_of_cores);
-    let file_path = create_scheduler_log_yaml(&arg.output_dir_path, "federated");
-    let homogeneous_processor = homogeneous::HomogeneousProcessor::new(number_of_cores);
-    dump_dag_set_info_to_yaml(&file_path, dag_set);
-    dump_processor_info_to_yaml(&file_path, &homogeneous_processor);
-    dump_federated_result_to_yaml(&file_path, result);
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
+    let dag_set = create_dag_set_from_dir(&arg.dag_dir_path)?;
+    let mut run_metadata = RunMetadata::new("federated", arg.number_of_cores);
+    run_metadata.add_input_dir(&arg.dag_dir_path)?;
+    run_federated(
+        dag_set,
+        arg.number_of_cores,
+        &arg.output_dir_path,
+        output_format,
+        run_metadata,
+        arg.enable_task_splitting,
+        arg.simulate,
+    )?;
+    Ok(())
 }
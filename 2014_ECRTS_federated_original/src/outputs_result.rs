@@ -12,17 +12,17 @@ struct ResultInfo<FederateResult> {
     result: FederateResult,
 }
 
-pub(crate) fn dump_federated_result_to_yaml(file_path: &str, result: FederateResult) {
+pub fn dump_federated_result_to_yaml(file_path: &str, result: FederateResult) {
     let result_info = ResultInfo { result };
     dump_struct(file_path, &result_info);
 }
 
-pub(crate) fn dump_dag_set_info_to_yaml(file_path: &str, dag_set: Vec<Graph<NodeData, i32>>) {
+pub fn dump_dag_set_info_to_yaml(file_path: &str, dag_set: Vec<Graph<NodeData, i64>>) {
     let dag_set_info = DAGSetInfo::new(&dag_set);
     dump_struct(file_path, &dag_set_info);
 }
 
-pub(crate) fn dump_processor_info_to_yaml(file_path: &str, processor: &impl ProcessorBase) {
+pub fn dump_processor_info_to_yaml(file_path: &str, processor: &impl ProcessorBase) {
     let processor_info = ProcessorInfo::new(processor.get_number_of_cores());
     dump_struct(file_path, &processor_info);
 }
@@ -30,7 +30,10 @@ pub(crate) fn dump_processor_info_to_yaml(file_path: &str, processor: &impl Proc
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::federated::HeavyDAG;
     use lib::{
+        dag_creator::create_dag_set_from_dir,
+        golden::assert_matches_golden,
         graph_extension::NodeData,
         homogeneous,
         util::{create_yaml, load_yaml},
@@ -38,14 +41,14 @@ mod tests {
     use petgraph::Graph;
     use std::{collections::BTreeMap, fs::remove_file};
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
-    fn create_high_utilization_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_high_utilization_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = {
             let mut params = BTreeMap::new();
             params.insert("execution_time".to_owned(), 4);
@@ -62,8 +65,8 @@ mod tests {
         dag
     }
 
-    fn create_low_utilization_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_low_utilization_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = {
             let mut params = BTreeMap::new();
             params.insert("execution_time".to_owned(), 3);
@@ -78,8 +81,8 @@ mod tests {
         dag
     }
 
-    fn create_period_exceeding_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_period_exceeding_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 20);
         params.insert("period".to_owned(), 10);
@@ -107,6 +110,19 @@ mod tests {
             FederateResult::Schedulable {
                 high_dedicated_cores: 6,
                 low_dedicated_cores: 34,
+                heavy_dags: vec![
+                    HeavyDAG {
+                        dag_id: 0,
+                        dedicated_cores: 3,
+                    },
+                    HeavyDAG {
+                        dag_id: 1,
+                        dedicated_cores: 3,
+                    },
+                ],
+                light_dag_ids: vec![2],
+                low_core_partition: vec![vec![2]],
+                split_dags: vec![],
             }
         );
 
@@ -158,7 +174,7 @@ mod tests {
             result_info.result,
             FederateResult::Unschedulable {
                 reason: (String::from("Insufficient number of cores for low-utilization tasks.")),
-                insufficient_cores: 2
+                insufficient_cores: 1
             }
         );
 
@@ -195,7 +211,7 @@ mod tests {
         let file_path = create_yaml("../lib/tests", "dag_set_info");
         dump_dag_set_info_to_yaml(&file_path, dag_set);
 
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         assert_eq!(yaml_doc["total_utilization"].as_f64().unwrap(), 1.4285715);
@@ -229,11 +245,43 @@ mod tests {
         let homogeneous_processor = homogeneous::HomogeneousProcessor::new(4);
         dump_processor_info_to_yaml(&file_path, &homogeneous_processor);
 
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         assert_eq!(yaml_doc["number_of_cores"].as_i64().unwrap(), 4);
 
         remove_file(file_path).unwrap();
     }
+
+    fn dump_golden_log(allows_task_splitting: bool, number_of_cores: usize, alg_name: &str) {
+        let mut dag_set = create_dag_set_from_dir("../lib/tests/golden_logs/dag_set").unwrap();
+        let result = if allows_task_splitting {
+            crate::federated::semi_partitioned_federated(&mut dag_set, number_of_cores)
+        } else {
+            crate::federated::federated(&mut dag_set, number_of_cores)
+        };
+        let file_path = create_yaml("../lib/tests", alg_name);
+        dump_dag_set_info_to_yaml(&file_path, dag_set);
+        dump_processor_info_to_yaml(
+            &file_path,
+            &homogeneous::HomogeneousProcessor::new(number_of_cores),
+        );
+        dump_federated_result_to_yaml(&file_path, result);
+
+        assert_matches_golden(
+            &file_path,
+            &format!("../lib/tests/golden_logs/{}.yaml", alg_name),
+        );
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_federated_golden_log() {
+        dump_golden_log(false, 4, "golden_federated");
+    }
+
+    #[test]
+    fn test_semi_partitioned_federated_golden_log() {
+        dump_golden_log(true, 2, "golden_semi_partitioned_federated");
+    }
 }
@@ -1,18 +1,22 @@
-mod decomposition;
-mod handle_segment;
-
 use clap::Parser;
-use decomposition::decompose;
 use lib::{
     dag_creator::create_dag_set_from_dir,
     dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    error::Result,
+    event_script::EventScript,
     global_edf_scheduler::GlobalEDFScheduler,
-    graph_extension::GraphExtension,
     homogeneous::HomogeneousProcessor,
-    log::dump_dag_set_scheduler_result_to_yaml,
+    log::{
+        dump_dag_set_scheduler_result_to_yaml, dump_run_time_to_yaml, print_result_summary,
+        OutputFormat, RunTimeLog,
+    },
     processor::ProcessorBase,
-    util::{adjust_to_implicit_deadline, load_yaml},
+    run_metadata::RunMetadata,
+    transformations::{create_segmentation_strategy, decompose_with_strategy},
+    util::adjust_to_implicit_deadline,
+    visualization::dump_gantt_svg,
 };
+use serde_yaml::{Mapping, Value};
 
 #[derive(Parser)]
 #[clap(
@@ -36,20 +40,45 @@ struct ArgParser {
     ///Enable or disable preemptive scheduling.
     #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
     enable_preemption: bool,
+    ///Strategy dividing the period into the deadlines of the segments:
+    ///basic, even, proportional_to_slack or critical_path_aware.
+    #[clap(short = 's', long = "segmentation_strategy", default_value = "basic")]
+    segmentation_strategy: String,
+    ///Path to event script file for what-if scenarios.
+    #[clap(short = 'e', long = "event_script_path")]
+    event_script_path: Option<String>,
+    ///Render the schedule as an SVG Gantt chart next to the log.
+    #[clap(long = "render_gantt", alias = "render-gantt", default_value = "false")]
+    render_gantt: bool,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
 }
 
-fn main() {
+fn main() -> Result<()> {
     let arg: ArgParser = ArgParser::parse();
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
 
-    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
-    adjust_to_implicit_deadline(&mut dag_set);
-    // Decompose DAGs
-    for dag in dag_set.iter_mut() {
-        decompose(dag);
-    }
+    let segmentation_strategy = create_segmentation_strategy(&arg.segmentation_strategy);
+    let mut run_time_log = RunTimeLog::default();
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path)?;
+    run_time_log.measure_preprocessing(|| {
+        adjust_to_implicit_deadline(&mut dag_set);
+        // Decompose DAGs
+        for dag in dag_set.iter_mut() {
+            decompose_with_strategy(dag, segmentation_strategy.as_ref());
+        }
+    });
 
     let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
     let mut gedf_scheduler = GlobalEDFScheduler::new(&dag_set, &homogeneous_processor);
+    if let Some(event_script_path) = &arg.event_script_path {
+        gedf_scheduler.set_event_script(EventScript::from_yaml_file(event_script_path)?);
+    }
 
     // Change whether it is preemptive or not depending on the argument
     let (preemptive_type, file_name) = if arg.enable_preemption {
@@ -63,23 +92,39 @@ fn main() {
         (PreemptiveType::NonPreemptive, "decomp_gedf_non_preemptive")
     };
 
-    gedf_scheduler.schedule(preemptive_type);
+    let schedule_outcome =
+        run_time_log.measure_simulation(|| gedf_scheduler.schedule(preemptive_type))?;
+    let mut run_metadata = RunMetadata::new("decomp_gedf", arg.number_of_cores);
+    run_metadata.add_input_dir(&arg.dag_dir_path)?;
+    if let Some(event_script_path) = &arg.event_script_path {
+        run_metadata.add_input_file(event_script_path)?;
+    }
+    // The strategy is recorded to tell the logs of the decomposition variants apart.
+    let mut config = Mapping::new();
+    config.insert(
+        "segmentation_strategy".into(),
+        segmentation_strategy.get_name().into(),
+    );
+    run_metadata.config = Some(Value::Mapping(config));
+    gedf_scheduler
+        .get_log_mut()
+        .write_run_metadata(run_metadata);
     let file_path = gedf_scheduler.dump_log(&arg.output_dir_path, file_name);
+    if arg.render_gantt {
+        dump_gantt_svg(&schedule_outcome.log, &file_path.replace(".yaml", ".svg"));
+    }
 
     // Check the result
-    let yaml_doc = &load_yaml(&file_path)[0];
-    let dag_set_log = &yaml_doc["dag_set_log"];
-    let mut result = true;
-    for dag in dag_set {
-        if dag_set_log[dag.get_dag_param("dag_id") as usize]["worst_response_time"]
-            .as_i64()
-            .unwrap()
-            > dag.get_head_period().unwrap() as i64
-        {
-            result = false;
-            break;
-        }
-    }
+    let result = !schedule_outcome.has_deadline_miss();
 
     dump_dag_set_scheduler_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_outcome.schedule_length),
+        &schedule_outcome.worst_response_times,
+    );
+    Ok(())
 }
@@ -0,0 +1,67 @@
+//! Runs of the intra-task priority assignment from the arguments of the command line,
+//! which are shared by the binary of this crate and the sched_sim binary.
+use crate::prioritization_intra_task::assign_intra_task_priority;
+use lib::{
+    dag_scheduler::DAGSchedulerBase,
+    error::Result,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    log::{
+        dump_dag_scheduler_result_to_yaml, dump_run_time_to_yaml, print_result_summary,
+        OutputFormat, RunTimeLog,
+    },
+    processor::ProcessorBase,
+    run_metadata::RunMetadata,
+};
+use log::warn;
+use petgraph::Graph;
+
+/// Prioritize the nodes of the DAG path by path, and schedule it by global fixed-priority scheduling.
+/// Returns whether the schedule length is within the constrained end-to-end deadline.
+pub fn run_intra_task_priority(
+    mut dag: Graph<NodeData, i64>,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    output_format: OutputFormat,
+    run_metadata: RunMetadata,
+    ratio_deadline_to_period: f32,
+) -> Result<bool> {
+    if ratio_deadline_to_period > 1.0 {
+        panic!("ratio_deadline_to_period must be less than or equal to 1.0");
+    }
+    let homogeneous_processor = HomogeneousProcessor::new(number_of_cores);
+    let mut run_time_log = RunTimeLog::default();
+    run_time_log.measure_preprocessing(|| assign_intra_task_priority(&mut dag));
+    let mut fixed_priority_scheduler = FixedPriorityScheduler::new(&dag, &homogeneous_processor);
+    let (schedule_length, _) =
+        run_time_log.measure_simulation(|| fixed_priority_scheduler.schedule());
+    let mut log = fixed_priority_scheduler.get_log();
+    log.write_run_metadata(run_metadata);
+    fixed_priority_scheduler.set_log(log);
+    let constrained_end_to_end_deadline = if let Some(deadline) = dag.get_end_to_end_deadline() {
+        deadline as f32
+    } else {
+        warn!("Since the end-to-end deadline is not set in the input DAG, the end-to-end deadline is determined using ratio_deadline_to_period.");
+        dag.get_head_period().unwrap() as f32 * ratio_deadline_to_period
+    };
+    let result = (schedule_length as f32) <= constrained_end_to_end_deadline;
+    let file_path = fixed_priority_scheduler.dump_log(output_dir_path, "intra_task_priority");
+
+    dump_dag_scheduler_result_to_yaml(
+        &file_path,
+        schedule_length,
+        ratio_deadline_to_period,
+        result,
+    );
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    // The response time of the single DAG is the schedule length.
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_length),
+        &[schedule_length],
+    );
+    Ok(result)
+}
@@ -0,0 +1,54 @@
+use clap::Parser;
+use lib::{
+    dag_creator::create_dag_from_yaml, error::Result, log::OutputFormat, run_metadata::RunMetadata,
+};
+use rtss_intra_task_priority::driver::run_intra_task_priority;
+
+#[derive(Parser)]
+#[clap(
+    name = "Intra_Task_Priority_Assignment",
+    version = "1.0",
+    about = "About:
+    The Intra_Task_Priority_Assignment prioritizes the nodes of the input DAG path by path from the longest one,
+    and schedules it by global fixed-priority scheduling as a comparison point to the CPC model.
+    If the input DAG does not contain an end-to-end deadline,
+    the algorithm will impose one by multiplying the period of the input DAG by an arbitrary multiplier."
+)]
+struct ArgParser {
+    ///Path to DAG file, or `-` to read it from the standard input.
+    #[clap(short = 'f', long = "dag_file_path", required = true)]
+    dag_file_path: String,
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Multiplier to compute constrained deadlines
+    #[clap(short = 'r', long = "ratio_deadline_to_period", default_value = "1.0")]
+    ratio_deadline_to_period: f32,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
+}
+
+fn main() -> Result<()> {
+    let arg: ArgParser = ArgParser::parse();
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
+    let dag = create_dag_from_yaml(&arg.dag_file_path, false)?;
+    let mut run_metadata = RunMetadata::new("intra_task_priority", arg.number_of_cores);
+    run_metadata.add_input_file(&arg.dag_file_path)?;
+    run_intra_task_priority(
+        dag,
+        arg.number_of_cores,
+        &arg.output_dir_path,
+        output_format,
+        run_metadata,
+        arg.ratio_deadline_to_period,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Intra-task priority assignment.
+//! Paper Information
+//! -----------------
+//! Title: Intra-Task Priority Assignment in Real-Time Scheduling of DAG Tasks on Multi-cores
+//! Authors: Qingqiang He, Xu Jiang, Nan Guan, and Zhishan Guo
+//! Conference: RTSS 2019
+//! -----------------
+//! The nodes are prioritized path by path for global fixed-priority scheduling.
+//! The longest path of the DAG, i.e., the critical path, has the highest priorities,
+//! and then the longest path of the nodes not yet prioritized has the next ones, until all the nodes are prioritized.
+//! The nodes of a path are prioritized in the order of the path, and the smaller value has the higher priority.
+use lib::graph_extension::{GraphExtension, NodeData};
+use petgraph::{
+    algo::toposort,
+    graph::{Graph, NodeIndex},
+};
+use std::{cmp::Reverse, collections::HashMap};
+
+/// The longest path of the nodes without priorities, whose length is the sum of their execution times.
+/// The first node in the topological order is taken among the ends of the paths of the same length.
+fn get_longest_unprioritized_path(
+    dag: &Graph<NodeData, i64>,
+    sorted_nodes: &[NodeIndex],
+) -> Vec<NodeIndex> {
+    let mut path_lengths = HashMap::new();
+    let mut pre_path_nodes = HashMap::new();
+    let mut path_end: Option<NodeIndex> = None;
+    for &node_i in sorted_nodes
+        .iter()
+        .filter(|node_i| !dag[**node_i].params.contains_key("priority"))
+    {
+        let pre_path_node = dag
+            .get_pre_nodes(node_i)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pre_node_i| path_lengths.contains_key(pre_node_i))
+            .max_by_key(|pre_node_i| (path_lengths[pre_node_i], Reverse(pre_node_i.index())));
+        let path_length = dag[node_i].get_params_value("execution_time")
+            + pre_path_node.map_or(0, |pre_node_i| path_lengths[&pre_node_i]);
+        path_lengths.insert(node_i, path_length);
+        pre_path_nodes.insert(node_i, pre_path_node);
+        if path_end.is_none_or(|path_end| path_lengths[&path_end] < path_length) {
+            path_end = Some(node_i);
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut node_i = path_end;
+    while let Some(path_node_i) = node_i {
+        path.push(path_node_i);
+        node_i = pre_path_nodes[&path_node_i];
+    }
+    path.reverse();
+    path
+}
+
+/// Assign the `priority` of every node, which is consumed by `FixedPriorityScheduler`.
+pub fn assign_intra_task_priority(dag: &mut Graph<NodeData, i64>) {
+    let sorted_nodes = toposort(&*dag, None).unwrap();
+    let mut next_priority = 0;
+    while next_priority < dag.node_count() as i64 {
+        for node_i in get_longest_unprioritized_path(dag, &sorted_nodes) {
+            dag.add_param(node_i, "priority", next_priority);
+            next_priority += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib::{
+        dag_creator::create_dag_from_yaml_str, dag_scheduler::DAGSchedulerBase,
+        fixed_priority_scheduler::FixedPriorityScheduler, homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+
+    fn create_sample_dag() -> Graph<NodeData, i64> {
+        create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 20}, {id: 1, execution_time: 6}, {id: 2, execution_time: 3}, {id: 3, execution_time: 2}, {id: 4, execution_time: 4}, {id: 5, execution_time: 1, end_to_end_deadline: 20}]\nlinks: [{source: 0, target: 1}, {source: 0, target: 2}, {source: 1, target: 5}, {source: 2, target: 5}, {source: 3, target: 4}, {source: 4, target: 5}]",
+            false,
+        )
+        .unwrap()
+    }
+
+    fn get_priorities(dag: &Graph<NodeData, i64>) -> Vec<i64> {
+        dag.node_weights()
+            .map(|node| node.get_params_value("priority"))
+            .collect()
+    }
+
+    #[test]
+    fn test_assign_intra_task_priority_normal() {
+        let mut dag = create_sample_dag();
+        assign_intra_task_priority(&mut dag);
+
+        // The critical path 0-1-5 comes first, then 3-4 of length 6, and then 2 of length 3.
+        assert_eq!(get_priorities(&dag), vec![0, 1, 5, 3, 4, 2]);
+    }
+
+    #[test]
+    fn test_assign_intra_task_priority_path_through_prioritized_nodes() {
+        // The path 3-4 does not include the prioritized node 2, so it is shorter than the node 1.
+        let mut dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 5, period: 30}, {id: 1, execution_time: 4}, {id: 2, execution_time: 5}, {id: 3, execution_time: 2}, {id: 4, execution_time: 1}]\nlinks: [{source: 0, target: 2}, {source: 1, target: 2}, {source: 3, target: 4}, {source: 4, target: 2}]",
+            false,
+        )
+        .unwrap();
+        assign_intra_task_priority(&mut dag);
+
+        assert_eq!(get_priorities(&dag), vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_schedule_by_intra_task_priority() {
+        let mut dag = create_sample_dag();
+        assign_intra_task_priority(&mut dag);
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        let (schedule_length, _) = fixed_priority_scheduler.schedule();
+
+        // The node 2 of the lowest priority waits for the node 4 until 6.
+        assert_eq!(schedule_length, 10);
+    }
+}
@@ -0,0 +1,148 @@
+//! Runs of the CPC model-based algorithm from the arguments of the command line,
+//! which are shared by the binary of this crate and the sched_sim binary.
+use crate::prioritization_cpc_model::{
+    assign_priority_to_cpc_model, assign_priority_to_cpc_model_for_dag_set,
+};
+use lib::{
+    dag_scheduler::DAGSchedulerBase,
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    error::Result,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    global_edf_scheduler::GlobalEDFScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    log::{
+        dump_dag_scheduler_result_to_yaml, dump_dag_set_scheduler_result_to_yaml,
+        dump_run_time_to_yaml, print_result_summary, OutputFormat, RunTimeLog,
+    },
+    processor::ProcessorBase,
+    run_metadata::RunMetadata,
+    util::adjust_to_constrained_deadline,
+};
+use log::warn;
+use petgraph::Graph;
+
+fn check_ratio_deadline_to_period(ratio_deadline_to_period: f32) {
+    if ratio_deadline_to_period > 1.0 {
+        panic!("ratio_deadline_to_period must be less than or equal to 1.0");
+    }
+}
+
+/// Prioritize the DAGs by the CPC model one by one, and schedule the set by global EDF,
+/// whose nodes of the same deadline are ordered by the priorities.
+/// Returns whether no deadline is missed.
+pub fn run_cpc_dag_set(
+    mut dag_set: Vec<Graph<NodeData, i64>>,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    output_format: OutputFormat,
+    run_metadata: RunMetadata,
+    ratio_deadline_to_period: f32,
+    enable_preemption: bool,
+) -> Result<bool> {
+    check_ratio_deadline_to_period(ratio_deadline_to_period);
+    adjust_to_constrained_deadline(&mut dag_set, ratio_deadline_to_period);
+    let mut run_time_log = RunTimeLog::default();
+    run_time_log.measure_preprocessing(|| assign_priority_to_cpc_model_for_dag_set(&mut dag_set));
+    let homogeneous_processor = HomogeneousProcessor::new(number_of_cores);
+    let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &homogeneous_processor);
+    // The nodes are scheduled non-preemptively as in the CPC model, unless the preemption is enabled
+    // to quantify the cost of the non-preemptive execution.
+    let (preemptive_type, alg_name) = if enable_preemption {
+        (
+            PreemptiveType::LexicographicPreemptive {
+                keys: vec!["node_absolute_deadline".to_string(), "priority".to_string()],
+            },
+            "cpc_model_based_preemptive",
+        )
+    } else {
+        (PreemptiveType::NonPreemptive, "cpc_model_based")
+    };
+    let schedule_outcome =
+        run_time_log.measure_simulation(|| global_edf_scheduler.schedule(preemptive_type))?;
+    global_edf_scheduler
+        .get_log_mut()
+        .write_run_metadata(run_metadata);
+    let file_path = global_edf_scheduler.dump_log(output_dir_path, alg_name);
+
+    let result = !schedule_outcome.has_deadline_miss();
+    dump_dag_set_scheduler_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_outcome.schedule_length),
+        &schedule_outcome.worst_response_times,
+    );
+    Ok(result)
+}
+
+/// Prioritize the DAG by the CPC model, and schedule it by fixed-priority scheduling.
+/// Returns whether the schedule length is within the constrained end-to-end deadline.
+pub fn run_cpc_dag(
+    mut dag: Graph<NodeData, i64>,
+    number_of_cores: usize,
+    output_dir_path: &str,
+    output_format: OutputFormat,
+    run_metadata: RunMetadata,
+    ratio_deadline_to_period: f32,
+    enable_preemption: bool,
+) -> Result<bool> {
+    check_ratio_deadline_to_period(ratio_deadline_to_period);
+    // The fixed-priority scheduler of a DAG is non-preemptive, so the DAG is preempted as a DAG set of one DAG.
+    if enable_preemption {
+        dag.set_dag_param("dag_id", 0);
+        return run_cpc_dag_set(
+            vec![dag],
+            number_of_cores,
+            output_dir_path,
+            output_format,
+            run_metadata,
+            ratio_deadline_to_period,
+            enable_preemption,
+        );
+    }
+    let homogeneous_processor = HomogeneousProcessor::new(number_of_cores);
+    let mut run_time_log = RunTimeLog::default();
+    run_time_log.measure_preprocessing(|| assign_priority_to_cpc_model(&mut dag));
+    let mut fixed_priority_scheduler = FixedPriorityScheduler::new(&dag, &homogeneous_processor);
+    let (schedule_length, _) =
+        run_time_log.measure_simulation(|| fixed_priority_scheduler.schedule());
+    let mut log = fixed_priority_scheduler.get_log();
+    log.write_run_metadata(run_metadata);
+    fixed_priority_scheduler.set_log(log);
+    let constrained_end_to_end_deadline = if let Some(deadline) = dag.get_end_to_end_deadline() {
+        deadline as f32
+    } else {
+        warn!("Since the end-to-end deadline is not set in the input DAG, the end-to-end deadline is determined using ratio_deadline_to_period.");
+        dag.get_head_period().unwrap() as f32 * ratio_deadline_to_period
+    };
+    let result = (schedule_length as f32) <= constrained_end_to_end_deadline;
+    // Printed on stderr so that the JSON summary on stdout stays parsable.
+    let makespan_lower_bound = dag.get_makespan_lower_bound(number_of_cores);
+    eprintln!(
+        "Schedule length / makespan lower bound: {} / {} = {:.3}",
+        schedule_length,
+        makespan_lower_bound,
+        schedule_length as f64 / makespan_lower_bound as f64
+    );
+    let file_path = fixed_priority_scheduler.dump_log(output_dir_path, "cpc_model_based");
+
+    dump_dag_scheduler_result_to_yaml(
+        &file_path,
+        schedule_length,
+        ratio_deadline_to_period,
+        result,
+    );
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    // The response time of the single DAG is the schedule length.
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_length),
+        &[schedule_length],
+    );
+    Ok(result)
+}
@@ -0,0 +1,3 @@
+pub mod driver;
+mod parallel_provider_consumer;
+pub mod prioritization_cpc_model;
@@ -1,14 +1,11 @@
-mod parallel_provider_consumer;
-mod prioritization_cpc_model;
-
 use clap::Parser;
 use lib::{
-    dag_creator::create_dag_from_yaml, dag_scheduler::DAGSchedulerBase,
-    fixed_priority_scheduler::FixedPriorityScheduler, graph_extension::GraphExtension,
-    homogeneous::HomogeneousProcessor, log::dump_dag_scheduler_result_to_yaml,
-    processor::ProcessorBase,
+    dag_creator::{create_dag_from_yaml, create_dag_set_from_dir},
+    error::Result,
+    log::OutputFormat,
+    run_metadata::RunMetadata,
 };
-use log::warn;
+use rtss_cpc::driver::{run_cpc_dag, run_cpc_dag_set};
 
 #[derive(Parser)]
 #[clap(
@@ -21,9 +18,18 @@ use log::warn;
     the algorithm will impose one by multiplying the period of the input DAG by an arbitrary multiplier."
 )]
 struct ArgParser {
-    ///Path to DAG file.
-    #[clap(short = 'f', long = "dag_file_path", required = true)]
-    dag_file_path: String,
+    ///Path to DAG file, or `-` to read it from the standard input.
+    #[clap(
+        short = 'f',
+        long = "dag_file_path",
+        required_unless_present = "dag_dir_path",
+        conflicts_with = "dag_dir_path"
+    )]
+    dag_file_path: Option<String>,
+    ///Path to DAGSet directory. The DAGs are prioritized by the CPC model one by one,
+    ///and the set is scheduled by global EDF, whose nodes of the same deadline are ordered by the priorities.
+    #[clap(short = 'd', long = "dag_dir_path")]
+    dag_dir_path: Option<String>,
     ///Number of processing cores.
     #[clap(short = 'c', long = "number_of_cores", required = true)]
     number_of_cores: usize,
@@ -33,31 +39,49 @@ struct ArgParser {
     ///Multiplier to compute constrained deadlines
     #[clap(short = 'r', long = "ratio_deadline_to_period", default_value = "1.0")]
     ratio_deadline_to_period: f32,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
+    ///Preempt the nodes by their absolute deadlines and then their CPC priorities, instead of the non-preemptive execution of the CPC model.
+    #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+    enable_preemption: bool,
 }
 
-fn main() {
+fn main() -> Result<()> {
     let arg: ArgParser = ArgParser::parse();
-    if arg.ratio_deadline_to_period > 1.0 {
-        panic!("ratio_deadline_to_period must be less than or equal to 1.0");
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
+    let mut run_metadata = RunMetadata::new("cpc", arg.number_of_cores);
+    match (&arg.dag_file_path, &arg.dag_dir_path) {
+        (Some(dag_file_path), _) => {
+            let dag = create_dag_from_yaml(dag_file_path, false)?;
+            run_metadata.add_input_file(dag_file_path)?;
+            run_cpc_dag(
+                dag,
+                arg.number_of_cores,
+                &arg.output_dir_path,
+                output_format,
+                run_metadata,
+                arg.ratio_deadline_to_period,
+                arg.enable_preemption,
+            )?;
+        }
+        (None, Some(dag_dir_path)) => {
+            run_metadata.add_input_dir(dag_dir_path)?;
+            run_cpc_dag_set(
+                create_dag_set_from_dir(dag_dir_path)?,
+                arg.number_of_cores,
+                &arg.output_dir_path,
+                output_format,
+                run_metadata,
+                arg.ratio_deadline_to_period,
+                arg.enable_preemption,
+            )?;
+        }
+        (None, None) => unreachable!("clap requires either of them."),
     }
-    let mut dag = create_dag_from_yaml(&arg.dag_file_path, false);
-    let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
-    prioritization_cpc_model::assign_priority_to_cpc_model(&mut dag);
-    let mut fixed_priority_scheduler = FixedPriorityScheduler::new(&dag, &homogeneous_processor);
-    let (schedule_length, _) = fixed_priority_scheduler.schedule();
-    let constrained_end_to_end_deadline = if let Some(deadline) = dag.get_end_to_end_deadline() {
-        deadline as f32
-    } else {
-        warn!("Since the end-to-end deadline is not set in the input DAG, the end-to-end deadline is determined using ratio_deadline_to_period.");
-        dag.get_head_period().unwrap() as f32 * arg.ratio_deadline_to_period
-    };
-    let result = (schedule_length as f32) <= constrained_end_to_end_deadline;
-    let file_path = fixed_priority_scheduler.dump_log(&arg.output_dir_path, "cpc_model_based");
-
-    dump_dag_scheduler_result_to_yaml(
-        &file_path,
-        schedule_length,
-        arg.ratio_deadline_to_period,
-        result,
-    );
+    Ok(())
 }
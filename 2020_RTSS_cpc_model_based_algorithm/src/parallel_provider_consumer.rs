@@ -13,7 +13,7 @@ use std::collections::{BTreeMap, HashSet, VecDeque};
 /// Algorithm 1: Step1 identifying capacity providers.
 /// capacity provider is a sub paths of the critical path
 pub fn get_providers(
-    dag: &Graph<NodeData, i32>,
+    dag: &Graph<NodeData, i64>,
     critical_path: &[NodeIndex],
 ) -> Vec<Vec<NodeIndex>> {
     let mut deque_critical_path: VecDeque<NodeIndex> = critical_path.iter().copied().collect();
@@ -34,7 +34,7 @@ pub fn get_providers(
 /// Capacity consumers represent specific non-critical nodes.
 /// F_consumers is a consumer set that can be simultaneous executed capacity providers, and whose execution delays the start of the next capacity providers.
 pub fn get_f_consumers(
-    dag: &mut Graph<NodeData, i32>,
+    dag: &mut Graph<NodeData, i64>,
     critical_path: &[NodeIndex],
 ) -> BTreeMap<Vec<NodeIndex>, Vec<NodeIndex>> {
     let providers = get_providers(dag, critical_path);
@@ -61,12 +61,12 @@ pub fn get_f_consumers(
     f_consumers
 }
 
-/// G_consumers is a consumer set belongs to the consumer set of the later providers, but can run in parallel with the capacity provider.
-/// Commented out because it is used only for the priority decision algorithm, rules of α-β pair analysis, Lemma, and equations, and is not involved in this simulator implementation.
-/// However, since there is a possibility that analytical α-β pair analysis will be implemented in the future, it has not been removed.
+// G_consumers is a consumer set belongs to the consumer set of the later providers, but can run in parallel with the capacity provider.
+// Commented out because it is used only for the priority decision algorithm, rules of α-β pair analysis, Lemma, and equations, and is not involved in this simulator implementation.
+// However, since there is a possibility that analytical α-β pair analysis will be implemented in the future, it has not been removed.
 /*
 pub fn get_g_consumers(
-    mut dag: Graph<NodeData, i32>,
+    mut dag: Graph<NodeData, i64>,
     critical_path: Vec<NodeIndex>,
 ) -> BTreeMap<Vec<NodeIndex>, Vec<NodeIndex>> {
     let mut providers = get_providers(&dag, critical_path);
@@ -111,15 +111,15 @@ mod tests {
     use super::*;
     use std::collections::BTreeMap;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
     ///DAG in Figure 2 (b) of the paper
-    fn create_sample_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 1));
         let c1 = dag.add_node(create_node(1, "execution_time", 1));
@@ -163,8 +163,8 @@ mod tests {
         dag
     }
 
-    fn create_sample_dag_not_consolidated() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag_not_consolidated() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
 
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 3));
@@ -189,7 +189,7 @@ mod tests {
 
     #[test]
     fn test_get_providers_normal() {
-        let mut dag = create_sample_dag();
+        let dag = create_sample_dag();
         let critical_path = dag.get_critical_path();
         let providers = get_providers(&dag, &critical_path);
         assert_eq!(providers.len(), 4);
@@ -203,7 +203,7 @@ mod tests {
 
     #[test]
     fn test_get_providers_dag_not_consolidated() {
-        let mut dag = create_sample_dag_not_consolidated();
+        let dag = create_sample_dag_not_consolidated();
         let critical_path = dag.get_critical_path();
         let providers = get_providers(&dag, &critical_path);
         assert_eq!(providers.len(), 3);
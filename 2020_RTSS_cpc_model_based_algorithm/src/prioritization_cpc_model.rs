@@ -1,14 +1,17 @@
 use crate::parallel_provider_consumer::{get_f_consumers, get_providers};
-use lib::graph_extension::{GraphExtension, NodeData};
+use lib::{
+    critical_path::CriticalPathAnalysis,
+    graph_extension::{GraphExtension, NodeData},
+};
 use petgraph::graph::{Graph, NodeIndex};
 
 //Create a dag for f_consumer only
 //NodeIndex is changed, but NodeData is retained.
 //Example: id and params information
 fn create_shrunk_dag(
-    referenced_dag: &mut Graph<NodeData, i32>,
+    referenced_dag: &mut Graph<NodeData, i64>,
     retained_nodes: Vec<NodeIndex>,
-) -> Graph<NodeData, i32> {
+) -> Graph<NodeData, i64> {
     let mut shrunk_dag = referenced_dag.clone();
     for node_i in shrunk_dag.node_indices().rev() {
         if !retained_nodes.contains(&node_i) && shrunk_dag.remove_node(node_i).is_none() {
@@ -19,7 +22,7 @@ fn create_shrunk_dag(
 }
 
 fn convert_shrunk_indices_to_original(
-    shrunk_dag: &Graph<NodeData, i32>,
+    shrunk_dag: &Graph<NodeData, i64>,
     shrunk_indices: &[NodeIndex],
 ) -> Vec<NodeIndex> {
     shrunk_indices
@@ -29,9 +32,9 @@ fn convert_shrunk_indices_to_original(
 }
 
 fn prioritize_path_from_head_with_increment(
-    original_dag: &mut Graph<NodeData, i32>,
+    original_dag: &mut Graph<NodeData, i64>,
     path: &[NodeIndex],
-    base_priority: &mut i32,
+    base_priority: &mut i64,
 ) {
     for node_i in path {
         if !original_dag[*node_i].params.contains_key("priority") {
@@ -42,22 +45,43 @@ fn prioritize_path_from_head_with_increment(
 }
 
 #[allow(dead_code)] //TODO: remove
-pub fn assign_priority_to_cpc_model(dag: &mut Graph<NodeData, i32>) {
+pub fn assign_priority_to_cpc_model(dag: &mut Graph<NodeData, i64>) {
     assign_priority_to_cpc_model_core(dag, &mut dag.clone(), &mut 0);
 }
 
+/// The priorities are of the nodes in each DAG, and order the nodes of the same deadline in the DAG set scheduler.
+/// The nodes left out of the CPC model, such as the branch to the second sink of a DAG, get the lowest priorities
+/// in the order of their indices, so that every node can be preempted by its priority.
+pub fn assign_priority_to_cpc_model_for_dag_set(dag_set: &mut [Graph<NodeData, i64>]) {
+    for dag in dag_set.iter_mut() {
+        assign_priority_to_cpc_model(dag);
+        let mut next_priority = dag
+            .node_weights()
+            .filter_map(|node| node.params.get("priority"))
+            .max()
+            .map_or(0, |priority| priority + 1);
+        for node_i in dag.node_indices() {
+            if !dag[node_i].params.contains_key("priority") {
+                dag.add_param(node_i, "priority", next_priority);
+                next_priority += 1;
+            }
+        }
+    }
+}
+
 fn assign_priority_to_cpc_model_core(
-    original_dag: &mut Graph<NodeData, i32>,
-    shrunk_dag: &mut Graph<NodeData, i32>,
-    current_priority: &mut i32,
+    original_dag: &mut Graph<NodeData, i64>,
+    shrunk_dag: &mut Graph<NodeData, i64>,
+    current_priority: &mut i64,
 ) {
-    let critical_path = shrunk_dag.get_critical_path();
-    let providers = get_providers(shrunk_dag, &critical_path);
-    let f_consumers = get_f_consumers(shrunk_dag, &critical_path);
+    let critical_path_analysis = CriticalPathAnalysis::new(shrunk_dag);
+    let critical_path = critical_path_analysis.get_critical_path();
+    let providers = get_providers(shrunk_dag, critical_path);
+    let f_consumers = get_f_consumers(shrunk_dag, critical_path);
     //Rule 1. Priority is given to critical nodes
     prioritize_path_from_head_with_increment(
         original_dag,
-        &convert_shrunk_indices_to_original(shrunk_dag, &critical_path),
+        &convert_shrunk_indices_to_original(shrunk_dag, critical_path),
         current_priority,
     );
     //Rule 2. Priority is given to consumers for providers located before
@@ -65,12 +89,15 @@ fn assign_priority_to_cpc_model_core(
         if let Some(f_consumer) = f_consumers.get(&provider) {
             let mut f_consumer_dag = create_shrunk_dag(shrunk_dag, f_consumer.to_vec());
             while f_consumer_dag.node_count() != 0 {
-                let f_consumer_critical_path = f_consumer_dag.get_critical_path();
+                // The f-consumer DAG loses its critical path on each iteration, so it is analyzed again.
+                let f_consumer_critical_path_analysis = CriticalPathAnalysis::new(&f_consumer_dag);
+                let f_consumer_critical_path =
+                    f_consumer_critical_path_analysis.get_critical_path();
                 //recursion if there are dependencies in the f-consumer.
                 if f_consumer_critical_path.iter().any(|&node_i| {
                     f_consumer_dag
                         .get_pre_nodes(node_i)
-                        .map_or(false, |pre_nodes| pre_nodes.len() > 1)
+                        .is_some_and(|pre_nodes| pre_nodes.len() > 1)
                 }) {
                     assign_priority_to_cpc_model_core(
                         original_dag,
@@ -83,12 +110,12 @@ fn assign_priority_to_cpc_model_core(
                         original_dag,
                         &convert_shrunk_indices_to_original(
                             &f_consumer_dag,
-                            &f_consumer_critical_path,
+                            f_consumer_critical_path,
                         ),
                         current_priority,
                     );
                 }
-                f_consumer_dag.remove_nodes(&f_consumer_critical_path);
+                f_consumer_dag.remove_nodes(f_consumer_critical_path);
             }
         }
     }
@@ -97,16 +124,27 @@ fn assign_priority_to_cpc_model_core(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::BTreeMap;
+    use lib::{
+        dag_creator::{create_dag_from_yaml, create_dag_set_from_dir},
+        dag_scheduler::DAGSchedulerBase,
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        fixed_priority_scheduler::FixedPriorityScheduler,
+        global_edf_scheduler::GlobalEDFScheduler,
+        golden::assert_matches_golden,
+        homogeneous::HomogeneousProcessor,
+        log::dump_dag_scheduler_result_to_yaml,
+        processor::ProcessorBase,
+    };
+    use std::{collections::BTreeMap, fs::remove_file};
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
-    fn create_sample_dag_not_consolidated() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag_not_consolidated() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
 
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 10));
@@ -141,8 +179,8 @@ mod tests {
         dag
     }
 
-    fn create_sample_dag_complex() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag_complex() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
 
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 10));
@@ -178,8 +216,8 @@ mod tests {
     }
 
     ///DAG in Figure 2 (b) of the paper
-    fn create_sample_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 4));
         let c1 = dag.add_node(create_node(1, "execution_time", 4));
@@ -265,4 +303,78 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_assign_priority_cpc_model_for_dag_set_normal() {
+        let mut dag_set = vec![create_sample_dag(), create_sample_dag_not_consolidated()];
+        let expected_values = [
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+            vec![0, 1, 2, 8, 6, 3, 7, 4, 5],
+        ];
+
+        assign_priority_to_cpc_model_for_dag_set(&mut dag_set);
+        for (dag, expected_value) in dag_set.iter().zip(expected_values.iter()) {
+            for node_i in dag.node_indices() {
+                assert_eq!(
+                    dag[node_i].params["priority"],
+                    expected_value[dag[node_i].id as usize]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_priority_cpc_model_for_dag_set_multiple_sinks() {
+        let mut dag_set =
+            vec![
+                create_dag_from_yaml("../lib/tests/golden_logs/dag_set/dag_2.yaml", false).unwrap(),
+            ];
+
+        assign_priority_to_cpc_model_for_dag_set(&mut dag_set);
+        // The branch to the second sink is out of the CPC model, so it has the lowest priority.
+        let priorities = dag_set[0]
+            .node_weights()
+            .map(|node| node.params["priority"])
+            .collect::<Vec<_>>();
+        assert_eq!(priorities, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_schedule_dag_set_preemptive_by_cpc_priority() {
+        let mut dag_set = create_dag_set_from_dir("../lib/tests/golden_logs/dag_set").unwrap();
+        assign_priority_to_cpc_model_for_dag_set(&mut dag_set);
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::LexicographicPreemptive {
+                keys: vec!["node_absolute_deadline".to_string(), "priority".to_string()],
+            })
+            .unwrap();
+
+        // The earliest deadline preempts first, and the CPC priorities break the ties of the deadlines.
+        assert_eq!(schedule_outcome.worst_response_times, vec![35, 81, 18]);
+    }
+
+    #[test]
+    fn test_assign_priority_cpc_model_golden_log() {
+        let mut dag =
+            create_dag_from_yaml("../lib/tests/golden_logs/dag_set/dag_0.yaml", false).unwrap();
+        assign_priority_to_cpc_model(&mut dag);
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(4));
+        let (schedule_length, _) = fixed_priority_scheduler.schedule();
+        let file_path = fixed_priority_scheduler.dump_log("../lib/tests", "golden_cpc_model_based");
+        dump_dag_scheduler_result_to_yaml(
+            &file_path,
+            schedule_length,
+            1.0,
+            schedule_length <= dag.get_end_to_end_deadline().unwrap(),
+        );
+
+        assert_matches_golden(
+            &file_path,
+            "../lib/tests/golden_logs/golden_cpc_model_based.yaml",
+        );
+        remove_file(file_path).unwrap();
+    }
 }
@@ -7,18 +7,35 @@
 //! -----------------
 use getset::{CopyGetters, Setters};
 use lib::{
+    admission_control::OnlineAdmission,
+    aperiodic_arrival::AperiodicArrivalTrace,
     core::ProcessResult,
     dag_scheduler::DAGSchedulerBase,
-    dag_set_scheduler::{DAGSetSchedulerBase, DAGState, DAGStateManagerBase, PreemptiveType},
+    dag_set_scheduler::{
+        DAGSetSchedulerBase, DAGState, DAGStateManagerBase, DeadlineMissPolicy, PreemptiveType,
+        ScheduleOutcome,
+    },
+    diagnostics::DiagnosticCollector,
+    error::Result,
+    event_script::EventScript,
     getset_dag_set_scheduler, getset_dag_state_manager,
     graph_extension::{GraphExtension, NodeData},
     homogeneous::HomogeneousProcessor,
+    interconnect::Interconnect,
     log::DAGSetSchedulerLog,
+    logical_execution_time::CommunicationSemantics,
+    mode_change::ModeSchedule,
+    network::Network,
     processor::ProcessorBase,
-    util::{get_hyper_period, get_process_core_indices},
+    simulation_horizon::SimulationHorizon,
+    sporadic_release::{validate_jitters, ArrivalTrace, ReleaseJitter},
+    util::{get_overhead_core_indices, get_process_core_indices},
 };
 use petgraph::{graph::NodeIndex, Graph};
-use std::collections::VecDeque;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Instant,
+};
 
 /// Calculate the execution order when minimum number of cores required to meet the end-to-end deadline.
 ///
@@ -26,54 +43,117 @@ use std::collections::VecDeque;
 ///
 /// * `dag` - The DAG to be scheduled.
 ///
+/// * `slowest_speed` - The speed of the slowest core of the platform.
+///
+/// * `number_of_cores` - The number of cores of the platform, which bounds the search.
+///
 /// # Returns
 ///
 /// * The minimum number of cores required to meet the end-to-end deadline.
+///
 /// * A vector of NodeIndex, representing the execution order of the tasks.
 ///
 /// # Description
 ///
 /// This function calculates the minimum number of cores required to meet the end-to-end deadline of the DAG.
 /// In addition, it returns the execution order of the tasks when the minimum number of cores are used.
+/// The number of cores is binary-searched between the lower bound by the volume and `number_of_cores`,
+/// assuming that the schedule length does not increase with more cores.
+/// If the deadline is missed even on `number_of_cores`, the DAG needs more cores than the platform has,
+/// so `number_of_cores + 1` is returned with the execution order on `number_of_cores`.
+/// The DAG is scheduled on the cores created by `T::new`, which run at speed 1.
+/// The cores allocated to the DAG on a heterogeneous platform are at least as fast as the slowest one,
+/// so the schedule length is stretched by the slowest speed. It is an upper bound
+/// because the execution order is fixed and thus free of timing anomalies.
 ///
 /// # Example
 ///
 /// Refer to the examples in the tests code.
 ///
 fn calculate_minimum_cores_and_execution_order<T>(
-    dag: &Graph<NodeData, i32>,
+    dag: &Graph<NodeData, i64>,
     scheduler: &mut impl DAGSchedulerBase<T>,
+    slowest_speed: f64,
+    number_of_cores: usize,
 ) -> (usize, VecDeque<NodeIndex>)
 where
     T: ProcessorBase + Clone,
 {
     let volume = dag.get_volume();
     let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap();
-    let mut minimum_cores = (volume as f32 / end_to_end_deadline as f32).ceil() as usize;
-
+    let lower_bound = ((volume as f64 / (end_to_end_deadline as f64 * slowest_speed)).ceil()
+        as usize)
+        .clamp(1, number_of_cores);
     scheduler.set_dag(dag);
-    scheduler.set_processor(&T::new(minimum_cores));
-
-    let (mut schedule_length, mut execution_order) = scheduler.schedule();
+    let mut schedule_on = |number_of_cores: usize| {
+        scheduler.set_processor(&T::new(number_of_cores));
+        let (schedule_length, execution_order) = scheduler.schedule();
+        let meets_deadline = schedule_length as f64 / slowest_speed <= end_to_end_deadline as f64;
+        (meets_deadline, execution_order)
+    };
+
+    let (meets_deadline, mut execution_order) = schedule_on(number_of_cores);
+    if !meets_deadline {
+        return (number_of_cores + 1, execution_order);
+    }
 
-    while schedule_length > end_to_end_deadline {
-        minimum_cores += 1;
-        scheduler.set_processor(&T::new(minimum_cores));
-        (schedule_length, execution_order) = scheduler.schedule();
+    // The deadline is met on upper_bound cores and missed below low.
+    let (mut low, mut upper_bound) = (lower_bound, number_of_cores);
+    while low < upper_bound {
+        let middle = (low + upper_bound) / 2;
+        let (meets_deadline, middle_execution_order) = schedule_on(middle);
+        if meets_deadline {
+            upper_bound = middle;
+            execution_order = middle_execution_order;
+        } else {
+            low = middle + 1;
+        }
     }
 
-    (minimum_cores, execution_order)
+    (upper_bound, execution_order)
+}
+
+/// Everything of a DAG the minimum-core search depends on, i.e., the DAG except its id,
+/// so that the identical DAGs of a set share the result.
+#[derive(PartialEq, Eq, Hash)]
+struct MinimumCoresKey {
+    nodes: Vec<(i64, BTreeMap<String, i64>)>,
+    edges: Vec<(usize, usize, i64)>,
+}
+
+impl MinimumCoresKey {
+    fn new(dag: &Graph<NodeData, i64>) -> Self {
+        Self {
+            nodes: dag
+                .node_weights()
+                .map(|node| {
+                    let mut params = node.params.clone();
+                    params.remove("dag_id");
+                    (node.id, params)
+                })
+                .collect(),
+            edges: dag
+                .raw_edges()
+                .iter()
+                .map(|edge| (edge.source().index(), edge.target().index(), edge.weight))
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, Default, CopyGetters, Setters)]
 pub struct DynFedDAGStateManager {
     #[getset(get_copy = "pub with_prefix", set = "pub")]
-    minimum_cores: i32,
-    num_using_cores: i32,
-    num_allocated_cores: i32,
+    minimum_cores: i64,
+    num_using_cores: i64,
+    num_allocated_cores: i64,
     execution_order: VecDeque<NodeIndex>,
     initial_execution_order: VecDeque<NodeIndex>,
-    release_count: i32,
+    release_count: i64,
+    aperiodic_release_count: i64,
+    skipped_release_count: i64,
+    absolute_deadline: i64,
+    pending_arrival_times: VecDeque<i64>,
     dag_state: DAGState,
 }
 
@@ -93,7 +173,7 @@ impl DynFedDAGStateManager {
         self.set_dag_state(DAGState::Running);
     }
 
-    fn can_start(&self, idle_core_num: i32) -> bool {
+    fn can_start(&self, idle_core_num: i64) -> bool {
         (self.dag_state == DAGState::Ready) && (self.minimum_cores <= idle_core_num)
     }
 
@@ -106,7 +186,7 @@ impl DynFedDAGStateManager {
         self.execution_order.front()
     }
 
-    fn get_unused_cores(&self) -> i32 {
+    fn get_unused_cores(&self) -> i64 {
         self.num_allocated_cores - self.num_using_cores
     }
 
@@ -120,7 +200,7 @@ impl DynFedDAGStateManager {
     }
 }
 
-fn get_total_allocated_cores(expansion_managers: &[DynFedDAGStateManager]) -> i32 {
+fn get_total_allocated_cores(expansion_managers: &[DynFedDAGStateManager]) -> i64 {
     let mut total_allocated_cores = 0;
     for expansion_manager in expansion_managers {
         total_allocated_cores += expansion_manager.num_allocated_cores;
@@ -128,52 +208,110 @@ fn get_total_allocated_cores(expansion_managers: &[DynFedDAGStateManager]) -> i3
     total_allocated_cores
 }
 
-pub struct DynamicFederatedScheduler<T>
+/// The cores are counted regardless of their speeds, and the minimum cores of a DAG are computed
+/// for the slowest core of the processor, so that any cores allocated to the DAG meet its deadline.
+pub struct DynamicFederatedScheduler<T, P = HomogeneousProcessor>
 where
-    T: DAGSchedulerBase<HomogeneousProcessor>,
+    T: DAGSchedulerBase<P>,
+    P: ProcessorBase + Clone,
 {
-    dag_set: Vec<Graph<NodeData, i32>>,
-    processor: HomogeneousProcessor,
+    dag_set: Vec<Graph<NodeData, i64>>,
+    processor: P,
     scheduler: T,
     log: DAGSetSchedulerLog,
-    current_time: i32,
+    current_time: i64,
+    event_script: EventScript,
+    interconnect: Option<Interconnect>,
+    network: Option<Network>,
+    arrival_trace: Option<ArrivalTrace>,
+    aperiodic_arrival_trace: Option<AperiodicArrivalTrace>,
+    release_jitter: ReleaseJitter,
+    deadline_miss_policy: DeadlineMissPolicy,
+    simulation_horizon: SimulationHorizon,
+    mode_schedule: Option<ModeSchedule>,
+    online_admission: Option<OnlineAdmission>,
+    communication_semantics: CommunicationSemantics,
 }
 
-impl<T> DAGSetSchedulerBase<HomogeneousProcessor> for DynamicFederatedScheduler<T>
+impl<T, P> DAGSetSchedulerBase<P> for DynamicFederatedScheduler<T, P>
 where
-    T: DAGSchedulerBase<HomogeneousProcessor>,
+    T: DAGSchedulerBase<P>,
+    P: ProcessorBase + Clone,
 {
-    getset_dag_set_scheduler!(HomogeneousProcessor);
+    getset_dag_set_scheduler!(P);
 
-    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+    fn new(dag_set: &[Graph<NodeData, i64>], processor: &P) -> Self {
         Self {
             dag_set: dag_set.to_vec(),
             processor: processor.clone(),
-            scheduler: T::new(&Graph::<NodeData, i32>::new(), processor),
+            scheduler: T::new(&Graph::<NodeData, i64>::new(), processor),
             log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
             current_time: 0,
+            event_script: EventScript::default(),
+            interconnect: None,
+            network: None,
+            arrival_trace: None,
+            aperiodic_arrival_trace: None,
+            release_jitter: ReleaseJitter::default(),
+            deadline_miss_policy: DeadlineMissPolicy::default(),
+            simulation_horizon: SimulationHorizon::default(),
+            mode_schedule: None,
+            online_admission: None,
+            communication_semantics: CommunicationSemantics::default(),
         }
     }
 
-    fn schedule(&mut self, _: PreemptiveType) -> i32 {
+    fn schedule(&mut self, _: PreemptiveType) -> Result<ScheduleOutcome> {
+        let diagnostic_collector = DiagnosticCollector::new();
+        self.log
+            .write_execution_time_seed(self.processor.get_execution_time_seed());
+        if let Some(arrival_trace) = &self.arrival_trace {
+            arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(aperiodic_arrival_trace) = &self.aperiodic_arrival_trace {
+            aperiodic_arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(mode_schedule) = &self.mode_schedule {
+            mode_schedule.validate(&self.dag_set)?;
+        }
+        validate_jitters(&self.dag_set)?;
+
         // Initialize DAGStateManagers
+        let preprocessing_start_time = Instant::now();
         let mut managers = vec![DynFedDAGStateManager::default(); self.dag_set.len()];
+        let slowest_speed = (0..self.processor.get_number_of_cores())
+            .map(|core_id| self.processor.get_core_speed(core_id))
+            .fold(f64::INFINITY, f64::min);
+        let mut minimum_cores_cache = HashMap::new();
         for dag in self.dag_set.iter() {
             let dag_id = dag.get_dag_param("dag_id") as usize;
-            let (minimum_cores, execution_order) =
-                calculate_minimum_cores_and_execution_order(dag, &mut self.scheduler);
-            managers[dag_id].set_minimum_cores(minimum_cores as i32);
+            let (minimum_cores, execution_order) = minimum_cores_cache
+                .entry(MinimumCoresKey::new(dag))
+                .or_insert_with(|| {
+                    calculate_minimum_cores_and_execution_order(
+                        dag,
+                        &mut self.scheduler,
+                        slowest_speed,
+                        self.processor.get_number_of_cores(),
+                    )
+                })
+                .clone();
+            managers[dag_id].set_minimum_cores(minimum_cores as i64);
             managers[dag_id].set_execution_order(execution_order);
         }
+        self.log
+            .write_preprocessing_time(preprocessing_start_time.elapsed());
 
         // Start scheduling
-        let hyper_period = get_hyper_period(&self.dag_set);
-        while self.get_current_time() < hyper_period {
+        let end_time = self.get_end_time()?;
+        while self.get_current_time() < end_time {
+            self.handle_mode_changes(&managers);
+
             // Release DAGs
             self.release_dags(&mut managers);
             // Start DAGs if there are free cores
             let mut idle_core_num =
-                self.processor.get_number_of_cores() as i32 - get_total_allocated_cores(&managers);
+                self.processor.get_number_of_cores() as i64 - get_total_allocated_cores(&managers);
             for manager in managers.iter_mut() {
                 if manager.can_start(idle_core_num) {
                     manager.start();
@@ -182,21 +320,17 @@ where
             }
 
             // Allocate the nodes of ready_queue to idle cores
-            for dag in self.get_dag_set().iter() {
-                let dag_id = dag.get_dag_param("dag_id") as usize;
-                if managers[dag_id].get_dag_state() != DAGState::Running {
+            for (dag_id, manager) in managers.iter_mut().enumerate() {
+                if manager.get_dag_state() != DAGState::Running {
                     continue;
                 }
 
-                while let Some(node_i) = managers[dag_id].get_execution_order_head() {
-                    if dag.is_node_ready(*node_i) && managers[dag_id].get_unused_cores() > 0 {
+                while let Some(node_i) = manager.get_execution_order_head() {
+                    if self.dag_set[dag_id].is_node_ready(*node_i) && manager.get_unused_cores() > 0
+                    {
                         let core_id = self.processor.get_idle_core_index().unwrap();
-                        let node = &dag[managers[dag_id].allocate_head()];
-                        self.allocate_node(
-                            node,
-                            core_id,
-                            managers[dag_id].get_release_count() as usize,
-                        );
+                        let node = self.dag_set[dag_id][manager.allocate_head()].clone();
+                        self.allocate_node(&node, core_id, manager.get_release_count() as usize);
                     } else {
                         break;
                     }
@@ -206,6 +340,8 @@ where
             let process_result = self.process_unit_time();
             let indices: Vec<usize> = get_process_core_indices(&process_result);
             self.log.write_processing_time(&indices);
+            let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+            self.log.write_overhead_time(&indices);
 
             // Post-process on completion of node execution
             for (core_id, result) in process_result.iter().enumerate() {
@@ -215,31 +351,47 @@ where
                     self.post_process_on_node_completion(node_data, core_id, &mut managers);
                 }
             }
+
+            // Handle deadline misses
+            let missed_dag_ids = self.handle_deadline_misses(&mut managers);
+            match self.deadline_miss_policy {
+                DeadlineMissPolicy::Abort if !missed_dag_ids.is_empty() => break,
+                DeadlineMissPolicy::KillJob => {
+                    for dag_id in missed_dag_ids {
+                        let num_killed_nodes = self.kill_job(dag_id, &mut managers[dag_id]);
+                        managers[dag_id].num_using_cores -= num_killed_nodes as i64;
+                    }
+                }
+                _ => {}
+            }
         }
 
         self.calculate_log();
-        self.get_current_time()
+        Ok(self.get_schedule_outcome(diagnostic_collector.finish()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lib::dag_creator::create_dag_set_from_dir;
     use lib::fixed_priority_scheduler::FixedPriorityScheduler;
+    use lib::golden::assert_matches_golden;
     use lib::homogeneous::HomogeneousProcessor;
     use lib::processor::ProcessorBase;
-    use lib::util::load_yaml;
+    use lib::util::{adjust_to_implicit_deadline, load_yaml};
     use std::collections::BTreeMap;
     use std::fs::remove_file;
+    use std::time::Duration;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
-    fn create_sample_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 10));
         let c1 = dag.add_node(create_node(1, "execution_time", 20));
@@ -263,8 +415,8 @@ mod tests {
         dag
     }
 
-    fn create_sample_dag2() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag2() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 11));
         let c1 = dag.add_node(create_node(1, "execution_time", 21));
@@ -285,6 +437,42 @@ mod tests {
         dag
     }
 
+    #[test]
+    fn test_calculate_minimum_cores_on_slower_cores() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 10));
+        dag.add_param(source, "period", 100);
+        for node_id in 1..5 {
+            let node_i = dag.add_node(create_node(node_id, "execution_time", 20));
+            dag.add_param(node_i, "end_to_end_deadline", 80);
+            dag.add_edge(source, node_i, 1);
+        }
+        let mut scheduler = FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(4));
+
+        let (minimum_cores, _) =
+            calculate_minimum_cores_and_execution_order(&dag, &mut scheduler, 1.0, 4);
+        assert_eq!(minimum_cores, 2);
+        // The schedule length of 50 on 3 cores takes 100 at half the speed, which exceeds the deadline.
+        let (minimum_cores, _) =
+            calculate_minimum_cores_and_execution_order(&dag, &mut scheduler, 0.5, 4);
+        assert_eq!(minimum_cores, 4);
+        // Even 4 cores are not enough at a quarter of the speed.
+        let (minimum_cores, _) =
+            calculate_minimum_cores_and_execution_order(&dag, &mut scheduler, 0.25, 4);
+        assert_eq!(minimum_cores, 5);
+    }
+
+    #[test]
+    fn test_minimum_cores_key_ignores_dag_id() {
+        let mut dag = create_sample_dag();
+        let mut same_dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        same_dag.set_dag_param("dag_id", 1);
+
+        assert!(MinimumCoresKey::new(&dag) == MinimumCoresKey::new(&same_dag));
+        assert!(MinimumCoresKey::new(&dag) != MinimumCoresKey::new(&create_sample_dag2()));
+    }
+
     #[test]
     fn test_dynfed_normal() {
         let mut dag = create_sample_dag();
@@ -295,11 +483,13 @@ mod tests {
 
         let mut dynfed: DynamicFederatedScheduler<FixedPriorityScheduler<HomogeneousProcessor>> =
             DynamicFederatedScheduler::new(&dag_set, &HomogeneousProcessor::new(5));
-        let time = dynfed.schedule(PreemptiveType::NonPreemptive);
-        assert_eq!(time, 300);
+        let schedule_outcome = dynfed.schedule(PreemptiveType::NonPreemptive).unwrap();
+        assert_eq!(schedule_outcome.schedule_length, 300);
+        // The minimum-core search is reported as pre-processing.
+        assert!(schedule_outcome.preprocessing_time > Duration::ZERO);
 
         let file_path = dynfed.dump_log("../lib/tests", "dyn_test");
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         assert_eq!(
@@ -449,4 +639,17 @@ mod tests {
 
         remove_file(file_path).unwrap();
     }
+
+    #[test]
+    fn test_dynfed_golden_log() {
+        let mut dag_set = create_dag_set_from_dir("../lib/tests/golden_logs/dag_set").unwrap();
+        adjust_to_implicit_deadline(&mut dag_set);
+        let mut dynfed: DynamicFederatedScheduler<FixedPriorityScheduler<HomogeneousProcessor>> =
+            DynamicFederatedScheduler::new(&dag_set, &HomogeneousProcessor::new(4));
+        dynfed.schedule(PreemptiveType::NonPreemptive).unwrap();
+        let file_path = dynfed.dump_log("../lib/tests", "golden_dynfed");
+
+        assert_matches_golden(&file_path, "../lib/tests/golden_logs/golden_dynfed.yaml");
+        remove_file(file_path).unwrap();
+    }
 }
@@ -1,17 +1,21 @@
-mod dynfed;
-
 use clap::Parser;
-use dynfed::DynamicFederatedScheduler;
 use lib::{
     dag_creator::create_dag_set_from_dir,
     dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+    error::Result,
     fixed_priority_scheduler::FixedPriorityScheduler,
-    graph_extension::GraphExtension,
     homogeneous::HomogeneousProcessor,
-    log::dump_dag_set_scheduler_result_to_yaml,
+    log::{
+        dump_dag_set_scheduler_result_to_yaml, dump_run_time_to_yaml,
+        dump_schedulability_verdicts_to_yaml, print_result_summary, OutputFormat, RunTimeLog,
+    },
     processor::ProcessorBase,
-    util::{adjust_to_implicit_deadline, load_yaml},
+    run_metadata::RunMetadata,
+    schedulability_test::test_all,
+    util::adjust_to_implicit_deadline,
+    visualization::dump_gantt_svg,
 };
+use rtcsa_dynfed::dynfed::DynamicFederatedScheduler;
 
 #[derive(Parser)]
 #[clap(
@@ -32,36 +36,66 @@ struct ArgParser {
     ///Path to output directory.
     #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
     output_dir_path: String,
+    ///Also dump the verdicts of the analytic schedulability tests.
+    #[clap(
+        long = "schedulability_tests",
+        alias = "schedulability-tests",
+        default_value = "false"
+    )]
+    schedulability_tests: bool,
+    ///Render the schedule as an SVG Gantt chart next to the log.
+    #[clap(long = "render_gantt", alias = "render-gantt", default_value = "false")]
+    render_gantt: bool,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
 }
 
-fn main() {
+fn main() -> Result<()> {
     let arg: ArgParser = ArgParser::parse();
+    let output_format = OutputFormat::from_name(&arg.output_format)?;
 
-    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path);
-    adjust_to_implicit_deadline(&mut dag_set);
+    let mut run_time_log = RunTimeLog::default();
+    let mut dag_set = create_dag_set_from_dir(&arg.dag_dir_path)?;
+    run_time_log.measure_preprocessing(|| adjust_to_implicit_deadline(&mut dag_set));
 
     let homogeneous_processor = HomogeneousProcessor::new(arg.number_of_cores);
     let mut dynfed_scheduler: DynamicFederatedScheduler<
         FixedPriorityScheduler<HomogeneousProcessor>,
     > = DynamicFederatedScheduler::new(&dag_set, &homogeneous_processor);
 
-    dynfed_scheduler.schedule(PreemptiveType::NonPreemptive);
+    let schedule_outcome = run_time_log
+        .measure_simulation(|| dynfed_scheduler.schedule(PreemptiveType::NonPreemptive))?;
+    // The minimum-core search is done inside the scheduling.
+    run_time_log.move_to_preprocessing(schedule_outcome.preprocessing_time);
+    let mut run_metadata = RunMetadata::new("dynfed", arg.number_of_cores);
+    run_metadata.add_input_dir(&arg.dag_dir_path)?;
+    dynfed_scheduler
+        .get_log_mut()
+        .write_run_metadata(run_metadata);
     let file_path = dynfed_scheduler.dump_log(&arg.output_dir_path, "FixedPriority");
+    if arg.render_gantt {
+        dump_gantt_svg(&schedule_outcome.log, &file_path.replace(".yaml", ".svg"));
+    }
 
     // Check the result
-    let yaml_doc = &load_yaml(&file_path)[0];
-    let dag_set_log = &yaml_doc["dag_set_log"];
-    let mut result = true;
-    for dag in dag_set {
-        if dag_set_log[dag.get_dag_param("dag_id") as usize]["worst_response_time"]
-            .as_i64()
-            .unwrap()
-            > dag.get_head_period().unwrap() as i64
-        {
-            result = false;
-            break;
-        }
-    }
+    let result = !schedule_outcome.has_deadline_miss();
 
     dump_dag_set_scheduler_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    if arg.schedulability_tests {
+        dump_schedulability_verdicts_to_yaml(&file_path, &test_all(&dag_set, arg.number_of_cores));
+    }
+    print_result_summary(
+        output_format,
+        &file_path,
+        result,
+        Some(schedule_outcome.schedule_length),
+        &schedule_outcome.worst_response_times,
+    );
+    Ok(())
 }
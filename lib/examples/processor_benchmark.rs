@@ -0,0 +1,58 @@
+//! Benchmark of the tick of the homogeneous processor over many cores.
+//!
+//! Keeps every core busy and reports the wall-clock time per tick for 64 to 1024 cores,
+//! once with long nodes, where a tick only advances the counters, and once with nodes completing every few ticks.
+//!
+//! ```bash
+//! cargo run --release -p lib --example processor_benchmark
+//! ```
+use lib::{
+    core::ProcessResult, graph_extension::NodeData, homogeneous::HomogeneousProcessor,
+    processor::ProcessorBase,
+};
+use std::{collections::BTreeMap, time::Instant};
+
+const NUMS_OF_CORES: [usize; 4] = [64, 128, 256, 1024];
+const EXECUTION_TIMES: [i64; 2] = [1_000_000, 4];
+const NUM_TICKS: usize = 100_000;
+
+fn create_node(id: i64, execution_time: i64) -> NodeData {
+    let mut params = BTreeMap::new();
+    params.insert("execution_time".to_string(), execution_time);
+    NodeData::new(id, params)
+}
+
+/// Tick the processor and reallocate the completed nodes, and return the nanoseconds per tick.
+fn run_case(num_cores: usize, execution_time: i64) -> f64 {
+    let mut processor = HomogeneousProcessor::new(num_cores);
+    for core_id in 0..num_cores {
+        processor.allocate_specific_core(core_id, &create_node(core_id as i64, execution_time));
+    }
+
+    let start = Instant::now();
+    for _ in 0..NUM_TICKS {
+        for (core_id, process_result) in processor.process().into_iter().enumerate() {
+            if let ProcessResult::Done(node_data) = process_result {
+                processor.allocate_specific_core(core_id, &node_data);
+            }
+        }
+    }
+    start.elapsed().as_nanos() as f64 / NUM_TICKS as f64
+}
+
+fn main() {
+    println!(
+        "{:>6} {:>16} {:>14}",
+        "cores", "execution time", "time [ns/tick]"
+    );
+    for num_cores in NUMS_OF_CORES {
+        for execution_time in EXECUTION_TIMES {
+            println!(
+                "{:>6} {:>16} {:>14.1}",
+                num_cores,
+                execution_time,
+                run_case(num_cores, execution_time)
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Scalability benchmark of the DAG set schedulers.
+//!
+//! Runs every combination of 10/100/1000 DAGs and 4/16/64 cores for each scheduler of SchedulerFactory,
+//! and reports the wall-clock simulation time and the peak RSS.
+//! G-EDF is also run preemptively, since the other schedulers ignore the preemptive type.
+//!
+//! ```bash
+//! cargo run --release -p lib --example scalability_benchmark
+//! ```
+//!
+//! Each case runs in its own child process so that the peak RSS of one case does not hide that of the others.
+//! The peak RSS is read from /proc and is reported as "n/a" on other platforms.
+use lib::{
+    dag_set_scheduler::PreemptiveType, graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor, processor::ProcessorBase,
+    scheduler_factory::SchedulerFactory, util::replicate_dag_set,
+};
+use petgraph::Graph;
+use std::{collections::BTreeMap, env, fs, process::Command, time::Instant};
+
+const NUMS_OF_DAGS: [usize; 3] = [10, 100, 1000];
+const NUMS_OF_CORES: [usize; 3] = [4, 16, 64];
+const PREEMPTIVE_SCHEDULER: &str = "global_edf";
+const PERIOD: i64 = 100;
+
+fn create_node(id: i64, execution_time: i64) -> NodeData {
+    let mut params = BTreeMap::new();
+    params.insert("execution_time".to_string(), execution_time);
+    NodeData::new(id, params)
+}
+
+/// Fork-join DAG whose copies make up the workload.
+fn create_template_dag() -> Graph<NodeData, i64> {
+    let mut dag = Graph::<NodeData, i64>::new();
+    let source = dag.add_node(create_node(0, 1));
+    dag[source].params.insert("period".to_string(), PERIOD);
+    let sink = dag.add_node(create_node(1, 1));
+    dag[sink]
+        .params
+        .insert("end_to_end_deadline".to_string(), PERIOD);
+    for (id, execution_time) in (2..6).zip(2..6) {
+        let fork = dag.add_node(create_node(id, execution_time));
+        dag.add_edge(source, fork, 0);
+        dag.add_edge(fork, sink, 0);
+    }
+    dag
+}
+
+fn get_peak_rss_kb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// The cases as pairs of the scheduler name and whether it is run preemptively.
+fn get_cases(factory: &SchedulerFactory) -> Vec<(&str, bool)> {
+    let mut cases = Vec::new();
+    for name in factory.get_names() {
+        cases.push((name, false));
+        if name == PREEMPTIVE_SCHEDULER {
+            cases.push((name, true));
+        }
+    }
+    cases
+}
+
+/// Run one case and print "<wall-clock ms> <peak RSS kB>".
+fn run_case(scheduler: &str, is_preemptive: bool, num_dags: usize, num_cores: usize) {
+    let dag_set = replicate_dag_set(&[create_template_dag()], num_dags, 1);
+    let processor = HomogeneousProcessor::new(num_cores);
+    let preemptive_type = if is_preemptive {
+        PreemptiveType::Preemptive {
+            key: "node_absolute_deadline".to_string(),
+        }
+    } else {
+        PreemptiveType::NonPreemptive
+    };
+    let factory = SchedulerFactory::default();
+
+    let start = Instant::now();
+    factory
+        .create(scheduler, &dag_set, &processor)
+        .unwrap()
+        .schedule(preemptive_type)
+        .unwrap();
+    let elapsed_ms = start.elapsed().as_millis();
+
+    let peak_rss = get_peak_rss_kb().map_or("n/a".to_string(), |kb| kb.to_string());
+    println!("{} {}", elapsed_ms, peak_rss);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() == 6 && args[1] == "--case" {
+        run_case(
+            &args[2],
+            args[3].parse().unwrap(),
+            args[4].parse().unwrap(),
+            args[5].parse().unwrap(),
+        );
+        return;
+    }
+
+    let current_exe = env::current_exe().unwrap();
+    let factory = SchedulerFactory::default();
+    println!(
+        "{:<20} {:>10} {:>6} {:>6} {:>12} {:>14}",
+        "scheduler", "preemptive", "dags", "cores", "time [ms]", "peak RSS [kB]"
+    );
+    for (scheduler, is_preemptive) in get_cases(&factory) {
+        for num_dags in NUMS_OF_DAGS {
+            for num_cores in NUMS_OF_CORES {
+                let output = Command::new(&current_exe)
+                    .args([
+                        "--case",
+                        scheduler,
+                        &is_preemptive.to_string(),
+                        &num_dags.to_string(),
+                        &num_cores.to_string(),
+                    ])
+                    .output()
+                    .unwrap();
+                if !output.status.success() {
+                    panic!(
+                        "The case failed: {} {} {} {}\n{}",
+                        scheduler,
+                        is_preemptive,
+                        num_dags,
+                        num_cores,
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+                let result = String::from_utf8(output.stdout).unwrap();
+                let mut result = result.split_whitespace();
+                println!(
+                    "{:<20} {:>10} {:>6} {:>6} {:>12} {:>14}",
+                    scheduler,
+                    is_preemptive,
+                    num_dags,
+                    num_cores,
+                    result.next().unwrap(),
+                    result.next().unwrap()
+                );
+            }
+        }
+    }
+}
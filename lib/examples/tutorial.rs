@@ -0,0 +1,204 @@
+//! A walk through the whole pipeline of the simulator in code:
+//! generate a DAG set, check it analytically, assign priorities, run two schedulers,
+//! verify the simulated schedules and export them as Gantt charts and Chrome traces.
+//!
+//! Copy this file as a template for experiments that the paper-specific binaries do not cover.
+//!
+//! ```text
+//! cargo run --example tutorial -- [output_dir_path]
+//! ```
+use lib::{
+    dag_scheduler::DAGSchedulerBase,
+    dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType, ScheduleOutcome},
+    dag_set_statistics::DAGSetStatistics,
+    error::SchedSimError,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    global_edf_scheduler::GlobalEDFScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    log::dump_dag_set_scheduler_result_to_yaml,
+    processor::ProcessorBase,
+    round_robin_scheduler::RoundRobinScheduler,
+    schedulability_test::test_all,
+    util::create_yaml,
+    visualization::dump_gantt_svg,
+    workload_calibration::GeneratorParams,
+};
+use petgraph::{visit::EdgeRef, Graph};
+
+const NUMBER_OF_CORES: usize = 4;
+const SEED: u64 = 0;
+
+/// Check the invariants every valid schedule satisfies, and return the first violation.
+/// A core runs at most one job at a time, and a job of a node starts after the same job of its predecessors finishes.
+fn verify_schedule(
+    dag_set: &[Graph<NodeData, i64>],
+    schedule_outcome: &ScheduleOutcome,
+) -> Result<(), String> {
+    let execution_slices = schedule_outcome.log.get_execution_slices();
+    for core_id in 0..NUMBER_OF_CORES {
+        let mut core_slices: Vec<_> = execution_slices
+            .iter()
+            .filter(|slice| slice.core_id == core_id)
+            .collect();
+        core_slices.sort_by_key(|slice| slice.start_time);
+        if let Some(overlap) = core_slices
+            .windows(2)
+            .find(|slices| slices[1].start_time < slices[0].end_time)
+        {
+            return Err(format!(
+                "Core {} runs DAG {} node {} and DAG {} node {} at the same time {}.",
+                core_id,
+                overlap[0].dag_id,
+                overlap[0].node_id,
+                overlap[1].dag_id,
+                overlap[1].node_id,
+                overlap[1].start_time
+            ));
+        }
+    }
+
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        for edge in dag.edge_references() {
+            let (pre_node_id, suc_node_id) = (dag[edge.source()].id, dag[edge.target()].id);
+            let pre_job_intervals = schedule_outcome
+                .log
+                .get_job_intervals(dag_id, pre_node_id as usize);
+            let suc_job_intervals = schedule_outcome
+                .log
+                .get_job_intervals(dag_id, suc_node_id as usize);
+            // The k-th job of the successor belongs to the same DAG job as the k-th job of the predecessor.
+            for (job_id, ((_, pre_finish_time), (suc_start_time, _))) in pre_job_intervals
+                .iter()
+                .zip(suc_job_intervals.iter())
+                .enumerate()
+            {
+                if suc_start_time < pre_finish_time {
+                    return Err(format!(
+                        "Job {} of DAG {} node {} starts at {} before its predecessor node {} finishes at {}.",
+                        job_id, dag_id, suc_node_id, suc_start_time, pre_node_id, pre_finish_time
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the result of a DAG set scheduler, verify it, and dump its log, Gantt chart and Chrome trace.
+fn report(
+    alg_name: &str,
+    dag_set: &[Graph<NodeData, i64>],
+    schedule_outcome: &ScheduleOutcome,
+    file_path: &str,
+) {
+    if let Err(violation) = verify_schedule(dag_set, schedule_outcome) {
+        panic!("The {} schedule is invalid: {}", alg_name, violation);
+    }
+    let result = !schedule_outcome.has_deadline_miss();
+    dump_dag_set_scheduler_result_to_yaml(file_path, result);
+    dump_gantt_svg(&schedule_outcome.log, &file_path.replace(".yaml", ".svg"));
+    // Open the trace in chrome://tracing or https://ui.perfetto.dev.
+    schedule_outcome
+        .log
+        .dump_chrome_trace(&file_path.replace(".yaml", "_trace.json"));
+    println!(
+        "{}: schedulable = {}, schedule length = {}, worst response times = {:?}, log = {}",
+        alg_name,
+        result,
+        schedule_outcome.schedule_length,
+        schedule_outcome.worst_response_times,
+        file_path
+    );
+}
+
+fn main() -> Result<(), SchedSimError> {
+    let output_dir_path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "../outputs".to_string());
+
+    // 1. Generate a DAG set.
+    // Each DAG is a G(n, p) DAG whose single source has the period and the implicit end-to-end deadline.
+    // The total utilization is split among the DAGs by UUniFast, and the same seed always yields the same DAG set.
+    // To load DAGs from YAML files instead, use dag_creator::create_dag_set_from_dir.
+    let generator_params = GeneratorParams {
+        number_of_dags: 3,
+        total_utilization: 2.0,
+        number_of_nodes: 8,
+        edge_probability: 0.3,
+        periods: vec![100, 200, 400],
+    };
+    let mut dag_set = generator_params.generate_dag_set(SEED);
+    let statistics = DAGSetStatistics::new(&dag_set);
+    for dag_statistics in statistics.each_dag_statistics.iter() {
+        println!(
+            "DAG {}: {} nodes, volume = {}, period = {}, parallelism = {:.2}",
+            dag_statistics.dag_id,
+            dag_statistics.number_of_nodes,
+            dag_statistics.volume,
+            dag_statistics.period.unwrap(),
+            dag_statistics.parallelism
+        );
+    }
+    statistics.dump_to_yaml(&create_yaml(
+        &output_dir_path,
+        "tutorial_dag_set_statistics",
+    ));
+
+    // 2. Check the DAG set analytically before simulating it.
+    // Each verdict carries the metric deciding it, so that a failing test shows how far the DAG set is from passing.
+    let verdicts = test_all(&dag_set, NUMBER_OF_CORES);
+    println!("Schedulability tests: {:?}", verdicts);
+
+    // 3. Assign priorities to the nodes.
+    // FixedPriorityScheduler prefers the node with the smaller "priority",
+    // so the node with the earlier latest start time, i.e., with less slack, goes first.
+    // The latest start times are relative to a single sink, so a dummy sink joins the sinks of the DAG meanwhile.
+    for dag in dag_set.iter_mut() {
+        dag.add_dummy_sink_node();
+        dag.calculate_latest_start_times();
+        dag.remove_dummy_sink_node();
+        for node_i in dag.node_indices() {
+            let latest_start_time = dag[node_i].params["latest_start_time"];
+            dag.add_param(node_i, "priority", latest_start_time);
+        }
+    }
+
+    // A single DAG is scheduled once by a DAGSchedulerBase, and the schedule length is compared with
+    // the length no scheduler can beat.
+    let processor = HomogeneousProcessor::new(NUMBER_OF_CORES);
+    let mut fixed_priority_scheduler = FixedPriorityScheduler::new(&dag_set[0], &processor);
+    let (schedule_length, _) = fixed_priority_scheduler.schedule();
+    println!(
+        "Fixed priority (DAG 0 alone): schedule length = {}, makespan lower bound = {}",
+        schedule_length,
+        dag_set[0].get_makespan_lower_bound(NUMBER_OF_CORES)
+    );
+    fixed_priority_scheduler.dump_log(&output_dir_path, "tutorial_fixed_priority");
+
+    // 4. Run two DAG set schedulers over the hyper period.
+    // A DAGSetSchedulerBase releases a job of each DAG every period, and returns the outcome of the whole run.
+    // The key of PreemptiveType::Preemptive is the node parameter compared on preemption.
+    // schedule returns an error instead of scheduling if the DAG set violates the model, e.g., a jitter longer than the period.
+    let mut gedf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+    let gedf_outcome = gedf_scheduler.schedule(PreemptiveType::Preemptive {
+        key: "node_absolute_deadline".to_string(),
+    })?;
+    let gedf_file_path = gedf_scheduler.dump_log(&output_dir_path, "tutorial_gedf");
+
+    let mut round_robin_scheduler = RoundRobinScheduler::new(&dag_set, &processor);
+    round_robin_scheduler.set_quantum(10)?;
+    let round_robin_outcome = round_robin_scheduler.schedule(PreemptiveType::NonPreemptive)?;
+    let round_robin_file_path =
+        round_robin_scheduler.dump_log(&output_dir_path, "tutorial_round_robin");
+
+    // 5. Verify the schedules and export them.
+    report("G-EDF", &dag_set, &gedf_outcome, &gedf_file_path);
+    report(
+        "Round robin",
+        &dag_set,
+        &round_robin_outcome,
+        &round_robin_file_path,
+    );
+    Ok(())
+}
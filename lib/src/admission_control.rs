@@ -0,0 +1,330 @@
+//! Admission control of DAGs arriving online.
+//! An `AdmissionController` decides whether a candidate DAG can join the admitted DAG set
+//! by a utilization or density check on the cores, and it can be called directly as a library.
+//!
+//! In the online DAG-arrival mode of the DAG-set scheduler, each DAG listed in the arrivals is inactive until it arrives,
+//! and it is admitted only if the controller accepts it together with the DAGs admitted so far.
+//! An admitted DAG is released from its first periodic arrival at or after the admission, and a rejected DAG is never released.
+//! The DAGs not listed in the arrivals are admitted from the start.
+//!
+//! Example of an online admission file:
+//!
+//! ```yaml
+//! admission_test: density
+//! arrivals:
+//!   - time: 100
+//!     dag_id: 1
+//! ```
+#[cfg(feature = "file_io")]
+use crate::util::load_yaml_struct;
+use crate::{
+    error::{Result, SchedSimError},
+    graph_extension::{GraphExtension, NodeData},
+    schedulability_test::{test_global_edf_of_dags, SchedulabilityVerdict},
+};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdmissionTest {
+    // The total utilization does not exceed the number of cores, and the critical path of the candidate fits in its deadline.
+    Utilization,
+    // The density test of global EDF.
+    Density,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdmissionController {
+    number_of_cores: usize,
+    admission_test: AdmissionTest,
+}
+
+impl AdmissionController {
+    pub fn new(number_of_cores: usize, admission_test: AdmissionTest) -> Self {
+        Self {
+            number_of_cores,
+            admission_test,
+        }
+    }
+
+    /// Decide whether `candidate_dag` is admitted in addition to `admitted_dag_set`.
+    /// The returned verdict is schedulable if the candidate is admitted.
+    pub fn decide(
+        &self,
+        admitted_dag_set: &[&Graph<NodeData, i64>],
+        candidate_dag: &Graph<NodeData, i64>,
+    ) -> SchedulabilityVerdict {
+        let dag_set = admitted_dag_set
+            .iter()
+            .copied()
+            .chain(std::iter::once(candidate_dag));
+        match self.admission_test {
+            AdmissionTest::Utilization => {
+                let critical_path = candidate_dag.get_critical_path();
+                let critical_path_ratio = candidate_dag.get_total_wcet_from_nodes(&critical_path)
+                    as f64
+                    / candidate_dag
+                        .get_end_to_end_deadline()
+                        .unwrap()
+                        .min(candidate_dag.get_head_period().unwrap()) as f64;
+                if critical_path_ratio > 1.0 {
+                    return SchedulabilityVerdict::new(
+                        "critical_path_ratio",
+                        critical_path_ratio,
+                        1.0,
+                    );
+                }
+                let total_utilization = dag_set
+                    .map(|dag| dag.get_volume() as f64 / dag.get_head_period().unwrap() as f64)
+                    .sum();
+                SchedulabilityVerdict::new(
+                    "total_utilization",
+                    total_utilization,
+                    self.number_of_cores as f64,
+                )
+            }
+            AdmissionTest::Density => test_global_edf_of_dags(dag_set, self.number_of_cores),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGArrival {
+    pub time: i64,
+    pub dag_id: usize,
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Deserialize)]
+struct OnlineAdmissionFile {
+    admission_test: AdmissionTest,
+    arrivals: Vec<DAGArrival>,
+}
+
+fn get_pending_dag_ids(arrivals: &[DAGArrival]) -> Result<BTreeSet<usize>> {
+    let mut pending_dag_ids = BTreeSet::new();
+    for arrival in arrivals.iter() {
+        if !pending_dag_ids.insert(arrival.dag_id) {
+            return Err(SchedSimError::Validation(format!(
+                "DAG {} arrives more than once.",
+                arrival.dag_id
+            )));
+        }
+    }
+    Ok(pending_dag_ids)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnlineAdmission {
+    admission_test: AdmissionTest,
+    arrivals: VecDeque<DAGArrival>,
+    // The DAGs that have not arrived yet, and the DAGs rejected on their arrivals.
+    pending_dag_ids: BTreeSet<usize>,
+    rejected_dag_ids: BTreeSet<usize>,
+}
+
+impl OnlineAdmission {
+    pub fn new(admission_test: AdmissionTest, mut arrivals: Vec<DAGArrival>) -> Self {
+        arrivals.sort_by_key(|arrival| arrival.time);
+        let pending_dag_ids =
+            get_pending_dag_ids(&arrivals).unwrap_or_else(|err| panic!("{}", err));
+        Self {
+            admission_test,
+            arrivals: arrivals.into(),
+            pending_dag_ids,
+            rejected_dag_ids: BTreeSet::new(),
+        }
+    }
+
+    /// load yaml file and return an online admission
+    ///
+    /// # Arguments
+    ///
+    /// *  `file_path` - yaml file path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lib::admission_control::OnlineAdmission;
+    ///
+    /// let online_admission = OnlineAdmission::from_yaml_file("tests/sample_online_admissions/late_arrival.yaml").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        let online_admission_file: OnlineAdmissionFile = load_yaml_struct(file_path)?;
+        get_pending_dag_ids(&online_admission_file.arrivals)?;
+        Ok(Self::new(
+            online_admission_file.admission_test,
+            online_admission_file.arrivals,
+        ))
+    }
+
+    /// Return an error if a DAG that is not in the DAG set arrives.
+    pub fn validate(&self, dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+        if let Some(dag_id) = self
+            .pending_dag_ids
+            .iter()
+            .find(|dag_id| **dag_id >= dag_set.len())
+        {
+            return Err(SchedSimError::Validation(format!(
+                "A non-existent DAG arrives: {}",
+                dag_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn get_admission_test(&self) -> AdmissionTest {
+        self.admission_test
+    }
+
+    /// A DAG is admitted if it is not listed in the arrivals, or if it has arrived and been accepted.
+    pub fn is_dag_admitted(&self, dag_id: usize) -> bool {
+        !self.pending_dag_ids.contains(&dag_id) && !self.rejected_dag_ids.contains(&dag_id)
+    }
+
+    /// Remove and return the arrivals by `current_time`, whose admissions have to be decided.
+    pub fn pop_arrivals(&mut self, current_time: i64) -> Vec<DAGArrival> {
+        let mut arrivals = Vec::new();
+        while self
+            .arrivals
+            .front()
+            .is_some_and(|arrival| arrival.time <= current_time)
+        {
+            arrivals.push(self.arrivals.pop_front().unwrap());
+        }
+        arrivals
+    }
+
+    pub fn set_admission(&mut self, dag_id: usize, is_admitted: bool) {
+        self.pending_dag_ids.remove(&dag_id);
+        if !is_admitted {
+            self.rejected_dag_ids.insert(dag_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag(execution_times: &[i64], period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(source, "period", period);
+        dag.add_param(source, "end_to_end_deadline", period);
+        for (id, execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node = dag.add_node(create_node(id as i64, "execution_time", *execution_time));
+            dag.add_edge(source, node, 0);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_decide_utilization() {
+        let admitted_dag = create_dag(&[10, 20, 20, 20], 100);
+        let admitted_dag_set = vec![&admitted_dag];
+        let admission_controller = AdmissionController::new(2, AdmissionTest::Utilization);
+
+        let verdict = admission_controller.decide(&admitted_dag_set, &create_dag(&[60, 60], 100));
+        assert!(!verdict.is_schedulable);
+        assert_eq!(verdict.binding_metric, "critical_path_ratio");
+
+        let verdict = admission_controller.decide(&admitted_dag_set, &create_dag(&[30, 30], 100));
+        assert!(verdict.is_schedulable);
+        assert_eq!(verdict.binding_metric, "total_utilization");
+        assert!((verdict.binding_value - 1.3).abs() < 1e-9);
+        assert_eq!(verdict.limit, 2.0);
+    }
+
+    #[test]
+    fn test_decide_density() {
+        // Total density 0.7 + 0.6, and the largest critical path ratio is 40 / 100.
+        let admitted_dag = create_dag(&[10, 20, 20, 20], 100);
+        let admitted_dag_set = vec![&admitted_dag];
+        let candidate_dag = create_dag(&[20, 20, 20], 100);
+
+        let verdict = AdmissionController::new(2, AdmissionTest::Density)
+            .decide(&admitted_dag_set, &candidate_dag);
+        assert!(verdict.is_schedulable);
+        assert!((verdict.limit - 1.6).abs() < 1e-9);
+        assert!(
+            !AdmissionController::new(1, AdmissionTest::Density)
+                .decide(&admitted_dag_set, &candidate_dag)
+                .is_schedulable
+        );
+    }
+
+    #[test]
+    fn test_online_admission_normal() {
+        let mut online_admission = OnlineAdmission::new(
+            AdmissionTest::Density,
+            vec![
+                DAGArrival {
+                    time: 200,
+                    dag_id: 2,
+                },
+                DAGArrival {
+                    time: 100,
+                    dag_id: 1,
+                },
+            ],
+        );
+        assert!(online_admission.is_dag_admitted(0));
+        assert!(!online_admission.is_dag_admitted(1));
+
+        assert_eq!(online_admission.pop_arrivals(50), vec![]);
+        assert_eq!(
+            online_admission.pop_arrivals(200),
+            vec![
+                DAGArrival {
+                    time: 100,
+                    dag_id: 1
+                },
+                DAGArrival {
+                    time: 200,
+                    dag_id: 2
+                }
+            ]
+        );
+        online_admission.set_admission(1, true);
+        online_admission.set_admission(2, false);
+        assert!(online_admission.is_dag_admitted(1));
+        assert!(!online_admission.is_dag_admitted(2));
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_online_admission_from_yaml_file() {
+        let online_admission =
+            OnlineAdmission::from_yaml_file("tests/sample_online_admissions/late_arrival.yaml")
+                .unwrap();
+        assert_eq!(
+            online_admission.get_admission_test(),
+            AdmissionTest::Density
+        );
+        assert!(online_admission.is_dag_admitted(0));
+        assert!(!online_admission.is_dag_admitted(1));
+    }
+
+    #[test]
+    fn test_online_admission_validate_non_existent_dag() {
+        let online_admission = OnlineAdmission::new(
+            AdmissionTest::Utilization,
+            vec![DAGArrival { time: 0, dag_id: 1 }],
+        );
+        assert!(matches!(
+            online_admission.validate(&[create_dag(&[10], 100)]),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+//! Response-time analysis of DAG tasks on identical cores.
+//! The analytical bounds are written into the DAG logs, so that they can be compared with the simulated worst_response_time.
+//!
+//! The bound of Melani et al. is derived for conditional DAGs, where the volume is replaced by the worst-case workload
+//! over the branches. The DAGs of this crate have no conditional nodes, so their worst-case workload is the volume.
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponseTimeBound {
+    // Bound of the DAG executed alone by any work-conserving scheduler
+    pub graham_bound: i64,
+    // Bound under global fixed-priority scheduling with deadline-monotonic priorities, or None if it exceeds the deadline
+    pub melani_bound: Option<i64>,
+}
+
+struct DAGTaskParams {
+    volume: i64,
+    critical_path_length: i64,
+    period: i64,
+    end_to_end_deadline: i64,
+}
+
+impl DAGTaskParams {
+    fn new(dag: &Graph<NodeData, i64>) -> Self {
+        let critical_path = dag.get_critical_path();
+        Self {
+            volume: dag.get_volume(),
+            critical_path_length: dag.get_total_wcet_from_nodes(&critical_path),
+            period: dag.get_head_period().unwrap(),
+            end_to_end_deadline: dag.get_end_to_end_deadline().unwrap(),
+        }
+    }
+
+    /// len + (vol - len) / m, rounded down because the response times are integers.
+    fn calculate_graham_bound(&self, number_of_cores: usize) -> i64 {
+        self.critical_path_length
+            + (self.volume - self.critical_path_length) / number_of_cores as i64
+    }
+
+    /// Upper bound of the workload of the DAG in a window of `window_length`, with its carry-in job finishing at its response time.
+    fn calculate_carry_in_workload(
+        &self,
+        window_length: i64,
+        response_time: i64,
+        number_of_cores: usize,
+    ) -> f64 {
+        let number_of_cores = number_of_cores as f64;
+        let shifted_length =
+            (window_length + response_time) as f64 - self.volume as f64 / number_of_cores;
+        let number_of_body_jobs = (shifted_length / self.period as f64).floor();
+        let carry_out_length = shifted_length - number_of_body_jobs * self.period as f64;
+        number_of_body_jobs * self.volume as f64
+            + (self.volume as f64).min(number_of_cores * carry_out_length)
+    }
+}
+
+/// Calculate the Graham bound and the bound of Melani et al. of each DAG on `number_of_cores` cores.
+/// The returned bounds are indexed by dag_id.
+///
+/// The bound of DAG k is the fixed point of
+/// R_k = len_k + (vol_k - len_k + sum of W_i(R_k) over the higher-priority DAGs i) / m,
+/// where W_i(L) = floor((L + R_i - vol_i / m) / T_i) vol_i + min(vol_i, m ((L + R_i - vol_i / m) mod T_i)).
+/// A DAG with a shorter end-to-end deadline has a higher priority, and ties are broken by dag_id.
+pub fn calculate_response_time_bounds(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> Vec<ResponseTimeBound> {
+    let dag_task_params: Vec<DAGTaskParams> = dag_set.iter().map(DAGTaskParams::new).collect();
+    let mut priority_order: Vec<usize> = (0..dag_task_params.len()).collect();
+    priority_order.sort_by_key(|&dag_id| (dag_task_params[dag_id].end_to_end_deadline, dag_id));
+
+    let mut response_time_bounds: Vec<ResponseTimeBound> = dag_task_params
+        .iter()
+        .map(|params| ResponseTimeBound {
+            graham_bound: params.calculate_graham_bound(number_of_cores),
+            melani_bound: None,
+        })
+        .collect();
+    // (dag_id, melani_bound) of the analyzed higher-priority DAGs
+    let mut higher_priority_bounds: Vec<(usize, i64)> = Vec::new();
+    for dag_id in priority_order {
+        let params = &dag_task_params[dag_id];
+        let mut response_time = response_time_bounds[dag_id].graham_bound;
+        let melani_bound = loop {
+            if response_time > params.end_to_end_deadline {
+                break None;
+            }
+            let interference: f64 = higher_priority_bounds
+                .iter()
+                .map(|&(hp_dag_id, hp_response_time)| {
+                    dag_task_params[hp_dag_id].calculate_carry_in_workload(
+                        response_time,
+                        hp_response_time,
+                        number_of_cores,
+                    )
+                })
+                .sum();
+            let next_response_time = params.critical_path_length
+                + (((params.volume - params.critical_path_length) as f64 + interference)
+                    / number_of_cores as f64)
+                    .floor() as i64;
+            if next_response_time == response_time {
+                break Some(response_time);
+            }
+            response_time = next_response_time;
+        };
+        response_time_bounds[dag_id].melani_bound = melani_bound;
+        // The interference of an unschedulable DAG is unbounded, so the lower-priority DAGs are not analyzed.
+        match melani_bound {
+            Some(melani_bound) => higher_priority_bounds.push((dag_id, melani_bound)),
+            None => break,
+        }
+    }
+    response_time_bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag(execution_times: &[i64], period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(source, "period", period);
+        dag.add_param(source, "end_to_end_deadline", period);
+        for (id, execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node = dag.add_node(create_node(id as i64, "execution_time", *execution_time));
+            dag.add_edge(source, node, 0);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_calculate_response_time_bounds_normal() {
+        // Volume 70 and critical path length 30, volume 60 and critical path length 40.
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 150),
+            create_dag(&[20, 20, 20], 100),
+        ];
+
+        // DAG 1 has the higher priority and no interference.
+        // DAG 0 converges at 30 + (40 + 60) / 2 with one body job of DAG 1 in the window.
+        assert_eq!(
+            calculate_response_time_bounds(&dag_set, 2),
+            vec![
+                ResponseTimeBound {
+                    graham_bound: 50,
+                    melani_bound: Some(80)
+                },
+                ResponseTimeBound {
+                    graham_bound: 50,
+                    melani_bound: Some(50)
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calculate_response_time_bounds_unschedulable() {
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 150),
+            create_dag(&[20, 20, 20], 100),
+            create_dag(&[10], 200),
+        ];
+
+        // The response time of DAG 0 grows 70, 130, 160 and exceeds its deadline.
+        let response_time_bounds = calculate_response_time_bounds(&dag_set, 1);
+        assert_eq!(response_time_bounds[0].graham_bound, 70);
+        assert_eq!(response_time_bounds[0].melani_bound, None);
+        assert_eq!(response_time_bounds[1].melani_bound, Some(60));
+        assert_eq!(response_time_bounds[2].graham_bound, 10);
+        assert_eq!(response_time_bounds[2].melani_bound, None);
+    }
+}
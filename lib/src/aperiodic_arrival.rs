@@ -0,0 +1,233 @@
+//! Aperiodic arrivals of DAG instances.
+//! A DAG in an aperiodic arrival trace is released only at the arrival times in the trace, not by its period.
+//! An instance arriving while the previous instance is unfinished waits in the DAGStateManager,
+//! and the waiting instances are released in the order of arrival.
+//! The absolute deadline of an instance is relative to its arrival.
+//!
+//! The trace is either a YAML list or a CSV file of dag_id and arrival_time.
+//!
+//! ```yaml
+//! - dag_id: 0
+//!   arrival_time: 15
+//! - dag_id: 1
+//!   arrival_time: 20
+//! ```
+//!
+//! ```csv
+//! dag_id,arrival_time
+//! 0,15
+//! 1,20
+//! ```
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::NodeData;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "file_io")]
+use {crate::util::load_yaml_struct, std::fs};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Arrival {
+    pub dag_id: usize,
+    pub arrival_time: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AperiodicArrivalTrace {
+    // Sorted in descending order of arrival time so that the arrivals are popped from the back.
+    arrival_times: BTreeMap<usize, Vec<i64>>,
+}
+
+impl AperiodicArrivalTrace {
+    pub fn new(arrivals: &[Arrival]) -> Self {
+        let mut arrival_times: BTreeMap<usize, Vec<i64>> = BTreeMap::new();
+        for arrival in arrivals {
+            if arrival.arrival_time < 0 {
+                panic!(
+                    "The arrival time of DAG {} is negative: {}",
+                    arrival.dag_id, arrival.arrival_time
+                );
+            }
+            arrival_times
+                .entry(arrival.dag_id)
+                .or_default()
+                .push(arrival.arrival_time);
+        }
+        for dag_arrival_times in arrival_times.values_mut() {
+            dag_arrival_times.sort_by(|a, b| b.cmp(a));
+        }
+        Self { arrival_times }
+    }
+
+    /// load yaml or csv file and return an aperiodic arrival trace
+    ///
+    /// # Arguments
+    ///
+    /// *  `file_path` - yaml or csv file path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lib::aperiodic_arrival::AperiodicArrivalTrace;
+    ///
+    /// let arrival_trace = AperiodicArrivalTrace::from_file("tests/sample_arrival_traces/aperiodic.csv").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_file(file_path: &str) -> Result<Self> {
+        let arrivals: Vec<Arrival> = if file_path.ends_with(".csv") {
+            let file_content =
+                fs::read_to_string(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+            parse_csv(&file_content).map_err(|err| SchedSimError::parse(file_path, err))?
+        } else {
+            load_yaml_struct(file_path)?
+        };
+        Ok(Self::new(&arrivals))
+    }
+
+    pub fn contains_dag(&self, dag_id: usize) -> bool {
+        self.arrival_times.contains_key(&dag_id)
+    }
+
+    /// Remove and return the arrivals by `current_time` in the order of arrival for each DAG.
+    pub fn pop_arrivals(&mut self, current_time: i64) -> Vec<Arrival> {
+        let mut arrivals = Vec::new();
+        for (dag_id, dag_arrival_times) in self.arrival_times.iter_mut() {
+            while let Some(arrival_time) = dag_arrival_times
+                .last()
+                .copied()
+                .filter(|arrival_time| *arrival_time <= current_time)
+            {
+                dag_arrival_times.pop();
+                arrivals.push(Arrival {
+                    dag_id: *dag_id,
+                    arrival_time,
+                });
+            }
+        }
+        arrivals
+    }
+
+    /// Return an error if the trace contains a DAG that is not in the DAG set.
+    pub fn validate(&self, dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+        if let Some(dag_id) = self
+            .arrival_times
+            .keys()
+            .find(|dag_id| **dag_id >= dag_set.len())
+        {
+            return Err(SchedSimError::Validation(format!(
+                "The aperiodic arrival trace contains a non-existent DAG: {}",
+                dag_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The first line is skipped if it is a header.
+/// Returns the message of the first malformed line.
+#[cfg(feature = "file_io")]
+fn parse_csv(file_content: &str) -> std::result::Result<Vec<Arrival>, String> {
+    let parse_field = |field: &str, line_number: usize| {
+        field.trim().parse::<i64>().map_err(|_| {
+            format!(
+                "Invalid field in line {} of the aperiodic arrival trace: {}",
+                line_number, field
+            )
+        })
+    };
+
+    file_content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter(|(line_i, line)| !(*line_i == 0 && line.trim_start().starts_with("dag_id")))
+        .map(|(line_i, line)| {
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != 2 {
+                return Err(format!(
+                    "Line {} of the aperiodic arrival trace must be dag_id,arrival_time: {}",
+                    line_i + 1,
+                    line
+                ));
+            }
+            Ok(Arrival {
+                dag_id: parse_field(fields[0], line_i + 1)? as usize,
+                arrival_time: parse_field(fields[1], line_i + 1)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_file_csv_and_yaml() {
+        let arrival_trace =
+            AperiodicArrivalTrace::from_file("tests/sample_arrival_traces/aperiodic.csv").unwrap();
+        assert_eq!(
+            arrival_trace,
+            AperiodicArrivalTrace::from_file("tests/sample_arrival_traces/aperiodic.yaml").unwrap()
+        );
+        assert!(arrival_trace.contains_dag(0));
+        assert!(!arrival_trace.contains_dag(2));
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_file_no_yaml_or_csv() {
+        assert!(matches!(
+            AperiodicArrivalTrace::from_file("tests/sample_dags/no_yaml.tex"),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pop_arrivals_normal() {
+        let mut arrival_trace = AperiodicArrivalTrace::new(&[
+            Arrival {
+                dag_id: 0,
+                arrival_time: 12,
+            },
+            Arrival {
+                dag_id: 1,
+                arrival_time: 5,
+            },
+            Arrival {
+                dag_id: 0,
+                arrival_time: 3,
+            },
+        ]);
+
+        assert_eq!(arrival_trace.pop_arrivals(2), vec![]);
+        assert_eq!(
+            arrival_trace.pop_arrivals(5),
+            vec![
+                Arrival {
+                    dag_id: 0,
+                    arrival_time: 3
+                },
+                Arrival {
+                    dag_id: 1,
+                    arrival_time: 5
+                }
+            ]
+        );
+        assert_eq!(
+            arrival_trace.pop_arrivals(20),
+            vec![Arrival {
+                dag_id: 0,
+                arrival_time: 12
+            }]
+        );
+        assert_eq!(arrival_trace.pop_arrivals(20), vec![]);
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_parse_csv_missing_field() {
+        assert!(parse_csv("dag_id,arrival_time\n0\n").is_err());
+    }
+}
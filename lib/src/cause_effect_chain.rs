@@ -0,0 +1,183 @@
+//! End-to-end latencies of cause-effect chains over the nodes of the DAG set.
+//! A node reads its input when its job starts and writes its output when the job finishes (implicit communication),
+//! and the latencies are taken from the job logs of a simulated schedule.
+//! Under the logical execution time (LET), a node reads at its release and writes at its publication instead.
+//! - The data age is the time from the read of the first node to the write of the last node that uses the data.
+//! - The reaction time is the time from an external event, arriving just after a read of the first node,
+//!   to the first write of the last node that reflects it.
+//!
+//! A DAG is not released while its previous job is unfinished, so the jobs of a node are analyzed in the job order.
+//!
+//! Example of a chain file:
+//!
+//! ```yaml
+//! - name: sensor_to_actuator
+//!   elements:
+//!     - dag_id: 0
+//!       node_id: 1
+//!     - dag_id: 1
+//!       node_id: 0
+//! ```
+use crate::{
+    graph_extension::NodeData, log::DAGSetSchedulerLog,
+    logical_execution_time::CommunicationSemantics,
+};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "file_io")]
+use std::fs;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainElement {
+    pub dag_id: usize,
+    pub node_id: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CauseEffectChain {
+    pub name: String,
+    pub elements: Vec<ChainElement>,
+}
+
+/// load yaml file and return the cause-effect chains
+///
+/// # Arguments
+///
+/// *  `file_path` - yaml file path
+///
+/// # Example
+///
+/// ```
+/// use lib::cause_effect_chain::load_cause_effect_chains;
+///
+/// let chains = load_cause_effect_chains("tests/sample_chains/two_dag_chain.yaml");
+/// ```
+#[cfg(feature = "file_io")]
+pub fn load_cause_effect_chains(file_path: &str) -> Vec<CauseEffectChain> {
+    if !file_path.ends_with(".yaml") && !file_path.ends_with(".yml") {
+        panic!("Invalid file type: {}", file_path);
+    }
+    let file_content = fs::read_to_string(file_path).unwrap();
+    serde_yaml::from_str(&file_content)
+        .unwrap_or_else(|err| panic!("Failed to parse the cause-effect chains: {}", err))
+}
+
+impl CauseEffectChain {
+    /// Panic if the chain is empty or contains a node that is not in the DAG set.
+    pub fn validate(&self, dag_set: &[Graph<NodeData, i64>]) {
+        if self.elements.is_empty() {
+            panic!("The chain {} has no elements.", self.name);
+        }
+        for element in self.elements.iter() {
+            if element.dag_id >= dag_set.len()
+                || element.node_id >= dag_set[element.dag_id].node_count()
+            {
+                panic!(
+                    "The chain {} contains a non-existent node: {:?}",
+                    self.name, element
+                );
+            }
+        }
+    }
+
+    /// Returns the maximum data age and the maximum reaction time,
+    /// or None for each if no job chain is completed in the log.
+    pub fn calculate_latencies(&self, log: &DAGSetSchedulerLog) -> (Option<i64>, Option<i64>) {
+        let job_intervals: Vec<Vec<(i64, i64)>> = self
+            .elements
+            .iter()
+            .map(|element| match log.get_communication_semantics() {
+                CommunicationSemantics::Implicit => {
+                    log.get_job_intervals(element.dag_id, element.node_id)
+                }
+                CommunicationSemantics::LogicalExecutionTime => {
+                    log.get_let_intervals(element.dag_id, element.node_id)
+                }
+            })
+            .collect();
+        (
+            calculate_max_data_age(&job_intervals),
+            calculate_max_reaction_time(&job_intervals),
+        )
+    }
+}
+
+/// Follow the chain backward from each job of the last element to the latest job of each previous element
+/// that has finished by the start of the next one.
+fn calculate_max_data_age(job_intervals: &[Vec<(i64, i64)>]) -> Option<i64> {
+    let (last_intervals, previous_intervals) = job_intervals.split_last()?;
+    last_intervals
+        .iter()
+        .filter_map(|(start_time, finish_time)| {
+            let mut read_time = *start_time;
+            for intervals in previous_intervals.iter().rev() {
+                read_time = intervals
+                    .iter()
+                    .rev()
+                    .find(|(_, finish_time)| *finish_time <= read_time)?
+                    .0;
+            }
+            Some(finish_time - read_time)
+        })
+        .max()
+}
+
+/// Follow the chain forward from each job of the first element to the earliest job of each next element
+/// that starts after the finish of the previous one. The external event arrives just after the previous read.
+fn calculate_max_reaction_time(job_intervals: &[Vec<(i64, i64)>]) -> Option<i64> {
+    let (first_intervals, next_intervals) = job_intervals.split_first()?;
+    first_intervals
+        .windows(2)
+        .filter_map(|window| {
+            let event_time = window[0].0;
+            let mut write_time = window[1].1;
+            for intervals in next_intervals.iter() {
+                write_time = intervals
+                    .iter()
+                    .find(|(start_time, _)| *start_time >= write_time)?
+                    .1;
+            }
+            Some(write_time - event_time)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_load_cause_effect_chains_normal() {
+        let chains = load_cause_effect_chains("tests/sample_chains/two_dag_chain.yaml");
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].name, "sensor_to_actuator");
+        assert_eq!(
+            chains[0].elements[1],
+            ChainElement {
+                dag_id: 1,
+                node_id: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_calculate_latencies_normal() {
+        // The producer runs every 10 and the consumer every 25.
+        let job_intervals = vec![
+            vec![(0, 2), (10, 12), (20, 22), (30, 32), (40, 42)],
+            vec![(3, 5), (25, 27)],
+        ];
+        // The consumer job starting at 25 reads the data produced by the job started at 20.
+        assert_eq!(calculate_max_data_age(&job_intervals), Some(7));
+        // The event just after 0 is read at 10 and reaches the consumer finished at 27.
+        assert_eq!(calculate_max_reaction_time(&job_intervals), Some(27));
+    }
+
+    #[test]
+    fn test_calculate_latencies_incomplete_chain() {
+        let job_intervals = vec![vec![(10, 12)], vec![(3, 5)]];
+        assert_eq!(calculate_max_data_age(&job_intervals), None);
+        assert_eq!(calculate_max_reaction_time(&job_intervals), None);
+    }
+}
@@ -0,0 +1,154 @@
+//! Concurrent-access conflict analysis.
+//! Nodes declare the shared data regions they access with the `shared_regions` bitmask, whose i-th bit means region i.
+//! Two nodes conflict when they can be executed in parallel and access the same region.
+use crate::{graph_extension::NodeData, log::DAGSchedulerLog, reachability::ReachabilityMatrix};
+use petgraph::graph::{Graph, NodeIndex};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaceWindow {
+    pub node_ids: (usize, usize),
+    pub shared_regions: i64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+fn get_shared_regions(node_data: &NodeData) -> i64 {
+    node_data.params.get("shared_regions").copied().unwrap_or(0)
+}
+
+pub fn get_conflicting_node_pairs(dag: &Graph<NodeData, i64>) -> Vec<(NodeIndex, NodeIndex)> {
+    let reachability_matrix = ReachabilityMatrix::new(dag);
+    let mut conflicting_node_pairs = Vec::new();
+    for node_i in dag.node_indices() {
+        let shared_regions = get_shared_regions(&dag[node_i]);
+        if shared_regions == 0 {
+            continue;
+        }
+        for parallel_node_i in reachability_matrix
+            .get_parallel_process_nodes(node_i)
+            .unwrap_or_default()
+        {
+            if node_i < parallel_node_i
+                && shared_regions & get_shared_regions(&dag[parallel_node_i]) != 0
+            {
+                conflicting_node_pairs.push((node_i, parallel_node_i));
+            }
+        }
+    }
+    conflicting_node_pairs
+}
+
+/// Color the conflict graph greedily in descending order of degree (Welsh-Powell).
+/// Nodes with the same color never conflict, so each color class can be executed concurrently without synchronization.
+pub fn color_conflict_graph(dag: &Graph<NodeData, i64>) -> BTreeMap<NodeIndex, usize> {
+    let mut neighbors: BTreeMap<NodeIndex, Vec<NodeIndex>> = dag
+        .node_indices()
+        .map(|node_i| (node_i, Vec::new()))
+        .collect();
+    for (node_i, node_j) in get_conflicting_node_pairs(dag) {
+        neighbors.get_mut(&node_i).unwrap().push(node_j);
+        neighbors.get_mut(&node_j).unwrap().push(node_i);
+    }
+
+    let mut ordered_nodes: Vec<NodeIndex> = dag.node_indices().collect();
+    ordered_nodes.sort_by_key(|node_i| std::cmp::Reverse(neighbors[node_i].len()));
+
+    let mut colors = BTreeMap::new();
+    for node_i in ordered_nodes {
+        let neighbor_colors: Vec<usize> = neighbors[&node_i]
+            .iter()
+            .filter_map(|neighbor_i| colors.get(neighbor_i).copied())
+            .collect();
+        let color = (0..)
+            .find(|color| !neighbor_colors.contains(color))
+            .unwrap();
+        colors.insert(node_i, color);
+    }
+    colors
+}
+
+/// Find the time windows in which conflicting nodes are executed simultaneously in the schedule.
+pub fn find_race_windows(dag: &Graph<NodeData, i64>, log: &DAGSchedulerLog) -> Vec<RaceWindow> {
+    let execution_intervals = log.get_execution_intervals();
+    let mut race_windows = Vec::new();
+    for (node_i, node_j) in get_conflicting_node_pairs(dag) {
+        let node_ids = (dag[node_i].id as usize, dag[node_j].id as usize);
+        if let (Some((start_i, finish_i)), Some((start_j, finish_j))) = (
+            execution_intervals.get(&node_ids.0),
+            execution_intervals.get(&node_ids.1),
+        ) {
+            let start_time = *start_i.max(start_j);
+            let end_time = *finish_i.min(finish_j);
+            if start_time < end_time {
+                race_windows.push(RaceWindow {
+                    node_ids,
+                    shared_regions: get_shared_regions(&dag[node_i])
+                        & get_shared_regions(&dag[node_j]),
+                    start_time,
+                    end_time,
+                });
+            }
+        }
+    }
+    race_windows
+}
+
+#[cfg(all(test, feature = "file_io"))]
+mod tests {
+    use super::*;
+    use crate::{
+        dag_creator::create_dag_from_yaml, dag_scheduler::DAGSchedulerBase,
+        fixed_priority_scheduler::FixedPriorityScheduler, homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+
+    #[test]
+    fn test_get_conflicting_node_pairs_normal() {
+        let dag = create_dag_from_yaml("tests/sample_dags/shared_regions.yaml", false).unwrap();
+
+        // Node 4 shares region 0 with node 1, but they are not executed in parallel.
+        assert_eq!(
+            get_conflicting_node_pairs(&dag),
+            vec![(NodeIndex::new(1), NodeIndex::new(2))]
+        );
+    }
+
+    #[test]
+    fn test_color_conflict_graph_normal() {
+        let dag = create_dag_from_yaml("tests/sample_dags/shared_regions.yaml", false).unwrap();
+        let colors = color_conflict_graph(&dag);
+
+        assert_ne!(colors[&NodeIndex::new(1)], colors[&NodeIndex::new(2)]);
+        assert_eq!(colors[&NodeIndex::new(3)], 0);
+        assert_eq!(colors.values().max(), Some(&1));
+    }
+
+    #[test]
+    fn test_find_race_windows_normal() {
+        let dag = create_dag_from_yaml("tests/sample_dags/shared_regions.yaml", false).unwrap();
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(3));
+        fixed_priority_scheduler.schedule();
+
+        assert_eq!(
+            find_race_windows(&dag, &fixed_priority_scheduler.get_log()),
+            vec![RaceWindow {
+                node_ids: (1, 2),
+                shared_regions: 0b10,
+                start_time: 3,
+                end_time: 13,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_race_windows_serialized() {
+        let dag = create_dag_from_yaml("tests/sample_dags/shared_regions.yaml", false).unwrap();
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(1));
+        fixed_priority_scheduler.schedule();
+
+        assert!(find_race_windows(&dag, &fixed_priority_scheduler.get_log()).is_empty());
+    }
+}
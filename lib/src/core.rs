@@ -1,14 +1,24 @@
 //! This module contains the definition of the core and the process result enum
-use crate::{core::ProcessResult::*, graph_extension::NodeData};
+//! The remaining processing time of the node on a core is owned by the caller, e.g., the processor,
+//! which keeps those of all its cores in a compact vector and advances them in one loop.
+use crate::{
+    core::ProcessResult::*,
+    diagnostics::{report, DiagnosticKind},
+    graph_extension::NodeData,
+};
 use getset::{CopyGetters, Getters};
-use log::warn;
-///enum to represent three types of states
-///execution not possible because not allocate, execution in progress, execution finished
+use std::collections::VecDeque;
+///enum to represent five types of states
+///execution not possible because not allocate, execution in progress, execution finished,
+///spending the time on overhead such as dispatch, preemption or waiting for data,
+///and waiting until the completion timestamped by a drifting core clock is observed
 #[derive(Debug, PartialEq, Clone)]
 pub enum ProcessResult {
     Idle,
     Continue,
     Done(NodeData),
+    Overhead,
+    ObservationDelay,
 }
 
 #[derive(Clone, CopyGetters, Getters, Debug)]
@@ -17,7 +27,19 @@ pub struct Core {
     pub is_idle: bool,
     #[get = "pub with_prefix"]
     pub processing_node: Option<NodeData>,
-    pub remain_proc_time: i32,
+    #[get_copy = "pub with_prefix"]
+    pub is_failed: bool,
+    pub remain_overhead_time: i64,
+    #[get_copy = "pub with_prefix"]
+    dispatch_overhead: i64,
+    #[get_copy = "pub with_prefix"]
+    preemption_overhead: i64,
+    #[get = "pub with_prefix"]
+    pub dispatch_queue: VecDeque<NodeData>, // Nodes partitioned to the core and waiting for it
+    #[get_copy = "pub with_prefix"]
+    clock_drift_ppm: i64,
+    elapsed_time: i64, // Time units since the start of the simulation, on the global clock
+    remain_observation_delay: i64,
 }
 
 impl Default for Core {
@@ -25,58 +47,183 @@ impl Default for Core {
         Self {
             is_idle: true,
             processing_node: None,
-            remain_proc_time: 0,
+            is_failed: false,
+            remain_overhead_time: 0,
+            dispatch_overhead: 0,
+            preemption_overhead: 0,
+            dispatch_queue: VecDeque::new(),
+            clock_drift_ppm: 0,
+            elapsed_time: 0,
+            remain_observation_delay: 0,
         }
     }
 }
 
 ///return bool since "panic!" would terminate
 impl Core {
-    pub fn allocate(&mut self, node_data: &NodeData) -> bool {
+    /// Set the time units charged to the core on each dispatch and on each preemption.
+    pub fn set_overhead(&mut self, dispatch_overhead: i64, preemption_overhead: i64) {
+        self.dispatch_overhead = dispatch_overhead;
+        self.preemption_overhead = preemption_overhead;
+    }
+
+    /// Set the drift of the core clock from the global clock in parts per million.
+    /// The core timestamps a completion with its own clock, and the global scheduler observes it
+    /// when its clock reaches the timestamp. A core whose clock runs ahead (positive ppm) therefore
+    /// stays occupied for the accumulated offset after the completion.
+    /// A clock lagging behind (negative ppm) would report a completion before it happens,
+    /// which the scheduler cannot observe, so it is rejected.
+    pub fn set_clock_drift(&mut self, clock_drift_ppm: i64) {
+        if clock_drift_ppm < 0 {
+            panic!(
+                "clock_drift_ppm must be non-negative, but got {}",
+                clock_drift_ppm
+            );
+        }
+        self.clock_drift_ppm = clock_drift_ppm;
+    }
+
+    fn get_clock_offset(&self) -> i64 {
+        self.elapsed_time * self.clock_drift_ppm / 1_000_000
+    }
+
+    /// Set `remain_proc_time` to the time needed to process the node.
+    pub fn allocate(&mut self, node_data: &NodeData, remain_proc_time: &mut i64) -> bool {
+        if self.is_failed {
+            report(DiagnosticKind::CoreUnavailable, "Core has failed");
+            return false;
+        }
         if !self.is_idle {
-            warn!("Core is already allocated to a node");
+            report(
+                DiagnosticKind::CoreUnavailable,
+                "Core is already allocated to a node",
+            );
             return false;
         }
         self.is_idle = false;
         self.processing_node = Some(node_data.clone());
+        // Dummy nodes are not actually dispatched.
+        if !node_data.params.contains_key("dummy") {
+            self.remain_overhead_time += self.dispatch_overhead;
+        }
         if let Some(exec_time) = node_data.params.get("execution_time") {
-            self.remain_proc_time = *exec_time;
+            *remain_proc_time = *exec_time;
+            // The cache contents are lost while the node is preempted, so reloading them delays the resumption.
+            if node_data.params.contains_key("is_preempted") {
+                *remain_proc_time += node_data.params.get("crpd").copied().unwrap_or(0);
+            }
             true
         } else {
-            warn!("Node {} does not have execution_time", node_data.id);
+            report(
+                DiagnosticKind::MissingExecutionTime,
+                format!("Node {} does not have execution_time", node_data.id),
+            );
             false
         }
     }
 
-    pub fn process(&mut self) -> ProcessResult {
+    pub fn process(&mut self, remain_proc_time: &mut i64) -> ProcessResult {
+        let process_result = self.tick(remain_proc_time);
+        if self.is_completed(*remain_proc_time, &process_result) {
+            return Done(self.complete());
+        }
+        process_result
+    }
+
+    /// Advance the counters by one time unit without touching the node data.
+    /// A completed node is detected by `is_completed` and must be taken by `complete`.
+    pub(crate) fn tick(&mut self, remain_proc_time: &mut i64) -> ProcessResult {
+        self.elapsed_time += 1;
+        if self.is_failed {
+            return Idle;
+        }
+        if self.remain_overhead_time > 0 {
+            self.remain_overhead_time -= 1;
+            return Overhead;
+        }
         if self.is_idle {
             return Idle;
         }
-        self.remain_proc_time -= 1;
-        if self.remain_proc_time == 0 {
-            self.is_idle = true;
-            let finish_node_data = self.processing_node.clone().unwrap();
-            self.processing_node = None;
-            return Done(finish_node_data);
+        // The completion is not observed until the clock offset has passed.
+        if self.remain_observation_delay > 0 {
+            self.remain_observation_delay -= 1;
+            return ObservationDelay;
+        }
+        // The progress is tracked only by remain_proc_time, and the node data is not touched until completion.
+        // A node preempted while its completion was not yet observed has no remaining time when resumed.
+        if *remain_proc_time > 0 {
+            *remain_proc_time -= 1;
+            if *remain_proc_time == 0 {
+                self.remain_observation_delay = self.get_clock_offset();
+            }
         }
         Continue
     }
 
-    pub fn preempt(&mut self) -> Option<NodeData> {
+    /// Whether the node completed in the last tick and its completion is observed.
+    /// The overhead of a node is charged before its execution, so the node has completed
+    /// if it has no remaining time after either processing or waiting for the observation.
+    pub(crate) fn is_completed(
+        &self,
+        remain_proc_time: i64,
+        process_result: &ProcessResult,
+    ) -> bool {
+        !self.is_idle
+            && remain_proc_time == 0
+            && self.remain_observation_delay == 0
+            && matches!(process_result, Continue | Overhead | ObservationDelay)
+    }
+
+    pub(crate) fn complete(&mut self) -> NodeData {
+        self.is_idle = true;
+        self.processing_node.take().unwrap()
+    }
+
+    pub fn preempt(&mut self, remain_proc_time: &mut i64) -> Option<NodeData> {
         if self.is_idle {
             None
         } else {
-            let mut node_data = self.processing_node.clone().unwrap();
+            let mut node_data = self.processing_node.take().unwrap();
+            // The execution time of the whole job is kept from the first preemption to restart it on a core failure.
+            if !node_data.params.contains_key("is_preempted") {
+                let execution_time = node_data.get_params_value("execution_time");
+                node_data
+                    .params
+                    .insert("original_execution_time".to_string(), execution_time);
+            }
             node_data
                 .params
-                .insert("execution_time".to_string(), self.remain_proc_time);
+                .insert("execution_time".to_string(), *remain_proc_time);
             node_data.params.insert("is_preempted".to_string(), 1);
+            // The context switch is charged before the next node starts.
+            self.remain_overhead_time += self.preemption_overhead;
             self.is_idle = true;
-            self.processing_node = None;
-            self.remain_proc_time = 0;
+            *remain_proc_time = 0;
+            self.remain_observation_delay = 0;
             Some(node_data)
         }
     }
+
+    /// Stop the core permanently and return the node that was being processed.
+    /// The returned node is the same as when it was first allocated, because the progress is lost.
+    /// A resumed node gets back the execution time of the whole job and is no longer preempted.
+    pub fn fail(&mut self, remain_proc_time: &mut i64) -> Option<NodeData> {
+        let mut lost_node_data = self.processing_node.take();
+        if let Some(node_data) = lost_node_data.as_mut() {
+            if let Some(execution_time) = node_data.params.remove("original_execution_time") {
+                node_data
+                    .params
+                    .insert("execution_time".to_string(), execution_time);
+                node_data.params.remove("is_preempted");
+            }
+        }
+        self.is_failed = true;
+        self.is_idle = false; // A failed core is never selected as an idle core.
+        *remain_proc_time = 0;
+        self.remain_overhead_time = 0;
+        self.remain_observation_delay = 0;
+        lost_node_data
+    }
 }
 
 #[cfg(test)]
@@ -84,7 +231,7 @@ mod tests {
     use super::*;
     use std::collections::BTreeMap;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
@@ -95,58 +242,183 @@ mod tests {
         let core = Core::default();
         assert!(core.is_idle);
         assert_eq!(core.processing_node, None);
-        assert_eq!(core.remain_proc_time, 0);
     }
 
     #[test]
     fn test_core_allocate_normal() {
         let mut core = Core::default();
-        core.allocate(&create_node(0, "execution_time", 10));
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
         assert!(!core.is_idle);
         assert_eq!(
             core.processing_node,
             Some(create_node(0, "execution_time", 10))
         );
-        assert_eq!(core.remain_proc_time, 10);
+        assert_eq!(remain_proc_time, 10);
     }
 
     #[test]
     fn test_core_allocate_already_allocated() {
         let mut core = Core::default();
-        core.allocate(&create_node(0, "execution_time", 10));
-        assert!(!core.allocate(&create_node(1, "execution_time", 10)));
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        assert!(!core.allocate(&create_node(1, "execution_time", 10), &mut remain_proc_time));
     }
 
     #[test]
     fn test_core_allocate_node_no_has_execution_time() {
         let mut core = Core::default();
-        assert!(!core.allocate(&create_node(0, "no_execution_time", 10)));
+        let mut remain_proc_time = 0;
+        assert!(!core.allocate(
+            &create_node(0, "no_execution_time", 10),
+            &mut remain_proc_time
+        ));
     }
 
     #[test]
     fn test_core_process_normal() {
         let mut core = Core::default();
-        core.allocate(&create_node(0, "execution_time", 10));
-        assert_eq!(core.process(), Continue);
-        assert_eq!(core.remain_proc_time, 9);
-        core.process();
-        assert_eq!(core.remain_proc_time, 8);
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        assert_eq!(core.process(&mut remain_proc_time), Continue);
+        assert_eq!(remain_proc_time, 9);
+        core.process(&mut remain_proc_time);
+        assert_eq!(remain_proc_time, 8);
     }
 
     #[test]
     fn test_core_process_no_allocated() {
         let mut core = Core::default();
-        assert_eq!(core.process(), Idle);
+        let mut remain_proc_time = 0;
+        assert_eq!(core.process(&mut remain_proc_time), Idle);
     }
 
     #[test]
     fn test_core_process_when_finished() {
         let mut core = Core::default();
-        core.allocate(&create_node(0, "execution_time", 2));
-        core.process();
-        assert_eq!(core.process(), Done(create_node(0, "execution_time", 2)));
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 2), &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        assert_eq!(
+            core.process(&mut remain_proc_time),
+            Done(create_node(0, "execution_time", 2))
+        );
         assert!(core.is_idle);
         assert_eq!(core.processing_node, None);
-        assert_eq!(core.remain_proc_time, 0);
+        assert_eq!(remain_proc_time, 0);
+    }
+
+    #[test]
+    fn test_core_process_with_dispatch_overhead() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        core.set_overhead(2, 0);
+        core.allocate(&create_node(0, "execution_time", 1), &mut remain_proc_time);
+        assert_eq!(core.process(&mut remain_proc_time), Overhead);
+        assert_eq!(core.process(&mut remain_proc_time), Overhead);
+        assert_eq!(remain_proc_time, 1);
+        assert_eq!(
+            core.process(&mut remain_proc_time),
+            Done(create_node(0, "execution_time", 1))
+        );
+    }
+
+    #[test]
+    fn test_core_process_with_clock_drift() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        core.set_clock_drift(200_000);
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        for _ in 0..10 {
+            assert_eq!(core.process(&mut remain_proc_time), Continue);
+        }
+        assert_eq!(remain_proc_time, 0);
+        assert_eq!(core.remain_observation_delay, 2);
+        assert_eq!(core.process(&mut remain_proc_time), ObservationDelay);
+        assert_eq!(
+            core.process(&mut remain_proc_time),
+            Done(create_node(0, "execution_time", 10))
+        );
+        assert!(core.is_idle);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_core_set_clock_drift_lagging_clock() {
+        let mut core = Core::default();
+        core.set_clock_drift(-200_000);
+    }
+
+    #[test]
+    fn test_core_preempt_with_preemption_overhead() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        core.set_overhead(0, 1);
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        core.preempt(&mut remain_proc_time);
+        core.allocate(&create_node(1, "execution_time", 10), &mut remain_proc_time);
+        assert_eq!(core.process(&mut remain_proc_time), Overhead);
+        assert_eq!(core.process(&mut remain_proc_time), Continue);
+        assert_eq!(remain_proc_time, 9);
+    }
+
+    #[test]
+    fn test_core_allocate_preempted_node_with_crpd() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        let mut node_data = create_node(0, "execution_time", 10);
+        node_data.params.insert("crpd".to_string(), 2);
+        core.allocate(&node_data, &mut remain_proc_time);
+        assert_eq!(remain_proc_time, 10);
+
+        core.process(&mut remain_proc_time);
+        let preempted_node_data = core.preempt(&mut remain_proc_time).unwrap();
+        core.allocate(&preempted_node_data, &mut remain_proc_time);
+        assert_eq!(remain_proc_time, 11);
+    }
+
+    #[test]
+    fn test_core_fail_normal() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        assert_eq!(
+            core.fail(&mut remain_proc_time),
+            Some(create_node(0, "execution_time", 10))
+        );
+        assert!(core.is_failed);
+        assert!(!core.is_idle);
+        assert_eq!(core.process(&mut remain_proc_time), Idle);
+        assert!(!core.allocate(&create_node(1, "execution_time", 10), &mut remain_proc_time));
+    }
+
+    #[test]
+    fn test_core_fail_resumed_node() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        core.allocate(&create_node(0, "execution_time", 10), &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        let preempted_node_data = core.preempt(&mut remain_proc_time).unwrap();
+        core.allocate(&preempted_node_data, &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        let preempted_node_data = core.preempt(&mut remain_proc_time).unwrap();
+        assert_eq!(preempted_node_data.get_params_value("execution_time"), 8);
+
+        core.allocate(&preempted_node_data, &mut remain_proc_time);
+        core.process(&mut remain_proc_time);
+        assert_eq!(
+            core.fail(&mut remain_proc_time),
+            Some(create_node(0, "execution_time", 10))
+        );
+    }
+
+    #[test]
+    fn test_core_fail_when_idle() {
+        let mut core = Core::default();
+        let mut remain_proc_time = 0;
+        assert_eq!(core.fail(&mut remain_proc_time), None);
+        assert!(core.is_failed);
     }
 }
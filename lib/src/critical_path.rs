@@ -0,0 +1,475 @@
+//! Earliest and latest start times and the critical path of a DAG.
+//! They are computed on the DAG as it is, without adding the dummy source and sink nodes, in O(V + E).
+//! The analysis is not updated when the DAG is mutated, so a caller that queries an unchanged DAG repeatedly
+//! holds the CriticalPathAnalysis, and creates a new one after mutating the DAG.
+//! The communication times of the edges are ignored unless CommunicationMode::Included is given.
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::NodeData;
+use petgraph::{
+    algo::toposort,
+    graph::{EdgeReference, Graph, NodeIndex},
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use std::collections::VecDeque;
+
+fn get_execution_times(dag: &Graph<NodeData, i64>) -> Vec<i64> {
+    dag.node_weights()
+        .map(|node| {
+            *node
+                .params
+                .get("execution_time")
+                .unwrap_or_else(|| panic!("execution_time not found"))
+        })
+        .collect()
+}
+
+/// Whether the communication time of an edge, its weight, delays the start of the consumer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommunicationMode {
+    /// The producer and the consumer are on the same core, so the data is passed at no cost.
+    #[default]
+    Ignored,
+    /// The producer and the consumer are on different cores, so the consumer starts
+    /// the communication time after the producer finishes at the earliest.
+    Included,
+}
+
+impl CommunicationMode {
+    pub(crate) fn get_delay(&self, edge: EdgeReference<i64>) -> i64 {
+        match self {
+            Self::Ignored => 0,
+            Self::Included => *edge.weight(),
+        }
+    }
+}
+
+/// How to choose one of multiple critical paths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CriticalPathPolicy {
+    /// The path with the most nodes, and the lexicographically smallest one among them.
+    LongestByNodes,
+    /// The lexicographically smallest path in NodeIndex.
+    Lexicographic,
+}
+
+impl CriticalPathPolicy {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "longest_by_nodes" => Ok(Self::LongestByNodes),
+            "lexicographic" => Ok(Self::Lexicographic),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown critical path policy: {}",
+                name
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CriticalPathAnalysis {
+    earliest_start_times: Vec<i64>,
+    latest_start_times: Vec<i64>,
+    critical_path_length: i64,
+    // The successors through which a critical node passes on the critical path, in the order of the outgoing edges.
+    // The edge to a critical successor may not be on any critical path, if the successor waits for another predecessor.
+    critical_successors: Vec<Vec<NodeIndex>>,
+    // The sources on the critical paths, in the ascending order of NodeIndex
+    critical_sources: Vec<NodeIndex>,
+    critical_path: Vec<NodeIndex>,
+    // Saturates instead of overflowing, because only whether it exceeds 1 matters.
+    number_of_critical_paths: usize,
+}
+
+impl CriticalPathAnalysis {
+    pub fn new(dag: &Graph<NodeData, i64>) -> Self {
+        Self::new_with_communication_mode(dag, CommunicationMode::Ignored)
+    }
+
+    pub fn new_with_communication_mode(
+        dag: &Graph<NodeData, i64>,
+        communication_mode: CommunicationMode,
+    ) -> Self {
+        let execution_times = get_execution_times(dag);
+        let sorted_nodes = toposort(dag, None).unwrap();
+
+        let mut earliest_start_times = vec![0; dag.node_count()];
+        for &node_i in sorted_nodes.iter() {
+            earliest_start_times[node_i.index()] = dag
+                .edges_directed(node_i, Incoming)
+                .map(|edge| {
+                    let pre_index = edge.source().index();
+                    earliest_start_times[pre_index]
+                        + execution_times[pre_index]
+                        + communication_mode.get_delay(edge)
+                })
+                .max()
+                .unwrap_or(0);
+        }
+        let critical_path_length = dag
+            .node_indices()
+            .map(|node_i| earliest_start_times[node_i.index()] + execution_times[node_i.index()])
+            .max()
+            .unwrap_or(0);
+
+        let mut latest_start_times = vec![0; dag.node_count()];
+        for &node_i in sorted_nodes.iter().rev() {
+            latest_start_times[node_i.index()] = dag
+                .edges_directed(node_i, Outgoing)
+                .map(|edge| {
+                    latest_start_times[edge.target().index()] - communication_mode.get_delay(edge)
+                })
+                .min()
+                .unwrap_or(critical_path_length)
+                - execution_times[node_i.index()];
+        }
+
+        let mut analysis = Self {
+            earliest_start_times,
+            latest_start_times,
+            critical_path_length,
+            critical_successors: vec![Vec::new(); dag.node_count()],
+            critical_sources: Vec::new(),
+            critical_path: Vec::new(),
+            number_of_critical_paths: 0,
+        };
+        analysis.critical_sources = dag
+            .node_indices()
+            .filter(|&node_i| {
+                dag.neighbors_directed(node_i, Incoming).count() == 0
+                    && analysis.is_critical(node_i)
+            })
+            .collect();
+        for node_i in dag.node_indices() {
+            if analysis.is_critical(node_i) {
+                analysis.critical_successors[node_i.index()] = dag
+                    .edges_directed(node_i, Outgoing)
+                    .filter(|&edge| {
+                        analysis.get_earliest_start_time(edge.target())
+                            == analysis.get_earliest_start_time(node_i)
+                                + execution_times[node_i.index()]
+                                + communication_mode.get_delay(edge)
+                            && analysis.is_critical(edge.target())
+                    })
+                    .map(|edge| edge.target())
+                    .collect();
+            }
+        }
+        analysis.search_critical_paths(dag, &sorted_nodes);
+        analysis
+    }
+
+    /// A critical path is a path from a source to a sink through the nodes without slack.
+    /// The one with the fewest nodes is chosen, and the ties are broken in the order of the outgoing edges.
+    fn search_critical_paths(&mut self, dag: &Graph<NodeData, i64>, sorted_nodes: &[NodeIndex]) {
+        // The empty path is the only critical path of an empty DAG.
+        if dag.node_count() == 0 {
+            self.number_of_critical_paths = 1;
+            return;
+        }
+
+        // Count the paths by dynamic programming, because enumerating them may take exponential time.
+        let mut path_counts = vec![0usize; dag.node_count()];
+        for &source_i in self.critical_sources.iter() {
+            path_counts[source_i.index()] = 1;
+        }
+        for &node_i in sorted_nodes.iter() {
+            for &suc_node_i in self.critical_successors[node_i.index()].iter() {
+                path_counts[suc_node_i.index()] =
+                    path_counts[suc_node_i.index()].saturating_add(path_counts[node_i.index()]);
+            }
+        }
+        self.number_of_critical_paths = dag
+            .node_indices()
+            .filter(|&node_i| dag.neighbors_directed(node_i, Outgoing).count() == 0)
+            .fold(0usize, |count, node_i| {
+                count.saturating_add(path_counts[node_i.index()])
+            });
+
+        // Breadth-first search from the sources in the descending order of NodeIndex,
+        // which is the order of the outgoing edges of a dummy source node.
+        let mut pre_nodes_on_path: Vec<Option<NodeIndex>> = vec![None; dag.node_count()];
+        let mut is_visited = vec![false; dag.node_count()];
+        let mut search_queue = VecDeque::new();
+        for &source_i in self.critical_sources.iter().rev() {
+            is_visited[source_i.index()] = true;
+            search_queue.push_back(source_i);
+        }
+        while let Some(node_i) = search_queue.pop_front() {
+            if dag.neighbors_directed(node_i, Outgoing).count() == 0 {
+                let mut critical_path = vec![node_i];
+                while let Some(pre_node_i) =
+                    pre_nodes_on_path[critical_path.last().unwrap().index()]
+                {
+                    critical_path.push(pre_node_i);
+                }
+                critical_path.reverse();
+                self.critical_path = critical_path;
+                return;
+            }
+            for &suc_node_i in self.critical_successors[node_i.index()].iter() {
+                if !is_visited[suc_node_i.index()] {
+                    is_visited[suc_node_i.index()] = true;
+                    pre_nodes_on_path[suc_node_i.index()] = Some(node_i);
+                    search_queue.push_back(suc_node_i);
+                }
+            }
+        }
+    }
+
+    pub fn get_earliest_start_time(&self, node_i: NodeIndex) -> i64 {
+        self.earliest_start_times[node_i.index()]
+    }
+
+    pub fn get_latest_start_time(&self, node_i: NodeIndex) -> i64 {
+        self.latest_start_times[node_i.index()]
+    }
+
+    pub fn is_critical(&self, node_i: NodeIndex) -> bool {
+        self.get_earliest_start_time(node_i) == self.get_latest_start_time(node_i)
+    }
+
+    pub fn get_critical_path_length(&self) -> i64 {
+        self.critical_path_length
+    }
+
+    pub fn get_critical_path(&self) -> &[NodeIndex] {
+        &self.critical_path
+    }
+
+    pub fn has_multiple_critical_paths(&self) -> bool {
+        self.number_of_critical_paths > 1
+    }
+
+    /// Enumerate every critical path in the lexicographic order of NodeIndex.
+    /// The number of paths may grow exponentially with the number of nodes, unlike the other queries.
+    pub fn get_all_critical_paths(&self) -> Vec<Vec<NodeIndex>> {
+        if self.earliest_start_times.is_empty() {
+            return vec![Vec::new()];
+        }
+        let mut critical_paths = Vec::new();
+        for &source_i in self.critical_sources.iter() {
+            self.extend_critical_paths(&mut vec![source_i], &mut critical_paths);
+        }
+        critical_paths
+    }
+
+    fn extend_critical_paths(
+        &self,
+        path: &mut Vec<NodeIndex>,
+        critical_paths: &mut Vec<Vec<NodeIndex>>,
+    ) {
+        let mut suc_nodes = self.critical_successors[path.last().unwrap().index()].clone();
+        if suc_nodes.is_empty() {
+            critical_paths.push(path.clone());
+            return;
+        }
+        suc_nodes.sort();
+        for suc_node_i in suc_nodes {
+            path.push(suc_node_i);
+            self.extend_critical_paths(path, critical_paths);
+            path.pop();
+        }
+    }
+
+    /// Choose one of the critical paths deliberately, instead of the first one found by get_critical_path.
+    pub fn get_critical_path_with_policy(&self, policy: CriticalPathPolicy) -> Vec<NodeIndex> {
+        let critical_paths = self.get_all_critical_paths();
+        match policy {
+            // The paths are already in the lexicographic order.
+            CriticalPathPolicy::Lexicographic => critical_paths.into_iter().next(),
+            CriticalPathPolicy::LongestByNodes => critical_paths
+                .into_iter()
+                .min_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b))),
+        }
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+        dag
+    }
+
+    #[test]
+    fn test_critical_path_analysis_normal() {
+        let analysis = CriticalPathAnalysis::new(&create_dag());
+
+        assert_eq!(analysis.earliest_start_times, vec![0, 4, 4, 11, 59]);
+        assert_eq!(analysis.latest_start_times, vec![0, 70, 4, 77, 59]);
+        assert_eq!(analysis.get_critical_path_length(), 113);
+        assert_eq!(
+            analysis.get_critical_path(),
+            &[NodeIndex::new(0), NodeIndex::new(2), NodeIndex::new(4)]
+        );
+        assert!(!analysis.has_multiple_critical_paths());
+    }
+
+    #[test]
+    fn test_critical_path_analysis_multiple_critical_paths() {
+        let mut dag = create_dag();
+        // n0 -> n1 -> n3 becomes as long as n0 -> n2 -> n4, and the tie is broken by the newer edge.
+        dag.update_param(NodeIndex::new(3), "execution_time", 102);
+        let analysis = CriticalPathAnalysis::new(&dag);
+
+        assert!(analysis.has_multiple_critical_paths());
+        assert_eq!(
+            analysis.get_critical_path(),
+            &[NodeIndex::new(0), NodeIndex::new(2), NodeIndex::new(4)]
+        );
+    }
+
+    #[test]
+    fn test_get_all_critical_paths_normal() {
+        let mut dag = create_dag();
+        dag.update_param(NodeIndex::new(3), "execution_time", 102);
+        let n5 = dag.add_node(create_node(5, "execution_time", 0));
+        dag.add_edge(NodeIndex::new(2), n5, 1);
+        dag.add_edge(n5, NodeIndex::new(4), 1);
+        let analysis = CriticalPathAnalysis::new(&dag);
+
+        let [n0, n1, n2, n3, n4] = [0, 1, 2, 3, 4].map(NodeIndex::new);
+        assert_eq!(
+            analysis.get_all_critical_paths(),
+            vec![vec![n0, n1, n3], vec![n0, n2, n4], vec![n0, n2, n5, n4]]
+        );
+        assert_eq!(
+            analysis.get_critical_path_with_policy(CriticalPathPolicy::Lexicographic),
+            vec![n0, n1, n3]
+        );
+        assert_eq!(
+            analysis.get_critical_path_with_policy(CriticalPathPolicy::LongestByNodes),
+            vec![n0, n2, n5, n4]
+        );
+    }
+
+    #[test]
+    fn test_get_all_critical_paths_skip_non_critical_edge() {
+        // n1 is critical because of n4, but n3 waits for n2, so n0 -> n1 -> n3 is not a critical path.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        let n4 = dag.add_node(create_node(4, "execution_time", 5));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n3, 1);
+        dag.add_edge(n1, n4, 1);
+        let analysis = CriticalPathAnalysis::new(&dag);
+
+        assert_eq!(
+            analysis.get_all_critical_paths(),
+            vec![vec![n0, n1, n4], vec![n0, n2, n3]]
+        );
+        assert_eq!(analysis.number_of_critical_paths, 2);
+    }
+
+    #[test]
+    fn test_critical_path_analysis_without_mutation() {
+        let mut dag = create_dag();
+        // calculate_latest_start_times assumes a single sink node.
+        let n5 = dag.add_node(create_node(5, "execution_time", 0));
+        dag.add_edge(NodeIndex::new(3), n5, 1);
+        dag.add_edge(NodeIndex::new(4), n5, 1);
+        let shared_dag = &dag;
+        let critical_path = shared_dag.get_critical_path();
+        let analysis = CriticalPathAnalysis::new(shared_dag);
+
+        let mut calculated_dag = dag.clone();
+        calculated_dag.calculate_latest_start_times();
+        for node_i in dag.node_indices() {
+            // The DAG is left as it is, without the params and the dummy nodes of the calculation.
+            assert_eq!(dag[node_i].params.len(), 1);
+            assert_eq!(
+                analysis.get_earliest_start_time(node_i),
+                calculated_dag[node_i].params["earliest_start_time"]
+            );
+            assert_eq!(
+                analysis.get_latest_start_time(node_i),
+                calculated_dag[node_i].params["latest_start_time"]
+            );
+        }
+        assert_eq!(dag.node_count(), 6);
+        assert_eq!(
+            critical_path,
+            vec![NodeIndex::new(0), NodeIndex::new(2), NodeIndex::new(4), n5]
+        );
+    }
+
+    #[test]
+    fn test_critical_path_analysis_with_communication() {
+        let mut dag = create_dag();
+        *dag.edge_weight_mut(petgraph::graph::EdgeIndex::new(2))
+            .unwrap() = 80;
+        let analysis =
+            CriticalPathAnalysis::new_with_communication_mode(&dag, CommunicationMode::Included);
+
+        assert_eq!(analysis.earliest_start_times, vec![0, 5, 5, 92, 61]);
+        assert_eq!(analysis.latest_start_times, vec![0, 5, 18, 92, 74]);
+        assert_eq!(analysis.get_critical_path_length(), 128);
+        assert_eq!(
+            analysis.get_critical_path(),
+            &[NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(3)]
+        );
+        assert_eq!(
+            CriticalPathAnalysis::new(&dag).get_critical_path_length(),
+            113
+        );
+    }
+
+    #[test]
+    fn test_critical_path_policy_from_name() {
+        assert_eq!(
+            CriticalPathPolicy::from_name("longest_by_nodes").unwrap(),
+            CriticalPathPolicy::LongestByNodes
+        );
+        assert_eq!(
+            CriticalPathPolicy::from_name("lexicographic").unwrap(),
+            CriticalPathPolicy::Lexicographic
+        );
+        assert!(CriticalPathPolicy::from_name("shortest").is_err());
+    }
+
+    #[test]
+    fn test_new_after_mutation() {
+        let mut dag = create_dag();
+        let analysis = CriticalPathAnalysis::new(&dag);
+
+        // The analysis held by the caller is not updated by the mutation.
+        dag.update_param(NodeIndex::new(3), "execution_time", 200);
+        assert_ne!(analysis.get_critical_path_length(), 211);
+        assert_eq!(
+            CriticalPathAnalysis::new(&dag).get_critical_path_length(),
+            211
+        );
+
+        dag.remove_node(NodeIndex::new(3));
+        assert_eq!(
+            CriticalPathAnalysis::new(&dag).get_critical_path_length(),
+            113
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Fluent construction of a DAG, e.g., in tests.
+//!
+//! ```
+//! use lib::dag_builder::DagBuilder;
+//! let mut builder = DagBuilder::new();
+//! builder.node(0).wcet(10).period(100);
+//! builder.node(1).wcet(5);
+//! builder.edge(0, 1).comm(2);
+//! let dag = builder.build().unwrap();
+//! assert_eq!(dag.edge_count(), 1);
+//! ```
+use crate::graph_extension::NodeData;
+use petgraph::{algo::is_cyclic_directed, graph::NodeIndex, Graph};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DagBuildError {
+    /// The node ids must be 0, 1, ..., n-1 so that each id equals its NodeIndex.
+    NonContiguousNodeId(i64),
+    UnknownNode(i64),
+    Cyclic,
+}
+
+impl fmt::Display for DagBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NonContiguousNodeId(id) => write!(
+                f,
+                "Node {} breaks the node ids numbered from 0 without gaps.",
+                id
+            ),
+            Self::UnknownNode(id) => write!(f, "Node {} of an edge is not added.", id),
+            Self::Cyclic => write!(f, "The graph has a cycle."),
+        }
+    }
+}
+
+impl std::error::Error for DagBuildError {}
+
+#[derive(Default)]
+pub struct DagBuilder {
+    nodes: BTreeMap<i64, BTreeMap<String, i64>>,
+    // (source node id, target node id, communication time)
+    edges: Vec<(i64, i64, i64)>,
+}
+
+pub struct NodeBuilder<'a> {
+    params: &'a mut BTreeMap<String, i64>,
+}
+
+impl NodeBuilder<'_> {
+    pub fn param(self, key: &str, value: i64) -> Self {
+        self.params.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn wcet(self, execution_time: i64) -> Self {
+        self.param("execution_time", execution_time)
+    }
+
+    pub fn period(self, period: i64) -> Self {
+        self.param("period", period)
+    }
+
+    pub fn offset(self, offset: i64) -> Self {
+        self.param("offset", offset)
+    }
+
+    pub fn end_to_end_deadline(self, end_to_end_deadline: i64) -> Self {
+        self.param("end_to_end_deadline", end_to_end_deadline)
+    }
+
+    pub fn priority(self, priority: i64) -> Self {
+        self.param("priority", priority)
+    }
+}
+
+pub struct EdgeBuilder<'a> {
+    edge: &'a mut (i64, i64, i64),
+}
+
+impl EdgeBuilder<'_> {
+    pub fn comm(self, communication_time: i64) -> Self {
+        self.edge.2 = communication_time;
+        self
+    }
+}
+
+impl DagBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the node, or set more params of the node if it is already added.
+    pub fn node(&mut self, id: i64) -> NodeBuilder<'_> {
+        NodeBuilder {
+            params: self.nodes.entry(id).or_default(),
+        }
+    }
+
+    /// Add the edge with no communication time.
+    pub fn edge(&mut self, source: i64, target: i64) -> EdgeBuilder<'_> {
+        self.edges.push((source, target, 0));
+        EdgeBuilder {
+            edge: self.edges.last_mut().unwrap(),
+        }
+    }
+
+    pub fn build(&self) -> Result<Graph<NodeData, i64>, DagBuildError> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        for (expected_id, (id, params)) in self.nodes.iter().enumerate() {
+            if *id != expected_id as i64 {
+                return Err(DagBuildError::NonContiguousNodeId(*id));
+            }
+            dag.add_node(NodeData::new(*id, params.clone()));
+        }
+        for (source, target, communication_time) in self.edges.iter() {
+            for id in [source, target] {
+                if !self.nodes.contains_key(id) {
+                    return Err(DagBuildError::UnknownNode(*id));
+                }
+            }
+            dag.add_edge(
+                NodeIndex::new(*source as usize),
+                NodeIndex::new(*target as usize),
+                *communication_time,
+            );
+        }
+        if is_cyclic_directed(&dag) {
+            return Err(DagBuildError::Cyclic);
+        }
+        Ok(dag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+
+    #[test]
+    fn test_dag_builder_normal() {
+        let mut builder = DagBuilder::new();
+        builder.node(1).wcet(5);
+        builder.node(0).wcet(10).period(100).end_to_end_deadline(80);
+        builder.node(2).wcet(3).priority(1);
+        builder.edge(0, 1).comm(2);
+        builder.edge(0, 2);
+        let dag = builder.build().unwrap();
+
+        assert_eq!(dag.node_count(), 3);
+        for node_i in dag.node_indices() {
+            assert_eq!(dag[node_i].id, node_i.index() as i64);
+        }
+        assert_eq!(dag[NodeIndex::new(0)].params["execution_time"], 10);
+        assert_eq!(dag.get_head_period(), Some(100));
+        assert_eq!(dag.get_end_to_end_deadline(), Some(80));
+        assert_eq!(dag[NodeIndex::new(2)].params["priority"], 1);
+        let edge_i = dag.find_edge(NodeIndex::new(0), NodeIndex::new(1)).unwrap();
+        assert_eq!(dag[edge_i], 2);
+        assert_eq!(dag.get_sink_nodes().len(), 2);
+    }
+
+    #[test]
+    fn test_dag_builder_non_contiguous_node_id() {
+        let mut builder = DagBuilder::new();
+        builder.node(0).wcet(10);
+        builder.node(2).wcet(5);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            DagBuildError::NonContiguousNodeId(2)
+        );
+    }
+
+    #[test]
+    fn test_dag_builder_unknown_node() {
+        let mut builder = DagBuilder::new();
+        builder.node(0).wcet(10);
+        builder.edge(0, 1);
+        assert_eq!(builder.build().unwrap_err(), DagBuildError::UnknownNode(1));
+    }
+
+    #[test]
+    fn test_dag_builder_cyclic() {
+        let mut builder = DagBuilder::new();
+        builder.node(0).wcet(10);
+        builder.node(1).wcet(5);
+        builder.edge(0, 1);
+        builder.edge(1, 0);
+        assert_eq!(builder.build().unwrap_err(), DagBuildError::Cyclic);
+    }
+}
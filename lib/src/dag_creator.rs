@@ -1,11 +1,21 @@
 //! Generate a petgraph DAG object from a yaml file
+use crate::execution_time_distribution::{
+    get_execution_time_sample_key, ExecutionTimeDistribution,
+};
 use crate::graph_extension::{GraphExtension, NodeData};
-use crate::util::load_yaml;
+#[cfg(feature = "file_io")]
+use crate::util::{load_yaml, load_yaml_from_reader};
 
-use log::warn;
+use crate::diagnostics::{report, DiagnosticKind};
+use crate::error::{Result, SchedSimError};
+#[cfg(feature = "file_io")]
+use crate::log::{DAGSetSchedulerLog, NodeSchedule};
 use petgraph::{graph::Graph, prelude::*};
-use std::{collections::BTreeMap, path::PathBuf};
-use yaml_rust::Yaml;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "file_io")]
+use std::{fs, path::PathBuf};
+use yaml_rust::{yaml::Hash, Yaml, YamlLoader};
 
 fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
     let mut minimum_decimal_places = 0;
@@ -43,6 +53,25 @@ fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
     minimum_decimal_places
 }
 
+/// `allowed_cores` and `shared_regions` are given as a bitmask or a list of ids, and are stored as a bitmask.
+/// They are identifiers rather than times, so they are not scaled by the int conversion factor.
+fn convert_ids_to_bitmask(file_path: &str, key: &str, yaml: &Yaml) -> Result<i64> {
+    match yaml {
+        Yaml::Integer(bitmask) => Ok(*bitmask),
+        Yaml::Array(ids) => ids.iter().try_fold(0, |bitmask, id| match id.as_i64() {
+            Some(id) if (0..64).contains(&id) => Ok(bitmask | (1 << id)),
+            _ => Err(SchedSimError::parse(
+                file_path,
+                format!("{} supports ids from 0 to 63: {:?}", key, id),
+            )),
+        }),
+        _ => Err(SchedSimError::parse(
+            file_path,
+            format!("{} must be a bitmask or a list of ids.", key),
+        )),
+    }
+}
+
 /// load yaml file and return a dag object (petgraph)
 ///
 /// # Arguments
@@ -58,7 +87,7 @@ fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
 /// ```
 /// use lib::dag_creator::create_dag_from_yaml;
 ///
-/// let dag = create_dag_from_yaml("tests/sample_dags/chain_base_format.yaml", false);
+/// let dag = create_dag_from_yaml("tests/sample_dags/chain_base_format.yaml", false).unwrap();
 /// let first_node = dag.node_indices().next().unwrap();
 /// let first_edge = dag.edge_indices().next().unwrap();
 ///
@@ -67,47 +96,441 @@ fn get_minimum_decimal_places(yaml: &Yaml) -> usize {
 /// let node_id = dag[first_node].id;
 /// let edge_weight = dag[first_edge];
 /// ```
-pub fn create_dag_from_yaml(file_path: &str, exist_other_float_dag: bool) -> Graph<NodeData, i32> {
-    let yaml_docs = load_yaml(file_path);
-    let yaml_doc = &yaml_docs[0];
+#[cfg(feature = "file_io")]
+pub fn create_dag_from_yaml(
+    file_path: &str,
+    exist_other_float_dag: bool,
+) -> Result<Graph<NodeData, i64>> {
+    create_dag_from_yaml_in_format(file_path, DagFormat::Base, exist_other_float_dag)
+}
+
+/// Load a DAG from a YAML file written in `dag_format`.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::{create_dag_from_yaml_in_format, DagFormat};
+/// use lib::graph_extension::GraphExtension;
+///
+/// let dag = create_dag_from_yaml_in_format("tests/sample_dags/rd_gen_format.yaml", DagFormat::RdGen, false).unwrap();
+/// // The end-to-end deadline is given by ratio_of_deadline_to_period of the graph.
+/// assert!(dag.get_end_to_end_deadline().is_some());
+/// ```
+#[cfg(feature = "file_io")]
+pub fn create_dag_from_yaml_in_format(
+    file_path: &str,
+    dag_format: DagFormat,
+    exist_other_float_dag: bool,
+) -> Result<Graph<NodeData, i64>> {
+    let yaml_doc = dag_format.normalize(
+        file_path,
+        get_first_yaml_doc(file_path, load_yaml(file_path)?)?,
+    );
+    create_dag_from_yaml_doc(file_path, &yaml_doc, exist_other_float_dag)
+}
+
+/// Load a DAG from the YAML read from `reader`, e.g., the output of a generator piped to the standard input.
+/// `name` is the name of the input in the errors.
+/// create_dag_from_yaml reads the standard input for the path `-`.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::create_dag_from_reader;
+///
+/// let yaml = "nodes: [{id: 0, execution_time: 3}, {id: 1, execution_time: 4}]\nlinks: [{source: 0, target: 1}]";
+/// let dag = create_dag_from_reader("<generator>", yaml.as_bytes(), false).unwrap();
+/// assert_eq!(dag.edge_count(), 1);
+/// ```
+#[cfg(feature = "file_io")]
+pub fn create_dag_from_reader(
+    name: &str,
+    reader: impl std::io::Read,
+    exist_other_float_dag: bool,
+) -> Result<Graph<NodeData, i64>> {
+    let yaml_doc = get_first_yaml_doc(name, load_yaml_from_reader(name, reader)?)?;
+    create_dag_from_yaml_doc(name, &yaml_doc, exist_other_float_dag)
+}
+
+/// Load a DAG from a YAML string in the same format as create_dag_from_yaml, e.g., one uploaded in a browser.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::create_dag_from_yaml_str;
+///
+/// let dag = create_dag_from_yaml_str(
+///     "nodes: [{id: 0, execution_time: 3}, {id: 1, execution_time: 4}]\nlinks: [{source: 0, target: 1}]",
+///     false,
+/// )
+/// .unwrap();
+/// assert_eq!(dag.edge_count(), 1);
+/// ```
+pub fn create_dag_from_yaml_str(
+    yaml_str: &str,
+    exist_other_float_dag: bool,
+) -> Result<Graph<NodeData, i64>> {
+    let yaml_doc = load_yaml_str(YAML_STR_NAME, yaml_str)?;
+    create_dag_from_yaml_doc(YAML_STR_NAME, &yaml_doc, exist_other_float_dag)
+}
+
+/// The name of a YAML string in the errors, where the name of a file is its path.
+const YAML_STR_NAME: &str = "<YAML string>";
+
+/// The first document with its merge keys resolved.
+fn get_first_yaml_doc(file_path: &str, yaml_docs: Vec<Yaml>) -> Result<Yaml> {
+    let yaml_doc = yaml_docs
+        .into_iter()
+        .next()
+        .ok_or_else(|| SchedSimError::parse(file_path, "The YAML file is empty."))?;
+    resolve_merge_keys(file_path, yaml_doc)
+}
+
+/// yaml-rust expands the aliases of anchors but leaves the merge keys `<<` as ordinary keys,
+/// so the node parameter blocks shared by anchors are merged here.
+/// The keys of the mapping override the merged ones, and the earlier mappings of a merged list override the later ones.
+fn resolve_merge_keys(file_path: &str, yaml: Yaml) -> Result<Yaml> {
+    match yaml {
+        Yaml::Array(array) => Ok(Yaml::Array(
+            array
+                .into_iter()
+                .map(|element| resolve_merge_keys(file_path, element))
+                .collect::<Result<_>>()?,
+        )),
+        Yaml::Hash(hash) => {
+            let merge_key = Yaml::String("<<".to_string());
+            let mut resolved_hash = Hash::new();
+            let mut merged_hashes = Vec::new();
+            for (key, value) in hash {
+                let value = resolve_merge_keys(file_path, value)?;
+                if key != merge_key {
+                    resolved_hash.insert(key, value);
+                    continue;
+                }
+                let merged_values = match value {
+                    Yaml::Array(merged_values) => merged_values,
+                    merged_value => vec![merged_value],
+                };
+                for merged_value in merged_values {
+                    let Yaml::Hash(merged_hash) = merged_value else {
+                        return Err(SchedSimError::parse(
+                            file_path,
+                            format!(
+                                "A merge key must refer to a mapping or a list of mappings: {:?}",
+                                merged_value
+                            ),
+                        ));
+                    };
+                    merged_hashes.push(merged_hash);
+                }
+            }
+            for merged_hash in merged_hashes {
+                for (key, value) in merged_hash {
+                    if !resolved_hash.contains_key(&key) {
+                        resolved_hash.insert(key, value);
+                    }
+                }
+            }
+            Ok(Yaml::Hash(resolved_hash))
+        }
+        _ => Ok(yaml),
+    }
+}
+
+fn load_yaml_str(name: &str, yaml_str: &str) -> Result<Yaml> {
+    let yaml_docs =
+        YamlLoader::load_from_str(yaml_str).map_err(|err| SchedSimError::parse(name, err))?;
+    get_first_yaml_doc(name, yaml_docs)
+}
+
+/// The format of the DAG YAML files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DagFormat {
+    /// The node-link format whose fields are numbers, except for the few fields named in create_dag_from_yaml_doc.
+    #[default]
+    Base,
+    /// The output of the RD-Gen generator, which is converted to the base format by convert_rd_gen_yaml_doc.
+    RdGen,
+}
+
+impl DagFormat {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "base" => Ok(Self::Base),
+            "rd_gen" => Ok(Self::RdGen),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown DAG format: {}. The formats are base and rd_gen.",
+                name
+            ))),
+        }
+    }
+
+    /// Convert the YAML document to the base format.
+    fn normalize(&self, file_path: &str, yaml_doc: Yaml) -> Yaml {
+        match self {
+            Self::Base => yaml_doc,
+            Self::RdGen => convert_rd_gen_yaml_doc(file_path, &yaml_doc),
+        }
+    }
+}
+
+/// The fields of the base format that are not numbers. They are left to create_dag_from_yaml_doc.
+const BASE_FORMAT_NON_NUMERIC_KEYS: [&str; 5] = [
+    "id",
+    "allowed_cores",
+    "shared_regions",
+    "execution_time_distribution",
+    "execution_time_samples",
+];
+
+fn as_number(yaml: &Yaml) -> Option<f64> {
+    match yaml {
+        Yaml::Integer(integer) => Some(*integer as f64),
+        Yaml::Real(_) => yaml.as_f64(),
+        _ => None,
+    }
+}
+
+/// Convert a field of RD-Gen to the params of the base format, which are numbers.
+fn convert_rd_gen_field(file_path: &str, key: &Yaml, value: &Yaml, params: &mut Hash) {
+    match (key.as_str(), value) {
+        (Some(key_str), _) if BASE_FORMAT_NON_NUMERIC_KEYS.contains(&key_str) => {
+            params.insert(key.clone(), value.clone());
+        }
+        (_, Yaml::Integer(_) | Yaml::Real(_)) => {
+            params.insert(key.clone(), value.clone());
+        }
+        (_, Yaml::Boolean(flag)) => {
+            params.insert(key.clone(), Yaml::Integer(*flag as i64));
+        }
+        (Some(key_str), Yaml::Array(values)) if values.iter().all(|v| as_number(v).is_some()) => {
+            for (index, value) in values.iter().enumerate() {
+                params.insert(
+                    Yaml::String(format!("{}_{}", key_str, index)),
+                    value.clone(),
+                );
+            }
+        }
+        _ => report(
+            DiagnosticKind::NonNumericParamDropped,
+            format!(
+                "{:?} of {} is not a number and is dropped: {:?}",
+                key, file_path, value
+            ),
+        ),
+    }
+}
+
+/// The end-to-end deadline of a sink node is `ratio_of_deadline_to_period` times the period of the chain it ends,
+/// i.e., the shortest period among the node and its ancestors, as the DAGs of RD-Gen may have multiple periods.
+fn assign_rd_gen_end_to_end_deadlines(file_path: &str, nodes: &mut [Yaml], links: &[Yaml]) {
+    let mut predecessors = vec![Vec::new(); nodes.len()];
+    let mut is_sink = vec![true; nodes.len()];
+    for link in links {
+        if let (Some(source), Some(target)) = (link["source"].as_i64(), link["target"].as_i64()) {
+            let (source, target) = (source as usize, target as usize);
+            // A link to no node is reported by create_dag_from_yaml_doc.
+            if source < nodes.len() && target < nodes.len() {
+                predecessors[target].push(source);
+                is_sink[source] = false;
+            }
+        }
+    }
+
+    for sink_i in (0..nodes.len()).filter(|node_i| is_sink[*node_i]) {
+        let Some(ratio) = as_number(&nodes[sink_i]["ratio_of_deadline_to_period"]) else {
+            continue;
+        };
+        if !nodes[sink_i]["end_to_end_deadline"].is_badvalue() {
+            continue;
+        }
+        let mut period = None;
+        let mut visited = vec![false; nodes.len()];
+        let mut stack = vec![sink_i];
+        while let Some(node_i) = stack.pop() {
+            if std::mem::replace(&mut visited[node_i], true) {
+                continue;
+            }
+            if let Some(node_period) = as_number(&nodes[node_i]["period"]) {
+                period = Some(period.map_or(node_period, |period: f64| period.min(node_period)));
+            }
+            stack.extend(predecessors[node_i].iter().copied());
+        }
+        let Some(period) = period else {
+            report(
+                DiagnosticKind::MissingPeriod,
+                format!(
+                    "The sink node {} of {} has no period to apply ratio_of_deadline_to_period.",
+                    sink_i, file_path
+                ),
+            );
+            continue;
+        };
+        // Round off the error of the multiplication, which is finer than the int conversion factor.
+        let end_to_end_deadline = (period * ratio * 100000.0).round() / 100000.0;
+        let end_to_end_deadline = if end_to_end_deadline.fract() == 0.0 {
+            Yaml::Integer(end_to_end_deadline as i64)
+        } else {
+            Yaml::Real(end_to_end_deadline.to_string())
+        };
+        if let Yaml::Hash(node) = &mut nodes[sink_i] {
+            node.insert(
+                Yaml::String("end_to_end_deadline".to_string()),
+                end_to_end_deadline,
+            );
+        }
+    }
+}
+
+/// RD-Gen writes the DAGs in the node-link format with fields that the base format rejects or ignores,
+/// which are converted as follows:
+/// - The numeric fields of `graph`, e.g., `ratio_of_deadline_to_period`, are added to every node
+///   as DAG params unless the node has its own.
+/// - Booleans become 0 or 1, and lists of numbers become the params `<key>_<index>`.
+///   They are scaled by the int conversion factor of a float DAG as the other params are.
+/// - The other non-numeric fields, e.g., labels, are dropped with a diagnostic because the params are integers.
+/// - `ratio_of_deadline_to_period` gives the end-to-end deadline of the sink nodes without one.
+///
+/// A document that is not a DAG is returned as it is, and rejected by create_dag_from_yaml_doc.
+fn convert_rd_gen_yaml_doc(file_path: &str, yaml_doc: &Yaml) -> Yaml {
+    let (Some(dag_hash), Some(nodes), Some(links)) = (
+        yaml_doc.as_hash(),
+        yaml_doc["nodes"].as_vec(),
+        yaml_doc["links"].as_vec(),
+    ) else {
+        return yaml_doc.clone();
+    };
+    let mut dag_params = Hash::new();
+    if let Some(graph) = yaml_doc["graph"].as_hash() {
+        for (key, value) in graph {
+            convert_rd_gen_field(file_path, key, value, &mut dag_params);
+        }
+    }
+
+    let mut converted_nodes = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let Some(node_hash) = node.as_hash() else {
+            converted_nodes.push(node.clone());
+            continue;
+        };
+        let mut converted_node = Hash::new();
+        for (key, value) in node_hash {
+            convert_rd_gen_field(file_path, key, value, &mut converted_node);
+        }
+        for (key, value) in dag_params.iter() {
+            if !converted_node.contains_key(key) {
+                converted_node.insert(key.clone(), value.clone());
+            }
+        }
+        converted_nodes.push(Yaml::Hash(converted_node));
+    }
+    assign_rd_gen_end_to_end_deadlines(file_path, &mut converted_nodes, links);
+
+    let mut converted_dag_hash = dag_hash.clone();
+    converted_dag_hash.insert(
+        Yaml::String("nodes".to_string()),
+        Yaml::Array(converted_nodes),
+    );
+    Yaml::Hash(converted_dag_hash)
+}
+
+/// `file_path` is the name of the YAML in the errors.
+fn create_dag_from_yaml_doc(
+    file_path: &str,
+    yaml_doc: &Yaml,
+    exist_other_float_dag: bool,
+) -> Result<Graph<NodeData, i64>> {
     let mut int_conversion_factor =
-        10f32.powi(get_minimum_decimal_places(yaml_doc).try_into().unwrap()) as i32;
+        10f32.powi(get_minimum_decimal_places(yaml_doc).try_into().unwrap()) as i64;
     if exist_other_float_dag || int_conversion_factor > 1 {
         if int_conversion_factor > 100000 {
-            warn!("The number of decimal places is too large. The sixth decimal place is rounded off.")
+            report(DiagnosticKind::DecimalPlacesRounded, "The number of decimal places is too large. The sixth decimal place is rounded off.")
         }
         int_conversion_factor = 100000;
     }
 
     // Check if nodes and links fields exist
     if let (Some(nodes), Some(links)) = (yaml_doc["nodes"].as_vec(), yaml_doc["links"].as_vec()) {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
 
         // add nodes to dag
         for node in nodes {
             let mut params = BTreeMap::new();
-            let id = node["id"].as_i64().unwrap() as i32;
+            let (Some(id), Some(node_hash)) = (node["id"].as_i64(), node.as_hash()) else {
+                return Err(SchedSimError::parse(
+                    file_path,
+                    format!("A node must be a map with an integer id: {:?}", node),
+                ));
+            };
 
             // add node parameters to BTreeMap
-            for (key, value) in node.as_hash().unwrap() {
-                let key_str = key.as_str().unwrap();
-                if key_str != "id" {
+            for (key, value) in node_hash {
+                let key_str = key.as_str().ok_or_else(|| {
+                    SchedSimError::parse(file_path, format!("Keys must be strings: {:?}", key))
+                })?;
+                if key_str == "allowed_cores" || key_str == "shared_regions" {
+                    params.insert(
+                        key_str.to_owned(),
+                        convert_ids_to_bitmask(file_path, key_str, value)?,
+                    );
+                } else if key_str == "execution_time_distribution" {
+                    let name = value.as_str().ok_or_else(|| {
+                        SchedSimError::parse(
+                            file_path,
+                            format!("execution_time_distribution must be a string: {:?}", value),
+                        )
+                    })?;
+                    let distribution =
+                        ExecutionTimeDistribution::from_name(name).map_err(|_| {
+                            SchedSimError::parse(
+                                file_path,
+                                format!("Unknown execution_time_distribution: {}", name),
+                            )
+                        })?;
+                    params.insert(key_str.to_owned(), distribution as i64);
+                } else if key_str == "execution_time_samples" {
+                    let samples = value.as_vec().ok_or_else(|| {
+                        SchedSimError::parse(
+                            file_path,
+                            format!("execution_time_samples must be a list: {:?}", value),
+                        )
+                    })?;
+                    for (index, sample) in samples.iter().enumerate() {
+                        let sample = match sample {
+                            Yaml::Integer(sample) => sample * int_conversion_factor,
+                            Yaml::Real(_) => (sample.as_f64().unwrap()
+                                * int_conversion_factor as f64)
+                                .round() as i64,
+                            _ => {
+                                return Err(SchedSimError::parse(
+                                    file_path,
+                                    format!("Execution time samples must be numbers: {:?}", sample),
+                                ))
+                            }
+                        };
+                        params.insert(get_execution_time_sample_key(index), sample);
+                    }
+                } else if key_str != "id" {
                     match value {
                         Yaml::Integer(_i) => {
                             params.insert(
                                 key_str.to_owned(),
-                                (value.as_i64().unwrap() * int_conversion_factor as i64) as i32,
+                                value.as_i64().unwrap() * int_conversion_factor,
                             );
                         }
                         Yaml::Real(_r) => {
                             params.insert(
                                 key_str.to_owned(),
                                 (value.as_f64().unwrap() * int_conversion_factor as f64).round()
-                                    as i32,
+                                    as i64,
                             );
                         }
                         _ => {
-                            panic!("Unknown type: {}", std::any::type_name::<Yaml>());
+                            return Err(SchedSimError::parse(
+                                file_path,
+                                format!("{} must be a number: {:?}", key_str, value),
+                            ));
                         }
                     }
                 }
@@ -117,21 +540,38 @@ pub fn create_dag_from_yaml(file_path: &str, exist_other_float_dag: bool) -> Gra
 
         // add edges to dag
         for link in links {
-            let source = link["source"].as_i64().unwrap() as usize;
-            let target = link["target"].as_i64().unwrap() as usize;
+            let (Some(source), Some(target)) = (link["source"].as_i64(), link["target"].as_i64())
+            else {
+                return Err(SchedSimError::parse(
+                    file_path,
+                    format!("A link must have an integer source and target: {:?}", link),
+                ));
+            };
+            let (source, target) = (source as usize, target as usize);
+            if source >= dag.node_count() || target >= dag.node_count() {
+                return Err(SchedSimError::parse(
+                    file_path,
+                    format!("A link refers to a node that does not exist: {:?}", link),
+                ));
+            }
             let mut communication_time = 0;
 
             match &link["communication_time"] {
                 Yaml::Integer(communication_time_value) => {
-                    communication_time = *communication_time_value as i32 * int_conversion_factor;
+                    communication_time = *communication_time_value * int_conversion_factor;
                 }
                 Yaml::Real(communication_time_value) => {
                     communication_time = (communication_time_value.parse::<f32>().unwrap()
                         * int_conversion_factor as f32)
-                        as i32;
+                        as i64;
                 }
                 Yaml::BadValue => {}
-                _ => unreachable!(),
+                value => {
+                    return Err(SchedSimError::parse(
+                        file_path,
+                        format!("communication_time must be a number: {:?}", value),
+                    ))
+                }
             }
             dag.add_edge(
                 NodeIndex::new(source),
@@ -139,28 +579,37 @@ pub fn create_dag_from_yaml(file_path: &str, exist_other_float_dag: bool) -> Gra
                 communication_time,
             );
         }
-        dag
+        Ok(dag)
     } else {
-        panic!("YAML files are not DAG structures.");
+        Err(SchedSimError::parse(
+            file_path,
+            "YAML files are not DAG structures.",
+        ))
     }
 }
 
-fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
-    if !std::fs::metadata(dir_path).unwrap().is_dir() {
-        panic!("Not a directory");
-    }
+#[cfg(feature = "file_io")]
+pub(crate) fn get_yaml_paths_from_dir(dir_path: &str) -> Result<Vec<String>> {
+    let dir_entries = fs::read_dir(dir_path).map_err(|err| SchedSimError::io(dir_path, err))?;
     let mut file_path_list = Vec::new();
-    for dir_entry_result in PathBuf::from(dir_path).read_dir().unwrap() {
-        let path = dir_entry_result.unwrap().path();
-        let extension = path.extension().unwrap();
-        if extension == "yaml" || extension == "yml" {
-            file_path_list.push(path.to_str().unwrap().to_string());
+    for dir_entry_result in dir_entries {
+        let path = dir_entry_result
+            .map_err(|err| SchedSimError::io(dir_path, err))?
+            .path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml")
+        {
+            file_path_list.push(path.to_string_lossy().into_owned());
         }
     }
     if file_path_list.is_empty() {
-        panic!("No YAML file found in {}", dir_path);
+        return Err(SchedSimError::Validation(format!(
+            "No YAML file found in {}",
+            dir_path
+        )));
     }
-    file_path_list
+    Ok(file_path_list)
 }
 
 /// load yaml files and return a DAGSet (dag list)
@@ -177,42 +626,389 @@ fn get_yaml_paths_from_dir(dir_path: &str) -> Vec<String> {
 ///
 /// ```
 /// use lib::dag_creator::create_dag_set_from_dir;
-/// let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
+/// let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml").unwrap();
 /// let first_node_num = dag_set[0].node_count();
 /// let first_edge_num = dag_set[0].edge_count();
 /// let first_node_exe_time = dag_set[0][dag_set[0].node_indices().next().unwrap()].params["execution_time"];
 /// ```
-pub fn create_dag_set_from_dir(dir_path: &str) -> Vec<Graph<NodeData, i32>> {
-    let mut file_path_list = get_yaml_paths_from_dir(dir_path);
+#[cfg(feature = "file_io")]
+pub fn create_dag_set_from_dir(dir_path: &str) -> Result<Vec<Graph<NodeData, i64>>> {
+    create_dag_set_from_dir_in_format(dir_path, DagFormat::Base)
+}
+
+/// Load a DAG set from the YAML files in the directory written in `dag_format`.
+#[cfg(feature = "file_io")]
+pub fn create_dag_set_from_dir_in_format(
+    dir_path: &str,
+    dag_format: DagFormat,
+) -> Result<Vec<Graph<NodeData, i64>>> {
+    let mut file_path_list = get_yaml_paths_from_dir(dir_path)?;
     file_path_list.sort();
-    let exist_float_dag = get_yaml_paths_from_dir(dir_path).iter().any(|file_path| {
-        let yaml_doc = &load_yaml(file_path)[0];
-        get_minimum_decimal_places(yaml_doc) > 0
-    });
-    let mut dag_set: Vec<Graph<NodeData, i32>> = Vec::new();
+    let named_yaml_docs = file_path_list
+        .into_iter()
+        .map(|file_path| {
+            let yaml_doc = dag_format.normalize(
+                &file_path,
+                get_first_yaml_doc(&file_path, load_yaml(&file_path)?)?,
+            );
+            Ok((file_path, yaml_doc))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    create_dag_set_from_yaml_docs(&named_yaml_docs)
+}
+
+/// Load a DAG set from a YAML file with one DAG per document, i.e., the DAGs are separated by `---`.
+/// The DAG ids are assigned in the order of the documents.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::create_dag_set_from_yaml;
+///
+/// let dag_set = create_dag_set_from_yaml("tests/sample_dags/multiple_documents.yaml").unwrap();
+/// assert_eq!(dag_set.len(), 2);
+/// ```
+#[cfg(feature = "file_io")]
+pub fn create_dag_set_from_yaml(file_path: &str) -> Result<Vec<Graph<NodeData, i64>>> {
+    create_dag_set_from_yaml_in_format(file_path, DagFormat::Base)
+}
 
-    for (dag_id, file_path) in file_path_list.iter().enumerate() {
-        let mut dag = create_dag_from_yaml(file_path, exist_float_dag);
-        dag.set_dag_param("dag_id", dag_id as i32);
-        dag_set.push(dag);
+/// Load a DAG set from a YAML file with one DAG per document written in `dag_format`.
+#[cfg(feature = "file_io")]
+pub fn create_dag_set_from_yaml_in_format(
+    file_path: &str,
+    dag_format: DagFormat,
+) -> Result<Vec<Graph<NodeData, i64>>> {
+    let yaml_docs = load_yaml(file_path)?;
+    if yaml_docs.is_empty() {
+        return Err(SchedSimError::parse(file_path, "The YAML file is empty."));
     }
-    dag_set
+    let named_yaml_docs = yaml_docs
+        .into_iter()
+        .enumerate()
+        .map(|(dag_id, yaml_doc)| {
+            let name = format!("{} (document {})", file_path, dag_id);
+            let yaml_doc = dag_format.normalize(&name, resolve_merge_keys(&name, yaml_doc)?);
+            Ok((name, yaml_doc))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    create_dag_set_from_yaml_docs(&named_yaml_docs)
 }
 
-#[cfg(test)]
+/// Load a DAG set from YAML strings. The DAG ids are assigned in the order of the strings.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::create_dag_set_from_yaml_strs;
+///
+/// let dag_set = create_dag_set_from_yaml_strs(&[
+///     "nodes: [{id: 0, execution_time: 3, period: 10}]\nlinks: []".to_string(),
+///     "nodes: [{id: 0, execution_time: 1.5, period: 20}]\nlinks: []".to_string(),
+/// ])
+/// .unwrap();
+/// // The int conversion factor of the float DAG is applied to both DAGs.
+/// assert_eq!(dag_set[0][dag_set[0].node_indices().next().unwrap()].params["execution_time"], 300000);
+/// ```
+pub fn create_dag_set_from_yaml_strs(yaml_strs: &[String]) -> Result<Vec<Graph<NodeData, i64>>> {
+    create_dag_set_from_yaml_strs_in_format(yaml_strs, DagFormat::Base)
+}
+
+/// Load a DAG set from YAML strings written in `dag_format`.
+pub fn create_dag_set_from_yaml_strs_in_format(
+    yaml_strs: &[String],
+    dag_format: DagFormat,
+) -> Result<Vec<Graph<NodeData, i64>>> {
+    let named_yaml_docs = yaml_strs
+        .iter()
+        .enumerate()
+        .map(|(dag_id, yaml_str)| {
+            let name = format!("{} {}", YAML_STR_NAME, dag_id);
+            let yaml_doc = dag_format.normalize(&name, load_yaml_str(&name, yaml_str)?);
+            Ok((name, yaml_doc))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    create_dag_set_from_yaml_docs(&named_yaml_docs)
+}
+
+/// If any DAG has float params, all the DAGs are scaled by the same int conversion factor.
+fn create_dag_set_from_yaml_docs(
+    named_yaml_docs: &[(String, Yaml)],
+) -> Result<Vec<Graph<NodeData, i64>>> {
+    let exist_float_dag = named_yaml_docs
+        .iter()
+        .any(|(_, yaml_doc)| get_minimum_decimal_places(yaml_doc) > 0);
+    named_yaml_docs
+        .iter()
+        .enumerate()
+        .map(|(dag_id, (name, yaml_doc))| {
+            let mut dag = create_dag_from_yaml_doc(name, yaml_doc, exist_float_dag)?;
+            dag.set_dag_param("dag_id", dag_id as i64);
+            Ok(dag)
+        })
+        .collect()
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize)]
+struct LinkYaml {
+    source: usize,
+    target: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    communication_time: i64,
+}
+
+#[cfg(feature = "file_io")]
+fn is_zero(value: &i64) -> bool {
+    *value == 0
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize)]
+struct DAGYaml {
+    directed: bool,
+    multigraph: bool,
+    graph: BTreeMap<String, i64>,
+    nodes: Vec<BTreeMap<String, i64>>,
+    links: Vec<LinkYaml>,
+}
+
+/// Write the DAG in the node-link format read by create_dag_from_yaml.
+/// The params are written as integers, so bitmask and name params such as `allowed_cores` are not restored.
+/// `dag_id` is omitted because it is assigned on loading.
+#[cfg(feature = "file_io")]
+pub fn dump_dag_to_yaml(dag: &Graph<NodeData, i64>, file_path: &str) -> Result<()> {
+    let dag_yaml = DAGYaml {
+        directed: true,
+        multigraph: false,
+        graph: BTreeMap::new(),
+        nodes: dag
+            .node_indices()
+            .map(|node_i| {
+                let mut node = dag[node_i].params.clone();
+                node.remove("dag_id");
+                node.insert("id".to_string(), dag[node_i].id);
+                node
+            })
+            .collect(),
+        links: dag
+            .edge_references()
+            .map(|edge| LinkYaml {
+                source: edge.source().index(),
+                target: edge.target().index(),
+                communication_time: *edge.weight(),
+            })
+            .collect(),
+    };
+    let yaml = serde_yaml::to_string(&dag_yaml).expect("Failed to serialize.");
+    fs::write(file_path, yaml).map_err(|err| SchedSimError::io(file_path, err))
+}
+
+/// Write a copy of the DAG YAML with `assigned_core`, `start_time` and `finish_time` added to each scheduled node.
+/// The original fields are kept as they are, so the copy can be loaded again by create_dag_from_yaml.
+/// The times are in the units of the simulation, which are scaled if the DAG set has float params.
+#[cfg(feature = "file_io")]
+pub fn dump_annotated_dag_yaml(
+    input_file_path: &str,
+    node_schedules: &BTreeMap<usize, NodeSchedule>,
+    output_file_path: &str,
+) -> Result<()> {
+    let file_content = fs::read_to_string(input_file_path)
+        .map_err(|err| SchedSimError::io(input_file_path, err))?;
+    let mut dag_yaml: serde_yaml::Value = serde_yaml::from_str(&file_content)
+        .map_err(|err| SchedSimError::parse(input_file_path, err))?;
+    let nodes = dag_yaml["nodes"]
+        .as_sequence_mut()
+        .ok_or_else(|| SchedSimError::parse(input_file_path, "No nodes found."))?;
+    for node in nodes.iter_mut() {
+        let Some(node_schedule) = node["id"]
+            .as_u64()
+            .and_then(|node_id| node_schedules.get(&(node_id as usize)))
+        else {
+            continue;
+        };
+        let Some(node) = node.as_mapping_mut() else {
+            continue;
+        };
+        node.insert(
+            "assigned_core".into(),
+            (node_schedule.assigned_core as u64).into(),
+        );
+        node.insert("start_time".into(), node_schedule.start_time.into());
+        node.insert("finish_time".into(), node_schedule.finish_time.into());
+    }
+    let yaml = serde_yaml::to_string(&dag_yaml).expect("Failed to serialize.");
+    fs::write(output_file_path, yaml).map_err(|err| SchedSimError::io(output_file_path, err))
+}
+
+/// Annotate each DAG YAML in the directory with the schedule of its `job_id`-th job in the log,
+/// and write the copies with the same file names to the output directory.
+/// The DAG ids are assigned in the same order as create_dag_set_from_dir.
+///
+/// # Returns
+///
+/// *  `output_file_paths` - paths of the annotated DAGs in the order of the DAG id
+#[cfg(feature = "file_io")]
+pub fn dump_annotated_dag_set_yamls(
+    dir_path: &str,
+    log: &DAGSetSchedulerLog,
+    job_id: usize,
+    output_dir_path: &str,
+) -> Result<Vec<String>> {
+    if fs::canonicalize(dir_path).ok() == fs::canonicalize(output_dir_path).ok() {
+        return Err(SchedSimError::Validation(format!(
+            "The annotated DAGs would overwrite the input DAGs in {}",
+            dir_path
+        )));
+    }
+    fs::create_dir_all(output_dir_path).map_err(|err| SchedSimError::io(output_dir_path, err))?;
+    let mut file_path_list = get_yaml_paths_from_dir(dir_path)?;
+    file_path_list.sort();
+    file_path_list
+        .iter()
+        .enumerate()
+        .map(|(dag_id, file_path)| {
+            let output_file_path = PathBuf::from(output_dir_path)
+                .join(PathBuf::from(file_path).file_name().unwrap())
+                .to_str()
+                .unwrap()
+                .to_string();
+            dump_annotated_dag_yaml(
+                file_path,
+                &log.get_node_schedules(dag_id, job_id),
+                &output_file_path,
+            )?;
+            Ok(output_file_path)
+        })
+        .collect()
+}
+
+/// Derive the RNG seed of a run from the DAG file names in the directory and the repetition index.
+/// Only the file names are hashed, so the same run of a sweep gets the same seed wherever the DAG set is located.
+/// The hash is FNV-1a because the hasher of the standard library may change between Rust versions.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::get_experiment_seed;
+///
+/// let seed = get_experiment_seed("tests/sample_dags/multiple_yaml", 0).unwrap();
+/// ```
+#[cfg(feature = "file_io")]
+pub fn get_experiment_seed(dir_path: &str, repetition_index: u64) -> Result<u64> {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut file_names: Vec<String> = get_yaml_paths_from_dir(dir_path)?
+        .iter()
+        .map(|file_path| {
+            PathBuf::from(file_path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    file_names.sort();
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in file_names
+        .iter()
+        .flat_map(|file_name| file_name.bytes().chain([b'\n']))
+        .chain(repetition_index.to_le_bytes())
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    Ok(hash)
+}
+
+#[cfg(all(test, feature = "file_io"))]
 mod tests {
     use super::*;
+    use crate::diagnostics::DiagnosticCollector;
+
+    #[test]
+    fn test_get_experiment_seed_normal() {
+        let seed = get_experiment_seed("tests/sample_dags/multiple_yaml", 0).unwrap();
+        assert_eq!(
+            seed,
+            get_experiment_seed("tests/sample_dags/../sample_dags/multiple_yaml", 0).unwrap()
+        );
+        assert_ne!(
+            seed,
+            get_experiment_seed("tests/sample_dags/multiple_yaml", 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dump_dag_to_yaml_normal() {
+        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", false).unwrap();
+        let file_path = "../lib/tests/dump_dag_test.yaml";
+        dump_dag_to_yaml(&dag, file_path).unwrap();
+
+        let dumped_dag = create_dag_from_yaml(file_path, false).unwrap();
+        assert_eq!(dumped_dag.node_count(), dag.node_count());
+        for node_i in dag.node_indices() {
+            assert_eq!(dumped_dag[node_i], dag[node_i]);
+        }
+        assert_eq!(
+            dumped_dag
+                .edge_references()
+                .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+                .collect::<Vec<_>>(),
+            dag.edge_references()
+                .map(|edge| (edge.source(), edge.target(), *edge.weight()))
+                .collect::<Vec<_>>()
+        );
+        std::fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_dump_annotated_dag_yaml_normal() {
+        let file_path = "../lib/tests/dump_annotated_dag_test.yaml";
+        let mut node_schedules = BTreeMap::new();
+        node_schedules.insert(
+            1,
+            NodeSchedule {
+                assigned_core: 2,
+                start_time: 10,
+                finish_time: 23,
+            },
+        );
+        dump_annotated_dag_yaml(
+            "tests/sample_dags/gnp_format.yaml",
+            &node_schedules,
+            file_path,
+        )
+        .unwrap();
+
+        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", false).unwrap();
+        let annotated_dag = create_dag_from_yaml(file_path, false).unwrap();
+        assert_eq!(annotated_dag.node_count(), dag.node_count());
+        assert_eq!(annotated_dag.edge_count(), dag.edge_count());
+        let annotated_node = &annotated_dag[NodeIndex::new(1)];
+        assert_eq!(annotated_node.params["assigned_core"], 2);
+        assert_eq!(annotated_node.params["start_time"], 10);
+        assert_eq!(annotated_node.params["finish_time"], 23);
+        assert_eq!(
+            annotated_node.params["execution_time"],
+            dag[NodeIndex::new(1)].params["execution_time"]
+        );
+        assert_eq!(annotated_dag[NodeIndex::new(0)], dag[NodeIndex::new(0)]);
+        std::fs::remove_file(file_path).unwrap();
+    }
 
     #[test]
     fn test_get_minimum_decimal_places_normal() {
-        let yaml_docs = load_yaml("tests/sample_dags/float_params.yaml");
+        let yaml_docs = load_yaml("tests/sample_dags/float_params.yaml").unwrap();
         let yaml_doc = &yaml_docs[0];
         let number_of_digits = get_minimum_decimal_places(yaml_doc);
         assert_eq!(number_of_digits, 1, "number of digits is expected to be 1");
     }
     #[test]
     fn test_create_dag_set_from_dir_multiple_int_yaml() {
-        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml");
+        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml").unwrap();
         let first_node = NodeIndex::new(0);
         assert_eq!(dag_set.len(), 2, "number of dag_set is expected to be 2");
         assert_eq!(
@@ -227,7 +1023,7 @@ mod tests {
 
     #[test]
     fn test_create_dag_set_from_dir_multiple_float_yaml() {
-        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_float_yaml");
+        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_float_yaml").unwrap();
         let first_node = NodeIndex::new(0);
         assert_eq!(dag_set.len(), 2, "number of dag_set is expected to be 2");
         assert_eq!(
@@ -242,7 +1038,7 @@ mod tests {
 
     #[test]
     fn test_create_dag_set_from_dir_int_float_yaml() {
-        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_int_float_yaml");
+        let dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_int_float_yaml").unwrap();
         let first_node = NodeIndex::new(0);
         assert_eq!(dag_set.len(), 2, "number of dag_set is expected to be 2");
         assert_eq!(
@@ -255,37 +1051,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_dag_set_from_yaml_multiple_documents() {
+        let dag_set =
+            create_dag_set_from_yaml("tests/sample_dags/multiple_documents.yaml").unwrap();
+        let dir_dag_set = create_dag_set_from_dir("tests/sample_dags/multiple_yaml").unwrap();
+        assert_eq!(dag_set.len(), dir_dag_set.len());
+        for (dag, dir_dag) in dag_set.iter().zip(dir_dag_set.iter()) {
+            assert_eq!(dag.node_count(), dir_dag.node_count());
+            for node_i in dag.node_indices() {
+                assert_eq!(dag[node_i], dir_dag[node_i]);
+            }
+            assert_eq!(
+                dag.edge_weights().collect::<Vec<_>>(),
+                dir_dag.edge_weights().collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(dag_set[1].get_dag_param("dag_id"), 1);
+    }
+
+    #[test]
+    fn test_create_dag_set_from_yaml_broken_document() {
+        let file_path = "../lib/tests/broken_document_test.yaml";
+        std::fs::write(
+            file_path,
+            "nodes: [{id: 0, execution_time: 3}]\nlinks: []\n---\nnodes: [{id: 0}]\n",
+        )
+        .unwrap();
+        let result = create_dag_set_from_yaml(file_path);
+        std::fs::remove_file(file_path).unwrap();
+        let Err(SchedSimError::Parse { path, .. }) = result else {
+            panic!("The second document has no links.");
+        };
+        assert!(path.ends_with("(document 1)"));
+    }
+
     #[test]
     fn test_create_dag_set_from_dir_mixing_dif_ext() {
-        let dag_set = create_dag_set_from_dir("tests/sample_dags/mixing_different_extensions");
+        let dag_set =
+            create_dag_set_from_dir("tests/sample_dags/mixing_different_extensions").unwrap();
         assert_eq!(dag_set.len(), 1, "number of dag_set is expected to be 1");
     }
 
     #[test]
-    #[should_panic]
     fn test_create_dag_set_from_dir_mixing_not_dag_yaml() {
-        create_dag_set_from_dir("tests/sample_dags/mixing_not_dag_yaml");
+        assert!(matches!(
+            create_dag_set_from_dir("tests/sample_dags/mixing_not_dag_yaml"),
+            Err(SchedSimError::Parse { .. })
+        ));
     }
 
     #[test]
-    #[should_panic]
     fn test_create_dag_set_from_dir_no_yaml() {
-        create_dag_set_from_dir("tests/sample_dags/no_yaml");
+        assert!(matches!(
+            create_dag_set_from_dir("tests/sample_dags/no_yaml"),
+            Err(SchedSimError::Validation(_))
+        ));
     }
 
     #[test]
-    #[should_panic]
     fn test_create_dag_set_from_dir_no_dir() {
-        create_dag_set_from_dir("tests/sample_dags/gnp_format.yaml");
+        assert!(matches!(
+            create_dag_set_from_dir("tests/sample_dags/gnp_format.yaml"),
+            Err(SchedSimError::Io { .. })
+        ));
     }
 
     #[test]
     fn test_create_dag_from_yaml_chain_base() {
-        let dag = create_dag_from_yaml("tests/sample_dags/chain_base_format.yaml", false);
+        let dag = create_dag_from_yaml("tests/sample_dags/chain_base_format.yaml", false).unwrap();
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 22, "number of nodes is expected to be 22");
         assert_eq!(
@@ -332,11 +1170,12 @@ mod tests {
 
     #[test]
     fn test_create_dag_from_yaml_fan_in_fan_out() {
-        let dag = create_dag_from_yaml("tests/sample_dags/fan_in_fan_out_format.yaml", false);
+        let dag =
+            create_dag_from_yaml("tests/sample_dags/fan_in_fan_out_format.yaml", false).unwrap();
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 20, "number of nodes is expected to be 20");
         assert_eq!(
@@ -389,11 +1228,11 @@ mod tests {
 
     #[test]
     fn test_create_dag_from_yaml_gnp() {
-        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", false);
+        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", false).unwrap();
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 70, "number of nodes is expected to be 70");
         assert_eq!(dag[first_node].id, 0, "first node id is expected to be 0");
@@ -469,11 +1308,11 @@ mod tests {
 
     #[test]
     fn test_create_dag_from_yaml_float_params() {
-        let dag = create_dag_from_yaml("tests/sample_dags/float_params.yaml", false);
+        let dag = create_dag_from_yaml("tests/sample_dags/float_params.yaml", false).unwrap();
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 3, "number of nodes is expected to be 3");
         assert_eq!(
@@ -529,11 +1368,11 @@ mod tests {
 
     #[test]
     fn test_create_dag_from_dag_int_when_other_dag_float() {
-        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", true);
+        let dag = create_dag_from_yaml("tests/sample_dags/gnp_format.yaml", true).unwrap();
         let first_node = dag.node_indices().next().unwrap();
-        let last_node = dag.node_indices().last().unwrap();
+        let last_node = dag.node_indices().next_back().unwrap();
         let first_edge = dag.edge_indices().next().unwrap();
-        let last_edge = dag.edge_indices().last().unwrap();
+        let last_edge = dag.edge_indices().next_back().unwrap();
 
         assert_eq!(dag.node_count(), 70, "number of nodes is expected to be 70");
         assert_eq!(dag[first_node].id, 0, "first node id is expected to be 0");
@@ -608,20 +1447,199 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_create_dag_from_yaml_allowed_cores() {
+        let dag = create_dag_from_yaml("tests/sample_dags/allowed_cores.yaml", false).unwrap();
+        // allowed_cores is given as a list.
+        assert_eq!(dag[NodeIndex::new(0)].params["allowed_cores"], 0b101);
+        // allowed_cores is given as a bitmask and is not scaled even if there are float params.
+        assert_eq!(dag[NodeIndex::new(1)].params["allowed_cores"], 2);
+        assert_eq!(dag[NodeIndex::new(1)].params["execution_time"], 150000);
+        assert!(!dag[NodeIndex::new(2)].params.contains_key("allowed_cores"));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_shared_regions() {
+        let dag = create_dag_from_yaml("tests/sample_dags/shared_regions.yaml", false).unwrap();
+        assert_eq!(dag[NodeIndex::new(1)].params["shared_regions"], 0b11);
+        assert_eq!(dag[NodeIndex::new(3)].params["shared_regions"], 4);
+        assert!(!dag[NodeIndex::new(0)].params.contains_key("shared_regions"));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_execution_time_distribution() {
+        let dag = create_dag_from_yaml("tests/sample_dags/execution_time_distribution.yaml", false)
+            .unwrap();
+        assert_eq!(
+            dag[NodeIndex::new(0)].params["execution_time_distribution"],
+            ExecutionTimeDistribution::Normal as i64
+        );
+        assert_eq!(dag[NodeIndex::new(0)].params["acet"], 60000);
+        // The samples are scaled in the same way as the execution time.
+        assert_eq!(dag[NodeIndex::new(1)].params["execution_time"], 800000);
+        assert_eq!(
+            dag[NodeIndex::new(1)].params[&get_execution_time_sample_key(0)],
+            250000
+        );
+        assert_eq!(
+            dag[NodeIndex::new(1)].params[&get_execution_time_sample_key(2)],
+            800000
+        );
+        assert!(!dag[NodeIndex::new(1)]
+            .params
+            .contains_key(&get_execution_time_sample_key(3)));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_in_format_rd_gen() {
+        let diagnostic_collector = DiagnosticCollector::new();
+        let dag = create_dag_from_yaml_in_format(
+            "tests/sample_dags/rd_gen_format.yaml",
+            DagFormat::RdGen,
+            false,
+        )
+        .unwrap();
+        let diagnostics = diagnostic_collector.finish();
+        // ratio_of_deadline_to_period is a float, so the params are scaled by the int conversion factor.
+        let params = |node_id: usize| &dag[NodeIndex::new(node_id)].params;
+        assert_eq!(params(0)["execution_time"], 1000000);
+        assert_eq!(params(0)["ratio_of_deadline_to_period"], 80000);
+        assert!(params(3).contains_key("multi_rate"));
+        assert!(!params(0).contains_key("label"));
+        assert_eq!(
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.kind == DiagnosticKind::NonNumericParamDropped)
+                .count(),
+            2
+        );
+        // Each chain ends with the deadline of its own period, and the given deadline is kept.
+        assert_eq!(params(2)["end_to_end_deadline"], 8000000);
+        assert_eq!(params(3)["end_to_end_deadline"], 4000000);
+        assert_eq!(params(4)["end_to_end_deadline"], 9000000);
+        assert!(!params(0).contains_key("end_to_end_deadline"));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_in_format_base_rejects_rd_gen() {
+        assert!(matches!(
+            create_dag_from_yaml_in_format(
+                "tests/sample_dags/rd_gen_format.yaml",
+                DagFormat::Base,
+                false
+            ),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_convert_rd_gen_yaml_doc_lists() {
+        let yaml_doc = load_yaml_str(
+            YAML_STR_NAME,
+            "nodes: [{id: 0, execution_time: 3, offsets: [0, 5], allowed_cores: [1]}]\nlinks: []",
+        )
+        .unwrap();
+        let dag = create_dag_from_yaml_doc(
+            YAML_STR_NAME,
+            &DagFormat::RdGen.normalize(YAML_STR_NAME, yaml_doc),
+            false,
+        )
+        .unwrap();
+        let params = &dag[NodeIndex::new(0)].params;
+        assert_eq!(params["offsets_0"], 0);
+        assert_eq!(params["offsets_1"], 5);
+        assert_eq!(params["allowed_cores"], 0b10);
+    }
+
+    #[test]
+    fn test_dag_format_from_name() {
+        assert_eq!(DagFormat::from_name("rd_gen").unwrap(), DagFormat::RdGen);
+        assert!(matches!(
+            DagFormat::from_name("rdgen"),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_anchors_and_merge_keys() {
+        let dag = create_dag_from_yaml("tests/sample_dags/anchored_params.yaml", false).unwrap();
+        let expanded_dag = create_dag_from_yaml_str(
+            concat!(
+                "nodes: [{id: 0, execution_time: 3, period: 10, priority: 0}, ",
+                "{id: 1, execution_time: 5, priority: 1}, ",
+                "{id: 2, execution_time: 5, period: 10, priority: 2, end_to_end_deadline: 10}, ",
+                "{id: 3, execution_time: 4, period: 10, priority: 3}]\n",
+                "links: [{source: 0, target: 1, communication_time: 2}, ",
+                "{source: 0, target: 2, communication_time: 2}, ",
+                "{source: 1, target: 3}, {source: 2, target: 3}]",
+            ),
+            false,
+        )
+        .unwrap();
+        assert_eq!(dag.node_count(), expanded_dag.node_count());
+        for node_i in dag.node_indices() {
+            assert_eq!(dag[node_i], expanded_dag[node_i]);
+        }
+        assert_eq!(
+            dag.edge_weights().collect::<Vec<_>>(),
+            expanded_dag.edge_weights().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_str_merge_scalar() {
+        assert!(matches!(
+            create_dag_from_yaml_str("nodes: [{id: 0, <<: 3}]\nlinks: []", false),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_create_dag_from_yaml_str_unknown_execution_time_distribution() {
+        assert!(matches!(
+            create_dag_from_yaml_str(
+                "nodes: [{id: 0, execution_time: 3, execution_time_distribution: gamma}]\nlinks: []",
+                false
+            ),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_create_dag_from_reader_normal() {
+        let file_path = "tests/sample_dags/anchored_params.yaml";
+        let dag =
+            create_dag_from_reader("<stdin>", fs::File::open(file_path).unwrap(), false).unwrap();
+        let file_dag = create_dag_from_yaml(file_path, false).unwrap();
+        for node_i in dag.node_indices() {
+            assert_eq!(dag[node_i], file_dag[node_i]);
+        }
+        assert!(matches!(
+            create_dag_from_reader("<stdin>", "".as_bytes(), false),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
     fn test_create_dag_from_yaml_path() {
-        let _dag = create_dag_from_yaml("tests/sample_dags/disable_path.yaml", false);
+        assert!(matches!(
+            create_dag_from_yaml("tests/sample_dags/disable_path.yaml", false),
+            Err(SchedSimError::Io { .. })
+        ));
     }
 
     #[test]
-    #[should_panic]
     fn test_create_dag_from_yaml_no_yaml() {
-        let _dag = create_dag_from_yaml("tests/sample_dags/no_yaml.tex", false);
+        assert!(matches!(
+            create_dag_from_yaml("tests/sample_dags/no_yaml.tex", false),
+            Err(SchedSimError::Parse { .. })
+        ));
     }
 
     #[test]
-    #[should_panic]
     fn test_create_dag_from_yaml_broken_link() {
-        create_dag_from_yaml("tests/sample_dags/broken_link.yaml", false);
+        assert!(matches!(
+            create_dag_from_yaml("tests/sample_dags/broken_link.yaml", false),
+            Err(SchedSimError::Parse { .. })
+        ));
     }
 }
@@ -0,0 +1,204 @@
+//! Differences between two DAGs, e.g., a DAG before and after a transformation.
+//! The nodes are matched by their `id` rather than their NodeIndex, and the edges by the ids of their ends,
+//! so that a transformation renumbering the indices is still compared node by node.
+//! A pass that assigns priorities, for example, is expected to only add params and never alter the structure.
+use crate::graph_extension::NodeData;
+use petgraph::{visit::EdgeRef, Graph};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamChange {
+    pub node_id: i64,
+    pub key: String,
+    // None if the param does not exist
+    pub before: Option<i64>,
+    pub after: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DAGDiff {
+    pub added_nodes: Vec<i64>,
+    pub removed_nodes: Vec<i64>,
+    // (source node id, target node id, communication time)
+    pub added_edges: Vec<(i64, i64, i64)>,
+    pub removed_edges: Vec<(i64, i64, i64)>,
+    // The params of the nodes in both DAGs, in the order of the node id and the key
+    pub param_changes: Vec<ParamChange>,
+}
+
+impl DAGDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the nodes and the edges are the same, regardless of the params.
+    pub fn is_structure_unchanged(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+
+    /// Whether the only differences are params added to the existing nodes.
+    pub fn has_only_added_params(&self) -> bool {
+        self.is_structure_unchanged()
+            && self
+                .param_changes
+                .iter()
+                .all(|param_change| param_change.before.is_none())
+    }
+}
+
+fn get_nodes_by_id(dag: &Graph<NodeData, i64>) -> BTreeMap<i64, &NodeData> {
+    dag.node_weights().map(|node| (node.id, node)).collect()
+}
+
+fn get_edges_by_ids(dag: &Graph<NodeData, i64>) -> BTreeSet<(i64, i64, i64)> {
+    dag.edge_references()
+        .map(|edge| (dag[edge.source()].id, dag[edge.target()].id, *edge.weight()))
+        .collect()
+}
+
+/// Compare `after` with `before`, and report what is added, removed or changed in `after`.
+pub fn compare_dags(before: &Graph<NodeData, i64>, after: &Graph<NodeData, i64>) -> DAGDiff {
+    let before_nodes = get_nodes_by_id(before);
+    let after_nodes = get_nodes_by_id(after);
+    let before_edges = get_edges_by_ids(before);
+    let after_edges = get_edges_by_ids(after);
+
+    let mut param_changes = Vec::new();
+    for (node_id, before_node) in before_nodes.iter() {
+        let Some(after_node) = after_nodes.get(node_id) else {
+            continue;
+        };
+        let keys: BTreeSet<&String> = before_node
+            .params
+            .keys()
+            .chain(after_node.params.keys())
+            .collect();
+        for key in keys {
+            let before_value = before_node.params.get(key).copied();
+            let after_value = after_node.params.get(key).copied();
+            if before_value != after_value {
+                param_changes.push(ParamChange {
+                    node_id: *node_id,
+                    key: key.clone(),
+                    before: before_value,
+                    after: after_value,
+                });
+            }
+        }
+    }
+
+    DAGDiff {
+        added_nodes: after_nodes
+            .keys()
+            .filter(|node_id| !before_nodes.contains_key(node_id))
+            .copied()
+            .collect(),
+        removed_nodes: before_nodes
+            .keys()
+            .filter(|node_id| !after_nodes.contains_key(node_id))
+            .copied()
+            .collect(),
+        added_edges: after_edges.difference(&before_edges).copied().collect(),
+        removed_edges: before_edges.difference(&after_edges).copied().collect(),
+        param_changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use petgraph::graph::NodeIndex;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag
+    }
+
+    #[test]
+    fn test_compare_dags_same() {
+        let diff = compare_dags(&create_dag(), &create_dag());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_compare_dags_only_added_params() {
+        let before = create_dag();
+        let mut after = create_dag();
+        for node_i in after.node_indices() {
+            after.add_param(node_i, "priority", node_i.index() as i64);
+        }
+        let diff = compare_dags(&before, &after);
+
+        assert!(diff.has_only_added_params());
+        assert_eq!(diff.param_changes.len(), 3);
+        assert_eq!(
+            diff.param_changes[1],
+            ParamChange {
+                node_id: 1,
+                key: "priority".to_string(),
+                before: None,
+                after: Some(1),
+            }
+        );
+    }
+
+    #[test]
+    fn test_compare_dags_structure_changed() {
+        let before = create_dag();
+        let mut after = create_dag();
+        after.update_param(NodeIndex::new(0), "execution_time", 8);
+        let n3 = after.add_node(create_node(3, "execution_time", 2));
+        after.add_edge(NodeIndex::new(1), n3, 0);
+        after.remove_edge(
+            after
+                .find_edge(NodeIndex::new(0), NodeIndex::new(2))
+                .unwrap(),
+        );
+        let diff = compare_dags(&before, &after);
+
+        assert!(!diff.is_structure_unchanged());
+        assert!(!diff.has_only_added_params());
+        assert_eq!(diff.added_nodes, vec![3]);
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.added_edges, vec![(1, 3, 0)]);
+        assert_eq!(diff.removed_edges, vec![(0, 2, 1)]);
+        assert_eq!(
+            diff.param_changes,
+            vec![ParamChange {
+                node_id: 0,
+                key: "execution_time".to_string(),
+                before: Some(4),
+                after: Some(8),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_compare_dags_renumbered() {
+        // The same DAG with the nodes added in the reverse order.
+        let mut after = Graph::<NodeData, i64>::new();
+        let n2 = after.add_node(create_node(2, "execution_time", 5));
+        let n1 = after.add_node(create_node(1, "execution_time", 7));
+        let n0 = after.add_node(create_node(0, "execution_time", 4));
+        after.add_edge(n0, n2, 1);
+        after.add_edge(n0, n1, 1);
+
+        assert!(compare_dags(&create_dag(), &after).is_empty());
+    }
+}
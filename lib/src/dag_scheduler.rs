@@ -1,38 +1,49 @@
+#[cfg(feature = "file_io")]
+use crate::util::create_scheduler_log_yaml;
 use crate::{
     core::ProcessResult,
     graph_extension::{GraphExtension, NodeData},
     log::DAGSchedulerLog,
     processor::ProcessorBase,
-    util::{create_scheduler_log_yaml, get_process_core_indices},
+    util::{get_overhead_core_indices, get_process_core_indices},
 };
 use petgraph::graph::{Graph, NodeIndex};
 use std::collections::VecDeque;
 
-const DUMMY_EXECUTION_TIME: i32 = 1;
+const DUMMY_EXECUTION_TIME: i64 = 1;
 
 pub trait DAGSchedulerBase<T>
 where
     T: ProcessorBase + Clone,
 {
     // getter, setter
-    fn set_dag(&mut self, dag: &Graph<NodeData, i32>);
+    fn set_dag(&mut self, dag: &Graph<NodeData, i64>);
     fn set_processor(&mut self, processor: &T);
     fn set_log(&mut self, log: DAGSchedulerLog);
-    fn get_dag(&self) -> Graph<NodeData, i32>;
+    fn get_dag(&self) -> Graph<NodeData, i64>;
     fn get_processor(&self) -> T;
     fn get_log(&self) -> DAGSchedulerLog;
     // method definition
-    fn new(dag: &Graph<NodeData, i32>, processor: &T) -> Self
+    fn new(dag: &Graph<NodeData, i64>, processor: &T) -> Self
     where
         Self: Sized;
     fn sort_ready_queue(ready_queue: &mut VecDeque<NodeData>);
     // method implementation
-    fn schedule(&mut self) -> (i32, VecDeque<NodeIndex>) {
+    fn schedule(&mut self) -> (i64, VecDeque<NodeIndex>) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "dag_schedule",
+            num_nodes = self.get_dag().node_count(),
+            num_cores = self.get_processor().get_number_of_cores()
+        )
+        .entered();
+
         {
             let mut dag = self.get_dag(); //To avoid adding pre_node_count to the original DAG
             let mut processor = self.get_processor();
             let mut ready_queue = VecDeque::new();
             let mut log = self.get_log();
+            log.write_execution_time_seed(processor.get_execution_time_seed());
             let mut execution_order = VecDeque::new();
             let source_node_i = dag.add_dummy_source_node();
 
@@ -50,22 +61,28 @@ where
             loop {
                 Self::sort_ready_queue(&mut ready_queue);
 
-                // Assign the highest priority task first to the first idle core found.
-                while let Some(core_index) = processor.get_idle_core_index() {
-                    if let Some(node_d) = ready_queue.pop_front() {
-                        processor.allocate_specific_core(core_index, &node_d);
-
-                        if node_d.id != dag[source_node_i].id && node_d.id != dag[sink_node_i].id {
-                            log.write_allocating_job(
-                                &node_d,
-                                core_index,
-                                current_time - DUMMY_EXECUTION_TIME,
-                            );
-                        }
-                        execution_order.push_back(NodeIndex::new(node_d.id as usize));
-                    } else {
-                        break;
+                // Assign the highest priority task first to the first idle core allowed by its affinity.
+                while let Some((queue_index, core_index)) =
+                    ready_queue
+                        .iter()
+                        .enumerate()
+                        .find_map(|(queue_index, node_d)| {
+                            processor
+                                .get_idle_core_index_with_affinity(node_d)
+                                .map(|core_index| (queue_index, core_index))
+                        })
+                {
+                    let node_d = ready_queue.remove(queue_index).unwrap();
+                    processor.allocate_specific_core(core_index, &node_d);
+
+                    if node_d.id != dag[source_node_i].id && node_d.id != dag[sink_node_i].id {
+                        log.write_allocating_job(
+                            &node_d,
+                            core_index,
+                            current_time - DUMMY_EXECUTION_TIME,
+                        );
                     }
+                    execution_order.push_back(NodeIndex::new(node_d.id as usize));
                 }
 
                 // Move one unit time so that the core state of the previous loop does not remain.
@@ -75,6 +92,8 @@ where
                 // Write the processing time of the core to the log.
                 let indices: Vec<usize> = get_process_core_indices(&process_result);
                 log.write_processing_time(&indices);
+                let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+                log.write_overhead_time(&indices);
 
                 // Process until there is a task finished.
                 while !process_result
@@ -87,7 +106,9 @@ where
                     // TODO: Will be refactoring the core structure to have a core log.
                     // Write the processing time of the core to the log.
                     let indices: Vec<usize> = get_process_core_indices(&process_result);
-                    log.write_processing_time(&indices)
+                    log.write_processing_time(&indices);
+                    let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+                    log.write_overhead_time(&indices);
                 }
 
                 let finish_nodes: Vec<NodeIndex> = process_result
@@ -145,6 +166,7 @@ where
 
             let schedule_length = current_time - DUMMY_EXECUTION_TIME * 2;
             log.calculate_utilization(schedule_length);
+            log.calculate_tardiness(&dag, schedule_length);
 
             self.set_log(log);
 
@@ -153,6 +175,7 @@ where
         }
     }
 
+    #[cfg(feature = "file_io")]
     fn dump_log(&self, dir_path: &str, alg_name: &str) -> String {
         let file_path = create_scheduler_log_yaml(dir_path, alg_name);
         self.get_log().dump_log_to_yaml(&file_path);
@@ -1,12 +1,43 @@
+#[cfg(feature = "file_io")]
+use crate::util::create_scheduler_log_yaml;
 use crate::{
+    admission_control::{AdmissionController, OnlineAdmission},
+    aperiodic_arrival::AperiodicArrivalTrace,
     core::ProcessResult,
+    diagnostics::{report, Diagnostic, DiagnosticCollector, DiagnosticKind},
+    error::{Result, SchedSimError},
+    event_script::{EventScript, ScriptedEvent},
+    execution_time_distribution::remove_execution_time_distribution,
     graph_extension::{GraphExtension, NodeData},
-    log::{DAGSetSchedulerLog, JobEventTimes},
+    interconnect::Interconnect,
+    log::{get_different_sink_deadlines, DAGSetSchedulerLog, JobEventTimes},
+    logical_execution_time::{
+        set_publish_time, validate_logical_execution_times, CommunicationSemantics,
+    },
+    mode_change::ModeSchedule,
+    multi_rate::{
+        get_head_source_nodes, get_offset_released_nodes, is_released_on_ready, set_release_times,
+    },
+    network::Network,
+    non_preemptive_section::{
+        is_in_non_preemptive_section, is_section_continuation, validate_non_preemptive_sections,
+    },
     processor::ProcessorBase,
-    util::{create_scheduler_log_yaml, get_hyper_period, get_process_core_indices},
+    simulation_horizon::SimulationHorizon,
+    sporadic_release::{get_jitter, validate_jitters, ArrivalTrace, ReleaseJitter},
+    util::{get_overhead_core_indices, get_process_core_indices},
+};
+use petgraph::{
+    graph::{Graph, NodeIndex},
+    visit::EdgeRef,
+    Direction::Incoming,
+};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, VecDeque},
+    time::Duration,
 };
-use petgraph::graph::{Graph, NodeIndex};
-use std::{cmp::Ordering, collections::BTreeSet};
 
 // Define a new wrapper type
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +57,59 @@ impl NodeDataWrapper {
     }
 }
 
+/// The results of `schedule`, so that the callers do not need to read the log file.
+/// The vectors are indexed by dag_id.
+#[derive(Clone)]
+pub struct ScheduleOutcome {
+    pub schedule_length: i64,
+    pub response_times: Vec<Vec<i64>>,
+    pub worst_response_times: Vec<i64>,
+    pub deadline_miss_flags: Vec<bool>,
+    pub log: DAGSetSchedulerLog,
+    /// The diagnostics reported during the scheduling.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The wall-clock time of the pre-processing inside the scheduling, such as the minimum-core search.
+    pub preprocessing_time: Duration,
+}
+
+impl ScheduleOutcome {
+    pub fn has_deadline_miss(&self) -> bool {
+        self.deadline_miss_flags
+            .iter()
+            .any(|deadline_miss| *deadline_miss)
+    }
+}
+
+/// What the scheduler does when a DAG job is unfinished at its absolute deadline.
+/// The miss is logged regardless of the policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum DeadlineMissPolicy {
+    /// Keep executing the late job.
+    #[default]
+    Continue,
+    /// Stop the simulation at the deadline miss.
+    Abort,
+    /// Discard the ready and running nodes of the late job.
+    KillJob,
+    /// Let the late job finish, and skip the next release of the DAG.
+    SkipNextRelease,
+}
+
+impl DeadlineMissPolicy {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "continue" => Ok(Self::Continue),
+            "abort" => Ok(Self::Abort),
+            "kill_job" => Ok(Self::KillJob),
+            "skip_next_release" => Ok(Self::SkipNextRelease),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown deadline miss policy: {}",
+                name
+            ))),
+        }
+    }
+}
+
 #[derive(Clone, Default, PartialEq)]
 pub enum DAGState {
     #[default]
@@ -36,10 +120,18 @@ pub enum DAGState {
 
 pub trait DAGStateManagerBase {
     // getter, setter
-    fn get_release_count(&self) -> i32;
-    fn set_release_count(&mut self, release_count: i32);
+    fn get_release_count(&self) -> i64;
+    fn set_release_count(&mut self, release_count: i64);
+    fn get_aperiodic_release_count(&self) -> i64;
+    fn set_aperiodic_release_count(&mut self, aperiodic_release_count: i64);
     fn get_dag_state(&self) -> DAGState;
     fn set_dag_state(&mut self, dag_state: DAGState);
+    fn get_skipped_release_count(&self) -> i64;
+    fn set_skipped_release_count(&mut self, skipped_release_count: i64);
+    fn get_absolute_deadline(&self) -> i64;
+    fn set_absolute_deadline(&mut self, absolute_deadline: i64);
+    // The arrival times of the aperiodic instances waiting for the previous instance to finish
+    fn get_pending_arrival_times_mut(&mut self) -> &mut VecDeque<i64>;
     // method implementation
     fn complete_execution(&mut self) {
         self.set_dag_state(DAGState::Waiting);
@@ -49,101 +141,581 @@ pub trait DAGStateManagerBase {
         self.set_release_count(self.get_release_count() + 1);
         self.set_dag_state(DAGState::Ready);
     }
+
+    fn release_aperiodic(&mut self) {
+        self.release();
+        self.set_aperiodic_release_count(self.get_aperiodic_release_count() + 1);
+    }
+
+    fn get_periodic_release_count(&self) -> i64 {
+        self.get_release_count() - self.get_aperiodic_release_count()
+    }
+
+    /// The number of periodic arrivals so far, including the skipped ones.
+    fn get_arrival_count(&self) -> i64 {
+        self.get_periodic_release_count() + self.get_skipped_release_count()
+    }
 }
 
 #[macro_export]
 macro_rules! getset_dag_state_manager {
     () => {
-        fn get_release_count(&self) -> i32 {
+        fn get_release_count(&self) -> i64 {
             self.release_count
         }
-        fn set_release_count(&mut self, release_count: i32) {
+        fn set_release_count(&mut self, release_count: i64) {
             self.release_count = release_count;
         }
+        fn get_aperiodic_release_count(&self) -> i64 {
+            self.aperiodic_release_count
+        }
+        fn set_aperiodic_release_count(&mut self, aperiodic_release_count: i64) {
+            self.aperiodic_release_count = aperiodic_release_count;
+        }
         fn get_dag_state(&self) -> DAGState {
             self.dag_state.clone()
         }
         fn set_dag_state(&mut self, dag_state: DAGState) {
             self.dag_state = dag_state;
         }
+        fn get_skipped_release_count(&self) -> i64 {
+            self.skipped_release_count
+        }
+        fn set_skipped_release_count(&mut self, skipped_release_count: i64) {
+            self.skipped_release_count = skipped_release_count;
+        }
+        fn get_absolute_deadline(&self) -> i64 {
+            self.absolute_deadline
+        }
+        fn set_absolute_deadline(&mut self, absolute_deadline: i64) {
+            self.absolute_deadline = absolute_deadline;
+        }
+        fn get_pending_arrival_times_mut(&mut self) -> &mut VecDeque<i64> {
+            &mut self.pending_arrival_times
+        }
     };
 }
 
 #[derive(Clone, Default)]
 pub struct DAGStateManager {
     dag_state: DAGState,
-    release_count: i32,
+    release_count: i64,
+    aperiodic_release_count: i64,
+    skipped_release_count: i64,
+    absolute_deadline: i64,
+    pending_arrival_times: VecDeque<i64>,
 }
 
 impl DAGStateManagerBase for DAGStateManager {
     getset_dag_state_manager!();
 }
 
+fn set_absolute_deadlines(dag: &mut Graph<NodeData, i64>, to_absolute: impl Fn(i64) -> i64) {
+    // If Node does not have individual deadlines, use DAG deadline.
+    if dag[NodeIndex::new(0)]
+        .params
+        .contains_key("int_scaled_node_relative_deadline")
+    {
+        for node_i in dag.node_indices() {
+            let node_relative_deadline =
+                dag[node_i].get_params_value("int_scaled_node_relative_deadline");
+            dag[node_i].params.insert(
+                "int_scaled_node_absolute_deadline".to_string(),
+                to_absolute(node_relative_deadline),
+            );
+        }
+    } else {
+        dag.set_dag_param(
+            "node_absolute_deadline",
+            to_absolute(dag.get_end_to_end_deadline().unwrap()),
+        );
+    }
+}
+
 pub enum PreemptiveType {
     NonPreemptive,
-    Preemptive { key: String },
+    Preemptive {
+        key: String,
+    },
+    /// A running node is preempted only by a node whose `key` is smaller than the `threshold_key` of the running node.
+    /// A node without `threshold_key` is fully preemptive, and a threshold is never below the priority of its own node.
+    /// The threshold is static, so it suits static priority keys.
+    PreemptionThreshold {
+        key: String,
+        threshold_key: String,
+    },
+    /// A running node is preempted by a node whose values of `keys` are lexicographically smaller,
+    /// e.g., the absolute deadline and then a priority that is only comparable within a DAG.
+    LexicographicPreemptive {
+        keys: Vec<String>,
+    },
+}
+
+/// The end-to-end deadline of the job arrived at `arrival_time`, or i64::MAX if the DAG has none.
+fn get_job_absolute_deadline(dag: &Graph<NodeData, i64>, arrival_time: i64) -> i64 {
+    dag.get_end_to_end_deadline()
+        .map_or(i64::MAX, |end_to_end_deadline| {
+            arrival_time + end_to_end_deadline
+        })
+}
+
+/// The `max_parallelism` of a DAG caps the number of its nodes running at the same time,
+/// modeling the thread pool of the deployed runtime. It is a DAG parameter, so every node must have the same one.
+pub(crate) fn validate_max_parallelisms(dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let max_parallelisms: BTreeSet<Option<i64>> = dag
+            .node_weights()
+            .map(|node| node.params.get("max_parallelism").copied())
+            .collect();
+        match max_parallelisms.into_iter().collect::<Vec<_>>()[..] {
+            [None] | [Some(1..)] => {}
+            _ => {
+                return Err(SchedSimError::Validation(format!(
+                "The max_parallelism of DAG {} must be a positive DAG parameter set on every node.",
+                dag_id
+            )))
+            }
+        }
+    }
+    Ok(())
 }
 
 pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
     // getter, setter
-    fn get_dag_set(&self) -> Vec<Graph<NodeData, i32>>;
-    fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i32>>);
+    fn get_dag_set(&self) -> Vec<Graph<NodeData, i64>>;
+    fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i64>>);
+    // Borrow the DAG set or a single DAG instead of cloning the whole DAG set on every event
+    fn get_dag_set_ref(&self) -> &[Graph<NodeData, i64>];
+    fn get_dag(&self, dag_id: usize) -> &Graph<NodeData, i64>;
+    fn get_dag_mut(&mut self, dag_id: usize) -> &mut Graph<NodeData, i64>;
     fn get_processor_mut(&mut self) -> &mut T;
     fn get_processor(&self) -> &T;
     fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog;
-    fn get_current_time(&self) -> i32;
-    fn set_current_time(&mut self, current_time: i32);
+    fn get_current_time(&self) -> i64;
+    fn set_current_time(&mut self, current_time: i64);
+    fn get_event_script(&self) -> &EventScript;
+    fn get_event_script_mut(&mut self) -> &mut EventScript;
+    fn set_event_script(&mut self, event_script: EventScript);
+    fn get_interconnect(&self) -> Option<&Interconnect>;
+    fn get_interconnect_mut(&mut self) -> Option<&mut Interconnect>;
+    fn set_interconnect(&mut self, interconnect: Interconnect);
+    fn get_network(&self) -> Option<&Network>;
+    fn get_network_mut(&mut self) -> Option<&mut Network>;
+    // The nodes with processor_id are restricted to the cores of the processor on setting.
+    fn set_network(&mut self, network: Network);
+    fn get_arrival_trace(&self) -> Option<&ArrivalTrace>;
+    fn set_arrival_trace(&mut self, arrival_trace: ArrivalTrace);
+    fn get_aperiodic_arrival_trace(&self) -> Option<&AperiodicArrivalTrace>;
+    fn get_aperiodic_arrival_trace_mut(&mut self) -> Option<&mut AperiodicArrivalTrace>;
+    fn set_aperiodic_arrival_trace(&mut self, aperiodic_arrival_trace: AperiodicArrivalTrace);
+    fn get_release_jitter_mut(&mut self) -> &mut ReleaseJitter;
+    fn set_release_jitter(&mut self, release_jitter: ReleaseJitter);
+    fn get_deadline_miss_policy(&self) -> DeadlineMissPolicy;
+    fn set_deadline_miss_policy(&mut self, deadline_miss_policy: DeadlineMissPolicy);
+    fn get_simulation_horizon(&self) -> SimulationHorizon;
+    fn set_simulation_horizon(&mut self, simulation_horizon: SimulationHorizon);
+    fn get_mode_schedule(&self) -> Option<&ModeSchedule>;
+    fn get_mode_schedule_mut(&mut self) -> Option<&mut ModeSchedule>;
+    fn set_mode_schedule(&mut self, mode_schedule: ModeSchedule);
+    fn get_online_admission(&self) -> Option<&OnlineAdmission>;
+    fn get_online_admission_mut(&mut self) -> Option<&mut OnlineAdmission>;
+    /// Returns an error if a DAG not in the DAG set arrives.
+    fn set_online_admission(&mut self, online_admission: OnlineAdmission) -> Result<()>;
+    /// Only the default `schedule` supports LET, and the schedulers overriding it keep the implicit communication.
+    fn get_communication_semantics(&self) -> CommunicationSemantics;
+    fn set_communication_semantics(&mut self, communication_semantics: CommunicationSemantics);
     // method definition
-    fn new(dag_set: &[Graph<NodeData, i32>], processor: &T) -> Self;
+    fn new(dag_set: &[Graph<NodeData, i64>], processor: &T) -> Self;
     // method implementation
     fn release_dags(&mut self, managers: &mut [impl DAGStateManagerBase]) -> Vec<NodeData> {
+        self.handle_dag_arrivals();
         let current_time = self.get_current_time();
         let mut ready_nodes = Vec::new();
-        let mut dag_set = self.get_dag_set();
-
-        for dag in dag_set.iter_mut() {
-            let dag_id = dag.get_dag_param("dag_id") as usize;
-            if (managers[dag_id].get_dag_state() == DAGState::Waiting)
-                && (current_time
-                    == dag.get_head_offset()
-                        + dag.get_head_period().unwrap() * managers[dag_id].get_release_count())
-            {
-                managers[dag_id].release();
-                // If Node does not have individual deadlines, use DAG deadline.
-                if dag[NodeIndex::new(0)]
-                    .params
-                    .contains_key("int_scaled_node_relative_deadline")
-                {
-                    for node_i in dag.node_indices() {
-                        let node_relative_deadline =
-                            dag[node_i].get_params_value("int_scaled_node_relative_deadline");
-                        dag[node_i].params.insert(
-                            "int_scaled_node_absolute_deadline".to_string(),
-                            node_relative_deadline * managers[dag_id].get_release_count(),
-                        );
-                    }
+
+        for (dag_id, manager) in managers.iter_mut().enumerate() {
+            let is_aperiodic =
+                self.get_aperiodic_arrival_trace()
+                    .is_some_and(|aperiodic_arrival_trace| {
+                        aperiodic_arrival_trace.contains_dag(dag_id)
+                    });
+            if is_aperiodic {
+                continue;
+            }
+            let dag = self.get_dag(dag_id);
+            let arrival_count = manager.get_arrival_count();
+            let arrival_time = match self.get_arrival_trace() {
+                Some(arrival_trace) => {
+                    arrival_trace.get_arrival_time(dag_id, arrival_count as usize)
+                }
+                None => {
+                    Some(dag.get_head_offset() + dag.get_head_period().unwrap() * arrival_count)
+                }
+            };
+            let jitter = get_jitter(dag);
+            let Some(arrival_time) = arrival_time else {
+                continue;
+            };
+            if manager.get_dag_state() != DAGState::Waiting || arrival_time > current_time {
+                continue;
+            }
+            let is_active = self
+                .get_mode_schedule()
+                .is_none_or(|mode_schedule| mode_schedule.is_dag_active(dag_id))
+                && self
+                    .get_online_admission()
+                    .is_none_or(|online_admission| online_admission.is_dag_admitted(dag_id));
+            if !is_active {
+                // The arrival is dropped so that the DAG keeps its release phase.
+                manager.set_skipped_release_count(manager.get_skipped_release_count() + 1);
+                continue;
+            }
+            // The job is released after the delay drawn at its arrival.
+            let release_delay = if jitter > 0 {
+                self.get_release_jitter_mut()
+                    .get_delay(dag_id, arrival_count as usize, jitter)
+            } else {
+                0
+            };
+            if arrival_time + release_delay == current_time {
+                manager.release();
+                manager.set_absolute_deadline(get_job_absolute_deadline(
+                    self.get_dag(dag_id),
+                    arrival_time,
+                ));
+                let arrival_count = manager.get_arrival_count();
+                let is_sporadic = self.get_arrival_trace().is_some();
+                let dag = self.get_dag_mut(dag_id);
+                if is_sporadic {
+                    set_absolute_deadlines(dag, |relative_deadline| {
+                        arrival_time + relative_deadline
+                    });
                 } else {
-                    dag.set_dag_param(
-                        "node_absolute_deadline",
-                        dag.get_end_to_end_deadline().unwrap()
-                            * managers[dag_id].get_release_count(),
-                    );
+                    set_absolute_deadlines(dag, |relative_deadline| {
+                        relative_deadline * arrival_count
+                    });
                 }
-                ready_nodes.push(dag[dag.get_source_nodes()[0]].clone());
+                set_release_times(dag, arrival_time);
+                ready_nodes.extend(get_head_source_nodes(dag));
                 self.get_log_mut()
                     .write_dag_release_time(dag_id, current_time);
             }
         }
-        self.set_dag_set(dag_set);
+        // The instances of the multi-rate DAGs released later than their jobs
+        for (dag_id, manager) in managers.iter().enumerate() {
+            if manager.get_dag_state() != DAGState::Waiting {
+                ready_nodes.extend(get_offset_released_nodes(
+                    self.get_dag(dag_id),
+                    current_time,
+                ));
+            }
+        }
+        ready_nodes.extend(self.release_aperiodic_arrivals(managers));
+        ready_nodes
+    }
+
+    /// Queue the aperiodic arrivals by the current time in the DAGStateManagers,
+    /// and release the earliest waiting instance of each DAG without an unfinished instance.
+    fn release_aperiodic_arrivals(
+        &mut self,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<NodeData> {
+        let current_time = self.get_current_time();
+        let Some(aperiodic_arrival_trace) = self.get_aperiodic_arrival_trace_mut() else {
+            return Vec::new();
+        };
+        for arrival in aperiodic_arrival_trace.pop_arrivals(current_time) {
+            managers[arrival.dag_id]
+                .get_pending_arrival_times_mut()
+                .push_back(arrival.arrival_time);
+        }
+
+        let mut ready_nodes = Vec::new();
+        for (dag_id, manager) in managers.iter_mut().enumerate() {
+            if manager.get_dag_state() != DAGState::Waiting {
+                continue;
+            }
+            let Some(arrival_time) = manager.get_pending_arrival_times_mut().pop_front() else {
+                continue;
+            };
+            self.get_log_mut()
+                .write_dag_arrival_time(dag_id, arrival_time);
+            self.get_log_mut()
+                .write_dag_release_time(dag_id, current_time);
+            ready_nodes.extend(self.release_aperiodic_job(dag_id, manager, arrival_time));
+        }
+        ready_nodes
+    }
+
+    /// The absolute deadline of the job is relative to `arrival_time`.
+    /// The caller logs the release, since a queued arrival and a postponed scripted job log different times.
+    fn release_aperiodic_job(
+        &mut self,
+        dag_id: usize,
+        manager: &mut impl DAGStateManagerBase,
+        arrival_time: i64,
+    ) -> Vec<NodeData> {
+        let dag = self.get_dag_mut(dag_id);
+
+        manager.release_aperiodic();
+        manager.set_absolute_deadline(get_job_absolute_deadline(dag, arrival_time));
+        set_absolute_deadlines(dag, |relative_deadline| arrival_time + relative_deadline);
+        set_release_times(dag, arrival_time);
+        get_head_source_nodes(dag)
+    }
+
+    /// Accept the mode-change requests made by the current time,
+    /// and switch the mode once the jobs of the DAGs leaving the mode have completed.
+    fn handle_mode_changes(&mut self, managers: &[impl DAGStateManagerBase]) {
+        let current_time = self.get_current_time();
+        while let Some((from_mode, request)) =
+            self.get_mode_schedule_mut().and_then(|mode_schedule| {
+                mode_schedule.switch_mode(current_time, |dag_id| {
+                    managers[dag_id].get_dag_state() == DAGState::Waiting
+                })
+            })
+        {
+            self.get_log_mut().write_mode_change(
+                from_mode,
+                request.mode,
+                request.time,
+                current_time,
+            );
+        }
+    }
+
+    /// Decide the admissions of the DAGs arriving by the current time against the DAGs admitted so far.
+    fn handle_dag_arrivals(&mut self) {
+        let current_time = self.get_current_time();
+        let Some(online_admission) = self.get_online_admission_mut() else {
+            return;
+        };
+        for arrival in online_admission.pop_arrivals(current_time) {
+            let online_admission = self.get_online_admission().unwrap();
+            let admission_controller = AdmissionController::new(
+                self.get_processor().get_number_of_cores(),
+                online_admission.get_admission_test(),
+            );
+            let admitted_dag_set: Vec<&Graph<NodeData, i64>> = (0..self.get_dag_set_ref().len())
+                .filter(|dag_id| online_admission.is_dag_admitted(*dag_id))
+                .map(|dag_id| self.get_dag(dag_id))
+                .collect();
+            let verdict =
+                admission_controller.decide(&admitted_dag_set, self.get_dag(arrival.dag_id));
+            self.get_online_admission_mut()
+                .unwrap()
+                .set_admission(arrival.dag_id, verdict.is_schedulable);
+            self.get_log_mut()
+                .write_admission(arrival.dag_id, arrival.time, current_time, verdict);
+        }
+    }
+
+    /// Mark the core as failed at `time`.
+    fn inject_core_failure(&mut self, time: i64, core_id: usize) {
+        self.get_event_script_mut()
+            .push(ScriptedEvent::CoreFailure { time, core_id });
+    }
+
+    /// The time the simulation stops, which is one hyper period unless the horizon is set.
+    fn get_end_time(&self) -> Result<i64> {
+        self.get_simulation_horizon()
+            .get_end_time(self.get_dag_set_ref())
+    }
+
+    /// Make each core fail with `failure_probability` per time unit within the simulation horizon.
+    fn inject_random_core_failures(&mut self, failure_probability: f64, seed: u64) -> Result<()> {
+        let number_of_cores = self.get_processor().get_number_of_cores();
+        let end_time = self.get_end_time()?;
+        self.get_event_script_mut().push_random_core_failures(
+            number_of_cores,
+            end_time,
+            failure_probability,
+            seed,
+        );
+        Ok(())
+    }
+
+    /// Consume the scripted events that have occurred by the current time.
+    /// Returns the nodes that become ready due to the events.
+    fn handle_scripted_events(
+        &mut self,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<NodeData> {
+        let current_time = self.get_current_time();
+        let mut ready_nodes = Vec::new();
+
+        for event in self.get_event_script_mut().pop_timed_events(current_time) {
+            match event {
+                ScriptedEvent::CoreFailure { core_id, .. } => {
+                    // The job running on the failed core is released again.
+                    let lost_node_data = self.get_processor_mut().fail_core(core_id);
+                    let lost_job = lost_node_data.as_ref().map(|node_data| {
+                        let dag_id = node_data.get_params_value("dag_id") as usize;
+                        (
+                            node_data,
+                            (managers[dag_id].get_release_count() - 1) as usize,
+                        )
+                    });
+                    self.get_log_mut()
+                        .write_core_failure(core_id, current_time, lost_job);
+                    if let Some((lost_node_data, job_id)) = lost_job {
+                        self.get_log_mut().write_job_event(
+                            lost_node_data,
+                            core_id,
+                            job_id,
+                            JobEventTimes::PreemptedTime(current_time),
+                        );
+                        ready_nodes.push(lost_node_data.clone());
+                    }
+                }
+                ScriptedEvent::AperiodicJob { time, dag_id } => {
+                    if managers[dag_id].get_dag_state() == DAGState::Waiting {
+                        // A postponed job keeps the scripted time for its deadline and its response time.
+                        self.get_log_mut().write_dag_release_time(dag_id, time);
+                        ready_nodes.extend(self.release_aperiodic_job(
+                            dag_id,
+                            &mut managers[dag_id],
+                            time,
+                        ));
+                    } else {
+                        // Only one job of a DAG can be executed at a time, so the release is postponed.
+                        self.get_event_script_mut().push(event);
+                    }
+                }
+                ScriptedEvent::WcetOverrun { .. } => unreachable!(),
+            }
+        }
+
         ready_nodes
     }
 
-    fn allocate_node(&mut self, node_data: &NodeData, core_id: usize, job_id: usize) {
-        self.get_processor_mut()
-            .allocate_specific_core(core_id, node_data);
+    fn apply_wcet_overruns(
+        &self,
+        ready_nodes: &mut [NodeData],
+        managers: &[impl DAGStateManagerBase],
+    ) {
+        for node_data in ready_nodes.iter_mut() {
+            let dag_id = node_data.get_params_value("dag_id") as usize;
+            let job_id = (managers[dag_id].get_release_count() - 1) as usize;
+            if let Some(execution_time) = self.get_event_script().get_overrun_execution_time(
+                dag_id,
+                node_data.id as usize,
+                job_id,
+            ) {
+                node_data
+                    .params
+                    .insert("execution_time".to_string(), execution_time);
+                remove_execution_time_distribution(node_data);
+            }
+        }
+    }
+
+    /// Returns false without logging if the core rejects the node, e.g. because it has failed.
+    fn allocate_node(&mut self, node_data: &NodeData, core_id: usize, job_id: usize) -> bool {
+        // The processor draws the execution time of the job by its job_id.
+        let mut job_node_data = node_data.clone();
+        job_node_data
+            .params
+            .insert("job_id".to_string(), job_id as i64 - 1);
+        if !self
+            .get_processor_mut()
+            .allocate_specific_core(core_id, &job_node_data)
+        {
+            return false;
+        }
+        // A resumed node already has its input data.
+        if !node_data.params.contains_key("is_preempted") {
+            let transfer_time = self.transfer_input_data(node_data, core_id, job_id);
+            self.get_processor_mut().delay_core(core_id, transfer_time);
+        }
         let current_time = self.get_current_time();
         self.get_log_mut()
-            .write_allocating_job(node_data, core_id, job_id, current_time)
+            .write_allocating_job(node_data, core_id, job_id, current_time);
+        true
+    }
+
+    /// Allocate the head of the dispatch queue of each idle core of the processor through `allocate_node`,
+    /// and return the allocated nodes. A node whose allocation fails stays at the head of the queue.
+    fn dispatch_queued_nodes(
+        &mut self,
+        managers: &[impl DAGStateManagerBase],
+    ) -> Vec<(usize, NodeData)> {
+        let mut dispatched_nodes = Vec::new();
+        for core_id in 0..self.get_processor().get_number_of_cores() {
+            if self.get_processor().get_processing_node(core_id).is_some() {
+                continue;
+            }
+            let Some(node_data) = self.get_processor().get_queued_head(core_id).cloned() else {
+                continue;
+            };
+            let job_id = managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                as usize;
+            if self.allocate_node(&node_data, core_id, job_id) {
+                self.get_processor_mut().dequeue_from_core(core_id);
+                dispatched_nodes.push((core_id, node_data));
+            }
+        }
+        dispatched_nodes
+    }
+
+    /// Transfer the data of the incoming edges from predecessors that finished on other cores.
+    /// The data from another processor of the network goes over the link, and the others over the interconnect.
+    /// Returns the time until all the data arrive, during which the core waits.
+    fn transfer_input_data(&mut self, node_data: &NodeData, core_id: usize, job_id: usize) -> i64 {
+        if !self.is_transfer_modeled() {
+            return 0;
+        }
+        let current_time = self.get_current_time();
+        let dag_id = node_data.get_params_value("dag_id") as usize;
+
+        let mut pending_transfers = Vec::new();
+        for edge in self
+            .get_dag(dag_id)
+            .edges_directed(NodeIndex::new(node_data.id as usize), Incoming)
+        {
+            let pre_node_data = &self.get_dag(dag_id)[edge.source()];
+            let communication_time = *edge.weight();
+            let pre_core_id = pre_node_data.get_params_value("finished_core_id") as usize;
+            if communication_time > 0 && pre_core_id != core_id {
+                pending_transfers.push((
+                    pre_node_data.id as usize,
+                    pre_core_id,
+                    communication_time,
+                ));
+            }
+        }
+
+        let mut transfers = Vec::new();
+        for (pre_node_id, pre_core_id, communication_time) in pending_transfers {
+            let network_transfer = self.get_network_mut().and_then(|network| {
+                network.reserve_transfer(pre_core_id, core_id, current_time, communication_time)
+            });
+            let transfer = network_transfer.or_else(|| {
+                self.get_interconnect_mut().map(|interconnect| {
+                    interconnect.reserve_transfer(current_time, communication_time)
+                })
+            });
+            if let Some((start_time, finish_time)) = transfer {
+                transfers.push((pre_node_id, start_time, finish_time));
+            }
+        }
+
+        let mut arrival_time = current_time;
+        for (pre_node_id, start_time, finish_time) in transfers {
+            self.get_log_mut().write_transfer(
+                dag_id,
+                pre_node_id,
+                node_data.id as usize,
+                job_id - 1,
+                start_time,
+                finish_time,
+            );
+            arrival_time = arrival_time.max(finish_time);
+        }
+        arrival_time - current_time
+    }
+
+    fn is_transfer_modeled(&self) -> bool {
+        self.get_interconnect().is_some() || self.get_network().is_some()
     }
 
     fn process_unit_time(&mut self) -> Vec<ProcessResult> {
@@ -157,19 +729,69 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
         core_id: usize,
         managers: &mut [impl DAGStateManagerBase],
     ) -> Vec<NodeData> {
-        let mut dag_set = self.get_dag_set();
         let current_time = self.get_current_time();
-        let log = self.get_log_mut();
+        let is_transfer_modeled = self.is_transfer_modeled();
+        let dag_id = node.get_params_value("dag_id") as usize;
 
-        log.write_job_event(
+        self.get_log_mut().write_job_event(
             node,
             core_id,
-            (managers[node.get_params_value("dag_id") as usize].get_release_count() - 1) as usize,
+            (managers[dag_id].get_release_count() - 1) as usize,
             JobEventTimes::FinishTime(current_time),
         );
+        let dag = self.get_dag_mut(dag_id);
+        if is_transfer_modeled {
+            // Record where the output data of the node is for the transfers to its successors.
+            dag[NodeIndex::new(node.get_id() as usize)]
+                .params
+                .insert("finished_core_id".to_string(), core_id as i64);
+        }
+
+        if let Some(&publish_time) = node.params.get("publish_time") {
+            if publish_time > current_time {
+                // The output is held until the end of the LET interval.
+                dag[NodeIndex::new(node.get_id() as usize)]
+                    .params
+                    .insert("pending_publish_time".to_string(), publish_time);
+                return Vec::new();
+            }
+            if publish_time < current_time {
+                report(
+                    DiagnosticKind::LogicalExecutionTimeOverrun,
+                    format!(
+                        "Node {} of DAG {} finished at {} after the end of its LET interval {}.",
+                        node.get_id(),
+                        dag_id,
+                        current_time,
+                        publish_time
+                    ),
+                );
+            }
+        }
+        self.publish_output(node, managers)
+    }
+
+    /// Release the successors whose predecessors have all published their outputs,
+    /// or finish the DAG job if the node is the last sink to finish.
+    fn publish_output(
+        &mut self,
+        node: &NodeData,
+        managers: &mut [impl DAGStateManagerBase],
+    ) -> Vec<NodeData> {
+        let current_time = self.get_current_time();
         let dag_id = node.get_params_value("dag_id") as usize;
-        let dag = &mut dag_set[dag_id];
+        if let Some(&publish_time) = node.params.get("publish_time") {
+            let release_time = publish_time - node.get_params_value("logical_execution_time");
+            self.get_log_mut().write_publication(
+                dag_id,
+                node.get_id() as usize,
+                (managers[dag_id].get_release_count() - 1) as usize,
+                release_time,
+                current_time,
+            );
+        }
 
+        let dag = self.get_dag_mut(dag_id);
         let mut ready_nodes = Vec::new();
         if let Some(suc_nodes) = dag.get_suc_nodes(NodeIndex::new(node.get_id() as usize)) {
             for suc_node in suc_nodes {
@@ -182,26 +804,259 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
                 } else {
                     dag.add_param(suc_node, "pre_done_count", 1);
                 }
-                if dag.is_node_ready(suc_node) {
+                if dag.is_node_ready(suc_node) && is_released_on_ready(&dag[suc_node], current_time)
+                {
                     ready_nodes.push(dag[suc_node].clone());
                 }
             }
         } else {
-            log.write_dag_finish_time(dag_id, current_time);
-            dag.set_dag_param("pre_done_count", 0);
-            managers[dag_id].complete_execution();
+            dag[NodeIndex::new(node.get_id() as usize)]
+                .params
+                .insert("is_sink_finished".to_string(), 1);
+            let is_job_finished = dag
+                .get_sink_nodes()
+                .iter()
+                .all(|sink_i| dag[*sink_i].params.contains_key("is_sink_finished"));
+            if is_job_finished {
+                dag.set_dag_param("pre_done_count", 0);
+                for node in dag.node_weights_mut() {
+                    node.params.remove("is_sink_finished");
+                }
+                managers[dag_id].complete_execution();
+                self.get_log_mut()
+                    .write_dag_finish_time(dag_id, current_time);
+            }
         }
 
-        self.set_dag_set(dag_set);
+        ready_nodes
+    }
 
+    /// Publish the outputs held until the current time, and return the released successors.
+    fn publish_let_outputs(&mut self, managers: &mut [impl DAGStateManagerBase]) -> Vec<NodeData> {
+        let current_time = self.get_current_time();
+        let mut ready_nodes = Vec::new();
+        for dag_id in 0..managers.len() {
+            let published_nodes: Vec<NodeData> = self
+                .get_dag(dag_id)
+                .node_weights()
+                .filter(|node| {
+                    node.params
+                        .get("pending_publish_time")
+                        .is_some_and(|publish_time| *publish_time <= current_time)
+                })
+                .cloned()
+                .collect();
+            for mut node in published_nodes {
+                let publish_time = node.params.remove("pending_publish_time").unwrap();
+                self.get_dag_mut(dag_id)[NodeIndex::new(node.get_id() as usize)]
+                    .params
+                    .remove("pending_publish_time");
+                node.params.insert("publish_time".to_string(), publish_time);
+                ready_nodes.extend(self.publish_output(&node, managers));
+            }
+        }
         ready_nodes
     }
 
+    /// Under LET, set the end of the LET interval of the nodes released now.
+    /// The nodes released again after a core failure keep their LET intervals.
+    fn set_publish_times(&self, ready_nodes: &mut [NodeData]) {
+        if self.get_communication_semantics() != CommunicationSemantics::LogicalExecutionTime {
+            return;
+        }
+        let current_time = self.get_current_time();
+        for node_data in ready_nodes
+            .iter_mut()
+            .filter(|node_data| !node_data.params.contains_key("publish_time"))
+        {
+            set_publish_time(node_data, current_time);
+        }
+    }
+
+    /// Log the jobs unfinished at their absolute deadline, and return the dag_ids of them.
+    /// A job of a DAG whose sink chains have different deadlines misses at the deadline of any unfinished sink,
+    /// and it is logged at its first miss only.
+    /// The skipped release is applied here, and the other policies are left to the caller.
+    fn handle_deadline_misses(&mut self, managers: &mut [impl DAGStateManagerBase]) -> Vec<usize> {
+        let current_time = self.get_current_time();
+        let deadline_miss_policy = self.get_deadline_miss_policy();
+        let mut missed_dag_ids = Vec::new();
+
+        for (dag_id, manager) in managers.iter_mut().enumerate() {
+            // The absolute deadline of the job is of its tightest sink, before which no sink misses.
+            if manager.get_dag_state() == DAGState::Waiting
+                || current_time < manager.get_absolute_deadline()
+            {
+                continue;
+            }
+            let job_id = (manager.get_release_count() - 1) as usize;
+            let dag = self.get_dag(dag_id);
+            let is_missed = match get_different_sink_deadlines(dag) {
+                Some(sink_deadlines) => {
+                    let deadline_base_time =
+                        manager.get_absolute_deadline() - dag.get_end_to_end_deadline().unwrap();
+                    let sink_node_ids: Vec<usize> = sink_deadlines
+                        .into_iter()
+                        .filter(|(_, end_to_end_deadline)| {
+                            deadline_base_time + end_to_end_deadline == current_time
+                        })
+                        .map(|(sink_i, _)| dag[sink_i].id as usize)
+                        .collect();
+                    let log = self.get_log_mut();
+                    !log.has_deadline_miss_log(dag_id, job_id)
+                        && sink_node_ids.into_iter().any(|sink_node_id| {
+                            log.get_node_finish_time(dag_id, sink_node_id, job_id)
+                                .is_none()
+                        })
+                }
+                None => manager.get_absolute_deadline() == current_time,
+            };
+            if !is_missed {
+                continue;
+            }
+            self.get_log_mut().write_deadline_miss(
+                dag_id,
+                job_id,
+                current_time,
+                deadline_miss_policy,
+            );
+            if deadline_miss_policy == DeadlineMissPolicy::SkipNextRelease {
+                manager.set_skipped_release_count(manager.get_skipped_release_count() + 1);
+            }
+            missed_dag_ids.push(dag_id);
+        }
+
+        missed_dag_ids
+    }
+
+    /// Discard the running nodes of the job and make the DAG wait for the next release.
+    /// Returns the number of the discarded running nodes.
+    fn kill_job(&mut self, dag_id: usize, manager: &mut impl DAGStateManagerBase) -> usize {
+        let current_time = self.get_current_time();
+        let job_id = (manager.get_release_count() - 1) as usize;
+        let mut num_killed_nodes = 0;
+
+        for core_id in 0..self.get_processor().get_number_of_cores() {
+            let is_dag_node = self
+                .get_processor()
+                .get_processing_node(core_id)
+                .is_some_and(|node_data| node_data.get_params_value("dag_id") as usize == dag_id);
+            if is_dag_node {
+                let killed_node_data = self.get_processor_mut().preempt(core_id).unwrap();
+                self.get_log_mut().write_job_event(
+                    &killed_node_data,
+                    core_id,
+                    job_id,
+                    JobEventTimes::PreemptedTime(current_time),
+                );
+                num_killed_nodes += 1;
+            }
+        }
+        let dag = self.get_dag_mut(dag_id);
+        dag.set_dag_param("pre_done_count", 0);
+        for node in dag.node_weights_mut() {
+            node.params.remove("pending_publish_time");
+            node.params.remove("is_sink_finished");
+        }
+        manager.complete_execution();
+        // The killed job never finishes.
+        self.get_log_mut().write_dag_finish_time(dag_id, i64::MAX);
+
+        num_killed_nodes
+    }
+
     fn calculate_log(&mut self) {
         let current_time = self.get_current_time();
+        let dag_set = self.get_dag_set();
+        let warm_up_time = self.get_simulation_horizon().get_warm_up_time();
         let log = self.get_log_mut();
         log.calculate_utilization(current_time);
-        log.calculate_response_time();
+        log.calculate_response_time(warm_up_time);
+        log.calculate_tardiness(&dag_set);
+        log.calculate_deadline_misses(&dag_set, current_time);
+    }
+
+    /// Find the highest priority node that has an idle core allowed by its affinity.
+    /// The nodes of the DAGs running as many nodes as their `max_parallelism` are skipped.
+    fn find_allocatable_node(
+        &self,
+        ready_queue: &BTreeSet<NodeDataWrapper>,
+    ) -> Option<(NodeDataWrapper, usize)> {
+        if self.get_processor().get_idle_core_num() == 0 {
+            return None;
+        }
+        ready_queue
+            .iter()
+            .filter(|ready_node| !self.is_parallelism_capped(&ready_node.node_data))
+            .find_map(|ready_node| {
+                self.get_processor()
+                    .get_idle_core_index_with_affinity(&ready_node.node_data)
+                    .map(|idle_core_i| (ready_node.clone(), idle_core_i))
+            })
+    }
+
+    fn get_number_of_running_nodes(&self, dag_id: usize) -> usize {
+        let processor = self.get_processor();
+        (0..processor.get_number_of_cores())
+            .filter(|&core_i| {
+                processor
+                    .get_processing_node(core_i)
+                    .is_some_and(|node_data| {
+                        node_data.get_params_value("dag_id") as usize == dag_id
+                    })
+            })
+            .count()
+    }
+
+    /// Whether the DAG of the node already runs as many nodes as its `max_parallelism`.
+    fn is_parallelism_capped(&self, node_data: &NodeData) -> bool {
+        node_data
+            .params
+            .get("max_parallelism")
+            .is_some_and(|max_parallelism| {
+                self.get_number_of_running_nodes(node_data.get_params_value("dag_id") as usize)
+                    >= *max_parallelism as usize
+            })
+    }
+
+    /// Log the waiting time of the DAGs whose ready nodes have an idle core but are held by `max_parallelism`.
+    fn write_parallelism_capped_times<'a>(
+        &mut self,
+        ready_nodes: impl Iterator<Item = &'a NodeData>,
+    ) {
+        let capped_dag_ids: BTreeSet<usize> = ready_nodes
+            .filter(|node_data| {
+                self.is_parallelism_capped(node_data)
+                    && self
+                        .get_processor()
+                        .get_idle_core_index_with_affinity(node_data)
+                        .is_some()
+            })
+            .map(|node_data| node_data.get_params_value("dag_id") as usize)
+            .collect();
+        for dag_id in capped_dag_ids {
+            self.get_log_mut().write_parallelism_capped_time(dag_id);
+        }
+    }
+
+    /// The first ready node that can preempt a running node and the core to preempt.
+    /// A node restricted by `allowed_cores` competes only on its cores, so the nodes after it
+    /// can still preempt on the other cores, as in partitioned scheduling.
+    /// A node that can run on any core but cannot preempt blocks the nodes after it.
+    fn find_preempting_node(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_queue: &BTreeSet<NodeDataWrapper>,
+    ) -> Option<(NodeDataWrapper, usize)> {
+        for ready_node in ready_queue.iter() {
+            if let Some(core_i) = self.can_preempt(preemptive_type, ready_node) {
+                return Some((ready_node.clone(), core_i));
+            }
+            if !ready_node.node_data.params.contains_key("allowed_cores") {
+                return None;
+            }
+        }
+        None
     }
 
     fn can_preempt(
@@ -209,54 +1064,200 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
         preemptive_type: &PreemptiveType,
         ready_head_node: &NodeDataWrapper,
     ) -> Option<usize> {
-        if let PreemptiveType::Preemptive {
-            key: preemptive_key,
-        } = &preemptive_type
-        {
-            let (max_value, core_i) = self
-                .get_processor()
-                .get_max_value_and_index(preemptive_key)
-                .unwrap();
+        self.find_preemptable_core(preemptive_type, ready_head_node, false)
+    }
+
+    /// Whether the ready head node could preempt a node if it were not in a non-preemptive section.
+    fn is_blocked_by_non_preemptive_section(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_head_node: &NodeDataWrapper,
+    ) -> bool {
+        self.can_preempt(preemptive_type, ready_head_node).is_none()
+            && self
+                .find_preemptable_core(preemptive_type, ready_head_node, true)
+                .is_some()
+    }
+
+    /// Find the core running the lowest priority node that is lower than the ready head node.
+    /// The nodes in non-preemptive sections are skipped unless `ignores_sections` is set.
+    fn find_preemptable_core(
+        &self,
+        preemptive_type: &PreemptiveType,
+        ready_head_node: &NodeDataWrapper,
+        ignores_sections: bool,
+    ) -> Option<usize> {
+        let (preemptive_keys, threshold_key) = match preemptive_type {
+            PreemptiveType::NonPreemptive => return None,
+            PreemptiveType::Preemptive { key } => (std::slice::from_ref(key), None),
+            PreemptiveType::PreemptionThreshold { key, threshold_key } => {
+                (std::slice::from_ref(key), Some(threshold_key))
+            }
+            PreemptiveType::LexicographicPreemptive { keys } => (keys.as_slice(), None),
+        };
+        let processor = self.get_processor();
+        // A capped DAG can only replace its own running node.
+        let head_dag_id = self
+            .is_parallelism_capped(&ready_head_node.node_data)
+            .then(|| ready_head_node.node_data.get_params_value("dag_id"));
+        // No node is running when all cores have failed.
+        let (max_value, core_i) = (0..processor.get_number_of_cores())
+            .filter(|core_i| ready_head_node.node_data.is_allowed_on_core(*core_i))
+            .filter_map(|core_i| {
+                let processing_node_data = processor.get_processing_node(core_i)?;
+                if !ignores_sections && is_in_non_preemptive_section(processing_node_data) {
+                    return None;
+                }
+                if head_dag_id
+                    .is_some_and(|dag_id| processing_node_data.get_params_value("dag_id") != dag_id)
+                {
+                    return None;
+                }
+                let mut values = preemptive_keys
+                    .iter()
+                    .map(|key| processing_node_data.params.get(key).copied())
+                    .collect::<Option<Vec<i64>>>()?;
+                // A running node is compared by its threshold instead of its priority.
+                if let Some(threshold) = threshold_key
+                    .and_then(|threshold_key| processing_node_data.params.get(threshold_key))
+                {
+                    values[0] = values[0].min(*threshold);
+                }
+                Some((values, core_i))
+            })
+            .max_by(|(values, _), (other_values, _)| values.cmp(other_values))?;
+        let ready_head_node_data = ready_head_node.convert_node_data();
+        let ready_head_values: Vec<i64> = preemptive_keys
+            .iter()
+            .map(|key| ready_head_node_data.get_params_value(key))
+            .collect();
+        (max_value > ready_head_values).then_some(core_i)
+    }
 
-            if max_value
-                > ready_head_node
-                    .convert_node_data()
-                    .get_params_value(preemptive_key)
+    /// Allocate the nodes continuing started non-preemptive sections ahead of the ready queue.
+    fn allocate_section_continuations(
+        &mut self,
+        section_queue: &mut VecDeque<NodeData>,
+        managers: &[impl DAGStateManagerBase],
+    ) {
+        let mut waiting_nodes = VecDeque::new();
+        while let Some(node_data) = section_queue.pop_front() {
+            if self.is_parallelism_capped(&node_data) {
+                waiting_nodes.push_back(node_data);
+                continue;
+            }
+            match self
+                .get_processor()
+                .get_idle_core_index_with_affinity(&node_data)
             {
-                return Some(core_i);
+                Some(idle_core_i) => {
+                    self.allocate_node(
+                        &node_data,
+                        idle_core_i,
+                        managers[node_data.get_params_value("dag_id") as usize].get_release_count()
+                            as usize,
+                    );
+                }
+                None => waiting_nodes.push_back(node_data),
             }
         }
+        *section_queue = waiting_nodes;
+    }
 
-        None
+    /// Collect the results from the log after `calculate_log`.
+    fn get_schedule_outcome(&mut self, diagnostics: Vec<Diagnostic>) -> ScheduleOutcome {
+        let schedule_length = self.get_current_time();
+        let log = self.get_log_mut();
+        ScheduleOutcome {
+            schedule_length,
+            response_times: log.get_response_times(),
+            worst_response_times: log.get_worst_response_times(),
+            deadline_miss_flags: log.get_deadline_miss_flags(),
+            preprocessing_time: log.get_preprocessing_time(),
+            log: log.clone(),
+            diagnostics,
+        }
     }
 
-    fn schedule(&mut self, preemptive_type: PreemptiveType) -> i32 {
+    /// Returns an error without scheduling if the DAG set or the traces given to the scheduler are invalid.
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> Result<ScheduleOutcome> {
+        let diagnostic_collector = DiagnosticCollector::new();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "dag_set_schedule",
+            num_dags = self.get_dag_set_ref().len(),
+            num_cores = self.get_processor().get_number_of_cores()
+        )
+        .entered();
+
+        let execution_time_seed = self.get_processor().get_execution_time_seed();
+        self.get_log_mut()
+            .write_execution_time_seed(execution_time_seed);
+
+        if let Some(arrival_trace) = self.get_arrival_trace() {
+            arrival_trace.validate(self.get_dag_set_ref())?;
+        }
+        if let Some(aperiodic_arrival_trace) = self.get_aperiodic_arrival_trace() {
+            aperiodic_arrival_trace.validate(self.get_dag_set_ref())?;
+        }
+        if let Some(mode_schedule) = self.get_mode_schedule() {
+            mode_schedule.validate(self.get_dag_set_ref())?;
+        }
+        validate_jitters(self.get_dag_set_ref())?;
+        validate_non_preemptive_sections(self.get_dag_set_ref())?;
+        validate_max_parallelisms(self.get_dag_set_ref())?;
+        let communication_semantics = self.get_communication_semantics();
+        if communication_semantics == CommunicationSemantics::LogicalExecutionTime {
+            validate_logical_execution_times(self.get_dag_set_ref())?;
+        }
+        self.get_log_mut()
+            .write_communication_semantics(communication_semantics);
+
         // Start scheduling
-        let mut managers = vec![DAGStateManager::default(); self.get_dag_set().len()];
+        let mut managers = vec![DAGStateManager::default(); self.get_dag_set_ref().len()];
         let mut ready_queue = BTreeSet::new();
-        let hyper_period = get_hyper_period(&self.get_dag_set());
-        while self.get_current_time() < hyper_period {
+        // The ready nodes that continue started non-preemptive sections, which have the ceiling priority.
+        let mut section_queue = VecDeque::new();
+        let end_time = self.get_end_time()?;
+        while self.get_current_time() < end_time {
+            self.handle_mode_changes(&managers);
+
+            // Handle scripted events
+            let mut ready_nodes = self.handle_scripted_events(&mut managers);
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            self.set_publish_times(&mut ready_nodes);
+            for ready_node in ready_nodes {
+                ready_queue.insert(NodeDataWrapper {
+                    node_data: ready_node,
+                });
+            }
+
             // Release DAGs
-            let ready_nodes = self.release_dags(&mut managers);
+            let mut ready_nodes = self.release_dags(&mut managers);
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            self.set_publish_times(&mut ready_nodes);
             for ready_node in ready_nodes {
                 ready_queue.insert(NodeDataWrapper {
                     node_data: ready_node,
                 });
             }
 
+            self.allocate_section_continuations(&mut section_queue, &managers);
+
             // Allocate nodes as long as there are idle cores, and attempt to preempt when all cores are busy.
             while !ready_queue.is_empty() {
-                if let Some(idle_core_i) = self.get_processor().get_idle_core_index() {
+                if let Some((ready_node, idle_core_i)) = self.find_allocatable_node(&ready_queue) {
                     // Allocate the node to the idle core
-                    let node_data = ready_queue.pop_first().unwrap().convert_node_data();
+                    ready_queue.remove(&ready_node);
+                    let node_data = ready_node.convert_node_data();
                     self.allocate_node(
                         &node_data,
                         idle_core_i,
                         managers[node_data.get_params_value("dag_id") as usize].get_release_count()
                             as usize,
                     );
-                } else if let Some(core_i) =
-                    self.can_preempt(&preemptive_type, ready_queue.first().unwrap())
+                } else if let Some((preempting_node, core_i)) =
+                    self.find_preempting_node(&preemptive_type, &ready_queue)
                 {
                     // Preempt the node with the lowest priority
                     let current_time = self.get_current_time();
@@ -272,7 +1273,8 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
                         JobEventTimes::PreemptedTime(current_time),
                     );
                     // Allocate the preempted node
-                    let allocate_node_data = &ready_queue.pop_first().unwrap().convert_node_data();
+                    ready_queue.remove(&preempting_node);
+                    let allocate_node_data = &preempting_node.convert_node_data();
                     self.allocate_node(
                         allocate_node_data,
                         core_i,
@@ -284,9 +1286,18 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
                         node_data: preempted_node_data,
                     });
                 } else {
+                    let ready_head_node = ready_queue.first().unwrap();
+                    if self.is_blocked_by_non_preemptive_section(&preemptive_type, ready_head_node)
+                    {
+                        let dag_id = ready_head_node.node_data.get_params_value("dag_id") as usize;
+                        self.get_log_mut().write_blocking_time(dag_id);
+                    }
                     break; // No core is idle and can not preempt. Exit the loop.
                 }
             }
+            self.write_parallelism_capped_times(
+                ready_queue.iter().map(|ready_node| &ready_node.node_data),
+            );
 
             // Process unit time
             let process_result = self.process_unit_time();
@@ -295,25 +1306,60 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
             let log = self.get_log_mut();
             let indices: Vec<usize> = get_process_core_indices(&process_result);
             log.write_processing_time(&indices);
+            let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+            log.write_overhead_time(&indices);
 
             // Post-process on completion of node execution
+            let mut ready_nodes = Vec::new();
             for (core_id, result) in process_result.iter().enumerate() {
                 if let ProcessResult::Done(node_data) = result {
-                    let ready_nodes =
-                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
-                    for ready_node in ready_nodes {
-                        ready_queue.insert(NodeDataWrapper {
-                            node_data: ready_node,
+                    ready_nodes.extend(self.post_process_on_node_completion(
+                        node_data,
+                        core_id,
+                        &mut managers,
+                    ));
+                }
+            }
+            if communication_semantics == CommunicationSemantics::LogicalExecutionTime {
+                ready_nodes.extend(self.publish_let_outputs(&mut managers));
+            }
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            self.set_publish_times(&mut ready_nodes);
+            for ready_node in ready_nodes {
+                let dag = self.get_dag(ready_node.get_params_value("dag_id") as usize);
+                if is_section_continuation(dag, &ready_node) {
+                    section_queue.push_back(ready_node);
+                } else {
+                    ready_queue.insert(NodeDataWrapper {
+                        node_data: ready_node,
+                    });
+                }
+            }
+
+            // Handle deadline misses
+            let missed_dag_ids = self.handle_deadline_misses(&mut managers);
+            match self.get_deadline_miss_policy() {
+                DeadlineMissPolicy::Abort if !missed_dag_ids.is_empty() => break,
+                DeadlineMissPolicy::KillJob => {
+                    for dag_id in missed_dag_ids {
+                        ready_queue.retain(|ready_node| {
+                            ready_node.node_data.get_params_value("dag_id") as usize != dag_id
+                        });
+                        section_queue.retain(|node_data| {
+                            node_data.get_params_value("dag_id") as usize != dag_id
                         });
+                        self.kill_job(dag_id, &mut managers[dag_id]);
                     }
                 }
+                _ => {}
             }
         }
 
         self.calculate_log();
-        self.get_current_time()
+        Ok(self.get_schedule_outcome(diagnostic_collector.finish()))
     }
 
+    #[cfg(feature = "file_io")]
     fn dump_log(&mut self, dir_path: &str, alg_name: &str) -> String {
         let file_path = create_scheduler_log_yaml(dir_path, alg_name);
         self.get_log_mut().dump_log_to_yaml(&file_path);
@@ -325,12 +1371,21 @@ pub trait DAGSetSchedulerBase<T: ProcessorBase + Clone> {
 #[macro_export]
 macro_rules! getset_dag_set_scheduler {
     { $t:ty } => {
-        fn get_dag_set(&self) -> Vec<Graph<NodeData, i32>>{
+        fn get_dag_set(&self) -> Vec<Graph<NodeData, i64>>{
             self.dag_set.clone()
         }
-        fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i32>>){
+        fn set_dag_set(&mut self, dag_set: Vec<Graph<NodeData, i64>>){
             self.dag_set = dag_set;
         }
+        fn get_dag_set_ref(&self) -> &[Graph<NodeData, i64>]{
+            &self.dag_set
+        }
+        fn get_dag(&self, dag_id: usize) -> &Graph<NodeData, i64>{
+            &self.dag_set[dag_id]
+        }
+        fn get_dag_mut(&mut self, dag_id: usize) -> &mut Graph<NodeData, i64>{
+            &mut self.dag_set[dag_id]
+        }
         fn get_processor_mut(&mut self) -> &mut $t{
             &mut self.processor
         }
@@ -340,11 +1395,98 @@ macro_rules! getset_dag_set_scheduler {
         fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog{
             &mut self.log
         }
-        fn get_current_time(&self) -> i32{
+        fn get_current_time(&self) -> i64{
             self.current_time
         }
-        fn set_current_time(&mut self, current_time: i32){
+        fn set_current_time(&mut self, current_time: i64){
             self.current_time = current_time;
         }
+        fn get_event_script(&self) -> &EventScript{
+            &self.event_script
+        }
+        fn get_event_script_mut(&mut self) -> &mut EventScript{
+            &mut self.event_script
+        }
+        fn set_event_script(&mut self, event_script: EventScript){
+            self.event_script = event_script;
+        }
+        fn get_interconnect(&self) -> Option<&Interconnect>{
+            self.interconnect.as_ref()
+        }
+        fn get_interconnect_mut(&mut self) -> Option<&mut Interconnect>{
+            self.interconnect.as_mut()
+        }
+        fn set_interconnect(&mut self, interconnect: Interconnect){
+            self.interconnect = Some(interconnect);
+        }
+        fn get_network(&self) -> Option<&Network>{
+            self.network.as_ref()
+        }
+        fn get_network_mut(&mut self) -> Option<&mut Network>{
+            self.network.as_mut()
+        }
+        fn set_network(&mut self, network: Network){
+            network.assign_nodes_to_processors(&mut self.dag_set);
+            self.network = Some(network);
+        }
+        fn get_arrival_trace(&self) -> Option<&ArrivalTrace>{
+            self.arrival_trace.as_ref()
+        }
+        fn set_arrival_trace(&mut self, arrival_trace: ArrivalTrace){
+            self.arrival_trace = Some(arrival_trace);
+        }
+        fn get_aperiodic_arrival_trace(&self) -> Option<&AperiodicArrivalTrace>{
+            self.aperiodic_arrival_trace.as_ref()
+        }
+        fn get_aperiodic_arrival_trace_mut(&mut self) -> Option<&mut AperiodicArrivalTrace>{
+            self.aperiodic_arrival_trace.as_mut()
+        }
+        fn set_aperiodic_arrival_trace(&mut self, aperiodic_arrival_trace: AperiodicArrivalTrace){
+            self.aperiodic_arrival_trace = Some(aperiodic_arrival_trace);
+        }
+        fn get_release_jitter_mut(&mut self) -> &mut ReleaseJitter{
+            &mut self.release_jitter
+        }
+        fn set_release_jitter(&mut self, release_jitter: ReleaseJitter){
+            self.release_jitter = release_jitter;
+        }
+        fn get_deadline_miss_policy(&self) -> DeadlineMissPolicy{
+            self.deadline_miss_policy
+        }
+        fn set_deadline_miss_policy(&mut self, deadline_miss_policy: DeadlineMissPolicy){
+            self.deadline_miss_policy = deadline_miss_policy;
+        }
+        fn get_simulation_horizon(&self) -> SimulationHorizon{
+            self.simulation_horizon
+        }
+        fn set_simulation_horizon(&mut self, simulation_horizon: SimulationHorizon){
+            self.simulation_horizon = simulation_horizon;
+        }
+        fn get_mode_schedule(&self) -> Option<&ModeSchedule>{
+            self.mode_schedule.as_ref()
+        }
+        fn get_mode_schedule_mut(&mut self) -> Option<&mut ModeSchedule>{
+            self.mode_schedule.as_mut()
+        }
+        fn set_mode_schedule(&mut self, mode_schedule: ModeSchedule){
+            self.mode_schedule = Some(mode_schedule);
+        }
+        fn get_online_admission(&self) -> Option<&OnlineAdmission>{
+            self.online_admission.as_ref()
+        }
+        fn get_online_admission_mut(&mut self) -> Option<&mut OnlineAdmission>{
+            self.online_admission.as_mut()
+        }
+        fn set_online_admission(&mut self, online_admission: OnlineAdmission) -> $crate::error::Result<()>{
+            online_admission.validate(&self.dag_set)?;
+            self.online_admission = Some(online_admission);
+            Ok(())
+        }
+        fn get_communication_semantics(&self) -> CommunicationSemantics{
+            self.communication_semantics
+        }
+        fn set_communication_semantics(&mut self, communication_semantics: CommunicationSemantics){
+            self.communication_semantics = communication_semantics;
+        }
     }
 }
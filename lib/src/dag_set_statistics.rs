@@ -0,0 +1,197 @@
+//! Structural and timing statistics of a DAG set, dumped as a summary YAML.
+//! The statistics describe the input alone, so that DAG sets can be characterized before any algorithm runs on them.
+use crate::graph_extension::{GraphExtension, NodeData};
+#[cfg(feature = "file_io")]
+use crate::log::dump_struct;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGStatistics {
+    pub dag_id: usize,
+    pub number_of_nodes: usize,
+    pub number_of_edges: usize,
+    pub volume: i64,
+    pub critical_path_length: i64,
+    // volume / critical_path_length, i.e., the average number of cores the DAG can keep busy
+    pub parallelism: f64,
+    // The timing statistics are omitted for a DAG without a period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_to_end_deadline: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utilization: Option<f64>,
+    // The density and the critical path ratio are relative to min(end-to-end deadline, period).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub critical_path_ratio: Option<f64>,
+}
+
+impl DAGStatistics {
+    pub fn new(dag_id: usize, dag: &Graph<NodeData, i64>) -> Self {
+        let volume = dag.get_volume();
+        let critical_path = dag.get_critical_path();
+        let critical_path_length = dag.get_total_wcet_from_nodes(&critical_path);
+        let period = dag.get_head_period();
+        let end_to_end_deadline = dag.get_end_to_end_deadline();
+        let relative_deadline = match (end_to_end_deadline, period) {
+            (Some(end_to_end_deadline), Some(period)) => Some(end_to_end_deadline.min(period)),
+            (end_to_end_deadline, period) => end_to_end_deadline.or(period),
+        };
+        Self {
+            dag_id,
+            number_of_nodes: dag.node_count(),
+            number_of_edges: dag.edge_count(),
+            volume,
+            critical_path_length,
+            parallelism: volume as f64 / critical_path_length as f64,
+            period,
+            end_to_end_deadline,
+            utilization: period.map(|period| volume as f64 / period as f64),
+            density: relative_deadline.map(|deadline| volume as f64 / deadline as f64),
+            critical_path_ratio: relative_deadline
+                .map(|deadline| critical_path_length as f64 / deadline as f64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGSetStatistics {
+    pub number_of_dags: usize,
+    pub total_number_of_nodes: usize,
+    pub total_number_of_edges: usize,
+    pub total_volume: i64,
+    // The DAGs without a period or a deadline do not count toward the totals.
+    pub total_utilization: f64,
+    pub total_density: f64,
+    pub max_critical_path_ratio: f64,
+    pub each_dag_statistics: Vec<DAGStatistics>,
+}
+
+impl DAGSetStatistics {
+    pub fn new(dag_set: &[Graph<NodeData, i64>]) -> Self {
+        let each_dag_statistics: Vec<DAGStatistics> = dag_set
+            .iter()
+            .enumerate()
+            .map(|(dag_id, dag)| DAGStatistics::new(dag_id, dag))
+            .collect();
+        Self {
+            number_of_dags: dag_set.len(),
+            total_number_of_nodes: each_dag_statistics
+                .iter()
+                .map(|stats| stats.number_of_nodes)
+                .sum(),
+            total_number_of_edges: each_dag_statistics
+                .iter()
+                .map(|stats| stats.number_of_edges)
+                .sum(),
+            total_volume: each_dag_statistics.iter().map(|stats| stats.volume).sum(),
+            total_utilization: each_dag_statistics
+                .iter()
+                .filter_map(|stats| stats.utilization)
+                .sum(),
+            total_density: each_dag_statistics
+                .iter()
+                .filter_map(|stats| stats.density)
+                .sum(),
+            max_critical_path_ratio: each_dag_statistics
+                .iter()
+                .filter_map(|stats| stats.critical_path_ratio)
+                .fold(0.0, f64::max),
+            each_dag_statistics,
+        }
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn dump_to_yaml(&self, file_path: &str) {
+        dump_struct(file_path, self);
+    }
+}
+
+/// Append the statistics of the DAG set to the YAML file.
+#[cfg(feature = "file_io")]
+pub fn dump_dag_set_statistics_to_yaml(dag_set: &[Graph<NodeData, i64>], file_path: &str) {
+    DAGSetStatistics::new(dag_set).dump_to_yaml(file_path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::fs;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag(period: i64, end_to_end_deadline: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let n2 = dag.add_node(create_node(2, "execution_time", 10));
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", end_to_end_deadline);
+        dag
+    }
+
+    #[test]
+    fn test_dag_set_statistics_normal() {
+        let dag_set = vec![create_dag(40, 40), create_dag(100, 20)];
+        let statistics = DAGSetStatistics::new(&dag_set);
+
+        let dag_statistics = &statistics.each_dag_statistics[1];
+        assert_eq!(
+            (
+                dag_statistics.number_of_nodes,
+                dag_statistics.number_of_edges
+            ),
+            (3, 2)
+        );
+        assert_eq!(dag_statistics.volume, 20);
+        assert_eq!(dag_statistics.critical_path_length, 14);
+        assert_eq!(dag_statistics.parallelism, 20.0 / 14.0);
+        assert_eq!(dag_statistics.utilization, Some(0.2));
+        // The constrained deadline is used for the density and the critical path ratio.
+        assert_eq!(dag_statistics.density, Some(1.0));
+        assert_eq!(dag_statistics.critical_path_ratio, Some(0.7));
+
+        assert_eq!(statistics.number_of_dags, 2);
+        assert_eq!(statistics.total_number_of_nodes, 6);
+        assert_eq!(statistics.total_number_of_edges, 4);
+        assert_eq!(statistics.total_volume, 40);
+        assert_eq!(statistics.total_utilization, 0.7);
+        assert_eq!(statistics.total_density, 1.5);
+        assert_eq!(statistics.max_critical_path_ratio, 0.7);
+    }
+
+    #[test]
+    fn test_dag_statistics_no_period() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        dag.add_node(create_node(0, "execution_time", 4));
+        let dag_statistics = DAGStatistics::new(0, &dag);
+
+        assert_eq!(dag_statistics.parallelism, 1.0);
+        assert_eq!(dag_statistics.utilization, None);
+        assert_eq!(dag_statistics.density, None);
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_dump_dag_set_statistics_to_yaml_normal() {
+        let file_path = "../lib/tests/dag_set_statistics_test.yaml";
+        let dag_set = vec![create_dag(40, 40), create_dag(100, 20)];
+        dump_dag_set_statistics_to_yaml(&dag_set, file_path);
+
+        let statistics: DAGSetStatistics =
+            serde_yaml::from_str(&fs::read_to_string(file_path).unwrap()).unwrap();
+        assert_eq!(statistics, DAGSetStatistics::new(&dag_set));
+        fs::remove_file(file_path).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+//! Structured diagnostics of the library.
+//! The library reports questionable inputs and states, such as multiple critical paths or a missing period,
+//! as diagnostics rather than only as log strings, so that batch runs can aggregate and filter them.
+//! While a `DiagnosticCollector` is alive on the thread, the diagnostics are collected into it.
+//! Otherwise they are emitted with `warn!` as before.
+use log::warn;
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    MultipleCriticalPaths,
+    MissingEndToEndDeadline,
+    MultipleSourceNodes,
+    MultiplePeriods,
+    MissingPeriod,
+    MultipleOffsets,
+    MissingOffset,
+    DeadlineOverridden,
+    DecimalPlacesRounded,
+    MissingPriority,
+    CoreUnavailable,
+    MissingExecutionTime,
+    ParamAlreadyExists,
+    ParamNotFound,
+    LogicalExecutionTimeOverrun,
+    NonNumericParamDropped,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+}
+
+thread_local! {
+    // A stack to support nested collectors. The innermost collector receives the diagnostics.
+    static COLLECTED_DIAGNOSTICS: RefCell<Vec<Vec<Diagnostic>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Report a diagnostic to the innermost collector on the thread, or emit it with `warn!` if there is none.
+pub fn report(kind: DiagnosticKind, message: impl Into<String>) {
+    let message = message.into();
+    COLLECTED_DIAGNOSTICS.with(|collected_diagnostics| {
+        match collected_diagnostics.borrow_mut().last_mut() {
+            Some(diagnostics) => diagnostics.push(Diagnostic { kind, message }),
+            None => warn!("{}", message),
+        }
+    });
+}
+
+/// Collects the diagnostics reported on the thread from its creation until `finish`.
+///
+/// # Example
+///
+/// ```
+/// use lib::dag_creator::create_dag_from_yaml_str;
+/// use lib::diagnostics::DiagnosticCollector;
+/// use lib::graph_extension::GraphExtension;
+///
+/// let diagnostic_collector = DiagnosticCollector::new();
+/// let dag = create_dag_from_yaml_str("nodes: [{id: 0, execution_time: 3, period: 10}]\nlinks: []", false).unwrap();
+/// dag.get_head_offset();
+/// let diagnostics = diagnostic_collector.finish();
+/// ```
+pub struct DiagnosticCollector {
+    depth: usize,
+}
+
+impl Default for DiagnosticCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiagnosticCollector {
+    pub fn new() -> Self {
+        let depth = COLLECTED_DIAGNOSTICS.with(|collected_diagnostics| {
+            let mut collected_diagnostics = collected_diagnostics.borrow_mut();
+            collected_diagnostics.push(Vec::new());
+            collected_diagnostics.len()
+        });
+        Self { depth }
+    }
+
+    pub fn finish(self) -> Vec<Diagnostic> {
+        let diagnostics = COLLECTED_DIAGNOSTICS.with(|collected_diagnostics| {
+            let mut collected_diagnostics = collected_diagnostics.borrow_mut();
+            // The inner collectors dropped without finish are discarded.
+            collected_diagnostics.truncate(self.depth);
+            collected_diagnostics.pop().unwrap_or_default()
+        });
+        std::mem::forget(self);
+        diagnostics
+    }
+}
+
+impl Drop for DiagnosticCollector {
+    /// A collector dropped without `finish` discards its diagnostics.
+    fn drop(&mut self) {
+        COLLECTED_DIAGNOSTICS.with(|collected_diagnostics| {
+            collected_diagnostics.borrow_mut().truncate(self.depth - 1);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_normal() {
+        let diagnostic_collector = DiagnosticCollector::new();
+        report(DiagnosticKind::MissingPeriod, "No period found.");
+        let diagnostics = diagnostic_collector.finish();
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                kind: DiagnosticKind::MissingPeriod,
+                message: "No period found.".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collector_nested() {
+        let outer_collector = DiagnosticCollector::new();
+        report(DiagnosticKind::MissingOffset, "outer");
+        let inner_collector = DiagnosticCollector::new();
+        report(DiagnosticKind::MissingOffset, "inner");
+
+        assert_eq!(inner_collector.finish().len(), 1);
+        let diagnostics = outer_collector.finish();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "outer");
+    }
+
+    #[test]
+    fn test_collector_dropped_without_finish() {
+        let outer_collector = DiagnosticCollector::new();
+        {
+            let _inner_collector = DiagnosticCollector::new();
+            report(DiagnosticKind::MissingOffset, "inner");
+        }
+        report(DiagnosticKind::MissingOffset, "outer");
+
+        assert_eq!(outer_collector.finish().len(), 1);
+    }
+}
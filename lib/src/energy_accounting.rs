@@ -0,0 +1,241 @@
+//! Energy consumption of each DAG and each of its jobs, taken from the execution slices of a simulated schedule.
+//! A core draws `active_power` per time unit while it executes a job and `idle_power` per time unit otherwise,
+//! so the active energy of a job is its execution time on the cores multiplied by `active_power`.
+//!
+//! The idle energy of the cores until the end of the schedule belongs to no job,
+//! and the idle energy policy decides how it is attributed to the DAGs:
+//! - `unattributed`: the idle energy is reported apart from the DAGs.
+//! - `proportional`: each job gets the share of its active time in the total active time.
+//! - `equal`: each DAG gets the same share, which is split equally among its jobs.
+use crate::error::{Result, SchedSimError};
+use crate::log::{DAGSetSchedulerLog, ExecutionSlice};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum IdleEnergyPolicy {
+    #[default]
+    Unattributed,
+    Proportional,
+    Equal,
+}
+
+impl IdleEnergyPolicy {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "unattributed" => Ok(Self::Unattributed),
+            "proportional" => Ok(Self::Proportional),
+            "equal" => Ok(Self::Equal),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown idle energy policy: {}",
+                name
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DAGEnergyLog {
+    pub dag_id: usize,
+    pub active_energy: f64,
+    pub attributed_idle_energy: f64,
+    // Active and attributed idle energy of each job, indexed by job_id
+    pub job_energies: Vec<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnergyLog {
+    pub idle_energy_policy: IdleEnergyPolicy,
+    pub total_energy: f64,
+    pub idle_energy: f64,
+    // The idle energy not attributed to any DAG
+    pub unattributed_idle_energy: f64,
+    pub dag_energy_logs: Vec<DAGEnergyLog>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PowerModel {
+    pub active_power: f64,
+    pub idle_power: f64,
+}
+
+impl PowerModel {
+    pub fn new(active_power: f64, idle_power: f64) -> Self {
+        if active_power < 0.0 || idle_power < 0.0 {
+            panic!("The power must be non-negative.");
+        }
+        Self {
+            active_power,
+            idle_power,
+        }
+    }
+
+    /// Calculate the energy of the schedule in `log` until `schedule_length`.
+    /// A job still running at the end of the log has no energy for its last execution, like its execution slice.
+    pub fn calculate_energy(
+        &self,
+        log: &DAGSetSchedulerLog,
+        schedule_length: i64,
+        idle_energy_policy: IdleEnergyPolicy,
+    ) -> EnergyLog {
+        let execution_slices = log.get_execution_slices();
+        let number_of_dags = log.get_number_of_dags();
+        let active_times = get_job_active_times(&execution_slices, number_of_dags);
+        let total_active_time: i64 = active_times.iter().flat_map(|jobs| jobs.values()).sum();
+        let idle_time =
+            (schedule_length * log.get_number_of_cores() as i64 - total_active_time).max(0);
+        let idle_energy = idle_time as f64 * self.idle_power;
+
+        let dag_energy_logs: Vec<DAGEnergyLog> = active_times
+            .iter()
+            .enumerate()
+            .map(|(dag_id, job_active_times)| {
+                let job_energies: Vec<f64> = job_active_times
+                    .values()
+                    .map(|active_time| {
+                        let idle_share = match idle_energy_policy {
+                            IdleEnergyPolicy::Unattributed => 0.0,
+                            IdleEnergyPolicy::Proportional if total_active_time > 0 => {
+                                *active_time as f64 / total_active_time as f64
+                            }
+                            IdleEnergyPolicy::Proportional => 0.0,
+                            IdleEnergyPolicy::Equal => {
+                                1.0 / (number_of_dags * job_active_times.len()) as f64
+                            }
+                        };
+                        *active_time as f64 * self.active_power + idle_energy * idle_share
+                    })
+                    .collect();
+                let active_energy =
+                    job_active_times.values().sum::<i64>() as f64 * self.active_power;
+                DAGEnergyLog {
+                    dag_id,
+                    active_energy,
+                    attributed_idle_energy: job_energies.iter().sum::<f64>() - active_energy,
+                    job_energies,
+                }
+            })
+            .collect();
+
+        let attributed_idle_energy: f64 = dag_energy_logs
+            .iter()
+            .map(|dag_energy_log| dag_energy_log.attributed_idle_energy)
+            .sum();
+        EnergyLog {
+            idle_energy_policy,
+            total_energy: total_active_time as f64 * self.active_power + idle_energy,
+            idle_energy,
+            unattributed_idle_energy: idle_energy - attributed_idle_energy,
+            dag_energy_logs,
+        }
+    }
+}
+
+/// Returns the execution time of each job summed over its slices, indexed by [dag_id][job_id].
+/// Jobs without any finished slice are omitted.
+fn get_job_active_times(
+    execution_slices: &[ExecutionSlice],
+    number_of_dags: usize,
+) -> Vec<BTreeMap<usize, i64>> {
+    let mut active_times = vec![BTreeMap::new(); number_of_dags];
+    for slice in execution_slices {
+        *active_times[slice.dag_id].entry(slice.job_id).or_insert(0) +=
+            slice.end_time - slice.start_time;
+    }
+    active_times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        graph_extension::{GraphExtension, NodeData},
+        homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+    use petgraph::Graph;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_single_node_dag(
+        dag_id: i64,
+        execution_time: i64,
+        period: i64,
+    ) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", period);
+        dag.add_param(n0, "end_to_end_deadline", period);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    /// DAG 0 runs 10 every 50 and DAG 1 runs 30 every 100 on 2 cores for 100,
+    /// so the active time is 20 + 30 = 50 and the idle time is 200 - 50 = 150.
+    fn schedule() -> (DAGSetSchedulerLog, i64) {
+        let dag_set = vec![
+            create_single_node_dag(0, 10, 50),
+            create_single_node_dag(1, 30, 100),
+        ];
+        let mut scheduler = GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let schedule_outcome = scheduler.schedule(PreemptiveType::NonPreemptive).unwrap();
+        (schedule_outcome.log, schedule_outcome.schedule_length)
+    }
+
+    #[test]
+    fn test_calculate_energy_unattributed() {
+        let (log, schedule_length) = schedule();
+        let energy_log = PowerModel::new(2.0, 1.0).calculate_energy(
+            &log,
+            schedule_length,
+            IdleEnergyPolicy::Unattributed,
+        );
+
+        assert_eq!(energy_log.idle_energy, 150.0);
+        assert_eq!(energy_log.total_energy, 250.0);
+        assert_eq!(energy_log.unattributed_idle_energy, 150.0);
+        assert_eq!(energy_log.dag_energy_logs[0].job_energies, vec![20.0, 20.0]);
+        assert_eq!(energy_log.dag_energy_logs[1].active_energy, 60.0);
+    }
+
+    #[test]
+    fn test_calculate_energy_proportional() {
+        let (log, schedule_length) = schedule();
+        let energy_log = PowerModel::new(2.0, 1.0).calculate_energy(
+            &log,
+            schedule_length,
+            IdleEnergyPolicy::Proportional,
+        );
+
+        // Each job of DAG 0 gets 10 / 50 of the idle energy.
+        assert_eq!(energy_log.dag_energy_logs[0].job_energies, vec![50.0, 50.0]);
+        assert_eq!(energy_log.dag_energy_logs[1].attributed_idle_energy, 90.0);
+        assert_eq!(energy_log.unattributed_idle_energy, 0.0);
+    }
+
+    #[test]
+    fn test_calculate_energy_equal() {
+        let (log, schedule_length) = schedule();
+        let energy_log = PowerModel::new(2.0, 1.0).calculate_energy(
+            &log,
+            schedule_length,
+            IdleEnergyPolicy::Equal,
+        );
+
+        assert_eq!(energy_log.dag_energy_logs[0].job_energies, vec![57.5, 57.5]);
+        assert_eq!(energy_log.dag_energy_logs[1].job_energies, vec![135.0]);
+        assert_eq!(energy_log.unattributed_idle_energy, 0.0);
+    }
+
+    #[test]
+    fn test_idle_energy_policy_from_name_unknown() {
+        assert!(IdleEnergyPolicy::from_name("unknown").is_err());
+    }
+}
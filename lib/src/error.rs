@@ -0,0 +1,40 @@
+//! The errors returned by the loaders and the schedulers instead of panicking,
+//! so that a long-running service can reject a bad input and keep going.
+use crate::dag_builder::DagBuildError;
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SchedSimError {
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Failed to parse {path}: {message}")]
+    Parse { path: String, message: String },
+    /// The input is well-formed but violates the assumptions of the model, e.g., a jitter longer than the period.
+    #[error("Invalid input: {0}")]
+    Validation(String),
+    #[error(transparent)]
+    DagBuild(#[from] DagBuildError),
+}
+
+impl SchedSimError {
+    pub fn io(path: &str, source: io::Error) -> Self {
+        Self::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+
+    pub fn parse(path: &str, message: impl ToString) -> Self {
+        Self::Parse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, SchedSimError>;
@@ -0,0 +1,268 @@
+//! Scripted events for what-if scenarios.
+//! An event script is a YAML list of timed events which the DAG set scheduler consumes during scheduling.
+//!
+//! Example of an event script:
+//!
+//! ```yaml
+//! - event: core_failure
+//!   time: 100
+//!   core_id: 1
+//! - event: wcet_overrun
+//!   dag_id: 0
+//!   node_id: 2
+//!   job_id: 1
+//!   execution_time: 30
+//! - event: aperiodic_job
+//!   time: 50
+//!   dag_id: 1
+//! ```
+#[cfg(feature = "file_io")]
+use crate::{error::Result, util::load_yaml_struct};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ScriptedEvent {
+    /// The core stops working at `time`. The job running on it is released again.
+    CoreFailure { time: i64, core_id: usize },
+    /// The node of the `job_id`-th release of the DAG executes for `execution_time` instead of its WCET.
+    WcetOverrun {
+        dag_id: usize,
+        node_id: usize,
+        job_id: usize,
+        execution_time: i64,
+    },
+    /// An extra job of the DAG is released at `time` in addition to the periodic releases.
+    AperiodicJob { time: i64, dag_id: usize },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EventScript {
+    events: Vec<ScriptedEvent>,
+}
+
+impl EventScript {
+    pub fn new(events: Vec<ScriptedEvent>) -> Self {
+        Self { events }
+    }
+
+    /// load yaml file and return an event script
+    ///
+    /// # Arguments
+    ///
+    /// *  `file_path` - yaml file path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lib::event_script::EventScript;
+    ///
+    /// let event_script = EventScript::from_yaml_file("tests/sample_event_scripts/what_if.yaml").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        load_yaml_struct(file_path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Remove and return the timed events that have occurred by `current_time`.
+    pub fn pop_timed_events(&mut self, current_time: i64) -> Vec<ScriptedEvent> {
+        let (occurred, remaining) = self.events.drain(..).partition(|event| match event {
+            ScriptedEvent::CoreFailure { time, .. } | ScriptedEvent::AperiodicJob { time, .. } => {
+                *time <= current_time
+            }
+            ScriptedEvent::WcetOverrun { .. } => false,
+        });
+        self.events = remaining;
+        occurred
+    }
+
+    pub fn push(&mut self, event: ScriptedEvent) {
+        self.events.push(event);
+    }
+
+    /// Add core failures drawn at random.
+    /// Each core fails with `failure_probability` per time unit before `end_time`, and fails at most once.
+    /// The same `seed` always yields the same failures.
+    pub fn push_random_core_failures(
+        &mut self,
+        number_of_cores: usize,
+        end_time: i64,
+        failure_probability: f64,
+        seed: u64,
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for core_id in 0..number_of_cores {
+            if let Some(time) = (0..end_time).find(|_| rng.gen::<f64>() < failure_probability) {
+                self.push(ScriptedEvent::CoreFailure { time, core_id });
+            }
+        }
+    }
+
+    pub fn get_overrun_execution_time(
+        &self,
+        target_dag_id: usize,
+        target_node_id: usize,
+        target_job_id: usize,
+    ) -> Option<i64> {
+        self.events.iter().find_map(|event| match event {
+            ScriptedEvent::WcetOverrun {
+                dag_id,
+                node_id,
+                job_id,
+                execution_time,
+            } if (*dag_id, *node_id, *job_id) == (target_dag_id, target_node_id, target_job_id) => {
+                Some(*execution_time)
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "file_io")]
+    use crate::error::SchedSimError;
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_normal() {
+        let event_script =
+            EventScript::from_yaml_file("tests/sample_event_scripts/what_if.yaml").unwrap();
+        assert_eq!(
+            event_script,
+            EventScript::new(vec![
+                ScriptedEvent::CoreFailure {
+                    time: 100,
+                    core_id: 1
+                },
+                ScriptedEvent::WcetOverrun {
+                    dag_id: 0,
+                    node_id: 2,
+                    job_id: 1,
+                    execution_time: 30
+                },
+                ScriptedEvent::AperiodicJob {
+                    time: 50,
+                    dag_id: 1
+                },
+            ])
+        );
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_no_yaml() {
+        assert!(matches!(
+            EventScript::from_yaml_file("tests/sample_dags/no_yaml.tex"),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_not_found() {
+        assert!(matches!(
+            EventScript::from_yaml_file("tests/sample_event_scripts/not_found.yaml"),
+            Err(SchedSimError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pop_timed_events_normal() {
+        let mut event_script = EventScript::new(vec![
+            ScriptedEvent::CoreFailure {
+                time: 10,
+                core_id: 0,
+            },
+            ScriptedEvent::WcetOverrun {
+                dag_id: 0,
+                node_id: 0,
+                job_id: 0,
+                execution_time: 5,
+            },
+            ScriptedEvent::AperiodicJob {
+                time: 20,
+                dag_id: 0,
+            },
+        ]);
+
+        assert!(event_script.pop_timed_events(9).is_empty());
+        assert_eq!(
+            event_script.pop_timed_events(10),
+            vec![ScriptedEvent::CoreFailure {
+                time: 10,
+                core_id: 0
+            }]
+        );
+        assert_eq!(
+            event_script.pop_timed_events(25),
+            vec![ScriptedEvent::AperiodicJob {
+                time: 20,
+                dag_id: 0
+            }]
+        );
+        // WCET overruns are not timed events, so they remain in the script.
+        assert!(!event_script.is_empty());
+    }
+
+    #[test]
+    fn test_push_random_core_failures_normal() {
+        let mut event_script = EventScript::default();
+        event_script.push_random_core_failures(4, 100, 0.05, 0);
+        let mut same_seed_event_script = EventScript::default();
+        same_seed_event_script.push_random_core_failures(4, 100, 0.05, 0);
+
+        assert_eq!(event_script, same_seed_event_script);
+        for event in event_script.pop_timed_events(100) {
+            match event {
+                ScriptedEvent::CoreFailure { time, core_id } => {
+                    assert!((0..100).contains(&time));
+                    assert!(core_id < 4);
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_random_core_failures_edge_probability() {
+        let mut event_script = EventScript::default();
+        event_script.push_random_core_failures(2, 100, 0.0, 0);
+        assert!(event_script.is_empty());
+
+        event_script.push_random_core_failures(2, 100, 1.0, 0);
+        assert_eq!(
+            event_script.pop_timed_events(0),
+            vec![
+                ScriptedEvent::CoreFailure {
+                    time: 0,
+                    core_id: 0
+                },
+                ScriptedEvent::CoreFailure {
+                    time: 0,
+                    core_id: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_overrun_execution_time_normal() {
+        let event_script = EventScript::new(vec![ScriptedEvent::WcetOverrun {
+            dag_id: 1,
+            node_id: 2,
+            job_id: 3,
+            execution_time: 40,
+        }]);
+
+        assert_eq!(event_script.get_overrun_execution_time(1, 2, 3), Some(40));
+        assert_eq!(event_script.get_overrun_execution_time(1, 2, 0), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Stochastic execution times.
+//! `execution_time` is the WCET, and a node with `bcet`, `acet` or execution time samples executes for a time drawn per job.
+//! The distribution is selected by `execution_time_distribution`:
+//!
+//! - `uniform`: uniform over [bcet, WCET]
+//! - `normal`: mean acet and standard deviation (WCET - bcet) / 6, truncated to [bcet, WCET]
+//! - `empirical`: one of `execution_time_samples` with equal probability
+//!
+//! If not specified, it is `empirical` for a node with samples, `normal` for a node with acet, and `uniform` otherwise.
+//! bcet defaults to 1 because a job takes at least one time unit.
+//!
+//! Example of a node in a DAG yaml file:
+//!
+//! ```yaml
+//! - id: 0
+//!   execution_time: 10
+//!   bcet: 4
+//!   acet: 6
+//!   execution_time_distribution: normal
+//! ```
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::NodeData;
+use rand::{rngs::StdRng, Rng};
+use rand_distr::{Distribution, Normal};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionTimeDistribution {
+    Uniform = 0,
+    Normal = 1,
+    Empirical = 2,
+}
+
+impl ExecutionTimeDistribution {
+    /// Node params are integers, so the distribution is stored as its discriminant.
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "uniform" => Ok(Self::Uniform),
+            "normal" => Ok(Self::Normal),
+            "empirical" => Ok(Self::Empirical),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown execution time distribution: {}",
+                name
+            ))),
+        }
+    }
+
+    fn from_code(code: i64) -> Self {
+        match code {
+            0 => Self::Uniform,
+            1 => Self::Normal,
+            2 => Self::Empirical,
+            _ => panic!("Unknown execution time distribution code: {}", code),
+        }
+    }
+}
+
+/// The samples are stored as `execution_time_sample_0`, `execution_time_sample_1`, ...
+pub fn get_execution_time_sample_key(index: usize) -> String {
+    format!("execution_time_sample_{}", index)
+}
+
+fn get_execution_time_samples(node_data: &NodeData) -> Vec<i64> {
+    (0..)
+        .map_while(|index| {
+            node_data
+                .params
+                .get(&get_execution_time_sample_key(index))
+                .copied()
+        })
+        .collect()
+}
+
+fn get_distribution(node_data: &NodeData) -> Option<ExecutionTimeDistribution> {
+    if let Some(code) = node_data.params.get("execution_time_distribution") {
+        Some(ExecutionTimeDistribution::from_code(*code))
+    } else if node_data
+        .params
+        .contains_key(&get_execution_time_sample_key(0))
+    {
+        Some(ExecutionTimeDistribution::Empirical)
+    } else if node_data.params.contains_key("acet") {
+        Some(ExecutionTimeDistribution::Normal)
+    } else if node_data.params.contains_key("bcet") {
+        Some(ExecutionTimeDistribution::Uniform)
+    } else {
+        None
+    }
+}
+
+/// Make the node always execute for its `execution_time`, e.g. when a scripted event overrides it.
+pub fn remove_execution_time_distribution(node_data: &mut NodeData) {
+    node_data.params.retain(|key, _| {
+        key != "bcet"
+            && key != "acet"
+            && key != "execution_time_distribution"
+            && !key.starts_with("execution_time_sample_")
+    });
+}
+
+/// Draw the actual execution time of a job of the node.
+/// Returns None if the node always executes for its WCET.
+pub fn draw_execution_time(node_data: &NodeData, rng: &mut StdRng) -> Option<i64> {
+    let distribution = get_distribution(node_data)?;
+    let wcet = node_data.get_params_value("execution_time");
+    let bcet = node_data.params.get("bcet").copied().unwrap_or(1).min(wcet);
+
+    let execution_time = match distribution {
+        ExecutionTimeDistribution::Uniform => rng.gen_range(bcet..=wcet),
+        ExecutionTimeDistribution::Normal => {
+            let std_dev = (wcet - bcet) as f64 / 6.0;
+            let normal = Normal::new(node_data.get_params_value("acet") as f64, std_dev).unwrap();
+            normal.sample(rng).round() as i64
+        }
+        ExecutionTimeDistribution::Empirical => {
+            let samples = get_execution_time_samples(node_data);
+            if samples.is_empty() {
+                panic!("Node {} does not have execution time samples", node_data.id);
+            }
+            samples[rng.gen_range(0..samples.len())]
+        }
+    };
+    Some(execution_time.clamp(bcet, wcet))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    #[test]
+    fn test_execution_time_distribution_from_name() {
+        assert_eq!(
+            ExecutionTimeDistribution::from_name("normal").unwrap(),
+            ExecutionTimeDistribution::Normal
+        );
+        assert!(ExecutionTimeDistribution::from_name("gamma").is_err());
+    }
+
+    #[test]
+    fn test_draw_execution_time_uniform() {
+        let mut node_data = create_node(0, "execution_time", 10);
+        node_data.params.insert("bcet".to_string(), 4);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let execution_times: Vec<i64> = (0..100)
+            .map(|_| draw_execution_time(&node_data, &mut rng).unwrap())
+            .collect();
+        assert!(execution_times.iter().all(|time| (4..=10).contains(time)));
+        assert!(execution_times.contains(&4));
+        assert!(execution_times.contains(&10));
+    }
+
+    #[test]
+    fn test_draw_execution_time_normal() {
+        let mut node_data = create_node(0, "execution_time", 10);
+        node_data.params.insert("bcet".to_string(), 4);
+        node_data.params.insert("acet".to_string(), 6);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let execution_times: Vec<i64> = (0..1000)
+            .map(|_| draw_execution_time(&node_data, &mut rng).unwrap())
+            .collect();
+        assert!(execution_times.iter().all(|time| (4..=10).contains(time)));
+        let average = execution_times.iter().sum::<i64>() as f64 / 1000.0;
+        assert!((average - 6.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_draw_execution_time_empirical() {
+        let mut node_data = create_node(0, "execution_time", 10);
+        node_data.params.insert(get_execution_time_sample_key(0), 3);
+        node_data.params.insert(get_execution_time_sample_key(1), 7);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let execution_time = draw_execution_time(&node_data, &mut rng).unwrap();
+            assert!(execution_time == 3 || execution_time == 7);
+        }
+    }
+
+    #[test]
+    fn test_draw_execution_time_same_seed() {
+        let mut node_data = create_node(0, "execution_time", 100);
+        node_data.params.insert("bcet".to_string(), 1);
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut same_seed_rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..10 {
+            assert_eq!(
+                draw_execution_time(&node_data, &mut rng),
+                draw_execution_time(&node_data, &mut same_seed_rng)
+            );
+        }
+    }
+
+    #[test]
+    fn test_remove_execution_time_distribution_normal() {
+        let mut node_data = create_node(0, "execution_time", 10);
+        node_data.params.insert("acet".to_string(), 6);
+        node_data.params.insert(get_execution_time_sample_key(0), 3);
+        remove_execution_time_distribution(&mut node_data);
+
+        assert_eq!(node_data, create_node(0, "execution_time", 10));
+    }
+
+    #[test]
+    fn test_draw_execution_time_wcet_only() {
+        let node_data = create_node(0, "execution_time", 10);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(draw_execution_time(&node_data, &mut rng), None);
+    }
+}
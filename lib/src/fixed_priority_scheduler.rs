@@ -1,7 +1,7 @@
+use crate::diagnostics::{report, DiagnosticKind};
 use crate::{
     dag_scheduler::DAGSchedulerBase, graph_extension::NodeData, log::*, processor::ProcessorBase,
 };
-use log::warn;
 use petgraph::Graph;
 use std::collections::VecDeque;
 
@@ -10,7 +10,7 @@ pub struct FixedPriorityScheduler<T>
 where
     T: ProcessorBase + Clone,
 {
-    dag: Graph<NodeData, i32>,
+    dag: Graph<NodeData, i64>,
     processor: T,
     log: DAGSchedulerLog,
 }
@@ -19,7 +19,7 @@ impl<T> DAGSchedulerBase<T> for FixedPriorityScheduler<T>
 where
     T: ProcessorBase + Clone,
 {
-    fn new(dag: &Graph<NodeData, i32>, processor: &T) -> Self {
+    fn new(dag: &Graph<NodeData, i64>, processor: &T) -> Self {
         Self {
             dag: dag.clone(),
             processor: processor.clone(),
@@ -27,7 +27,7 @@ where
         }
     }
 
-    fn set_dag(&mut self, dag: &Graph<NodeData, i32>) {
+    fn set_dag(&mut self, dag: &Graph<NodeData, i64>) {
         self.dag = dag.clone();
     }
 
@@ -39,7 +39,7 @@ where
         self.log = log;
     }
 
-    fn get_dag(&self) -> Graph<NodeData, i32> {
+    fn get_dag(&self) -> Graph<NodeData, i64> {
         self.dag.clone()
     }
 
@@ -54,9 +54,12 @@ where
     fn sort_ready_queue(ready_queue: &mut VecDeque<NodeData>) {
         ready_queue.make_contiguous().sort_by_key(|node| {
             *node.params.get("priority").unwrap_or_else(|| {
-                warn!(
-                    "Warning: 'priority' parameter not found for node {:?}",
-                    node
+                report(
+                    DiagnosticKind::MissingPriority,
+                    format!(
+                        "Warning: 'priority' parameter not found for node {:?}",
+                        node
+                    ),
                 );
                 &999 // Because sorting cannot be done well without a priority
             })
@@ -66,16 +69,21 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeMap, fs::remove_file};
+    use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::fs::{self, remove_file};
 
     use super::*;
     use crate::graph_extension::GraphExtension;
     use crate::homogeneous::HomogeneousProcessor;
+    #[cfg(feature = "file_io")]
+    use crate::log::DAGSchedulerLog;
     use crate::processor::ProcessorBase;
-    use crate::util::load_yaml;
+    #[cfg(feature = "file_io")]
+    use crate::util::{create_yaml, load_yaml};
     use petgraph::graph::{Graph, NodeIndex};
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
@@ -83,7 +91,7 @@ mod tests {
 
     #[test]
     fn test_fixed_priority_scheduler_schedule_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 52));
         let c1 = dag.add_node(create_node(1, "execution_time", 40));
@@ -122,7 +130,7 @@ mod tests {
 
     #[test]
     fn test_fixed_priority_scheduler_schedule_concurrent_task() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 52));
         let c1 = dag.add_node(create_node(1, "execution_time", 40));
@@ -160,7 +168,7 @@ mod tests {
 
     #[test]
     fn test_fixed_priority_scheduler_schedule_used_twice_for_same_dag() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 1));
         dag.add_param(c0, "period", 100);
@@ -177,9 +185,10 @@ mod tests {
         assert_eq!(result.1, vec![NodeIndex::new(0)]);
     }
 
+    #[cfg(feature = "file_io")]
     #[test]
     fn test_fixed_priority_scheduler_log_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         //cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 52));
         let c1 = dag.add_node(create_node(1, "execution_time", 40));
@@ -204,7 +213,7 @@ mod tests {
         fixed_priority_scheduler.schedule();
 
         let file_path = fixed_priority_scheduler.dump_log("tests", "test");
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         assert_eq!(
@@ -281,4 +290,187 @@ mod tests {
 
         remove_file(file_path).unwrap();
     }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_fixed_priority_scheduler_log_from_yaml_file() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let c0 = dag.add_node(create_node(0, "execution_time", 52));
+        let c1 = dag.add_node(create_node(1, "execution_time", 40));
+        let n0_0 = dag.add_node(create_node(2, "execution_time", 12));
+        dag.add_param(c0, "priority", 0);
+        dag.add_param(c0, "period", 100);
+        dag.add_param(c1, "priority", 0);
+        dag.add_param(n0_0, "priority", 1);
+        dag.add_edge(c0, c1, 1);
+        dag.add_edge(c0, n0_0, 1);
+
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        fixed_priority_scheduler.schedule();
+        let file_path = fixed_priority_scheduler.dump_log("../lib/tests", "fp_reader_test");
+
+        // The reloaded log is dumped as the same document.
+        let reloaded_file_path = create_yaml("../lib/tests", "fp_reader_test_reloaded");
+        DAGSchedulerLog::from_yaml_file(&file_path)
+            .unwrap()
+            .dump_log_to_yaml(&reloaded_file_path);
+        assert_eq!(
+            fs::read_to_string(&file_path).unwrap(),
+            fs::read_to_string(&reloaded_file_path).unwrap()
+        );
+
+        remove_file(file_path).unwrap();
+        remove_file(reloaded_file_path).unwrap();
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_fixed_priority_scheduler_schedule_with_core_affinity() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        //cX is the Xth critical node.
+        let c0 = dag.add_node(create_node(0, "execution_time", 52));
+        let c1 = dag.add_node(create_node(1, "execution_time", 40));
+        dag.add_param(c0, "priority", 0);
+        dag.add_param(c0, "period", 100);
+        dag.add_param(c1, "priority", 0);
+        dag.add_param(c1, "allowed_cores", 0b10);
+        //nY_X is the Yth suc node of cX.
+        let n0_0 = dag.add_node(create_node(2, "execution_time", 12));
+        let n1_0 = dag.add_node(create_node(3, "execution_time", 10));
+        dag.add_param(n0_0, "priority", 2);
+        dag.add_param(n1_0, "priority", 1);
+
+        //create critical path edges
+        dag.add_edge(c0, c1, 1);
+
+        //create non-critical path edges
+        dag.add_edge(c0, n0_0, 1);
+        dag.add_edge(c0, n1_0, 1);
+
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        let result = fixed_priority_scheduler.schedule();
+        assert_eq!(result.0, 92);
+
+        let file_path = fixed_priority_scheduler.dump_log("tests", "affinity_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let node_logs = &yaml_docs[0]["node_logs"];
+
+        // c1 is allocated to core 1 even though core 0 is also idle.
+        assert_eq!(node_logs[2]["node_id"].as_i64().unwrap(), 1);
+        assert_eq!(node_logs[2]["core_id"].as_i64().unwrap(), 1);
+        assert_eq!(node_logs[3]["node_id"].as_i64().unwrap(), 3);
+        assert_eq!(node_logs[3]["core_id"].as_i64().unwrap(), 0);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_fixed_priority_scheduler_log_with_soft_deadlines() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        //cX is the Xth critical node.
+        let c0 = dag.add_node(create_node(0, "execution_time", 52));
+        let c1 = dag.add_node(create_node(1, "execution_time", 40));
+        dag.add_param(c0, "priority", 0);
+        dag.add_param(c0, "period", 100);
+        dag.add_param(c1, "priority", 0);
+        dag.add_param(c1, "end_to_end_deadline", 80);
+        //nY_X is the Yth suc node of cX.
+        let n0_0 = dag.add_node(create_node(2, "execution_time", 12));
+        let n1_0 = dag.add_node(create_node(3, "execution_time", 10));
+        dag.add_param(n0_0, "priority", 2);
+        dag.add_param(n0_0, "soft_deadline", 60);
+        dag.add_param(n0_0, "tardiness_weight", 2);
+        dag.add_param(n1_0, "priority", 1);
+        dag.add_param(n1_0, "soft_deadline", 70);
+
+        //create critical path edges
+        dag.add_edge(c0, c1, 1);
+
+        //create non-critical path edges
+        dag.add_edge(c0, n0_0, 1);
+        dag.add_edge(c0, n1_0, 1);
+
+        let mut fixed_priority_scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(2));
+        fixed_priority_scheduler.schedule();
+        // The tardiness is not accumulated when the scheduler is reused.
+        fixed_priority_scheduler.schedule();
+
+        let file_path = fixed_priority_scheduler.dump_log("tests", "soft_deadline_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        assert_eq!(
+            yaml_doc["tardiness_stats"]["total_tardiness"]
+                .as_i64()
+                .unwrap(),
+            12
+        );
+        // n0_0 finishes at 74 and n1_0 at 62.
+        let node_tardiness_stats = &yaml_doc["node_tardiness_stats"];
+        assert_eq!(
+            node_tardiness_stats[2]["weighted_tardiness"]
+                .as_i64()
+                .unwrap(),
+            28
+        );
+        assert_eq!(
+            node_tardiness_stats[3]["max_tardiness"].as_i64().unwrap(),
+            0
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_fixed_priority_scheduler_schedule_with_dispatch_overhead() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        //cX is the Xth critical node.
+        let c0 = dag.add_node(create_node(0, "execution_time", 52));
+        let c1 = dag.add_node(create_node(1, "execution_time", 40));
+        dag.add_param(c0, "priority", 0);
+        dag.add_param(c0, "period", 100);
+        dag.add_param(c1, "priority", 0);
+        //nY_X is the Yth suc node of cX.
+        let n0_0 = dag.add_node(create_node(2, "execution_time", 12));
+        let n1_0 = dag.add_node(create_node(3, "execution_time", 10));
+        dag.add_param(n0_0, "priority", 2);
+        dag.add_param(n1_0, "priority", 1);
+
+        //create critical path edges
+        dag.add_edge(c0, c1, 1);
+
+        //create non-critical path edges
+        dag.add_edge(c0, n0_0, 1);
+        dag.add_edge(c0, n1_0, 1);
+
+        let mut processor = HomogeneousProcessor::new(2);
+        processor.set_overhead(2, 0);
+        let mut fixed_priority_scheduler = FixedPriorityScheduler::new(&dag, &processor);
+        let result = fixed_priority_scheduler.schedule();
+        // Each of c0 and c1 on the critical path is delayed by the dispatch overhead.
+        assert_eq!(result.0, 96);
+
+        let file_path = fixed_priority_scheduler.dump_log("tests", "dispatch_overhead_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let core_logs = yaml_docs[0]["processor_log"]["core_logs"].as_vec().unwrap();
+
+        // The overhead is not counted as processing time.
+        let total_overhead_time: i64 = core_logs
+            .iter()
+            .map(|core_log| core_log["total_overhead_time"].as_i64().unwrap())
+            .sum();
+        let total_proc_time: i64 = core_logs
+            .iter()
+            .map(|core_log| core_log["total_proc_time"].as_i64().unwrap())
+            .sum();
+        assert_eq!(total_overhead_time, 8);
+        assert_eq!(total_proc_time, 114);
+
+        remove_file(file_path).unwrap();
+    }
 }
@@ -1,12 +1,24 @@
-use crate::dag_set_scheduler::{DAGSetSchedulerBase, NodeDataWrapper};
+use crate::dag_set_scheduler::{DAGSetSchedulerBase, DeadlineMissPolicy, NodeDataWrapper};
 use crate::getset_dag_set_scheduler;
 use crate::{
-    graph_extension::NodeData, homogeneous::HomogeneousProcessor, log::DAGSetSchedulerLog,
+    admission_control::OnlineAdmission,
+    aperiodic_arrival::AperiodicArrivalTrace,
+    event_script::EventScript,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    interconnect::Interconnect,
+    log::DAGSetSchedulerLog,
+    logical_execution_time::CommunicationSemantics,
+    mode_change::ModeSchedule,
+    network::Network,
     processor::ProcessorBase,
+    simulation_horizon::SimulationHorizon,
+    sporadic_release::{ArrivalTrace, ReleaseJitter},
 };
 use petgraph::graph::Graph;
 use std::cmp::Ordering;
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for NodeDataWrapper {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         // Compare by absolute_deadline or int_scaled_absolute_deadline.
@@ -23,10 +35,20 @@ impl PartialOrd for NodeDataWrapper {
             comparison_metric = "int_scaled_node_absolute_deadline"; // decomposition-based algorithm
         }
 
+        // The nodes of the same deadline are ordered by their fixed priorities if given, e.g., by the CPC model,
+        // and the nodes without one follow.
+        let get_priority = |node_data: &NodeData| {
+            node_data
+                .params
+                .get("priority")
+                .copied()
+                .unwrap_or(i64::MAX)
+        };
         match self
             .node_data
             .get_params_value(comparison_metric)
             .cmp(&other.node_data.get_params_value(comparison_metric))
+            .then_with(|| get_priority(&self.node_data).cmp(&get_priority(&other.node_data)))
         {
             // If the keys are equal, compare by id
             Ordering::Equal => match self.node_data.id.partial_cmp(&other.node_data.id) {
@@ -44,40 +66,82 @@ impl PartialOrd for NodeDataWrapper {
 }
 
 pub struct GlobalEDFScheduler {
-    dag_set: Vec<Graph<NodeData, i32>>,
+    dag_set: Vec<Graph<NodeData, i64>>,
     processor: HomogeneousProcessor,
     log: DAGSetSchedulerLog,
-    current_time: i32,
+    current_time: i64,
+    event_script: EventScript,
+    interconnect: Option<Interconnect>,
+    network: Option<Network>,
+    arrival_trace: Option<ArrivalTrace>,
+    aperiodic_arrival_trace: Option<AperiodicArrivalTrace>,
+    release_jitter: ReleaseJitter,
+    deadline_miss_policy: DeadlineMissPolicy,
+    simulation_horizon: SimulationHorizon,
+    mode_schedule: Option<ModeSchedule>,
+    online_admission: Option<OnlineAdmission>,
+    communication_semantics: CommunicationSemantics,
 }
 
 impl DAGSetSchedulerBase<HomogeneousProcessor> for GlobalEDFScheduler {
-    fn new(dag_set: &[Graph<NodeData, i32>], processor: &HomogeneousProcessor) -> Self {
+    fn new(dag_set: &[Graph<NodeData, i64>], processor: &HomogeneousProcessor) -> Self {
         Self {
             dag_set: dag_set.to_vec(),
             processor: processor.clone(),
             log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
             current_time: 0,
+            event_script: EventScript::default(),
+            interconnect: None,
+            network: None,
+            arrival_trace: None,
+            aperiodic_arrival_trace: None,
+            release_jitter: ReleaseJitter::default(),
+            deadline_miss_policy: DeadlineMissPolicy::default(),
+            simulation_horizon: SimulationHorizon::default(),
+            mode_schedule: None,
+            online_admission: None,
+            communication_semantics: CommunicationSemantics::default(),
         }
     }
 
     getset_dag_set_scheduler!(HomogeneousProcessor);
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "file_io"))]
 mod tests {
     use super::*;
     use crate::graph_extension::GraphExtension;
-    use crate::{dag_set_scheduler::PreemptiveType, util::load_yaml};
-    use std::{collections::BTreeMap, fs::remove_file};
+    use crate::network::Network;
+    use crate::{
+        admission_control::{AdmissionTest, DAGArrival},
+        aperiodic_arrival::Arrival,
+        cause_effect_chain::load_cause_effect_chains,
+        dag_set_scheduler::{
+            DAGStateManager, DAGStateManagerBase, PreemptiveType, ScheduleOutcome,
+        },
+        diagnostics::DiagnosticKind,
+        error::SchedSimError,
+        event_script::ScriptedEvent,
+        log::{dump_dag_set_scheduler_result_to_yaml, CoreInterval},
+        mode_change::ModeChangeRequest,
+        simulation_horizon::{HorizonEnd, SimulationHorizon},
+        util::load_yaml,
+    };
+    use petgraph::graph::NodeIndex;
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        fs::remove_file,
+    };
+    use yaml_rust::Yaml;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
-    fn create_sample_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         // cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 10));
         let c1 = dag.add_node(create_node(1, "execution_time", 20));
@@ -101,8 +165,8 @@ mod tests {
         dag
     }
 
-    fn create_sample_dag2() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag2() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         // cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 10));
         let c1 = dag.add_node(create_node(1, "execution_time", 20));
@@ -123,8 +187,8 @@ mod tests {
         dag
     }
 
-    fn create_sample_dag3() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_sample_dag3() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         // cX is the Xth critical node.
         let c0 = dag.add_node(create_node(0, "execution_time", 5));
         let c1 = dag.add_node(create_node(1, "execution_time", 5));
@@ -148,6 +212,49 @@ mod tests {
         dag
     }
 
+    /// The volume of DAG 0 exceeds its end-to-end deadline on a single core, so every job misses it.
+    fn create_overloaded_dag_set() -> Vec<Graph<NodeData, i64>> {
+        let mut dag = create_sample_dag();
+        dag.update_param(NodeIndex::new(0), "period", 60);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut dag2 = Graph::<NodeData, i64>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 1));
+        dag2.add_param(n0, "period", 300);
+        dag2.add_param(n0, "end_to_end_deadline", 300);
+        dag2.set_dag_param("dag_id", 1);
+
+        vec![dag, dag2]
+    }
+
+    fn schedule_with_deadline_miss_policy(
+        deadline_miss_policy: DeadlineMissPolicy,
+        alg_name: &str,
+    ) -> (i64, Vec<Yaml>) {
+        let dag_set = create_overloaded_dag_set();
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_deadline_miss_policy(deadline_miss_policy);
+        let time = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", alg_name);
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        remove_file(file_path).unwrap();
+        (time, yaml_docs)
+    }
+
+    fn get_i64_vec(yaml: &Yaml) -> Vec<i64> {
+        yaml.as_vec()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_i64().unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_global_edf_normal() {
         let mut dag = create_sample_dag();
@@ -159,12 +266,15 @@ mod tests {
         let processor = HomogeneousProcessor::new(4);
 
         let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
-        let time = global_edf_scheduler.schedule(PreemptiveType::NonPreemptive);
+        let time = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
 
         assert_eq!(time, 300);
 
         let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_normal_test");
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         // Check the value of total_utilization
@@ -242,14 +352,17 @@ mod tests {
         let processor = HomogeneousProcessor::new(2);
 
         let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
-        let time = global_edf_scheduler.schedule(PreemptiveType::Preemptive {
-            key: "node_absolute_deadline".to_string(),
-        });
+        let time = global_edf_scheduler
+            .schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            })
+            .unwrap()
+            .schedule_length;
 
         assert_eq!(time, 150);
 
         let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_preemptive_test");
-        let yaml_docs = load_yaml(&file_path);
+        let yaml_docs = load_yaml(&file_path).unwrap();
         let yaml_doc = &yaml_docs[0];
 
         // Check the value of total_utilization
@@ -316,4 +429,1507 @@ mod tests {
 
         remove_file(file_path).unwrap();
     }
+
+    #[test]
+    fn test_global_edf_preemptive_with_crpd() {
+        let mut dag = create_sample_dag();
+        let mut dag3 = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        dag3.set_dag_param("dag_id", 1);
+        dag.add_param(NodeIndex::new(0), "crpd", 3);
+        let dag_set = vec![dag, dag3];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            })
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_crpd_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let node_set_logs = &yaml_docs[0]["node_set_logs"][0];
+
+        // start_time
+        assert!(node_set_logs[0]["crpd_inflation"].is_badvalue());
+        // resume_time
+        assert_eq!(node_set_logs[2]["event_time"].as_str().unwrap(), "10");
+        assert_eq!(node_set_logs[2]["crpd_inflation"].as_i64().unwrap(), 3);
+        // finish_time is delayed by the CRPD.
+        assert_eq!(node_set_logs[3]["event_time"].as_str().unwrap(), "18");
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_preemptive_with_non_preemptive_section() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 10));
+        dag.add_edge(n0, n1, 1);
+        dag.add_param(n0, "period", 60);
+        dag.add_param(n1, "end_to_end_deadline", 60);
+        dag.add_param(n0, "non_preemptive_section", 0);
+        dag.add_param(n1, "non_preemptive_section", 0);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut dag2 = Graph::<NodeData, i64>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 2));
+        dag2.add_param(n0, "period", 15);
+        dag2.add_param(n0, "end_to_end_deadline", 15);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            })
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_section_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // The section runs from 2 to 22 without being preempted by the job released at 15.
+        assert_eq!(
+            get_i64_vec(&yaml_doc["dag_set_log"][0]["finish_time"]),
+            vec![22]
+        );
+        assert_eq!(yaml_doc["node_set_logs"][0].as_vec().unwrap().len(), 4);
+        assert_eq!(
+            get_i64_vec(&yaml_doc["dag_set_log"][1]["finish_time"]),
+            vec![2, 24, 32, 47]
+        );
+        assert_eq!(
+            yaml_doc["dag_set_log"][1]["blocking_time"]
+                .as_i64()
+                .unwrap(),
+            7
+        );
+        assert!(yaml_doc["dag_set_log"][0]["blocking_time"].is_badvalue());
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_preemption_threshold() {
+        let create_single_node_dag =
+            |dag_id: i64, execution_time: i64, offset: i64, priority: i64| {
+                let mut dag = Graph::<NodeData, i64>::new();
+                let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+                dag.add_param(n0, "period", 100);
+                dag.add_param(n0, "end_to_end_deadline", 100);
+                dag.add_param(n0, "offset", offset);
+                dag.add_param(n0, "priority", priority);
+                dag.set_dag_param("dag_id", dag_id);
+                dag
+            };
+        let schedule = |preemptive_type: PreemptiveType, priority: i64| {
+            let mut dag = create_single_node_dag(0, 10, 0, 2);
+            dag.add_param(NodeIndex::new(0), "preemption_threshold", 1);
+            let dag_set = vec![dag, create_single_node_dag(1, 5, 3, priority)];
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1))
+                .schedule(preemptive_type)
+                .unwrap()
+                .response_times
+        };
+        let preemption_threshold = || PreemptiveType::PreemptionThreshold {
+            key: "priority".to_string(),
+            threshold_key: "preemption_threshold".to_string(),
+        };
+
+        // Priority 1 preempts priority 2 only if the threshold is ignored.
+        let preemptive = PreemptiveType::Preemptive {
+            key: "priority".to_string(),
+        };
+        assert_eq!(schedule(preemptive, 1), vec![vec![15], vec![5]]);
+        assert_eq!(
+            schedule(preemption_threshold(), 1),
+            vec![vec![10], vec![12]]
+        );
+        // Priority 0 is above the threshold.
+        assert_eq!(schedule(preemption_threshold(), 0), vec![vec![15], vec![5]]);
+    }
+
+    #[test]
+    fn test_global_edf_with_logical_execution_time() {
+        let create_chain_dag = |logical_execution_time: i64| {
+            let mut dag = Graph::<NodeData, i64>::new();
+            let n0 = dag.add_node(create_node(0, "execution_time", 5));
+            let n1 = dag.add_node(create_node(1, "execution_time", 5));
+            dag.add_edge(n0, n1, 0);
+            dag.add_param(n0, "period", 100);
+            dag.add_param(n1, "end_to_end_deadline", 100);
+            dag.add_param(n0, "logical_execution_time", 10);
+            dag.add_param(n1, "logical_execution_time", logical_execution_time);
+            dag.set_dag_param("dag_id", 0);
+            dag
+        };
+        let schedule = |dag: Graph<NodeData, i64>| {
+            let mut global_edf_scheduler =
+                GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+            global_edf_scheduler
+                .set_communication_semantics(CommunicationSemantics::LogicalExecutionTime);
+            global_edf_scheduler
+                .schedule(PreemptiveType::NonPreemptive)
+                .unwrap()
+        };
+
+        // n0 runs in [0, 5] and publishes at 10, and n1 runs in [10, 15] and publishes at 30.
+        let schedule_outcome = schedule(create_chain_dag(20));
+        assert_eq!(schedule_outcome.response_times, vec![vec![30]]);
+        assert_eq!(schedule_outcome.log.get_let_intervals(0, 0), vec![(0, 10)]);
+        assert_eq!(schedule_outcome.log.get_let_intervals(0, 1), vec![(10, 30)]);
+        let has_overrun = |schedule_outcome: &ScheduleOutcome| {
+            schedule_outcome
+                .diagnostics
+                .iter()
+                .any(|diagnostic| diagnostic.kind == DiagnosticKind::LogicalExecutionTimeOverrun)
+        };
+        assert!(!has_overrun(&schedule_outcome));
+
+        // n1 overruns its LET interval [10, 13] and publishes on finish.
+        let schedule_outcome = schedule(create_chain_dag(3));
+        assert_eq!(schedule_outcome.response_times, vec![vec![15]]);
+        assert!(has_overrun(&schedule_outcome));
+    }
+
+    #[test]
+    fn test_global_edf_max_parallelism() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let parallel_nodes: Vec<NodeIndex> = (1..4)
+            .map(|node_id| dag.add_node(create_node(node_id, "execution_time", 10)))
+            .collect();
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        for node_i in parallel_nodes {
+            dag.add_edge(n0, node_i, 0);
+            dag.add_edge(node_i, n4, 0);
+        }
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n0, "end_to_end_deadline", 100);
+        dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("max_parallelism", 2);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(4));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        // Two of the three parallel nodes run in [1, 11], and the last one waits for them until 11.
+        assert_eq!(schedule_outcome.response_times, vec![vec![22]]);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_max_parallelism_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        assert_eq!(
+            yaml_docs[0]["dag_set_log"][0]["parallelism_capped_time"]
+                .as_i64()
+                .unwrap(),
+            10
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_max_parallelism_not_dag_param() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        dag.add_param(NodeIndex::new(0), "max_parallelism", 2);
+        assert!(matches!(
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(4))
+                .schedule(PreemptiveType::NonPreemptive),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_global_edf_with_event_script() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_event_script(EventScript::new(vec![
+            ScriptedEvent::CoreFailure {
+                time: 5,
+                core_id: 0,
+            },
+            ScriptedEvent::WcetOverrun {
+                dag_id: 0,
+                node_id: 1,
+                job_id: 0,
+                execution_time: 30,
+            },
+            ScriptedEvent::AperiodicJob {
+                time: 210,
+                dag_id: 1,
+            },
+        ]));
+        let time = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
+
+        assert_eq!(time, 300);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_event_script_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // The job lost by the core failure is released again on another core.
+        let node_set_logs = &yaml_doc["node_set_logs"][0];
+        assert_eq!(node_set_logs[0]["core_id"].as_i64().unwrap(), 0);
+        assert_eq!(node_set_logs[1]["core_id"].as_i64().unwrap(), 0);
+        assert_eq!(node_set_logs[1]["event_time"].as_str().unwrap(), "5");
+        assert_eq!(node_set_logs[2]["core_id"].as_i64().unwrap(), 2);
+        assert_eq!(node_set_logs[2]["event_time"].as_str().unwrap(), "5");
+
+        // The first job of DAG 0 is delayed by the failure and the WCET overrun.
+        let dag_set_log = &yaml_doc["dag_set_log"];
+        assert_eq!(dag_set_log[0]["finish_time"][0].as_i64().unwrap(), 65);
+        assert_eq!(dag_set_log[0]["finish_time"][1].as_i64().unwrap(), 200);
+
+        // The aperiodic job is postponed until the running job of DAG 1 is completed,
+        // and its response time counts from the scripted time.
+        let release_time = &dag_set_log[1]["release_time"];
+        assert_eq!(release_time[2].as_i64().unwrap(), 200);
+        assert_eq!(release_time[3].as_i64().unwrap(), 210);
+        assert_eq!(dag_set_log[1]["finish_time"][3].as_i64().unwrap(), 300);
+        assert_eq!(dag_set_log[1]["response_time"][3].as_i64().unwrap(), 90);
+
+        // No node is processed on the failed core after the failure.
+        let core_logs = &yaml_doc["processor_log"]["core_logs"][0];
+        assert_eq!(core_logs["total_proc_time"].as_i64().unwrap(), 5);
+
+        // Another core is idle at the failure, so the lost job is recovered immediately.
+        let core_failure_log = &yaml_doc["core_failure_logs"][0];
+        assert_eq!(core_failure_log["failure_time"].as_i64().unwrap(), 5);
+        assert_eq!(core_failure_log["lost_job"]["node_id"].as_i64().unwrap(), 0);
+        assert_eq!(core_failure_log["recovery_latency"].as_i64().unwrap(), 0);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_overrun_of_aperiodic_job() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_event_script(EventScript::new(vec![
+            ScriptedEvent::AperiodicJob {
+                time: 210,
+                dag_id: 1,
+            },
+            ScriptedEvent::WcetOverrun {
+                dag_id: 1,
+                node_id: 0,
+                job_id: 3,
+                execution_time: 30,
+            },
+        ]));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_aperiodic_overrun_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+
+        // The aperiodic job is postponed to 250, and its source executes for the overrun execution time.
+        let node_set_logs = &yaml_docs[0]["node_set_logs"][1];
+        assert_eq!(node_set_logs[24]["job_id"].as_i64().unwrap(), 3);
+        assert_eq!(node_set_logs[24]["node_id"].as_i64().unwrap(), 0);
+        assert_eq!(node_set_logs[24]["event_time"].as_str().unwrap(), "250");
+        assert_eq!(node_set_logs[25]["event_time"].as_str().unwrap(), "280");
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_core_timeline() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        global_edf_scheduler.get_log_mut().enable_core_timeline();
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        assert_eq!(schedule_outcome.schedule_length, 10);
+        assert_eq!(
+            schedule_outcome.log.get_core_timeline(1).unwrap(),
+            &[
+                CoreInterval::Idle {
+                    start_time: 0,
+                    end_time: 3
+                },
+                CoreInterval::Busy {
+                    start_time: 3,
+                    end_time: 5,
+                    dag_id: 0,
+                    node_id: 2,
+                    job_id: 0
+                },
+                CoreInterval::Idle {
+                    start_time: 5,
+                    end_time: 10
+                },
+            ]
+        );
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_core_timeline_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let core_logs = &yaml_docs[0]["processor_log"]["core_logs"];
+        assert_eq!(core_logs[0]["timeline"].as_vec().unwrap().len(), 3);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_core_dag_time_matrix() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 4));
+        let n2 = dag.add_node(create_node(2, "execution_time", 2));
+        dag.add_param(n0, "period", 10);
+        dag.add_param(n1, "end_to_end_deadline", 10);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.set_dag_param("dag_id", 0);
+        let mut dag2 = Graph::<NodeData, i64>::new();
+        let n0 = dag2.add_node(create_node(0, "execution_time", 5));
+        dag2.add_param(n0, "period", 10);
+        dag2.add_param(n0, "end_to_end_deadline", 10);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        // DAG 0 is spread over both cores, and DAG 1 runs on core 1 only.
+        assert_eq!(
+            schedule_outcome.log.get_core_dag_time_matrix(),
+            &[vec![7, 0], vec![2, 5]]
+        );
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_core_dag_time_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let core_dag_time_matrix = &yaml_docs[0]["processor_log"]["core_dag_time_matrix"];
+        assert_eq!(get_i64_vec(&core_dag_time_matrix[1]), vec![2, 5]);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_injected_core_failure() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.inject_core_failure(15, 1);
+        global_edf_scheduler.inject_core_failure(120, 0);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_core_failure_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        let core_failure_logs = &yaml_doc["core_failure_logs"];
+        assert_eq!(core_failure_logs[0]["core_id"].as_i64().unwrap(), 1);
+        assert_eq!(core_failure_logs[0]["failure_time"].as_i64().unwrap(), 15);
+        assert_eq!(
+            core_failure_logs[0]["lost_job"]["node_id"]
+                .as_i64()
+                .unwrap(),
+            3
+        );
+        // The lost job waits until core 0 becomes idle.
+        assert_eq!(
+            core_failure_logs[0]["recovery_latency"].as_i64().unwrap(),
+            15
+        );
+        // No job is running on core 0 at its failure, so there is nothing to recover.
+        assert!(core_failure_logs[1]["lost_job"].is_null());
+        assert!(core_failure_logs[1]["recovery_latency"].is_null());
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_dispatch_queued_nodes() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(2));
+        let mut manager = DAGStateManager::default();
+        manager.set_release_count(1);
+
+        let processor = global_edf_scheduler.get_processor_mut();
+        processor.fail_core(1);
+        processor.enqueue_to_core(0, &dag_set[0][NodeIndex::new(0)]);
+        processor.enqueue_to_core(1, &dag_set[0][NodeIndex::new(1)]);
+
+        // The node queued to the failed core is not allocated, and stays queued.
+        assert_eq!(
+            global_edf_scheduler.dispatch_queued_nodes(&[manager]),
+            vec![(0, dag_set[0][NodeIndex::new(0)].clone())]
+        );
+        let processor = global_edf_scheduler.get_processor();
+        assert_eq!(processor.get_processing_node(0).unwrap().id, 0);
+        assert_eq!(processor.get_queued_node_num(0), 0);
+        assert_eq!(processor.get_queued_node_num(1), 1);
+    }
+
+    #[test]
+    fn test_global_edf_with_interconnect() {
+        let mut dag = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(3);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_interconnect(Interconnect::new(1));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_interconnect_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+        // Node 1 runs on the same core as node 0, so only the edges to nodes 3 and 4 are transferred.
+        // The two transfers contend for the bus and are serialized.
+        let transfer_logs = &yaml_doc["transfer_logs"];
+        assert_eq!(transfer_logs[0]["to_node_id"].as_i64().unwrap(), 3);
+        assert_eq!(transfer_logs[0]["start_time"].as_i64().unwrap(), 5);
+        assert_eq!(transfer_logs[1]["to_node_id"].as_i64().unwrap(), 4);
+        assert_eq!(transfer_logs[1]["start_time"].as_i64().unwrap(), 6);
+        assert_eq!(transfer_logs[1]["finish_time"].as_i64().unwrap(), 7);
+        // Node 2 waits for the data from nodes 3 and 4 on the other cores.
+        assert_eq!(transfer_logs[3]["to_node_id"].as_i64().unwrap(), 2);
+        assert_eq!(transfer_logs[3]["finish_time"].as_i64().unwrap(), 14);
+        assert_eq!(transfer_logs.as_vec().unwrap().len(), 4);
+
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            19
+        );
+        let core_logs = &yaml_doc["processor_log"]["core_logs"];
+        assert_eq!(core_logs[0]["total_overhead_time"].as_i64().unwrap(), 2);
+        assert_eq!(core_logs[2]["total_overhead_time"].as_i64().unwrap(), 2);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_network() {
+        let mut dag = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        dag.add_param(NodeIndex::new(2), "processor_id", 1);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(3);
+        let mut network = Network::new(&[2, 1]);
+        network.add_link(0, 1, 2, 1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_network(network);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_network_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+        // Node 4 is allocated to core 2 on processor 1, and its input data from node 0 goes over the link.
+        // The data within processor 0 is not transferred because no interconnect is modeled.
+        let transfer_logs = &yaml_doc["transfer_logs"];
+        assert_eq!(transfer_logs[0]["to_node_id"].as_i64().unwrap(), 4);
+        assert_eq!(transfer_logs[0]["start_time"].as_i64().unwrap(), 5);
+        assert_eq!(transfer_logs[0]["finish_time"].as_i64().unwrap(), 8);
+        // Node 2 is assigned to processor 1, and the data from nodes 1 and 3 is serialized on the link.
+        assert_eq!(transfer_logs[1]["from_node_id"].as_i64().unwrap(), 3);
+        assert_eq!(transfer_logs[2]["from_node_id"].as_i64().unwrap(), 1);
+        assert_eq!(transfer_logs[2]["finish_time"].as_i64().unwrap(), 19);
+        assert_eq!(transfer_logs.as_vec().unwrap().len(), 3);
+        assert_eq!(
+            yaml_doc["node_set_logs"][0][8]["core_id"].as_i64().unwrap(),
+            2
+        );
+
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            24
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_soft_deadlines() {
+        let mut dag = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        dag.add_param(NodeIndex::new(1), "tardiness_weight", 3);
+        dag.add_param(NodeIndex::new(2), "soft_deadline", 20);
+        dag.add_param(NodeIndex::new(3), "soft_deadline", 10);
+        dag.add_param(NodeIndex::new(3), "tardiness_weight", 2);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_soft_deadline_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+        let dag_set_log = &yaml_doc["dag_set_log"][0];
+        // The DAG finishes at 25 against the end-to-end deadline of 15, weighted by the deadline node.
+        let tardiness_stats = &dag_set_log["tardiness_stats"];
+        assert_eq!(tardiness_stats["total_tardiness"].as_i64().unwrap(), 10);
+        assert_eq!(tardiness_stats["max_tardiness"].as_i64().unwrap(), 10);
+        assert_eq!(tardiness_stats["weighted_tardiness"].as_i64().unwrap(), 30);
+        // Node 3 finishes at 15 and node 2 at 25.
+        let node_tardiness_stats = &dag_set_log["node_tardiness_stats"];
+        assert_eq!(node_tardiness_stats.as_hash().unwrap().len(), 2);
+        assert_eq!(
+            node_tardiness_stats[2]["total_tardiness"].as_i64().unwrap(),
+            5
+        );
+        assert_eq!(
+            node_tardiness_stats[3]["max_tardiness"].as_i64().unwrap(),
+            5
+        );
+        assert_eq!(
+            node_tardiness_stats[3]["weighted_tardiness"]
+                .as_i64()
+                .unwrap(),
+            10
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_execution_time_seed() {
+        let mut dag = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("bcet", 1);
+        let dag_set = vec![dag];
+
+        let mut processor = HomogeneousProcessor::new(2);
+        processor.set_execution_time_seed(7);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        let mut same_seed_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        same_seed_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_seed_test");
+        let same_seed_file_path =
+            same_seed_scheduler.dump_log("../lib/tests", "edf_same_seed_test");
+        let yaml_doc = &load_yaml(&file_path).unwrap()[0];
+        let same_seed_yaml_doc = &load_yaml(&same_seed_file_path).unwrap()[0];
+
+        assert_eq!(
+            yaml_doc["processor_info"]["execution_time_seed"]
+                .as_i64()
+                .unwrap(),
+            7
+        );
+        assert_eq!(
+            yaml_doc["node_set_logs"],
+            same_seed_yaml_doc["node_set_logs"]
+        );
+        // The drawn execution times never exceed the WCET, so the DAG finishes no later than 15.
+        assert!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap()
+                <= 15
+        );
+
+        remove_file(file_path).unwrap();
+        remove_file(same_seed_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_arrival_trace() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_arrival_trace(ArrivalTrace::new(BTreeMap::from([
+            (0, vec![0, 170]),
+            (1, vec![5, 130, 240]),
+        ])));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_arrival_trace_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let dag_set_log = &yaml_docs[0]["dag_set_log"];
+
+        let release_times: Vec<Vec<i64>> = (0..2)
+            .map(|dag_id| {
+                dag_set_log[dag_id]["release_time"]
+                    .as_vec()
+                    .unwrap()
+                    .iter()
+                    .map(|release_time| release_time.as_i64().unwrap())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(release_times, vec![vec![0, 170], vec![5, 130, 240]]);
+        assert_eq!(dag_set_log[0]["finish_time"][1].as_i64().unwrap(), 220);
+        assert_eq!(dag_set_log[1]["finish_time"][2].as_i64().unwrap(), 290);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_global_edf_with_arrival_trace_shorter_than_period() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .set_arrival_trace(ArrivalTrace::new(BTreeMap::from([(0, vec![0, 100])])));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_release_jitter() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        dag.add_param(NodeIndex::new(0), "jitter", 10);
+        dag2.add_param(NodeIndex::new(0), "jitter", 10);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_release_jitter(ReleaseJitter::from_trace(BTreeMap::from([
+            (0, vec![7, 3]),
+            (1, vec![0, 4, 9]),
+        ])));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_release_jitter_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let dag_set_log = &yaml_docs[0]["dag_set_log"];
+
+        let release_times: Vec<Vec<i64>> = (0..2)
+            .map(|dag_id| {
+                dag_set_log[dag_id]["release_time"]
+                    .as_vec()
+                    .unwrap()
+                    .iter()
+                    .map(|release_time| release_time.as_i64().unwrap())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(release_times, vec![vec![7, 153], vec![0, 104, 209]]);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_release_jitter_seeded() {
+        let mut dag = create_sample_dag();
+        dag.set_dag_param("dag_id", 0);
+        dag.add_param(NodeIndex::new(0), "jitter", 20);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_release_jitter(ReleaseJitter::from_seed(3));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        let mut expected_release_jitter = ReleaseJitter::from_seed(3);
+
+        let file_path =
+            global_edf_scheduler.dump_log("../lib/tests", "edf_release_jitter_seeded_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let release_times = &yaml_docs[0]["dag_set_log"][0]["release_time"];
+        assert_eq!(
+            release_times[0].as_i64().unwrap(),
+            expected_release_jitter.get_delay(0, 0, 20)
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_deadline_miss_continue() {
+        let (time, yaml_docs) = schedule_with_deadline_miss_policy(
+            DeadlineMissPolicy::Continue,
+            "edf_deadline_miss_continue_test",
+        );
+        let yaml_doc = &yaml_docs[0];
+
+        assert_eq!(time, 300);
+        let deadline_miss_logs = yaml_doc["deadline_miss_logs"].as_vec().unwrap();
+        assert_eq!(deadline_miss_logs.len(), 1);
+        assert_eq!(deadline_miss_logs[0]["dag_id"].as_i64().unwrap(), 0);
+        assert_eq!(deadline_miss_logs[0]["job_id"].as_i64().unwrap(), 0);
+        assert_eq!(deadline_miss_logs[0]["miss_time"].as_i64().unwrap(), 50);
+        assert_eq!(
+            deadline_miss_logs[0]["policy"].as_str().unwrap(),
+            "Continue"
+        );
+        // The late job finishes.
+        assert_eq!(
+            get_i64_vec(&yaml_doc["dag_set_log"][0]["finish_time"]),
+            vec![70]
+        );
+        let deadline_misses = yaml_doc["deadline_misses"].as_vec().unwrap();
+        assert_eq!(deadline_misses.len(), 1);
+        assert_eq!(
+            deadline_misses[0]["absolute_deadline"].as_i64().unwrap(),
+            50
+        );
+        assert_eq!(deadline_misses[0]["finish_time"].as_i64().unwrap(), 70);
+        assert_eq!(deadline_misses[0]["tardiness"].as_i64().unwrap(), 20);
+        let deadline_miss_summary = &yaml_doc["deadline_miss_summary"];
+        assert_eq!(deadline_miss_summary["total_misses"].as_i64().unwrap(), 1);
+        assert_eq!(deadline_miss_summary["miss_ratio"].as_f64().unwrap(), 0.5);
+    }
+
+    #[test]
+    fn test_global_edf_schedule_outcome() {
+        let dag_set = create_overloaded_dag_set();
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        assert_eq!(schedule_outcome.schedule_length, 300);
+        // The DAGs have no offset, which is collected instead of being warned.
+        assert!(schedule_outcome
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.kind == DiagnosticKind::MissingOffset));
+        assert!(schedule_outcome.has_deadline_miss());
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![true, false]);
+        assert_eq!(schedule_outcome.response_times[0], vec![70]);
+        assert_eq!(
+            schedule_outcome.worst_response_times,
+            schedule_outcome.log.get_worst_response_times()
+        );
+    }
+
+    #[test]
+    fn test_global_edf_log_from_yaml_file() {
+        let dag_set = create_overloaded_dag_set();
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(1));
+        global_edf_scheduler.set_deadline_miss_policy(DeadlineMissPolicy::KillJob);
+        global_edf_scheduler.get_log_mut().enable_core_timeline();
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_reader_test");
+        dump_dag_set_scheduler_result_to_yaml(&file_path, false);
+
+        // The result appended by the binaries is ignored.
+        let log = DAGSetSchedulerLog::from_yaml_file(&file_path).unwrap();
+        assert_eq!(log.get_response_times(), schedule_outcome.response_times);
+        assert_eq!(
+            log.get_deadline_misses(),
+            schedule_outcome.log.get_deadline_misses()
+        );
+        assert_eq!(
+            log.get_core_timeline(0),
+            schedule_outcome.log.get_core_timeline(0)
+        );
+        assert_eq!(
+            log.get_execution_slices(),
+            schedule_outcome.log.get_execution_slices()
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_deadline_miss_abort() {
+        let (time, yaml_docs) = schedule_with_deadline_miss_policy(
+            DeadlineMissPolicy::Abort,
+            "edf_deadline_miss_abort_test",
+        );
+        let yaml_doc = &yaml_docs[0];
+
+        assert_eq!(time, 50);
+        assert_eq!(yaml_doc["deadline_miss_logs"].as_vec().unwrap().len(), 1);
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["worst_response_time"]
+                .as_i64()
+                .unwrap(),
+            i64::MAX
+        );
+    }
+
+    #[test]
+    fn test_global_edf_deadline_miss_kill_job() {
+        let (time, yaml_docs) = schedule_with_deadline_miss_policy(
+            DeadlineMissPolicy::KillJob,
+            "edf_deadline_miss_kill_job_test",
+        );
+        let yaml_doc = &yaml_docs[0];
+        let dag_set_log = &yaml_doc["dag_set_log"];
+
+        assert_eq!(time, 300);
+        // Each job is killed at its deadline, so the next job is released on time.
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["release_time"]),
+            vec![0, 60, 120, 180, 240]
+        );
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["finish_time"]),
+            vec![i64::MAX; 5]
+        );
+        let miss_times: Vec<i64> = yaml_doc["deadline_miss_logs"]
+            .as_vec()
+            .unwrap()
+            .iter()
+            .map(|deadline_miss_log| deadline_miss_log["miss_time"].as_i64().unwrap())
+            .collect();
+        assert_eq!(miss_times, vec![50, 110, 170, 230, 290]);
+        // The killed jobs never finish, so their tardiness is unknown.
+        let deadline_misses = yaml_doc["deadline_misses"].as_vec().unwrap();
+        assert_eq!(deadline_misses.len(), 5);
+        assert!(deadline_misses[0]["finish_time"].is_null());
+        assert!(deadline_misses[0]["tardiness"].is_null());
+        // DAG 1 runs after the nodes of the first job of DAG 0 are discarded.
+        assert_eq!(get_i64_vec(&dag_set_log[1]["finish_time"]), vec![51]);
+    }
+
+    #[test]
+    fn test_global_edf_deadline_miss_skip_next_release() {
+        let (time, yaml_docs) = schedule_with_deadline_miss_policy(
+            DeadlineMissPolicy::SkipNextRelease,
+            "edf_deadline_miss_skip_next_release_test",
+        );
+        let yaml_doc = &yaml_docs[0];
+        let dag_set_log = &yaml_doc["dag_set_log"];
+
+        assert_eq!(time, 300);
+        // The arrivals at 60 and 180 are skipped.
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["release_time"]),
+            vec![0, 120, 240]
+        );
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["finish_time"]),
+            vec![70, 190, i64::MAX]
+        );
+        assert_eq!(yaml_doc["deadline_miss_logs"].as_vec().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_global_edf_deadline_miss_per_sink() {
+        // The sink chains n0 -> n1 and n0 -> n2 have different deadlines, and only n1 can miss.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 10));
+        let n2 = dag.add_node(create_node(2, "execution_time", 10));
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n1, "end_to_end_deadline", 15);
+        dag.add_param(n2, "end_to_end_deadline", 50);
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.set_dag_param("dag_id", 0);
+        assert_eq!(dag.get_deadlines_per_sink(), vec![(n1, 15), (n2, 50)]);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![true]);
+        let deadline_misses = schedule_outcome.log.get_deadline_misses();
+        assert_eq!(deadline_misses.len(), 1);
+        assert_eq!(deadline_misses[0].sink_node_id, Some(1));
+        assert_eq!(deadline_misses[0].absolute_deadline, 15);
+        assert!(schedule_outcome.log.has_deadline_miss_log(0, 0));
+    }
+
+    #[test]
+    fn test_global_edf_job_finished_by_last_sink() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 10));
+        let n2 = dag.add_node(create_node(2, "execution_time", 30));
+        let n3 = dag.add_node(create_node(3, "execution_time", 5));
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n1, "end_to_end_deadline", 100);
+        dag.add_param(n3, "end_to_end_deadline", 100);
+        dag.add_edge(n0, n1, 0);
+        dag.add_edge(n0, n2, 0);
+        dag.add_edge(n2, n3, 0);
+        dag.add_edge(n1, n3, 0);
+        let n4 = dag.add_node(create_node(4, "execution_time", 5));
+        dag.add_edge(n1, n4, 0);
+        dag.add_param(n4, "end_to_end_deadline", 100);
+        dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        // The job finishes when the last of the sinks n3 and n4 finishes.
+        assert_eq!(schedule_outcome.worst_response_times, vec![60]);
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![false]);
+    }
+
+    #[test]
+    fn test_deadline_miss_policy_from_name() {
+        assert_eq!(
+            DeadlineMissPolicy::from_name("skip_next_release").unwrap(),
+            DeadlineMissPolicy::SkipNextRelease
+        );
+    }
+
+    #[test]
+    fn test_global_edf_dump_log_to_json() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_json_test");
+        let json_file_path = file_path.replace(".yaml", ".json");
+        global_edf_scheduler
+            .get_log_mut()
+            .dump_log_to_json(&json_file_path);
+        let yaml_doc = &load_yaml(&file_path).unwrap()[0];
+        let json_value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_file_path).unwrap()).unwrap();
+
+        assert_eq!(
+            json_value["dag_set_log"][1]["release_time"],
+            serde_json::json!(get_i64_vec(&yaml_doc["dag_set_log"][1]["release_time"]))
+        );
+        assert_eq!(
+            json_value["node_set_logs"][0][0]["event_time"],
+            serde_json::json!({ "StartTime": 0 })
+        );
+
+        remove_file(file_path).unwrap();
+        remove_file(json_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_dump_job_logs_to_csv() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_csv_test");
+        let csv_file_path = file_path.replace(".yaml", ".csv");
+        global_edf_scheduler
+            .get_log_mut()
+            .dump_job_logs_to_csv(&csv_file_path);
+        let yaml_doc = &load_yaml(&file_path).unwrap()[0];
+        let csv = std::fs::read_to_string(&csv_file_path).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows[0], "dag_id,node_id,job_id,core,event,time");
+        let num_job_events: usize = yaml_doc["node_set_logs"]
+            .as_vec()
+            .unwrap()
+            .iter()
+            .map(|node_logs| node_logs.as_vec().unwrap().len())
+            .sum();
+        assert_eq!(rows.len(), num_job_events + 1);
+        assert_eq!(rows[1], "0,0,0,0,start,0");
+
+        remove_file(file_path).unwrap();
+        remove_file(csv_file_path).unwrap();
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_global_edf_dump_to_parquet() {
+        use arrow::array::{AsArray, RecordBatch};
+        use arrow::datatypes::{Int64Type, UInt64Type};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let read_parquet = |file_path: &str| -> RecordBatch {
+            let file = std::fs::File::open(file_path).unwrap();
+            let mut reader = ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap();
+            let record_batch = reader.next().unwrap().unwrap();
+            remove_file(file_path).unwrap();
+            record_batch
+        };
+
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+        let processor = HomogeneousProcessor::new(4);
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        let log = global_edf_scheduler.get_log_mut();
+
+        log.dump_job_logs_to_parquet("../lib/tests/edf_jobs_parquet_test.parquet")
+            .unwrap();
+        let job_logs = read_parquet("../lib/tests/edf_jobs_parquet_test.parquet");
+        assert_eq!(
+            job_logs.num_rows(),
+            // Each job starts and finishes without preemption.
+            2 * log.get_execution_slices().len()
+        );
+        assert_eq!(job_logs.schema().field(4).name(), "event");
+        assert_eq!(job_logs.column(4).as_string::<i32>().value(0), "start");
+        assert_eq!(job_logs.column(5).as_primitive::<Int64Type>().value(0), 0);
+
+        log.dump_dag_summaries_to_parquet("../lib/tests/edf_dags_parquet_test.parquet")
+            .unwrap();
+        let dag_summaries = read_parquet("../lib/tests/edf_dags_parquet_test.parquet");
+        assert_eq!(dag_summaries.num_rows(), 2);
+        let worst_response_times = dag_summaries
+            .column_by_name("worst_response_time")
+            .unwrap()
+            .as_primitive::<Int64Type>();
+        assert_eq!(
+            worst_response_times.value(1),
+            log.get_worst_response_times()[1]
+        );
+        assert_eq!(
+            dag_summaries
+                .column_by_name("number_of_deadline_misses")
+                .unwrap()
+                .as_primitive::<UInt64Type>()
+                .value(0),
+            0
+        );
+    }
+
+    #[test]
+    fn test_global_edf_dump_chrome_trace() {
+        let mut dag = create_sample_dag();
+        let mut dag3 = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        dag3.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag3];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            })
+            .unwrap();
+
+        let log = global_edf_scheduler.get_log_mut();
+        let execution_slices = log.get_execution_slices();
+        // Node 0 of DAG 0 is preempted at 5 and resumed at 10.
+        let node0_slices: Vec<(usize, i64, i64)> = execution_slices
+            .iter()
+            .filter(|slice| slice.dag_id == 0 && slice.node_id == 0 && slice.job_id == 0)
+            .map(|slice| (slice.core_id, slice.start_time, slice.end_time))
+            .collect();
+        assert_eq!(node0_slices.len(), 2);
+        assert_eq!((node0_slices[0].1, node0_slices[0].2), (0, 5));
+        assert_eq!((node0_slices[1].1, node0_slices[1].2), (10, 15));
+
+        let file_path = "../lib/tests/edf_chrome_trace_test.json";
+        log.dump_chrome_trace(file_path);
+        let json_value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(file_path).unwrap()).unwrap();
+        let trace_events = json_value["traceEvents"].as_array().unwrap();
+
+        assert_eq!(trace_events.len(), 2 + execution_slices.len());
+        assert_eq!(trace_events[0]["ph"], "M");
+        assert_eq!(trace_events[1]["args"]["name"], "Core 1");
+        assert_eq!(trace_events[2]["ph"], "X");
+        assert_eq!(trace_events[2]["ts"], 0);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_cause_effect_chain() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let chain = &load_cause_effect_chains("tests/sample_chains/two_dag_chain.yaml")[0];
+        chain.validate(&dag_set);
+        let (max_data_age, max_reaction_time) = chain.calculate_latencies(&schedule_outcome.log);
+        // Node 1 of DAG 0 runs in [10, 30] and [160, 180], and node 0 of DAG 1 runs every 100 for 10.
+        // The job of DAG 1 at 100 reads the data of the job at 10, and the event after 10 is output at 210.
+        assert_eq!(max_data_age, Some(100));
+        assert_eq!(max_reaction_time, Some(200));
+        global_edf_scheduler.get_log_mut().write_chain_latency(
+            &chain.name,
+            max_data_age,
+            max_reaction_time,
+        );
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_chain_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let chain_latency_log = &yaml_docs[0]["chain_latency_logs"][0];
+        assert_eq!(
+            chain_latency_log["name"].as_str().unwrap(),
+            "sensor_to_actuator"
+        );
+        assert_eq!(chain_latency_log["max_data_age"].as_i64().unwrap(), 100);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_aperiodic_arrivals() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_aperiodic_arrival_trace(AperiodicArrivalTrace::new(&[
+            Arrival {
+                dag_id: 0,
+                arrival_time: 0,
+            },
+            Arrival {
+                dag_id: 0,
+                arrival_time: 10,
+            },
+            Arrival {
+                dag_id: 0,
+                arrival_time: 200,
+            },
+        ]));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_aperiodic_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // The instance arrived at 10 waits for the previous instance to finish.
+        let dag_set_log = &yaml_doc["dag_set_log"];
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["arrival_time"]),
+            vec![0, 10, 200]
+        );
+        assert_eq!(
+            get_i64_vec(&dag_set_log[0]["release_time"]),
+            vec![0, 50, 200]
+        );
+        assert_eq!(
+            get_i64_vec(&dag_set_log[1]["release_time"]),
+            vec![0, 100, 200]
+        );
+        assert!(dag_set_log[1]["arrival_time"].is_badvalue());
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_mode_change() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler.set_mode_schedule(ModeSchedule::new(
+            vec![BTreeSet::from([0]), BTreeSet::from([1])],
+            0,
+            vec![ModeChangeRequest { time: 160, mode: 1 }],
+        ));
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_mode_change_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // The job of DAG 0 released at 150 completes before the switch.
+        let dag_set_log = &yaml_doc["dag_set_log"];
+        assert_eq!(get_i64_vec(&dag_set_log[0]["release_time"]), vec![0, 150]);
+        assert_eq!(get_i64_vec(&dag_set_log[0]["finish_time"]), vec![50, 200]);
+        // DAG 1 keeps its release phase after the switch.
+        assert_eq!(get_i64_vec(&dag_set_log[1]["release_time"]), vec![200]);
+
+        let mode_change_log = &yaml_doc["mode_change_logs"][0];
+        assert_eq!(mode_change_log["from_mode"].as_i64().unwrap(), 0);
+        assert_eq!(mode_change_log["to_mode"].as_i64().unwrap(), 1);
+        assert_eq!(mode_change_log["request_time"].as_i64().unwrap(), 160);
+        assert_eq!(mode_change_log["switch_time"].as_i64().unwrap(), 200);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_online_admission() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .set_online_admission(OnlineAdmission::new(
+                AdmissionTest::Utilization,
+                vec![DAGArrival {
+                    time: 150,
+                    dag_id: 1,
+                }],
+            ))
+            .unwrap();
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_online_admission_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // DAG 1 is released from its first periodic arrival after the admission.
+        let dag_set_log = &yaml_doc["dag_set_log"];
+        assert_eq!(get_i64_vec(&dag_set_log[0]["release_time"]), vec![0, 150]);
+        assert_eq!(get_i64_vec(&dag_set_log[1]["release_time"]), vec![200]);
+
+        let admission_log = &yaml_doc["admission_logs"][0];
+        assert_eq!(admission_log["dag_id"].as_i64().unwrap(), 1);
+        assert_eq!(admission_log["arrival_time"].as_i64().unwrap(), 150);
+        assert!(admission_log["verdict"]["is_schedulable"]
+            .as_bool()
+            .unwrap());
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_rejected_by_online_admission() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .set_online_admission(OnlineAdmission::new(
+                AdmissionTest::Density,
+                vec![DAGArrival { time: 0, dag_id: 1 }],
+            ))
+            .unwrap();
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        // The critical path of DAG 0 is as long as its deadline, so the density limit is 4 - 3 * 1.
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_admission_reject_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        assert!(yaml_doc["dag_set_log"][1]["release_time"]
+            .as_vec()
+            .unwrap()
+            .is_empty());
+        assert!(!yaml_doc["admission_logs"][0]["verdict"]["is_schedulable"]
+            .as_bool()
+            .unwrap());
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_simulation_horizon() {
+        let mut dag = create_sample_dag();
+        let mut dag2 = create_sample_dag2();
+        dag.set_dag_param("dag_id", 0);
+        dag2.set_dag_param("dag_id", 1);
+        let dag_set = vec![dag, dag2];
+
+        let processor = HomogeneousProcessor::new(4);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .set_simulation_horizon(SimulationHorizon::new(HorizonEnd::HyperPeriods(2), 300));
+        let time = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
+        assert_eq!(time, 600);
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_horizon_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        assert_eq!(yaml_doc["warm_up_time"].as_i64().unwrap(), 300);
+        let dag_set_log = &yaml_doc["dag_set_log"][1];
+        assert_eq!(
+            get_i64_vec(&dag_set_log["release_time"]),
+            vec![0, 100, 200, 300, 400, 500]
+        );
+        // Only the jobs released at or after 300 are counted.
+        let response_times = get_i64_vec(&dag_set_log["response_time"]);
+        assert_eq!(
+            dag_set_log["worst_response_time"].as_i64().unwrap(),
+            *response_times[3..].iter().max().unwrap()
+        );
+        assert_eq!(
+            dag_set_log["average_response_time"].as_f64().unwrap(),
+            response_times[3..].iter().sum::<i64>() as f64 / 3.0
+        );
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_global_edf_with_core_affinity() {
+        let mut dag = create_sample_dag3();
+        dag.set_dag_param("dag_id", 0);
+        // All nodes are restricted to core 1.
+        dag.set_dag_param("allowed_cores", 0b10);
+        let dag_set = vec![dag];
+
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut global_edf_scheduler = GlobalEDFScheduler::new(&dag_set, &processor);
+        global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path = global_edf_scheduler.dump_log("../lib/tests", "edf_affinity_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        for job_log in yaml_doc["node_set_logs"][0].as_vec().unwrap() {
+            assert_eq!(job_log["core_id"].as_i64().unwrap(), 1);
+        }
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            25
+        );
+        assert_eq!(
+            yaml_doc["processor_log"]["core_logs"][0]["total_proc_time"]
+                .as_i64()
+                .unwrap(),
+            0
+        );
+
+        remove_file(file_path).unwrap();
+    }
 }
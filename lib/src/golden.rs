@@ -0,0 +1,349 @@
+//! Golden-log regression tests.
+//! The result documents of the bundled algorithms on fixed sample DAG sets are compared against checked-in golden files,
+//! so that a refactor of the simulation core can be validated at a behavioral level with `cargo test --workspace`.
+//! Integers, such as times, are compared exactly,
+//! and only floating-point values, such as utilizations, are compared with a relative tolerance.
+//!
+//! After an intended change of the behavior, regenerate the golden files with
+//! `UPDATE_GOLDEN_LOGS=1 cargo test --workspace` and review their diff.
+//! A result document with a negative response time is never written as a golden file.
+use serde_yaml::Value;
+use std::{env, fs};
+
+pub const UPDATE_GOLDEN_LOGS_ENV: &str = "UPDATE_GOLDEN_LOGS";
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+fn load_yaml_value(file_path: &str) -> Value {
+    let file_content = fs::read_to_string(file_path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", file_path, err));
+    serde_yaml::from_str(&file_content)
+        .unwrap_or_else(|err| panic!("Failed to parse {}: {}", file_path, err))
+}
+
+fn format_key(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        _ => serde_yaml::to_string(key).unwrap().trim_end().to_string(),
+    }
+}
+
+/// Returns the path and the values of the first difference between the documents, or None if they match.
+/// Integers match only if they are equal. Otherwise, numbers match if their difference is within `tolerance`
+/// relative to the expected value (at least 1).
+pub fn find_mismatch(actual: &Value, expected: &Value, tolerance: f64) -> Option<String> {
+    find_mismatch_at("$", actual, expected, tolerance)
+}
+
+fn find_mismatch_at(
+    path: &str,
+    actual: &Value,
+    expected: &Value,
+    tolerance: f64,
+) -> Option<String> {
+    match (actual, expected) {
+        (Value::Number(actual_number), Value::Number(expected_number)) => {
+            let actual_f64 = actual_number.as_f64().unwrap();
+            let expected_f64 = expected_number.as_f64().unwrap();
+            let is_matched = if actual_number.is_f64() || expected_number.is_f64() {
+                (actual_f64 - expected_f64).abs() <= tolerance * expected_f64.abs().max(1.0)
+            } else {
+                actual_number == expected_number
+            };
+            if is_matched {
+                None
+            } else {
+                Some(format!(
+                    "{}: expected {}, but got {}",
+                    path, expected_number, actual_number
+                ))
+            }
+        }
+        (Value::Sequence(actual_values), Value::Sequence(expected_values)) => {
+            if actual_values.len() != expected_values.len() {
+                return Some(format!(
+                    "{}: expected {} elements, but got {}",
+                    path,
+                    expected_values.len(),
+                    actual_values.len()
+                ));
+            }
+            actual_values
+                .iter()
+                .zip(expected_values.iter())
+                .enumerate()
+                .find_map(|(i, (actual_value, expected_value))| {
+                    find_mismatch_at(
+                        &format!("{}[{}]", path, i),
+                        actual_value,
+                        expected_value,
+                        tolerance,
+                    )
+                })
+        }
+        (Value::Mapping(actual_mapping), Value::Mapping(expected_mapping)) => {
+            if let Some(key) = expected_mapping
+                .keys()
+                .find(|key| !actual_mapping.contains_key(*key))
+            {
+                return Some(format!("{}.{}: missing", path, format_key(key)));
+            }
+            if let Some(key) = actual_mapping
+                .keys()
+                .find(|key| !expected_mapping.contains_key(*key))
+            {
+                return Some(format!("{}.{}: unexpected", path, format_key(key)));
+            }
+            expected_mapping.iter().find_map(|(key, expected_value)| {
+                find_mismatch_at(
+                    &format!("{}.{}", path, format_key(key)),
+                    &actual_mapping[key],
+                    expected_value,
+                    tolerance,
+                )
+            })
+        }
+        _ if actual == expected => None,
+        _ => Some(format!(
+            "{}: expected {:?}, but got {:?}",
+            path, expected, actual
+        )),
+    }
+}
+
+/// Returns the path of the first negative number under a key containing `response_time`, which no schedule produces.
+fn find_negative_response_time(
+    path: &str,
+    value: &Value,
+    is_response_time: bool,
+) -> Option<String> {
+    match value {
+        Value::Number(number) if is_response_time && number.as_f64().unwrap() < 0.0 => {
+            Some(format!("{}: {}", path, number))
+        }
+        Value::Sequence(values) => values.iter().enumerate().find_map(|(i, value)| {
+            find_negative_response_time(&format!("{}[{}]", path, i), value, is_response_time)
+        }),
+        Value::Mapping(mapping) => mapping.iter().find_map(|(key, value)| {
+            let key = format_key(key);
+            find_negative_response_time(
+                &format!("{}.{}", path, key),
+                value,
+                is_response_time || key.contains("response_time"),
+            )
+        }),
+        Value::Tagged(tagged_value) => {
+            find_negative_response_time(path, &tagged_value.value, is_response_time)
+        }
+        _ => None,
+    }
+}
+
+/// Panic if the result document does not match the golden file.
+/// If `UPDATE_GOLDEN_LOGS` is set, the golden file is overwritten with the result document instead.
+///
+/// # Arguments
+///
+/// * `file_path` - path of the result document written by an algorithm
+/// * `golden_file_path` - path of the checked-in golden file
+pub fn assert_matches_golden(file_path: &str, golden_file_path: &str) {
+    if env::var_os(UPDATE_GOLDEN_LOGS_ENV).is_some() {
+        if let Some(negative_response_time) =
+            find_negative_response_time("$", &load_yaml_value(file_path), false)
+        {
+            panic!(
+                "{} has a negative response time, so it is not written as a golden file: {}",
+                file_path, negative_response_time
+            );
+        }
+        fs::copy(file_path, golden_file_path)
+            .unwrap_or_else(|err| panic!("Failed to update {}: {}", golden_file_path, err));
+        return;
+    }
+    if fs::metadata(golden_file_path).is_err() {
+        panic!(
+            "The golden file {} does not exist. Run the tests with {}=1 to create it.",
+            golden_file_path, UPDATE_GOLDEN_LOGS_ENV
+        );
+    }
+    if let Some(mismatch) = find_mismatch(
+        &load_yaml_value(file_path),
+        &load_yaml_value(golden_file_path),
+        DEFAULT_TOLERANCE,
+    ) {
+        panic!(
+            "{} does not match the golden file {}: {}",
+            file_path, golden_file_path, mismatch
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dag_creator::{create_dag_from_yaml, create_dag_set_from_dir},
+        dag_scheduler::DAGSchedulerBase,
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        fixed_priority_scheduler::FixedPriorityScheduler,
+        global_edf_scheduler::GlobalEDFScheduler,
+        homogeneous::HomogeneousProcessor,
+        log::{dump_dag_scheduler_result_to_yaml, dump_dag_set_scheduler_result_to_yaml},
+        processor::ProcessorBase,
+        proportional_share_scheduler::ProportionalShareScheduler,
+        round_robin_scheduler::RoundRobinScheduler,
+        util::adjust_to_implicit_deadline,
+    };
+    use std::fs::remove_file;
+
+    const GOLDEN_DAG_SET_DIR_PATH: &str = "../lib/tests/golden_logs/dag_set";
+    const GOLDEN_LOG_DIR_PATH: &str = "../lib/tests/golden_logs";
+    const NUMBER_OF_CORES: usize = 4;
+
+    fn run_dag_set_scheduler<T: DAGSetSchedulerBase<HomogeneousProcessor>>(
+        preemptive_type: PreemptiveType,
+        alg_name: &str,
+    ) {
+        let mut dag_set = create_dag_set_from_dir(GOLDEN_DAG_SET_DIR_PATH).unwrap();
+        adjust_to_implicit_deadline(&mut dag_set);
+        let mut scheduler = T::new(&dag_set, &HomogeneousProcessor::new(NUMBER_OF_CORES));
+        let schedule_outcome = scheduler.schedule(preemptive_type).unwrap();
+        let file_path = scheduler.dump_log("../lib/tests", alg_name);
+        dump_dag_set_scheduler_result_to_yaml(
+            &file_path,
+            schedule_outcome
+                .deadline_miss_flags
+                .iter()
+                .all(|flag| !flag),
+        );
+
+        assert_matches_golden(
+            &file_path,
+            &format!("{}/{}.yaml", GOLDEN_LOG_DIR_PATH, alg_name),
+        );
+        remove_file(file_path).unwrap();
+    }
+
+    fn parse_yaml(yaml: &str) -> Value {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_find_mismatch_within_tolerance() {
+        let expected = parse_yaml("a: [1, 0.5]\nb: {c: true}");
+        let actual = parse_yaml("b: {c: true}\na: [1, 0.5000000001]");
+
+        assert_eq!(find_mismatch(&actual, &expected, DEFAULT_TOLERANCE), None);
+    }
+
+    #[test]
+    fn test_find_mismatch_different_values() {
+        let expected = parse_yaml("a: [1, 0.5]\nb: {c: true}");
+
+        assert_eq!(
+            find_mismatch(
+                &parse_yaml("a: [1, 0.6]\nb: {c: true}"),
+                &expected,
+                DEFAULT_TOLERANCE
+            ),
+            Some("$.a[1]: expected 0.5, but got 0.6".to_string())
+        );
+        assert_eq!(
+            find_mismatch(&parse_yaml("a: [1]\nb: {c: true}"), &expected, 0.0),
+            Some("$.a: expected 2 elements, but got 1".to_string())
+        );
+        assert_eq!(
+            find_mismatch(&parse_yaml("a: [1, 0.5]\nb: {}"), &expected, 0.0),
+            Some("$.b.c: missing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_mismatch_integers_exactly() {
+        let expected = parse_yaml("a: [1000000000, 0.5]");
+
+        assert_eq!(
+            find_mismatch(
+                &parse_yaml("a: [1000000001, 0.5]"),
+                &expected,
+                DEFAULT_TOLERANCE
+            ),
+            Some("$.a[0]: expected 1000000000, but got 1000000001".to_string())
+        );
+        // An integer compared with a float is compared with the tolerance.
+        assert_eq!(
+            find_mismatch(
+                &parse_yaml("a: [1000000000.0, 0.5]"),
+                &expected,
+                DEFAULT_TOLERANCE
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_negative_response_time() {
+        assert_eq!(
+            find_negative_response_time(
+                "$",
+                &parse_yaml("dag_set_log: [{response_time: [3, -2]}]\nmin_lag: -1.5"),
+                false
+            ),
+            Some("$.dag_set_log[0].response_time[1]: -2".to_string())
+        );
+        assert_eq!(
+            find_negative_response_time("$", &parse_yaml("min_lag: -1.5"), false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_golden_log_global_edf_non_preemptive() {
+        run_dag_set_scheduler::<GlobalEDFScheduler>(
+            PreemptiveType::NonPreemptive,
+            "golden_gedf_non_preemptive",
+        );
+    }
+
+    #[test]
+    fn test_golden_log_global_edf_preemptive() {
+        run_dag_set_scheduler::<GlobalEDFScheduler>(
+            PreemptiveType::Preemptive {
+                key: "node_absolute_deadline".to_string(),
+            },
+            "golden_gedf_preemptive",
+        );
+    }
+
+    #[test]
+    fn test_golden_log_round_robin() {
+        run_dag_set_scheduler::<RoundRobinScheduler>(
+            PreemptiveType::NonPreemptive,
+            "golden_round_robin",
+        );
+    }
+
+    #[test]
+    fn test_golden_log_proportional_share() {
+        run_dag_set_scheduler::<ProportionalShareScheduler>(
+            PreemptiveType::NonPreemptive,
+            "golden_proportional_share",
+        );
+    }
+
+    #[test]
+    fn test_golden_log_fixed_priority() {
+        let dag = create_dag_from_yaml(&format!("{}/dag_0.yaml", GOLDEN_DAG_SET_DIR_PATH), false)
+            .unwrap();
+        let mut scheduler =
+            FixedPriorityScheduler::new(&dag, &HomogeneousProcessor::new(NUMBER_OF_CORES));
+        let (schedule_length, _) = scheduler.schedule();
+        let file_path = scheduler.dump_log("../lib/tests", "golden_fixed_priority");
+        dump_dag_scheduler_result_to_yaml(&file_path, schedule_length, 1.0, true);
+
+        assert_matches_golden(
+            &file_path,
+            &format!("{}/golden_fixed_priority.yaml", GOLDEN_LOG_DIR_PATH),
+        );
+        remove_file(file_path).unwrap();
+    }
+}
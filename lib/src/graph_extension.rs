@@ -1,87 +1,158 @@
-use log::warn;
+use crate::critical_path::{CommunicationMode, CriticalPathAnalysis, CriticalPathPolicy};
+use crate::diagnostics::{report, DiagnosticKind};
+use crate::node_params::NodeParams;
+use crate::reachability::ReachabilityMatrix;
 use petgraph::{
-    algo::toposort,
+    algo::{has_path_connecting, toposort},
     graph::{Graph, NodeIndex},
     visit::EdgeRef,
     Direction::{Incoming, Outgoing},
 };
 use std::cmp::Ord;
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
-const DUMMY_SOURCE_NODE_FLAG: i32 = -1;
-const DUMMY_SINK_NODE_FLAG: i32 = -2;
+const DUMMY_SOURCE_NODE_FLAG: i64 = -1;
+const DUMMY_SINK_NODE_FLAG: i64 = -2;
 
 /// custom node data structure for dag nodes (petgraph)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NodeData {
-    pub id: i32,
-    pub params: BTreeMap<String, i32>,
+    pub id: i64,
+    pub params: BTreeMap<String, i64>,
 }
 
 impl NodeData {
-    pub fn new(id: i32, params: BTreeMap<String, i32>) -> NodeData {
+    pub fn new(id: i64, params: BTreeMap<String, i64>) -> NodeData {
         NodeData { id, params }
     }
 
-    pub fn get_id(&self) -> i32 {
+    pub fn get_id(&self) -> i64 {
         self.id
     }
 
-    pub fn get_params_value(&self, key: &str) -> i32 {
+    pub fn get_params_value(&self, key: &str) -> i64 {
         *self
             .params
             .get(key)
             .unwrap_or_else(|| panic!("The key does not exist. key: {}", key))
     }
+
+    /// The params with the well-known ones typed. The params are copied, so avoid it in hot loops.
+    pub fn get_node_params(&self) -> NodeParams {
+        NodeParams::from(&self.params)
+    }
+
+    /// `allowed_cores` is a bitmask whose i-th bit allows the node to be allocated to core i.
+    /// A node without `allowed_cores` can be allocated to any core.
+    pub fn is_allowed_on_core(&self, core_id: usize) -> bool {
+        match self.params.get("allowed_cores") {
+            Some(allowed_cores) => core_id < 64 && (*allowed_cores as u64 >> core_id) & 1 == 1,
+            None => true,
+        }
+    }
 }
 
 pub trait GraphExtension {
-    fn add_param(&mut self, node_i: NodeIndex, key: &str, value: i32);
-    fn update_param(&mut self, node_i: NodeIndex, key: &str, value: i32);
+    fn add_param(&mut self, node_i: NodeIndex, key: &str, value: i64);
+    fn update_param(&mut self, node_i: NodeIndex, key: &str, value: i64);
     fn add_dummy_source_node(&mut self) -> NodeIndex;
     fn add_dummy_sink_node(&mut self) -> NodeIndex;
     fn remove_dummy_source_node(&mut self);
     fn remove_dummy_sink_node(&mut self);
+    /// The last node is swapped into the index of each removed node, so the ids of the remaining nodes
+    /// may differ from their indices afterwards. The indices may be in any order.
     fn remove_nodes(&mut self, node_indices: &[NodeIndex]);
+    /// Unlike remove_nodes, the remaining nodes keep their order and are renumbered so that their ids are their indices,
+    /// as NodeIndex::new(node.id) assumes. Returns the previous ids of the remaining nodes in their new order.
+    fn remove_nodes_with_id_consistency(&mut self, node_indices: &[NodeIndex]) -> Vec<i64>;
+    /// The nodes are renumbered in the given order, and only the edges among them are kept.
+    fn extract_subgraph(&self, node_indices: &[NodeIndex]) -> Graph<NodeData, i64>;
     fn calculate_earliest_start_times(&mut self);
+    /// With CommunicationMode::Included, the communication time of an edge is added between its producer and its consumer.
+    fn calculate_earliest_start_times_with_communication_mode(
+        &mut self,
+        communication_mode: CommunicationMode,
+    );
     fn calculate_earliest_finish_times(&mut self);
     fn calculate_latest_start_times(&mut self);
+    fn calculate_latest_start_times_with_communication_mode(
+        &mut self,
+        communication_mode: CommunicationMode,
+    );
     fn calculate_latest_finish_times(&mut self);
-    fn get_critical_path(&mut self) -> Vec<NodeIndex>;
+    fn calculate_slacks(&mut self);
+    fn calculate_laxities(&mut self, end_to_end_deadline: i64);
+    fn get_critical_path(&self) -> Vec<NodeIndex>;
+    /// All the critical paths in the lexicographic order of NodeIndex.
+    fn get_all_critical_paths(&self) -> Vec<Vec<NodeIndex>>;
+    /// Unlike get_critical_path, the choice among multiple critical paths is explicit, so nothing is reported.
+    fn get_critical_path_with_policy(&self, policy: CriticalPathPolicy) -> Vec<NodeIndex>;
     fn get_non_critical_nodes(&self, critical_path: &[NodeIndex]) -> Option<Vec<NodeIndex>>;
     fn get_source_nodes(&self) -> Vec<NodeIndex>;
     fn get_sink_nodes(&self) -> Vec<NodeIndex>;
-    fn get_volume(&self) -> i32;
-    fn get_total_wcet_from_nodes(&self, nodes: &[NodeIndex]) -> i32;
-    fn get_end_to_end_deadline(&self) -> Option<i32>;
-    fn get_head_period(&self) -> Option<i32>;
-    fn get_all_periods(&self) -> Option<HashMap<NodeIndex, i32>>;
-    fn get_head_offset(&self) -> i32;
+    fn get_volume(&self) -> i64;
+    /// No schedule on `number_of_cores` cores is shorter than the critical path or the volume spread evenly over the cores.
+    fn get_makespan_lower_bound(&self, number_of_cores: usize) -> i64;
+    fn get_total_wcet_from_nodes(&self, nodes: &[NodeIndex]) -> i64;
+    /// The tightest of the end-to-end deadlines, which is the deadline of the whole DAG.
+    fn get_end_to_end_deadline(&self) -> Option<i64>;
+    /// The sink nodes with their own end-to-end deadlines, in the order of NodeIndex.
+    /// Each sink chain of a DAG with multiple sinks may have a different deadline.
+    fn get_deadlines_per_sink(&self) -> Vec<(NodeIndex, i64)>;
+    fn get_head_period(&self) -> Option<i64>;
+    fn get_all_periods(&self) -> Option<HashMap<NodeIndex, i64>>;
+    fn get_head_offset(&self) -> i64;
     fn get_pre_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>>;
     fn get_suc_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>>;
+    /// The ancestors and the descendants in the order of a breadth-first search.
+    /// Build a ReachabilityMatrix instead to query many nodes of a DAG.
     fn get_anc_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>>;
     fn get_des_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>>;
     fn get_parallel_process_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>>;
-    fn get_dag_param(&self, key: &str) -> i32;
-    fn set_dag_param(&mut self, key: &str, value: i32);
+    /// Whether `to_node_i` is a descendant of `from_node_i`.
+    /// Build a ReachabilityMatrix instead to query a DAG many times.
+    fn is_reachable(&self, from_node_i: NodeIndex, to_node_i: NodeIndex) -> bool;
+    /// The most nodes that can run in parallel, i.e., the size of the maximum antichain,
+    /// which is the most cores the DAG can exploit at once.
+    fn get_max_parallelism(&self) -> usize;
+    /// The number of nodes at each level, where the level of a node is the number of edges
+    /// on the longest path from a source node to it. The nodes at a level are parallel,
+    /// so the maximum width is a lower bound of get_max_parallelism.
+    fn get_level_widths(&self) -> Vec<usize>;
+    fn get_dag_param(&self, key: &str) -> i64;
+    fn set_dag_param(&mut self, key: &str, value: i64);
     fn add_node_with_id_consistency(&mut self, node: NodeData) -> NodeIndex;
     fn is_node_ready(&self, node_i: NodeIndex) -> bool;
 }
 
-impl GraphExtension for Graph<NodeData, i32> {
-    fn add_param(&mut self, node_i: NodeIndex, key: &str, value: i32) {
+/// The source instances of a multi-rate DAG released by their offsets are not ambiguous heads.
+fn get_head_source_count(dag: &Graph<NodeData, i64>) -> usize {
+    dag.get_source_nodes()
+        .iter()
+        .filter(|source_i| !dag[**source_i].params.contains_key("release_offset"))
+        .count()
+}
+
+impl GraphExtension for Graph<NodeData, i64> {
+    fn add_param(&mut self, node_i: NodeIndex, key: &str, value: i64) {
         let target_node = self.node_weight_mut(node_i).unwrap();
         if target_node.params.contains_key(key) {
-            warn!("The key already exists. key: {}", key);
+            report(
+                DiagnosticKind::ParamAlreadyExists,
+                format!("The key already exists. key: {}", key),
+            );
         } else {
             target_node.params.insert(key.to_string(), value);
         }
     }
 
-    fn update_param(&mut self, node_i: NodeIndex, key: &str, value: i32) {
+    fn update_param(&mut self, node_i: NodeIndex, key: &str, value: i64) {
         let target_node = self.node_weight_mut(node_i).unwrap();
         if !target_node.params.contains_key(key) {
-            warn!("The key no exists. key: {}", key);
+            report(
+                DiagnosticKind::ParamNotFound,
+                format!("The key no exists. key: {}", key),
+            );
         } else {
             target_node.params.insert(key.to_string(), value);
         }
@@ -92,7 +163,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SOURCE_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SOURCE_NODE_FLAG)
         }) {
             panic!(
                 "The dummy source node has already been added. NodeIndex: {:?}",
@@ -101,7 +172,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
         let source_nodes = self.get_source_nodes();
         let dummy_source_i = self.add_node(NodeData::new(
-            self.node_count() as i32,
+            self.node_count() as i64,
             BTreeMap::from([
                 ("execution_time".to_string(), 0),
                 ("dummy".to_string(), DUMMY_SOURCE_NODE_FLAG),
@@ -118,7 +189,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SINK_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SINK_NODE_FLAG)
         }) {
             panic!(
                 "The dummy sink node has already been added. NodeIndex: {:?}",
@@ -127,7 +198,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
         let sink_nodes = self.get_sink_nodes();
         let dummy_sink_i = self.add_node(NodeData::new(
-            self.node_count() as i32,
+            self.node_count() as i64,
             BTreeMap::from([
                 ("execution_time".to_string(), 0),
                 ("dummy".to_string(), DUMMY_SINK_NODE_FLAG),
@@ -144,7 +215,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SOURCE_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SOURCE_NODE_FLAG)
         }) {
             self.remove_node(dummy_source_node);
         } else {
@@ -157,7 +228,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             self[i]
                 .params
                 .get("dummy")
-                .map_or(false, |&v| v == DUMMY_SINK_NODE_FLAG)
+                .is_some_and(|&v| v == DUMMY_SINK_NODE_FLAG)
         }) {
             self.remove_node(dummy_sink_node);
         } else {
@@ -166,13 +237,56 @@ impl GraphExtension for Graph<NodeData, i32> {
     }
 
     fn remove_nodes(&mut self, node_indices: &[NodeIndex]) {
-        for node_i in node_indices.iter().rev() {
-            self.remove_node(*node_i);
+        // Removing the larger indices first keeps the smaller ones valid.
+        let mut node_indices = node_indices.to_vec();
+        node_indices.sort_unstable_by(|a, b| b.cmp(a));
+        node_indices.dedup();
+        for node_i in node_indices {
+            self.remove_node(node_i);
+        }
+    }
+
+    fn remove_nodes_with_id_consistency(&mut self, node_indices: &[NodeIndex]) -> Vec<i64> {
+        let removed_nodes: HashSet<NodeIndex> = node_indices.iter().copied().collect();
+        let remaining_nodes: Vec<NodeIndex> = self
+            .node_indices()
+            .filter(|node_i| !removed_nodes.contains(node_i))
+            .collect();
+        let previous_ids = remaining_nodes
+            .iter()
+            .map(|&node_i| self[node_i].id)
+            .collect();
+        *self = self.extract_subgraph(&remaining_nodes);
+        previous_ids
+    }
+
+    fn extract_subgraph(&self, node_indices: &[NodeIndex]) -> Graph<NodeData, i64> {
+        let mut subgraph = Graph::<NodeData, i64>::new();
+        let mut index_map = HashMap::new();
+        for node_i in node_indices.iter() {
+            let mut node_data = self[*node_i].clone();
+            node_data.id = subgraph.node_count() as i64;
+            index_map.insert(*node_i, subgraph.add_node_with_id_consistency(node_data));
+        }
+        for edge in self.edge_references() {
+            if let (Some(source), Some(target)) =
+                (index_map.get(&edge.source()), index_map.get(&edge.target()))
+            {
+                subgraph.add_edge(*source, *target, *edge.weight());
+            }
         }
+        subgraph
     }
 
     /// Calculate the earliest start times for each node in the DAG.
     fn calculate_earliest_start_times(&mut self) {
+        self.calculate_earliest_start_times_with_communication_mode(CommunicationMode::Ignored);
+    }
+
+    fn calculate_earliest_start_times_with_communication_mode(
+        &mut self,
+        communication_mode: CommunicationMode,
+    ) {
         let mut earliest_start_times = vec![0; self.node_count()];
 
         let sorted_nodes = toposort(&*self, None).unwrap();
@@ -182,7 +296,8 @@ impl GraphExtension for Graph<NodeData, i32> {
                 .map(|edge| {
                     let source_node = edge.source();
                     let exe_time = self[source_node].params["execution_time"];
-                    earliest_start_times[source_node.index()] + exe_time
+                    let communication_time = communication_mode.get_delay(edge);
+                    earliest_start_times[source_node.index()] + exe_time + communication_time
                 })
                 .max_by(|a, b| a.partial_cmp(b).unwrap())
                 .unwrap_or(0);
@@ -216,9 +331,16 @@ impl GraphExtension for Graph<NodeData, i32> {
 
     /// Calculate the latest start times for each node in the DAG.
     fn calculate_latest_start_times(&mut self) {
-        self.calculate_earliest_start_times();
+        self.calculate_latest_start_times_with_communication_mode(CommunicationMode::Ignored);
+    }
+
+    fn calculate_latest_start_times_with_communication_mode(
+        &mut self,
+        communication_mode: CommunicationMode,
+    ) {
+        self.calculate_earliest_start_times_with_communication_mode(communication_mode);
         let sorted_nodes = toposort(&*self, None).unwrap();
-        let mut latest_start_times = vec![i32::MAX; self.node_count()];
+        let mut latest_start_times = vec![i64::MAX; self.node_count()];
         let sink_node_index = self.get_sink_nodes();
         latest_start_times[sink_node_index[0].index()] =
             self[sink_node_index[0]].params["earliest_start_time"];
@@ -229,7 +351,8 @@ impl GraphExtension for Graph<NodeData, i32> {
                 .map(|edge| {
                     let target_node = edge.target();
                     let pre_exe_time = self[node_i].params["execution_time"];
-                    latest_start_times[target_node.index()] - pre_exe_time
+                    let communication_time = communication_mode.get_delay(edge);
+                    latest_start_times[target_node.index()] - pre_exe_time - communication_time
                 })
                 .min_by(|a, b| a.partial_cmp(b).unwrap())
                 .unwrap_or(self[sink_node_index[0]].params["earliest_start_time"]);
@@ -262,8 +385,45 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
     }
 
+    /// Calculate the slack (latest start time - earliest start time) for each node in the DAG.
+    /// Like calculate_latest_start_times, the DAG is assumed to have a single sink node.
+    fn calculate_slacks(&mut self) {
+        self.calculate_latest_start_times();
+
+        for node_i in self.node_indices() {
+            let slack = self[node_i].params["latest_start_time"]
+                - self[node_i].params["earliest_start_time"];
+            if self[node_i].params.contains_key("slack") {
+                self.update_param(node_i, "slack", slack);
+            } else {
+                self.add_param(node_i, "slack", slack);
+            }
+        }
+    }
+
+    /// Calculate the laxity for each node, i.e., how long the node can be delayed from its earliest start time
+    /// without the DAG finishing after `end_to_end_deadline`. It is negative for all nodes if the critical path exceeds the deadline.
+    fn calculate_laxities(&mut self, end_to_end_deadline: i64) {
+        self.calculate_slacks();
+        let critical_path_length = self
+            .node_weights()
+            .map(|node| node.params["earliest_start_time"] + node.params["execution_time"])
+            .max()
+            .unwrap_or(0);
+
+        for node_i in self.node_indices() {
+            let laxity = end_to_end_deadline - critical_path_length + self[node_i].params["slack"];
+            if self[node_i].params.contains_key("laxity") {
+                self.update_param(node_i, "laxity", laxity);
+            } else {
+                self.add_param(node_i, "laxity", laxity);
+            }
+        }
+    }
+
     /// Returns the critical path of a DAG
-    /// Multiple critical paths are obtained using Breadth-First Search, BFS
+    /// Multiple critical paths are obtained using Breadth-First Search, BFS, and the first one is returned.
+    /// Each call analyzes the DAG again, so a caller that needs more than the path holds a CriticalPathAnalysis instead.
     ///
     /// # Arguments
     ///
@@ -281,7 +441,7 @@ impl GraphExtension for Graph<NodeData, i32> {
     /// use lib::graph_extension::NodeData;
     /// use lib::graph_extension::GraphExtension;
     ///
-    /// let mut dag = Graph::<NodeData, i32>::new();
+    /// let mut dag = Graph::<NodeData, i64>::new();
     /// let mut params = BTreeMap::new();
     /// params.insert("execution_time".to_string(), 1);
     /// let n0 = dag.add_node(NodeData { id: 0, params: params.clone() });
@@ -290,42 +450,23 @@ impl GraphExtension for Graph<NodeData, i32> {
     /// let critical_path = dag.get_critical_path();
     /// println!("The critical path is: {:?}", critical_path);
     /// ```
-    fn get_critical_path(&mut self) -> Vec<NodeIndex> {
-        self.add_dummy_sink_node();
-        let start_node = self.add_dummy_source_node();
-        self.calculate_earliest_start_times();
-        self.calculate_latest_start_times();
-        let mut path_search_queue = VecDeque::new();
-        path_search_queue.push_back((start_node, vec![start_node]));
-        let mut critical_path = Vec::new();
-
-        while let Some((node, mut current_critical_path)) = path_search_queue.pop_front() {
-            let outgoing_edges: Vec<_> = self.edges_directed(node, Outgoing).collect();
-
-            if outgoing_edges.is_empty() {
-                current_critical_path.pop(); // Remove the dummy sink node
-                current_critical_path.remove(0); // Remove the dummy source node
-                critical_path.push(current_critical_path);
-            } else {
-                for edge in outgoing_edges {
-                    let target_node = edge.target();
-                    if self[target_node].params["earliest_start_time"]
-                        == self[target_node].params["latest_start_time"]
-                    {
-                        let mut new_critical_path = current_critical_path.clone();
-                        new_critical_path.push(target_node);
-                        path_search_queue.push_back((target_node, new_critical_path));
-                    }
-                }
-            }
+    fn get_critical_path(&self) -> Vec<NodeIndex> {
+        let analysis = CriticalPathAnalysis::new(self);
+        if analysis.has_multiple_critical_paths() {
+            report(
+                DiagnosticKind::MultipleCriticalPaths,
+                "There are more than one critical paths.",
+            );
         }
+        analysis.get_critical_path().to_vec()
+    }
 
-        self.remove_dummy_source_node();
-        self.remove_dummy_sink_node();
-        if critical_path.len() > 1 {
-            warn!("There are more than one critical paths.");
-        }
-        critical_path[0].clone()
+    fn get_all_critical_paths(&self) -> Vec<Vec<NodeIndex>> {
+        CriticalPathAnalysis::new(self).get_all_critical_paths()
+    }
+
+    fn get_critical_path_with_policy(&self, policy: CriticalPathPolicy) -> Vec<NodeIndex> {
+        CriticalPathAnalysis::new(self).get_critical_path_with_policy(policy)
     }
 
     fn get_non_critical_nodes(&self, critical_path: &[NodeIndex]) -> Option<Vec<NodeIndex>> {
@@ -355,7 +496,7 @@ impl GraphExtension for Graph<NodeData, i32> {
             .collect::<Vec<_>>()
     }
 
-    fn get_volume(&self) -> i32 {
+    fn get_volume(&self) -> i64 {
         self.node_indices()
             .map(|node| {
                 *self[node]
@@ -366,7 +507,14 @@ impl GraphExtension for Graph<NodeData, i32> {
             .sum()
     }
 
-    fn get_total_wcet_from_nodes(&self, nodes: &[NodeIndex]) -> i32 {
+    fn get_makespan_lower_bound(&self, number_of_cores: usize) -> i64 {
+        let critical_path_length = CriticalPathAnalysis::new(self).get_critical_path_length();
+        let number_of_cores = number_of_cores as i64;
+        let volume_per_core = (self.get_volume() + number_of_cores - 1) / number_of_cores;
+        critical_path_length.max(volume_per_core)
+    }
+
+    fn get_total_wcet_from_nodes(&self, nodes: &[NodeIndex]) -> i64 {
         nodes
             .iter()
             .map(|node| {
@@ -378,42 +526,63 @@ impl GraphExtension for Graph<NodeData, i32> {
             .sum()
     }
 
-    fn get_end_to_end_deadline(&self) -> Option<i32> {
-        self.node_indices()
-            .find_map(|i| match self[i].params.get("end_to_end_deadline") {
-                Some(end_to_end_deadline) => Some(*end_to_end_deadline),
-                None => {
-                    warn!("The end-to-end deadline does not exist.");
-                    None
-                }
+    fn get_end_to_end_deadline(&self) -> Option<i64> {
+        let end_to_end_deadline = self
+            .node_weights()
+            .filter_map(|node| node.params.get("end_to_end_deadline").copied())
+            .min();
+        if end_to_end_deadline.is_none() {
+            report(
+                DiagnosticKind::MissingEndToEndDeadline,
+                "The end-to-end deadline does not exist.",
+            );
+        }
+        end_to_end_deadline
+    }
+
+    fn get_deadlines_per_sink(&self) -> Vec<(NodeIndex, i64)> {
+        self.get_sink_nodes()
+            .into_iter()
+            .filter_map(|sink_i| {
+                self[sink_i]
+                    .params
+                    .get("end_to_end_deadline")
+                    .map(|end_to_end_deadline| (sink_i, *end_to_end_deadline))
             })
+            .collect()
     }
 
-    fn get_head_period(&self) -> Option<i32> {
+    fn get_head_period(&self) -> Option<i64> {
         let source_nodes = self.get_source_nodes();
-        let periods: Vec<&i32> = source_nodes
+        let periods: Vec<i64> = source_nodes
             .iter()
-            .filter_map(|&node_i| self[node_i].params.get("period"))
+            .filter_map(|&node_i| self[node_i].params.get("period").copied())
             .collect();
 
-        if source_nodes.len() > 1 {
-            warn!("Multiple source nodes found.");
+        if get_head_source_count(self) > 1 {
+            report(
+                DiagnosticKind::MultipleSourceNodes,
+                "Multiple source nodes found.",
+            );
         }
         if periods.len() > 1 {
-            warn!("Multiple periods found. The first period is used.");
+            report(
+                DiagnosticKind::MultiplePeriods,
+                "Multiple periods found. The first period is used.",
+            );
         }
         if periods.is_empty() {
-            warn!("No period found.");
+            report(DiagnosticKind::MissingPeriod, "No period found.");
             return None;
         }
-        Some(*periods[0])
+        Some(periods[0])
     }
 
-    fn get_all_periods(&self) -> Option<HashMap<NodeIndex, i32>> {
+    fn get_all_periods(&self) -> Option<HashMap<NodeIndex, i64>> {
         let mut period_map = HashMap::new();
         for node in self.node_indices() {
-            if let Some(period) = self[node].params.get("period") {
-                period_map.insert(node, *period);
+            if let Some(&period) = self[node].params.get("period") {
+                period_map.insert(node, period);
             }
         }
         if period_map.is_empty() {
@@ -423,23 +592,29 @@ impl GraphExtension for Graph<NodeData, i32> {
         }
     }
 
-    fn get_head_offset(&self) -> i32 {
+    fn get_head_offset(&self) -> i64 {
         let source_nodes = self.get_source_nodes();
-        let offsets: Vec<&i32> = source_nodes
+        let offsets: Vec<i64> = source_nodes
             .iter()
-            .filter_map(|&node_i| self[node_i].params.get("offset"))
+            .filter_map(|&node_i| self[node_i].params.get("offset").copied())
             .collect();
-        if source_nodes.len() > 1 {
-            warn!("Multiple source nodes found.");
+        if get_head_source_count(self) > 1 {
+            report(
+                DiagnosticKind::MultipleSourceNodes,
+                "Multiple source nodes found.",
+            );
         }
         if offsets.len() > 1 {
-            warn!("Multiple offsets found. The first offset is used.");
+            report(
+                DiagnosticKind::MultipleOffsets,
+                "Multiple offsets found. The first offset is used.",
+            );
         }
         if offsets.is_empty() {
-            warn!("No offset found. 0 is used");
+            report(DiagnosticKind::MissingOffset, "No offset found. 0 is used");
             0
         } else {
-            *offsets[0]
+            offsets[0]
         }
     }
 
@@ -481,13 +656,15 @@ impl GraphExtension for Graph<NodeData, i32> {
 
     fn get_anc_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
         let mut anc_nodes = Vec::new();
+        let mut is_visited = vec![false; self.node_count()];
         let mut search_queue = VecDeque::new();
         search_queue.push_back(node_i);
 
         while let Some(node) = search_queue.pop_front() {
             //If the target node does not exist, get_pre_node causes panic!
             for pre_node in self.get_pre_nodes(node).unwrap_or_default() {
-                if !anc_nodes.contains(&pre_node) {
+                if !is_visited[pre_node.index()] {
+                    is_visited[pre_node.index()] = true;
                     anc_nodes.push(pre_node);
                     search_queue.push_back(pre_node);
                 }
@@ -498,13 +675,15 @@ impl GraphExtension for Graph<NodeData, i32> {
 
     fn get_des_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
         let mut des_nodes = Vec::new();
+        let mut is_visited = vec![false; self.node_count()];
         let mut search_queue = VecDeque::new();
         search_queue.push_back(node_i);
 
         while let Some(node) = search_queue.pop_front() {
             //If the target node does not exist, get_suc_node causes panic!
             for suc_node in self.get_suc_nodes(node).unwrap_or_default() {
-                if !des_nodes.contains(&suc_node) {
+                if !is_visited[suc_node.index()] {
+                    is_visited[suc_node.index()] = true;
                     des_nodes.push(suc_node);
                     search_queue.push_back(suc_node);
                 }
@@ -513,30 +692,37 @@ impl GraphExtension for Graph<NodeData, i32> {
         Some(des_nodes).filter(|des| !des.is_empty())
     }
 
+    fn is_reachable(&self, from_node_i: NodeIndex, to_node_i: NodeIndex) -> bool {
+        from_node_i != to_node_i && has_path_connecting(self, from_node_i, to_node_i, None)
+    }
+
     fn get_parallel_process_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
-        let parallel_process_nodes: Vec<_> = self
-            .node_indices()
-            .filter(|&node| {
-                node != node_i
-                    && !self
-                        .get_anc_nodes(node)
-                        .unwrap_or_default()
-                        .contains(&node_i)
-                    && !self
-                        .get_des_nodes(node)
-                        .unwrap_or_default()
-                        .contains(&node_i)
-            })
-            .collect();
+        ReachabilityMatrix::new(self).get_parallel_process_nodes(node_i)
+    }
 
-        if parallel_process_nodes.is_empty() {
-            None
-        } else {
-            Some(parallel_process_nodes)
+    fn get_max_parallelism(&self) -> usize {
+        ReachabilityMatrix::new(self).get_max_antichain_size()
+    }
+
+    fn get_level_widths(&self) -> Vec<usize> {
+        let mut levels = vec![0; self.node_count()];
+        let mut level_widths = Vec::new();
+        for node_i in toposort(self, None).unwrap() {
+            let level = self
+                .neighbors_directed(node_i, Incoming)
+                .map(|pre_node_i| levels[pre_node_i.index()] + 1)
+                .max()
+                .unwrap_or(0);
+            levels[node_i.index()] = level;
+            if level_widths.len() <= level {
+                level_widths.resize(level + 1, 0);
+            }
+            level_widths[level] += 1;
         }
+        level_widths
     }
 
-    fn get_dag_param(&self, key: &str) -> i32 {
+    fn get_dag_param(&self, key: &str) -> i64 {
         if self.node_indices().count() == 0 {
             panic!(
                 "Error: {} does not exist. Please use set_dag_param({}, value)",
@@ -546,7 +732,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         self[NodeIndex::new(0)].params[key]
     }
 
-    fn set_dag_param(&mut self, key: &str, value: i32) {
+    fn set_dag_param(&mut self, key: &str, value: i64) {
         if self.node_indices().count() == 0 {
             panic!("No node found.");
         }
@@ -563,7 +749,7 @@ impl GraphExtension for Graph<NodeData, i32> {
         let node_index = self.add_node(node);
 
         assert_eq!(
-            node_index.index() as i32,
+            node_index.index() as i64,
             self[node_index].id,
             "The add node id is different from NodeIndex."
         );
@@ -572,7 +758,7 @@ impl GraphExtension for Graph<NodeData, i32> {
     }
 
     fn is_node_ready(&self, node_i: NodeIndex) -> bool {
-        let pre_nodes_count = self.get_pre_nodes(node_i).unwrap_or_default().len() as i32;
+        let pre_nodes_count = self.get_pre_nodes(node_i).unwrap_or_default().len() as i64;
         let pre_done_nodes_count = self[node_i].params.get("pre_done_count").unwrap_or(&0);
         pre_nodes_count == *pre_done_nodes_count
     }
@@ -582,15 +768,31 @@ impl GraphExtension for Graph<NodeData, i32> {
 mod tests {
     use super::*;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
     }
 
+    #[test]
+    fn test_is_allowed_on_core_normal() {
+        let node = create_node(0, "allowed_cores", 0b101);
+        assert!(node.is_allowed_on_core(0));
+        assert!(!node.is_allowed_on_core(1));
+        assert!(node.is_allowed_on_core(2));
+        assert!(!node.is_allowed_on_core(40));
+    }
+
+    #[test]
+    fn test_is_allowed_on_core_no_exist_allowed_cores() {
+        let node = create_node(0, "execution_time", 1);
+        assert!(node.is_allowed_on_core(0));
+        assert!(node.is_allowed_on_core(40));
+    }
+
     #[test]
     fn test_add_param_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         dag.add_param(n0, "test", 1);
         assert_eq!(dag[n0].params.get("test").unwrap(), &1);
@@ -599,7 +801,7 @@ mod tests {
 
     #[test]
     fn test_add_param_duplicate() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         assert_eq!(dag[n0].params.get("execution_time").unwrap(), &0);
         dag.add_param(n0, "execution_time", 1);
@@ -608,7 +810,7 @@ mod tests {
 
     #[test]
     fn test_update_param_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         assert_eq!(dag[n0].params.get("execution_time").unwrap(), &0);
         dag.update_param(n0, "execution_time", 1);
@@ -616,7 +818,7 @@ mod tests {
     }
     #[test]
     fn test_update_param_no_exist_params() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         dag.update_param(n0, "test", 1);
         assert_eq!(dag[n0].params.get("test"), None);
@@ -625,7 +827,7 @@ mod tests {
 
     #[test]
     fn test_calculate_earliest_start_times_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -646,7 +848,7 @@ mod tests {
 
     #[test]
     fn test_calculate_earliest_finish_times_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -667,7 +869,7 @@ mod tests {
 
     #[test]
     fn test_calculate_lasted_start_times_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -691,9 +893,43 @@ mod tests {
         assert_eq!(dag[n4].params["latest_start_time"], 59);
     }
 
+    #[test]
+    fn test_calculate_latest_start_times_with_communication() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 80);
+        dag.add_edge(n2, n4, 1);
+        dag.add_dummy_sink_node();
+        dag.add_dummy_source_node();
+
+        dag.calculate_latest_start_times_with_communication_mode(CommunicationMode::Included);
+        dag.remove_dummy_sink_node();
+        dag.remove_dummy_source_node();
+
+        // The long communication of n1 -> n3 makes n0 -> n1 -> n3 the critical path.
+        assert_eq!(dag[n3].params["earliest_start_time"], 92);
+        assert_eq!(dag[n0].params["latest_start_time"], 0);
+        assert_eq!(dag[n1].params["latest_start_time"], 5);
+        assert_eq!(dag[n2].params["latest_start_time"], 18);
+        assert_eq!(dag[n3].params["latest_start_time"], 92);
+        assert_eq!(dag[n4].params["latest_start_time"], 74);
+        assert_eq!(
+            CriticalPathAnalysis::new_with_communication_mode(&dag, CommunicationMode::Included)
+                .get_critical_path(),
+            &[n0, n1, n3]
+        );
+        assert_eq!(dag.get_critical_path(), vec![n0, n2, n4]);
+    }
+
     #[test]
     fn test_calculate_lasted_finish_times_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -717,9 +953,54 @@ mod tests {
         assert_eq!(dag[n4].params["latest_finish_time"], 113);
     }
 
+    #[test]
+    fn test_calculate_slacks_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+        dag.add_dummy_sink_node();
+
+        dag.calculate_slacks();
+        dag.remove_dummy_sink_node();
+
+        assert_eq!(dag[n0].params["slack"], 0);
+        assert_eq!(dag[n1].params["slack"], 66);
+        assert_eq!(dag[n2].params["slack"], 0);
+        assert_eq!(dag[n3].params["slack"], 66);
+        assert_eq!(dag[n4].params["slack"], 0);
+    }
+
+    #[test]
+    fn test_calculate_laxities_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_dummy_sink_node();
+
+        dag.calculate_laxities(100);
+        assert_eq!(dag[n0].params["laxity"], 41);
+        assert_eq!(dag[n1].params["laxity"], 89);
+        assert_eq!(dag[n2].params["laxity"], 41);
+
+        // The laxities are updated for a tighter deadline.
+        dag.calculate_laxities(50);
+        assert_eq!(dag[n0].params["laxity"], -9);
+        assert_eq!(dag[n1].params["laxity"], 39);
+    }
+
     #[test]
     fn test_get_critical_path_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -736,9 +1017,34 @@ mod tests {
         assert_eq!(critical_path, &[n0, n2, n4]);
     }
 
+    #[test]
+    fn test_get_all_critical_paths_multiple() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 10));
+        let n2 = dag.add_node(create_node(2, "execution_time", 6));
+        let n3 = dag.add_node(create_node(3, "execution_time", 4));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n2, n3, 1);
+
+        assert_eq!(
+            dag.get_all_critical_paths(),
+            vec![vec![n0, n1], vec![n0, n2, n3]]
+        );
+        assert_eq!(
+            dag.get_critical_path_with_policy(CriticalPathPolicy::LongestByNodes),
+            vec![n0, n2, n3]
+        );
+        assert_eq!(
+            dag.get_critical_path_with_policy(CriticalPathPolicy::Lexicographic),
+            vec![n0, n1]
+        );
+    }
+
     #[test]
     fn test_get_non_critical_nodes_when_critical_path_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -758,7 +1064,7 @@ mod tests {
 
     #[test]
     fn test_get_non_critical_nodes_no_exist() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         let critical_path = dag.get_critical_path();
         let no_critical_path_nodes = dag.get_non_critical_nodes(&critical_path);
         assert_eq!(no_critical_path_nodes, None);
@@ -766,7 +1072,7 @@ mod tests {
 
     #[test]
     fn test_remove_dummy_node_check_whether_connected_edges_removed() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 3));
         let n1 = dag.add_node(create_node(1, "execution_time", 6));
         let n2 = dag.add_node(create_node(2, "execution_time", 45));
@@ -785,7 +1091,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_remove_dummy_node_no_exist() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -798,7 +1104,7 @@ mod tests {
 
     #[test]
     fn test_remove_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 3));
         let n1 = dag.add_node(create_node(1, "execution_time", 6));
         let n2 = dag.add_node(create_node(2, "execution_time", 45));
@@ -810,7 +1116,7 @@ mod tests {
         assert_eq!(dag.edge_count(), 0);
         assert_eq!(dag[n0].id, 0);
 
-        fn contains(dag: &Graph<NodeData, i32>, node: NodeIndex) -> bool {
+        fn contains(dag: &Graph<NodeData, i64>, node: NodeIndex) -> bool {
             dag.node_indices().any(|i| i == node)
         }
 
@@ -818,10 +1124,51 @@ mod tests {
         assert!(!contains(&dag, n2));
     }
 
+    #[test]
+    fn test_remove_nodes_descending_order() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        for node_id in 0..4 {
+            dag.add_node(create_node(node_id, "execution_time", 1));
+        }
+
+        // The removal of n1 first swaps n3 into its index, which must not hide n3 from the removal.
+        dag.remove_nodes(&[NodeIndex::new(3), NodeIndex::new(1)]);
+        let ids: Vec<i64> = dag.node_weights().map(|node| node.id).collect();
+        assert_eq!(ids, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_remove_nodes_with_id_consistency_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let n2 = dag.add_node(create_node(2, "execution_time", 45));
+        let n3 = dag.add_node(create_node(3, "execution_time", 8));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 2);
+        dag.add_edge(n2, n3, 3);
+
+        assert_eq!(dag.remove_nodes_with_id_consistency(&[n1]), vec![0, 2, 3]);
+        assert_eq!(dag.node_count(), 3);
+        for node_i in dag.node_indices() {
+            assert_eq!(dag[node_i].id, node_i.index() as i64);
+        }
+        assert_eq!(dag[NodeIndex::new(1)].params["execution_time"], 45);
+        assert_eq!(
+            dag.find_edge(n0, NodeIndex::new(1)).map(|e| dag[e]),
+            Some(2)
+        );
+        assert_eq!(
+            dag.find_edge(NodeIndex::new(1), NodeIndex::new(2))
+                .map(|e| dag[e]),
+            Some(3)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_add_dummy_node_duplication() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 3));
         let n1 = dag.add_node(create_node(1, "execution_time", 6));
         let n2 = dag.add_node(create_node(2, "execution_time", 45));
@@ -836,7 +1183,7 @@ mod tests {
 
     #[test]
     fn test_get_source_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -851,7 +1198,7 @@ mod tests {
 
     #[test]
     fn test_get_source_nodes_dummy_node() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -864,7 +1211,7 @@ mod tests {
 
     #[test]
     fn test_get_sink_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -882,7 +1229,7 @@ mod tests {
 
     #[test]
     fn test_get_sink_nodes_dummy_node() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -895,7 +1242,7 @@ mod tests {
 
     #[test]
     fn test_add_dummy_node_integrity_for_id_and_node_index() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -905,13 +1252,34 @@ mod tests {
         let source_index = dag.add_dummy_source_node();
         let sink_index = dag.add_dummy_sink_node();
 
-        assert!(dag[source_index].id == source_index.index() as i32);
-        assert!(dag[sink_index].id == sink_index.index() as i32);
+        assert!(dag[source_index].id == source_index.index() as i64);
+        assert!(dag[sink_index].id == sink_index.index() as i64);
+    }
+
+    #[test]
+    fn test_extract_subgraph_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n2, 2);
+
+        let subgraph = dag.extract_subgraph(&[n1, n2]);
+        assert_eq!(subgraph.node_count(), 2);
+        assert_eq!(subgraph.edge_count(), 1);
+        assert_eq!(subgraph[NodeIndex::new(0)].id, 0);
+        assert_eq!(subgraph[NodeIndex::new(0)].params["execution_time"], 6);
+        let edge_i = subgraph
+            .find_edge(NodeIndex::new(0), NodeIndex::new(1))
+            .unwrap();
+        assert_eq!(subgraph[edge_i], 2);
+        assert_eq!(subgraph.get_volume(), 11);
     }
 
     #[test]
     fn test_get_volume_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 3));
         let n1 = dag.add_node(create_node(1, "execution_time", 6));
         let n2 = dag.add_node(create_node(2, "execution_time", 5));
@@ -924,15 +1292,35 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_volume_node_no_includes_execution_time() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "weight", 3));
 
         dag.get_volume();
     }
 
+    #[test]
+    fn test_get_makespan_lower_bound_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "execution_time", 6));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        let n3 = dag.add_node(create_node(3, "execution_time", 4));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n3, 1);
+
+        // The critical path bounds the makespan on many cores, and the volume on few cores.
+        assert_eq!(dag.get_makespan_lower_bound(3), 9);
+        assert_eq!(dag.get_makespan_lower_bound(2), 9);
+        assert_eq!(dag.get_makespan_lower_bound(1), 18);
+        // The volume per core is rounded up.
+        dag.update_param(n3, "execution_time", 5);
+        assert_eq!(dag.get_makespan_lower_bound(2), 10);
+    }
+
     #[test]
     fn test_get_total_wcet_from_nodes_any_given_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let n1 = dag.add_node(create_node(1, "execution_time", 7));
         let n2 = dag.add_node(create_node(2, "execution_time", 55));
@@ -949,7 +1337,7 @@ mod tests {
 
     #[test]
     fn test_get_total_wcet_from_nodes_given_one_node() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 4));
         let nodes0 = vec![n0];
 
@@ -959,7 +1347,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_total_wcet_from_nodes_node_no_includes_execution_time() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "weight", 3));
 
         let nodes = vec![n0];
@@ -968,7 +1356,7 @@ mod tests {
 
     #[test]
     fn test_get_end_to_end_deadline_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 3));
         let n1 = dag.add_node(NodeData {
             id: 1,
@@ -985,9 +1373,24 @@ mod tests {
         assert_eq!(dag.get_end_to_end_deadline(), Some(25));
     }
 
+    #[test]
+    fn test_get_end_to_end_deadline_multiple_sinks() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 3));
+        let n1 = dag.add_node(create_node(1, "end_to_end_deadline", 40));
+        let n2 = dag.add_node(create_node(2, "end_to_end_deadline", 25));
+        let n3 = dag.add_node(create_node(3, "execution_time", 4));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n0, n3, 1);
+
+        assert_eq!(dag.get_end_to_end_deadline(), Some(25));
+        assert_eq!(dag.get_deadlines_per_sink(), vec![(n1, 40), (n2, 25)]);
+    }
+
     #[test]
     fn test_get_end_to_end_deadline_node_no_includes_end_to_end_deadline() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "execution_time", 3));
 
         assert_eq!(dag.get_end_to_end_deadline(), None);
@@ -995,7 +1398,7 @@ mod tests {
 
     #[test]
     fn test_get_head_period_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "period", 3));
         let n1 = dag.add_node(create_node(0, "period", 4));
 
@@ -1006,7 +1409,7 @@ mod tests {
 
     #[test]
     fn test_get_head_period_node_no_includes_period() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "weight", 3));
 
         assert_eq!(dag.get_head_period(), None);
@@ -1014,7 +1417,7 @@ mod tests {
 
     #[test]
     fn test_get_all_periods_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "period", 3));
         let n1 = dag.add_node(create_node(0, "period", 4));
 
@@ -1028,7 +1431,7 @@ mod tests {
 
     #[test]
     fn test_get_all_periods_node_no_includes_period() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "execution_time", 3));
 
         assert_eq!(dag.get_all_periods(), None);
@@ -1036,7 +1439,7 @@ mod tests {
 
     #[test]
     fn test_get_offset_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "offset", 3));
 
         assert_eq!(dag.get_head_offset(), 3);
@@ -1044,7 +1447,7 @@ mod tests {
 
     #[test]
     fn test_get_offset_multiple() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "offset", 3));
         dag.add_node(create_node(1, "offset", 2));
 
@@ -1053,14 +1456,14 @@ mod tests {
 
     #[test]
     fn test_get_offset_no_exist() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
 
         assert_eq!(dag.get_head_offset(), 0);
     }
 
     #[test]
     fn test_get_pre_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -1072,7 +1475,7 @@ mod tests {
 
     #[test]
     fn test_get_pre_nodes_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         dag.add_edge(n0, n1, 1);
@@ -1082,7 +1485,7 @@ mod tests {
 
     #[test]
     fn test_get_pre_nodes_no_exist_pre_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
 
         assert_eq!(dag.get_pre_nodes(n0), None);
@@ -1091,7 +1494,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_pre_nodes_no_exist_target_node() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         let invalid_node = NodeIndex::new(999);
 
         assert_eq!(dag.get_pre_nodes(invalid_node), None);
@@ -1099,7 +1502,7 @@ mod tests {
 
     #[test]
     fn test_get_suc_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -1111,7 +1514,7 @@ mod tests {
 
     #[test]
     fn test_get_suc_nodes_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         dag.add_edge(n0, n1, 1);
@@ -1121,7 +1524,7 @@ mod tests {
 
     #[test]
     fn test_get_suc_nodes_no_exist_suc_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
 
         assert_eq!(dag.get_suc_nodes(n0), None);
@@ -1130,7 +1533,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_suc_nodes_no_exist_target_node() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         let invalid_node = NodeIndex::new(999);
 
         assert_eq!(dag.get_suc_nodes(invalid_node), None);
@@ -1138,7 +1541,7 @@ mod tests {
 
     #[test]
     fn test_get_anc_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -1152,7 +1555,7 @@ mod tests {
 
     #[test]
     fn test_get_anc_nodes_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         dag.add_edge(n0, n1, 1);
@@ -1162,7 +1565,7 @@ mod tests {
 
     #[test]
     fn test_get_anc_nodes_no_exist_anc_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
 
         assert_eq!(dag.get_anc_nodes(n0), None);
@@ -1171,7 +1574,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_anc_nodes_no_exist_target_node() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         let invalid_node = NodeIndex::new(999);
 
         assert_eq!(dag.get_anc_nodes(invalid_node), None);
@@ -1179,7 +1582,7 @@ mod tests {
 
     #[test]
     fn test_get_des_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         let n2 = dag.add_node(create_node(2, "execution_time", 0));
@@ -1193,7 +1596,7 @@ mod tests {
 
     #[test]
     fn test_get_des_nodes_single() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         dag.add_edge(n0, n1, 1);
@@ -1203,7 +1606,7 @@ mod tests {
 
     #[test]
     fn test_get_des_nodes_no_exist_des_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
 
         assert_eq!(dag.get_des_nodes(n0), None);
@@ -1212,15 +1615,53 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_des_nodes_no_exist_target_node() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         let invalid_node = NodeIndex::new(999);
 
         assert_eq!(dag.get_des_nodes(invalid_node), None);
     }
 
+    #[test]
+    fn test_is_reachable_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        dag.add_edge(n0, n1, 1);
+
+        assert!(dag.is_reachable(n0, n1));
+        assert!(!dag.is_reachable(n1, n0));
+        assert!(!dag.is_reachable(n0, n2));
+
+        dag.add_edge(n1, n2, 1);
+        assert!(dag.is_reachable(n0, n2));
+    }
+
+    #[test]
+    fn test_get_max_parallelism_and_level_widths() {
+        // The sinks n1, n3 and n5 branch off the spine n0 -> n2 -> n4 at different levels.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "execution_time", 1));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        let n4 = dag.add_node(create_node(4, "execution_time", 1));
+        let n5 = dag.add_node(create_node(5, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n2, n3, 1);
+        dag.add_edge(n2, n4, 1);
+        dag.add_edge(n4, n5, 1);
+
+        assert_eq!(dag.get_level_widths(), vec![1, 2, 2, 1]);
+        // The sinks are parallel although no level has three nodes.
+        assert_eq!(dag.get_max_parallelism(), 3);
+        assert!(Graph::<NodeData, i64>::new().get_level_widths().is_empty());
+    }
+
     #[test]
     fn get_parallel_process_nodes_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "parallel_process", 0));
         let n1 = dag.add_node(create_node(1, "parallel_process", 0));
         let n2 = dag.add_node(create_node(2, "parallel_process", 0));
@@ -1235,7 +1676,7 @@ mod tests {
 
     #[test]
     fn get_parallel_process_nodes_no_exist_parallel_process_nodes() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "parallel_process", 0));
 
         assert_eq!(dag.get_parallel_process_nodes(n0), None);
@@ -1243,7 +1684,7 @@ mod tests {
 
     #[test]
     fn test_get_dag_id_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "dag_id", 0));
         assert_eq!(dag.get_dag_param("dag_id"), 0);
     }
@@ -1251,13 +1692,13 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_dag_id_no_exist_node() {
-        let dag = Graph::<NodeData, i32>::new();
+        let dag = Graph::<NodeData, i64>::new();
         dag.get_dag_param("dag_id");
     }
 
     #[test]
     fn test_set_dag_param_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node(create_node(0, "execution_time", 0));
         dag.add_node(create_node(1, "execution_time", 0));
         dag.set_dag_param("dag_id", 0);
@@ -1270,13 +1711,13 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_set_dag_param_no_exist_node() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.set_dag_param("dag_id", 0);
     }
 
     #[test]
     fn test_add_node_with_id_consistency_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
 
         let n0 = dag.add_node_with_id_consistency(create_node(0, "execution_time", 3));
         let n1 = dag.add_node_with_id_consistency(create_node(1, "execution_time", 3));
@@ -1288,14 +1729,14 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_add_node_with_id_consistency_id_duplication() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         dag.add_node_with_id_consistency(create_node(0, "execution_time", 3));
         dag.add_node_with_id_consistency(create_node(0, "execution_time", 3));
     }
 
     #[test]
     fn test_is_node_ready_normal() {
-        let mut dag = Graph::<NodeData, i32>::new();
+        let mut dag = Graph::<NodeData, i64>::new();
         let n0 = dag.add_node(create_node(0, "execution_time", 0));
         let n1 = dag.add_node(create_node(1, "execution_time", 0));
         dag.add_edge(n0, n1, 1);
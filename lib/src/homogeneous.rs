@@ -1,24 +1,59 @@
 //! Homogeneous processor module. This module uses Core struct.
-use crate::{core::Core, core::ProcessResult, graph_extension::NodeData, processor::ProcessorBase};
+//! The remaining processing times of the cores are kept in a compact vector owned by the processor,
+//! so that a tick over many cores advances them in one loop.
+use crate::{
+    core::Core,
+    core::ProcessResult,
+    execution_time_distribution::draw_execution_time,
+    graph_extension::NodeData,
+    job_rng::{create_job_rng, RandomStream},
+    processor::ProcessorBase,
+};
 
 #[derive(Clone, Debug)]
 pub struct HomogeneousProcessor {
     pub cores: Vec<Core>,
+    /// The remaining processing time of the node on each core, indexed by the core id.
+    pub remain_proc_times: Vec<i64>,
+    execution_time_seed: Option<u64>,
 }
 
 impl ProcessorBase for HomogeneousProcessor {
     fn new(num_cores: usize) -> Self {
         Self {
             cores: vec![Core::default(); num_cores],
+            remain_proc_times: vec![0; num_cores],
+            execution_time_seed: None,
         }
     }
 
     fn allocate_specific_core(&mut self, core_id: usize, node_data: &NodeData) -> bool {
-        self.cores[core_id].allocate(node_data)
+        let node_data = self.draw_job_execution_time(node_data);
+        self.cores[core_id].allocate(&node_data, &mut self.remain_proc_times[core_id])
     }
 
+    /// Advance the remaining times of all cores in one loop and collect the indices of the completed cores,
+    /// and move the completed nodes out afterwards by core index.
+    /// Completions are rare compared to ticks, so the loop over many cores only updates the counters.
     fn process(&mut self) -> Vec<ProcessResult> {
-        self.cores.iter_mut().map(|core| core.process()).collect()
+        let mut process_results = Vec::with_capacity(self.cores.len());
+        let mut completed_core_ids = Vec::new();
+        for (core_id, (core, remain_proc_time)) in self
+            .cores
+            .iter_mut()
+            .zip(self.remain_proc_times.iter_mut())
+            .enumerate()
+        {
+            let process_result = core.tick(remain_proc_time);
+            if core.is_completed(*remain_proc_time, &process_result) {
+                completed_core_ids.push(core_id);
+            }
+            process_results.push(process_result);
+        }
+        for core_id in completed_core_ids {
+            process_results[core_id] = ProcessResult::Done(self.cores[core_id].complete());
+        }
+        process_results
     }
 
     fn get_number_of_cores(&self) -> usize {
@@ -29,6 +64,10 @@ impl ProcessorBase for HomogeneousProcessor {
         self.cores.iter().filter(|core| core.get_is_idle()).count()
     }
 
+    fn get_processing_node(&self, core_id: usize) -> Option<&NodeData> {
+        self.cores[core_id].get_processing_node().as_ref()
+    }
+
     fn get_idle_core_index(&self) -> Option<usize> {
         for (index, core) in self.cores.iter().enumerate() {
             if core.get_is_idle() {
@@ -38,11 +77,74 @@ impl ProcessorBase for HomogeneousProcessor {
         None
     }
 
+    fn get_idle_core_index_with_affinity(&self, node_data: &NodeData) -> Option<usize> {
+        self.cores
+            .iter()
+            .enumerate()
+            .find(|(index, core)| core.get_is_idle() && node_data.is_allowed_on_core(*index))
+            .map(|(index, _)| index)
+    }
+
     fn preempt(&mut self, core_id: usize) -> Option<NodeData> {
-        self.cores[core_id].preempt()
+        self.cores[core_id].preempt(&mut self.remain_proc_times[core_id])
+    }
+
+    fn fail_core(&mut self, core_id: usize) -> Option<NodeData> {
+        self.cores[core_id].fail(&mut self.remain_proc_times[core_id])
+    }
+
+    fn set_overhead(&mut self, dispatch_overhead: i64, preemption_overhead: i64) {
+        for core in self.cores.iter_mut() {
+            core.set_overhead(dispatch_overhead, preemption_overhead);
+        }
+    }
+
+    fn set_clock_drift(&mut self, core_id: usize, clock_drift_ppm: i64) {
+        self.cores[core_id].set_clock_drift(clock_drift_ppm);
     }
 
-    fn get_max_value_and_index(&self, key: &str) -> Option<(i32, usize)> {
+    /// Make the core spend `delay_time` before processing, e.g. waiting for data transfers.
+    fn delay_core(&mut self, core_id: usize, delay_time: i64) {
+        self.cores[core_id].remain_overhead_time += delay_time;
+    }
+
+    fn set_execution_time_seed(&mut self, seed: u64) {
+        self.execution_time_seed = Some(seed);
+    }
+
+    fn get_execution_time_seed(&self) -> Option<u64> {
+        self.execution_time_seed
+    }
+
+    fn enqueue_to_core(&mut self, core_id: usize, node_data: &NodeData) {
+        self.cores[core_id]
+            .dispatch_queue
+            .push_back(node_data.clone());
+    }
+
+    fn dequeue_from_core(&mut self, core_id: usize) -> Option<NodeData> {
+        self.cores[core_id].dispatch_queue.pop_front()
+    }
+
+    fn get_queued_head(&self, core_id: usize) -> Option<&NodeData> {
+        self.cores[core_id].dispatch_queue.front()
+    }
+
+    fn get_queued_node_num(&self, core_id: usize) -> usize {
+        self.cores[core_id].dispatch_queue.len()
+    }
+
+    /// The remaining time of the processing node plus the execution time of the queued nodes.
+    fn get_backlog_time(&self, core_id: usize) -> i64 {
+        self.remain_proc_times[core_id]
+            + self.cores[core_id]
+                .dispatch_queue
+                .iter()
+                .map(|node_data| node_data.get_params_value("execution_time"))
+                .sum::<i64>()
+    }
+
+    fn get_max_value_and_index(&self, key: &str) -> Option<(i64, usize)> {
         self.cores
             .iter()
             .enumerate()
@@ -53,12 +155,65 @@ impl ProcessorBase for HomogeneousProcessor {
             })
             .max_by_key(|&(value, _)| value)
     }
+
+    fn get_max_value_and_index_with_affinity(
+        &self,
+        key: &str,
+        node_data: &NodeData,
+    ) -> Option<(i64, usize)> {
+        self.cores
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| node_data.is_allowed_on_core(*index))
+            .filter_map(|(index, core)| {
+                let processing_node_data = core.get_processing_node().as_ref()?;
+                let value = processing_node_data.params.get(key)?;
+                Some((*value, index))
+            })
+            .max_by_key(|&(value, _)| value)
+    }
+
+    /// All the cores run at the speed at which the execution times are given.
+    fn get_core_speed(&self, _core_id: usize) -> f64 {
+        1.0
+    }
+}
+
+/// A resumed job keeps the execution time drawn when it started.
+/// The execution time is keyed by the job, given by `dag_id` and `job_id` or 0 without them,
+/// so the same job gets the same execution time under any scheduler and even when it is executed again.
+fn with_drawn_execution_time(node_data: &NodeData, seed: u64) -> NodeData {
+    let mut node_data = node_data.clone();
+    if !node_data.params.contains_key("is_preempted") {
+        let get_key = |key: &str| node_data.params.get(key).copied().unwrap_or(0) as usize;
+        let mut rng = create_job_rng(
+            seed,
+            RandomStream::ExecutionTime,
+            get_key("dag_id"),
+            node_data.id as usize,
+            get_key("job_id"),
+        );
+        if let Some(execution_time) = draw_execution_time(&node_data, &mut rng) {
+            node_data
+                .params
+                .insert("execution_time".to_string(), execution_time);
+        }
+    }
+    node_data
 }
 
 impl HomogeneousProcessor {
+    fn draw_job_execution_time(&mut self, node_data: &NodeData) -> NodeData {
+        match self.execution_time_seed {
+            Some(seed) => with_drawn_execution_time(node_data, seed),
+            None => node_data.clone(),
+        }
+    }
+
     pub fn allocate_any_idle_core(&mut self, node_data: &NodeData) -> bool {
         if let Some(idle_core_i) = self.get_idle_core_index() {
-            self.cores[idle_core_i].allocate(node_data)
+            let node_data = self.draw_job_execution_time(node_data);
+            self.cores[idle_core_i].allocate(&node_data, &mut self.remain_proc_times[idle_core_i])
         } else {
             false
         }
@@ -71,7 +226,7 @@ mod tests {
     use crate::{core::ProcessResult, graph_extension::NodeData, processor::ProcessorBase};
     use std::collections::BTreeMap;
 
-    fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
         let mut params = BTreeMap::new();
         params.insert(key.to_string(), value);
         NodeData { id, params }
@@ -84,8 +239,8 @@ mod tests {
         for core in &homogeneous_processor.cores {
             assert!(core.is_idle);
             assert_eq!(core.processing_node, None);
-            assert_eq!(core.remain_proc_time, 0);
         }
+        assert_eq!(homogeneous_processor.remain_proc_times, vec![0, 0]);
     }
 
     #[test]
@@ -146,8 +301,8 @@ mod tests {
             homogeneous_processor.process(),
             vec![ProcessResult::Continue, ProcessResult::Continue]
         );
-        assert_eq!(homogeneous_processor.cores[0].remain_proc_time, 1);
-        assert_eq!(homogeneous_processor.cores[1].remain_proc_time, 2);
+        assert_eq!(homogeneous_processor.remain_proc_times[0], 1);
+        assert_eq!(homogeneous_processor.remain_proc_times[1], 2);
     }
 
     #[test]
@@ -182,6 +337,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_processor_process_many_cores() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(128);
+        for core_id in 0..128 {
+            homogeneous_processor.allocate_specific_core(
+                core_id,
+                &create_node(core_id as i64, "execution_time", core_id as i64 % 3 + 1),
+            );
+        }
+        homogeneous_processor.delay_core(0, 1);
+
+        let process_result = homogeneous_processor.process();
+        assert_eq!(process_result[0], ProcessResult::Overhead);
+        for (core_id, result) in process_result.iter().enumerate().skip(1) {
+            if core_id % 3 == 0 {
+                assert_eq!(
+                    *result,
+                    ProcessResult::Done(create_node(core_id as i64, "execution_time", 1))
+                );
+                assert!(homogeneous_processor.cores[core_id].is_idle);
+            } else {
+                assert_eq!(*result, ProcessResult::Continue);
+            }
+        }
+        assert_eq!(homogeneous_processor.get_idle_core_num(), 42);
+    }
+
     #[test]
     fn test_processor_get_idle_core_index_normal() {
         let mut homogeneous_processor = HomogeneousProcessor::new(2);
@@ -240,6 +422,66 @@ mod tests {
         assert_eq!(homogeneous_processor.preempt(0), None);
     }
 
+    #[test]
+    fn test_processor_fail_core_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(2);
+        let n0 = create_node(0, "execution_time", 2);
+
+        homogeneous_processor.allocate_specific_core(0, &n0);
+        homogeneous_processor.process();
+
+        assert_eq!(homogeneous_processor.fail_core(0), Some(n0.clone()));
+        assert_eq!(homogeneous_processor.get_idle_core_num(), 1);
+        assert_eq!(homogeneous_processor.get_idle_core_index(), Some(1));
+        assert!(!homogeneous_processor.allocate_specific_core(0, &n0));
+    }
+
+    #[test]
+    fn test_processor_get_idle_core_index_with_affinity_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(3);
+        let n0 = create_node(0, "execution_time", 2);
+        let n1 = create_node(1, "allowed_cores", 0b110);
+
+        assert_eq!(
+            homogeneous_processor.get_idle_core_index_with_affinity(&n0),
+            Some(0)
+        );
+        assert_eq!(
+            homogeneous_processor.get_idle_core_index_with_affinity(&n1),
+            Some(1)
+        );
+
+        homogeneous_processor.allocate_specific_core(1, &n0);
+        homogeneous_processor.allocate_specific_core(2, &n0);
+
+        assert_eq!(
+            homogeneous_processor.get_idle_core_index_with_affinity(&n1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_max_value_index_with_affinity() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(2);
+        homogeneous_processor.allocate_specific_core(0, &create_node(0, "execution_time", 10));
+        homogeneous_processor.allocate_specific_core(1, &create_node(1, "execution_time", 11));
+
+        assert_eq!(
+            homogeneous_processor.get_max_value_and_index_with_affinity(
+                "execution_time",
+                &create_node(2, "allowed_cores", 0b01)
+            ),
+            Some((10, 0))
+        );
+        assert_eq!(
+            homogeneous_processor.get_max_value_and_index_with_affinity(
+                "execution_time",
+                &create_node(2, "execution_time", 1)
+            ),
+            Some((11, 1))
+        );
+    }
+
     #[test]
     fn test_get_max_value_index() {
         let mut homogeneous_processor = HomogeneousProcessor::new(2);
@@ -265,4 +507,116 @@ mod tests {
             Some((11, 1))
         );
     }
+
+    #[test]
+    fn test_processor_dispatch_queued_nodes_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(2);
+        let n0 = create_node(0, "execution_time", 2);
+        let n1 = create_node(1, "execution_time", 3);
+        homogeneous_processor.enqueue_to_core(0, &n0);
+        homogeneous_processor.enqueue_to_core(0, &n1);
+
+        assert_eq!(homogeneous_processor.get_queued_node_num(0), 2);
+        assert_eq!(homogeneous_processor.get_queued_node_num(1), 0);
+        assert_eq!(homogeneous_processor.get_backlog_time(0), 5);
+
+        // Only the head of the queue is dispatched, and the other core stays idle.
+        assert_eq!(
+            homogeneous_processor.dispatch_queued_nodes(),
+            vec![(0, n0.clone())]
+        );
+        assert!(homogeneous_processor.cores[1].is_idle);
+        assert!(homogeneous_processor.dispatch_queued_nodes().is_empty());
+
+        homogeneous_processor.process();
+        assert_eq!(homogeneous_processor.get_backlog_time(0), 4);
+        homogeneous_processor.process();
+        assert_eq!(
+            homogeneous_processor.dispatch_queued_nodes(),
+            vec![(0, n1.clone())]
+        );
+        assert_eq!(homogeneous_processor.get_queued_node_num(0), 0);
+    }
+
+    #[test]
+    fn test_processor_dispatch_queued_nodes_failed_core() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        let n0 = create_node(0, "execution_time", 2);
+        homogeneous_processor.fail_core(0);
+        homogeneous_processor.enqueue_to_core(0, &n0);
+
+        // The node that the failed core rejects stays at the head of the queue.
+        assert!(homogeneous_processor.dispatch_queued_nodes().is_empty());
+        assert_eq!(homogeneous_processor.get_queued_head(0), Some(&n0));
+    }
+
+    #[test]
+    fn test_processor_dequeue_from_core_normal() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        let n0 = create_node(0, "execution_time", 2);
+        homogeneous_processor.enqueue_to_core(0, &n0);
+
+        assert_eq!(homogeneous_processor.dequeue_from_core(0), Some(n0));
+        assert_eq!(homogeneous_processor.dequeue_from_core(0), None);
+    }
+
+    #[test]
+    fn test_processor_allocate_with_execution_time_seed() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        homogeneous_processor.set_execution_time_seed(0);
+        assert_eq!(homogeneous_processor.get_execution_time_seed(), Some(0));
+        let mut n0 = create_node(0, "execution_time", 10);
+        n0.params.insert("execution_time_sample_0".to_string(), 3);
+
+        homogeneous_processor.allocate_specific_core(0, &n0);
+        assert_eq!(homogeneous_processor.remain_proc_times[0], 3);
+
+        // The resumed job is not drawn again.
+        homogeneous_processor.process();
+        let preempted_n0 = homogeneous_processor.preempt(0).unwrap();
+        homogeneous_processor.allocate_specific_core(0, &preempted_n0);
+        assert_eq!(homogeneous_processor.remain_proc_times[0], 2);
+    }
+
+    #[test]
+    fn test_processor_execution_time_independent_of_allocation_order() {
+        let create_job = |node_id: i64, job_id: i64| {
+            let mut node_data = create_node(node_id, "execution_time", 100);
+            for sample_i in 0..100 {
+                node_data
+                    .params
+                    .insert(format!("execution_time_sample_{}", sample_i), sample_i + 1);
+            }
+            node_data.params.insert("job_id".to_string(), job_id);
+            node_data
+        };
+        let jobs = [create_job(0, 0), create_job(1, 0), create_job(0, 1)];
+
+        let mut homogeneous_processor = HomogeneousProcessor::new(3);
+        homogeneous_processor.set_execution_time_seed(0);
+        let mut reversed_processor = homogeneous_processor.clone();
+        for (core_id, job) in jobs.iter().enumerate() {
+            homogeneous_processor.allocate_specific_core(core_id, job);
+        }
+        for (core_id, job) in jobs.iter().enumerate().rev() {
+            reversed_processor.allocate_specific_core(core_id, job);
+        }
+
+        for core_id in 0..3 {
+            assert_eq!(
+                homogeneous_processor.remain_proc_times[core_id],
+                reversed_processor.remain_proc_times[core_id]
+            );
+        }
+    }
+
+    #[test]
+    fn test_processor_allocate_without_execution_time_seed() {
+        let mut homogeneous_processor = HomogeneousProcessor::new(1);
+        let mut n0 = create_node(0, "execution_time", 10);
+        n0.params.insert("execution_time_sample_0".to_string(), 3);
+
+        homogeneous_processor.allocate_specific_core(0, &n0);
+        assert_eq!(homogeneous_processor.remain_proc_times[0], 10);
+    }
 }
@@ -0,0 +1,192 @@
+//! Unrolling of a periodic DAG set into a single super-DAG spanning one hyper period,
+//! for the offline, table-driven schedulers and the ILP backends that take the jobs as a static graph.
+//! Each job of each DAG in the hyper period is a copy of the DAG, and the copies are not connected.
+//! A node of the super-DAG has the release time of its job as `release_offset` and its deadline as `end_to_end_deadline`,
+//! both relative to the start of the hyper period, and traces back to the DAG set by
+//! `original_dag_id`, `original_node_id` and `job_index`.
+//! The super-DAG is also simulated as a DAG of the hyper period, because its nodes are released by their offsets.
+use crate::{
+    error::Result,
+    graph_extension::{GraphExtension, NodeData},
+    util::get_hyper_period,
+};
+use petgraph::{visit::EdgeRef, Graph};
+use std::collections::HashMap;
+
+/// Unroll the DAG set into the super-DAG of its jobs in one hyper period.
+/// The jobs of a DAG start at its `offset`, and a sink without an end-to-end deadline has the implicit one.
+/// A multi-rate DAG must be unrolled over its own hyper period beforehand,
+/// whose instances keep their offsets in the job and their `original_node_id`.
+pub fn unroll_dag_set_over_hyper_period(
+    dag_set: &[Graph<NodeData, i64>],
+) -> Result<Graph<NodeData, i64>> {
+    let hyper_period = get_hyper_period(dag_set)?;
+    let mut super_dag = Graph::<NodeData, i64>::new();
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let period = dag.get_head_period().unwrap();
+        let head_offset = dag
+            .get_source_nodes()
+            .iter()
+            .find_map(|source_i| dag[*source_i].params.get("offset").copied())
+            .unwrap_or(0);
+        for job_index in 0..hyper_period / period {
+            let job_release_offset = head_offset + job_index * period;
+            let mut node_indices = HashMap::new();
+            for node_i in dag.node_indices() {
+                let mut node_data = dag[node_i].clone();
+                node_data.id = super_dag.node_count() as i64;
+                for key in ["dag_id", "period", "offset", "release_time"] {
+                    node_data.params.remove(key);
+                }
+                node_data
+                    .params
+                    .entry("original_node_id".to_string())
+                    .or_insert(dag[node_i].id);
+                node_data
+                    .params
+                    .insert("original_dag_id".to_string(), dag_id as i64);
+                node_data.params.insert("job_index".to_string(), job_index);
+                let release_offset = node_data.params.get("release_offset").unwrap_or(&0);
+                node_data.params.insert(
+                    "release_offset".to_string(),
+                    job_release_offset + release_offset,
+                );
+                let end_to_end_deadline = match node_data.params.get("end_to_end_deadline") {
+                    Some(end_to_end_deadline) => Some(*end_to_end_deadline),
+                    None if dag.get_suc_nodes(node_i).is_none() => Some(period),
+                    None => None,
+                };
+                if let Some(end_to_end_deadline) = end_to_end_deadline {
+                    node_data.params.insert(
+                        "end_to_end_deadline".to_string(),
+                        job_release_offset + end_to_end_deadline,
+                    );
+                }
+                node_indices.insert(node_i, super_dag.add_node_with_id_consistency(node_data));
+            }
+            for edge in dag.edge_references() {
+                super_dag.add_edge(
+                    node_indices[&edge.source()],
+                    node_indices[&edge.target()],
+                    *edge.weight(),
+                );
+            }
+        }
+    }
+
+    if let Some(&head_i) = super_dag.get_source_nodes().first() {
+        super_dag[head_i]
+            .params
+            .insert("period".to_string(), hyper_period);
+    }
+    Ok(super_dag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dag_creator::create_dag_from_yaml_str,
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        homogeneous::HomogeneousProcessor,
+        multi_rate::unroll_multi_rate_dag,
+        processor::ProcessorBase,
+    };
+    use petgraph::graph::NodeIndex;
+
+    fn create_dag_set() -> Vec<Graph<NodeData, i64>> {
+        [
+            "nodes: [{id: 0, execution_time: 2, period: 10}, {id: 1, execution_time: 3, end_to_end_deadline: 8}]\nlinks: [{source: 0, target: 1, communication_time: 1}]",
+            "nodes: [{id: 0, execution_time: 4, period: 20, offset: 5}]\nlinks: []",
+        ]
+        .iter()
+        .map(|yaml_str| create_dag_from_yaml_str(yaml_str, false).unwrap())
+        .collect()
+    }
+
+    fn get_params_values(super_dag: &Graph<NodeData, i64>, key: &str) -> Vec<Option<i64>> {
+        super_dag
+            .node_weights()
+            .map(|node| node.params.get(key).copied())
+            .collect()
+    }
+
+    #[test]
+    fn test_unroll_dag_set_over_hyper_period_normal() {
+        let super_dag = unroll_dag_set_over_hyper_period(&create_dag_set()).unwrap();
+
+        assert_eq!(super_dag.node_count(), 5);
+        assert_eq!(super_dag.edge_count(), 2);
+        assert_eq!(super_dag.get_head_period(), Some(20));
+        assert_eq!(
+            get_params_values(&super_dag, "original_dag_id"),
+            vec![Some(0), Some(0), Some(0), Some(0), Some(1)]
+        );
+        assert_eq!(
+            get_params_values(&super_dag, "job_index"),
+            vec![Some(0), Some(0), Some(1), Some(1), Some(0)]
+        );
+        assert_eq!(
+            get_params_values(&super_dag, "release_offset"),
+            vec![Some(0), Some(0), Some(10), Some(10), Some(5)]
+        );
+        assert_eq!(
+            super_dag.get_deadlines_per_sink(),
+            vec![
+                (NodeIndex::new(1), 8),
+                (NodeIndex::new(3), 18),
+                (NodeIndex::new(4), 25)
+            ]
+        );
+        assert_eq!(
+            super_dag
+                .edge_references()
+                .map(|edge| (edge.source().index(), edge.target().index(), *edge.weight()))
+                .collect::<Vec<_>>(),
+            vec![(0, 1, 1), (2, 3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_unroll_dag_set_over_hyper_period_multi_rate() {
+        let multi_rate_dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 1, period: 10}, {id: 1, execution_time: 1, period: 20}]\nlinks: []",
+            false,
+        )
+        .unwrap();
+        let dag_set = vec![
+            unroll_multi_rate_dag(&multi_rate_dag).unwrap(),
+            create_dag_set().remove(1),
+        ];
+        let super_dag = unroll_dag_set_over_hyper_period(&dag_set).unwrap();
+
+        // The instances of the multi-rate DAG keep their offsets and their original node ids.
+        assert_eq!(
+            get_params_values(&super_dag, "release_offset"),
+            vec![Some(0), Some(10), Some(0), Some(5)]
+        );
+        assert_eq!(
+            get_params_values(&super_dag, "original_node_id"),
+            vec![Some(0), Some(0), Some(1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_schedule_super_dag() {
+        let mut super_dag = unroll_dag_set_over_hyper_period(&create_dag_set()).unwrap();
+        super_dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[super_dag], &HomogeneousProcessor::new(1));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        // The second job of DAG 0 is released at 10, after the only job of DAG 1 released at 5.
+        assert_eq!(schedule_outcome.log.get_node_finish_time(0, 4, 0), Some(9));
+        assert_eq!(schedule_outcome.log.get_node_finish_time(0, 2, 0), Some(12));
+        assert_eq!(schedule_outcome.worst_response_times, vec![15]);
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![false]);
+    }
+}
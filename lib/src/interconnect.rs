@@ -0,0 +1,56 @@
+//! Shared interconnect (bus or NoC) over which the data of the edges is transferred between cores.
+//! The bandwidth is modeled as the number of channels, and transfers exceeding it are serialized.
+//! The edge weight (`communication_time`) is the time a transfer occupies a channel.
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interconnect {
+    channel_free_times: Vec<i64>,
+}
+
+impl Interconnect {
+    pub fn new(num_channels: usize) -> Self {
+        if num_channels == 0 {
+            panic!("The interconnect requires at least one channel.");
+        }
+        Self {
+            channel_free_times: vec![0; num_channels],
+        }
+    }
+
+    /// Reserve the channel that becomes free first for a transfer which can start at `ready_time`.
+    /// Returns the (start time, finish time) of the transfer.
+    pub fn reserve_transfer(&mut self, ready_time: i64, communication_time: i64) -> (i64, i64) {
+        let channel_free_time = self.channel_free_times.iter_mut().min().unwrap();
+        let start_time = ready_time.max(*channel_free_time);
+        let finish_time = start_time + communication_time;
+        *channel_free_time = finish_time;
+        (start_time, finish_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_transfer_serialized_on_bus() {
+        let mut interconnect = Interconnect::new(1);
+        assert_eq!(interconnect.reserve_transfer(0, 5), (0, 5));
+        assert_eq!(interconnect.reserve_transfer(2, 3), (5, 8));
+        assert_eq!(interconnect.reserve_transfer(10, 3), (10, 13));
+    }
+
+    #[test]
+    fn test_reserve_transfer_multiple_channels() {
+        let mut interconnect = Interconnect::new(2);
+        assert_eq!(interconnect.reserve_transfer(0, 5), (0, 5));
+        assert_eq!(interconnect.reserve_transfer(0, 3), (0, 3));
+        assert_eq!(interconnect.reserve_transfer(1, 4), (3, 7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_no_channel() {
+        Interconnect::new(0);
+    }
+}
@@ -0,0 +1,61 @@
+//! Random streams keyed by the job rather than by the draw order (common random numbers).
+//! Each job of a node gets its own generator seeded from the seed and the key (dag_id, node_id, job_id),
+//! so the value drawn for a job does not depend on how many draws the other jobs made before it.
+//! Hence, different schedulers run with the same seed see the same random values for each job,
+//! and the difference between their results is due to the scheduling rather than to the random draws.
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The randomized features draw from independent streams for the same job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomStream {
+    ReleaseJitter,
+    ExecutionTime,
+}
+
+/// The finalizer of SplitMix64, which spreads close keys over the whole seed space.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+pub fn create_job_rng(
+    seed: u64,
+    stream: RandomStream,
+    dag_id: usize,
+    node_id: usize,
+    job_id: usize,
+) -> StdRng {
+    let job_seed = [stream as u64, dag_id as u64, node_id as u64, job_id as u64]
+        .iter()
+        .fold(mix(seed), |state, key| mix(state ^ key));
+    StdRng::seed_from_u64(job_seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draw(seed: u64, stream: RandomStream, key: (usize, usize, usize)) -> u64 {
+        create_job_rng(seed, stream, key.0, key.1, key.2).gen()
+    }
+
+    #[test]
+    fn test_create_job_rng_same_key() {
+        assert_eq!(
+            draw(0, RandomStream::ExecutionTime, (1, 2, 3)),
+            draw(0, RandomStream::ExecutionTime, (1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn test_create_job_rng_different_keys() {
+        let value = draw(0, RandomStream::ExecutionTime, (1, 2, 3));
+        assert_ne!(value, draw(1, RandomStream::ExecutionTime, (1, 2, 3)));
+        assert_ne!(value, draw(0, RandomStream::ReleaseJitter, (1, 2, 3)));
+        assert_ne!(value, draw(0, RandomStream::ExecutionTime, (2, 1, 3)));
+        assert_ne!(value, draw(0, RandomStream::ExecutionTime, (1, 2, 4)));
+    }
+}
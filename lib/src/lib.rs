@@ -1,11 +1,54 @@
+pub mod admission_control;
+pub mod analysis;
+pub mod aperiodic_arrival;
+pub mod cause_effect_chain;
+pub mod conflict_analysis;
 pub mod core;
+pub mod critical_path;
+pub mod dag_builder;
 pub mod dag_creator;
+pub mod dag_diff;
 pub mod dag_scheduler;
 pub mod dag_set_scheduler;
+pub mod dag_set_statistics;
+pub mod diagnostics;
+pub mod energy_accounting;
+pub mod error;
+pub mod event_script;
+pub mod execution_time_distribution;
 pub mod fixed_priority_scheduler;
 pub mod global_edf_scheduler;
+#[cfg(feature = "file_io")]
+pub mod golden;
 pub mod graph_extension;
 pub mod homogeneous;
+pub mod hyper_period_unrolling;
+pub mod interconnect;
+pub mod job_rng;
 pub mod log;
+pub mod logical_execution_time;
+pub mod mode_change;
+pub mod multi_rate;
+pub mod network;
+pub mod node_params;
+pub mod non_preemptive_section;
+#[cfg(feature = "arrow")]
+pub mod parquet_export;
 pub mod processor;
+pub mod proportional_share_scheduler;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod reachability;
+pub mod round_robin_scheduler;
+pub mod run_metadata;
+pub mod schedulability_test;
+pub mod scheduler_factory;
+pub mod simulation_horizon;
+pub mod sporadic_release;
+pub mod tardiness_analysis;
+pub mod transformations;
 pub mod util;
+pub mod visualization;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod workload_calibration;
@@ -1,15 +1,49 @@
+use crate::analysis::ResponseTimeBound;
+use crate::critical_path::CriticalPathAnalysis;
+use crate::dag_set_scheduler::DeadlineMissPolicy;
+use crate::diagnostics::{report, DiagnosticKind};
+use crate::energy_accounting::EnergyLog;
+use crate::error::{Result, SchedSimError};
 use crate::graph_extension::{GraphExtension, NodeData};
-use crate::util::append_info_to_yaml;
-use log::warn;
-use petgraph::Graph;
-use serde::Serialize;
+use crate::logical_execution_time::CommunicationSemantics;
+use crate::run_metadata::RunMetadata;
+use crate::schedulability_test::SchedulabilityVerdict;
+use petgraph::{graph::NodeIndex, Graph};
 use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
+#[cfg(feature = "arrow")]
+use {
+    crate::parquet_export::write_columns_to_parquet,
+    arrow::array::{ArrayRef, Float32Array, Int64Array, StringArray, UInt64Array},
+    std::sync::Arc,
+};
+#[cfg(feature = "file_io")]
+use {
+    crate::schedulability_test::SchedulabilityVerdicts,
+    crate::util::{append_info_to_yaml, convert_yaml_to_json, load_yaml_struct},
+    log::warn,
+    serde::Serialize,
+    std::fs,
+};
 
+#[cfg(feature = "file_io")]
 pub fn dump_struct(file_path: &str, target_struct: &impl Serialize) {
     let yaml = serde_yaml::to_string(&target_struct).expect("Failed to serialize.");
     append_info_to_yaml(file_path, &yaml);
 }
 
+/// Unlike dump_struct, the file is overwritten because JSON does not allow appending another object.
+#[cfg(feature = "file_io")]
+pub fn dump_struct_to_json(file_path: &str, target_struct: &impl Serialize) {
+    let json = serde_json::to_string_pretty(&target_struct).expect("Failed to serialize.");
+    if let Err(err) = fs::write(file_path, json) {
+        warn!("Failed to write to file: {}", err);
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGSetInfo {
     total_utilization: f32,
@@ -17,7 +51,7 @@ pub struct DAGSetInfo {
 }
 
 impl DAGSetInfo {
-    pub fn new(dag_set: &[Graph<NodeData, i32>]) -> Self {
+    pub fn new(dag_set: &[Graph<NodeData, i64>]) -> Self {
         let mut total_utilization = 0.0;
         let mut each_dag_info = Vec::new();
 
@@ -36,34 +70,37 @@ impl DAGSetInfo {
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGInfo {
-    critical_path_length: i32,
-    period: i32,
-    end_to_end_deadline: i32,
-    volume: i32,
+    critical_path_length: i64,
+    period: i64,
+    end_to_end_deadline: i64,
+    volume: i64,
     utilization: f32,
 }
 
 impl DAGInfo {
-    pub fn new(dag: &Graph<NodeData, i32>) -> Self {
+    pub fn new(dag: &Graph<NodeData, i64>) -> Self {
         let period = dag.get_head_period().unwrap_or(0);
         let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap_or(0);
         let volume = dag.get_volume();
         let utilization = match (end_to_end_deadline, period) {
             (0, 0) => {
-                warn!("Both period and end_to_end_deadline are not set.");
+                report(
+                    DiagnosticKind::MissingPeriod,
+                    "Both period and end_to_end_deadline are not set.",
+                );
                 0.0
             }
             (_, 0) => {
-                warn!("Period is not set.");
+                report(DiagnosticKind::MissingPeriod, "Period is not set.");
                 0.0
             }
             (0, _) => period as f32 / volume as f32,
             (_, _) => period as f32 / volume as f32,
         };
 
-        let critical_path = dag.clone().get_critical_path();
+        let critical_path_analysis = CriticalPathAnalysis::new(dag);
         Self {
-            critical_path_length: dag.get_total_wcet_from_nodes(&critical_path),
+            critical_path_length: critical_path_analysis.get_critical_path_length(),
             period,
             end_to_end_deadline,
             volume,
@@ -79,33 +116,141 @@ impl DAGInfo {
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct ProcessorInfo {
     number_of_cores: usize,
+    // Seed of the execution times drawn by the processor, to reproduce the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    execution_time_seed: Option<u64>,
 }
 
 impl ProcessorInfo {
     pub fn new(number_of_cores: usize) -> Self {
-        Self { number_of_cores }
+        Self {
+            number_of_cores,
+            execution_time_seed: None,
+        }
+    }
+}
+
+/// Tardiness is the time by which a job finishes after its soft deadline, and is 0 if it meets the deadline.
+/// Each tardiness is weighted by the `tardiness_weight` of the node (1 if not set).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TardinessStats {
+    total_tardiness: i64,
+    max_tardiness: i64,
+    weighted_tardiness: i64,
+}
+
+impl TardinessStats {
+    fn add(&mut self, finish_time: i64, absolute_deadline: i64, weight: i64) {
+        let tardiness = (finish_time - absolute_deadline).max(0);
+        self.total_tardiness += tardiness;
+        self.max_tardiness = self.max_tardiness.max(tardiness);
+        self.weighted_tardiness += weight * tardiness;
+    }
+}
+
+fn get_tardiness_weight(node_data: &NodeData) -> i64 {
+    node_data
+        .params
+        .get("tardiness_weight")
+        .copied()
+        .unwrap_or(1)
+}
+
+/// The tardiness of the nodes with `soft_deadline`, which is relative to the release time of the job.
+/// `finish_events` are the (node id, release time of the job, finish time) of the executed nodes.
+fn calculate_node_tardiness_stats(
+    dag: &Graph<NodeData, i64>,
+    finish_events: impl Iterator<Item = (usize, i64, i64)>,
+) -> BTreeMap<usize, TardinessStats> {
+    let mut node_tardiness_stats = BTreeMap::new();
+    for node_data in dag.node_weights() {
+        if node_data.params.contains_key("soft_deadline") {
+            node_tardiness_stats.insert(node_data.id as usize, TardinessStats::default());
+        }
+    }
+    for (node_id, release_time, finish_time) in finish_events {
+        if let Some(node_data) = dag.node_weight(NodeIndex::new(node_id)) {
+            if let Some(soft_deadline) = node_data.params.get("soft_deadline") {
+                node_tardiness_stats.get_mut(&node_id).unwrap().add(
+                    finish_time,
+                    release_time + soft_deadline,
+                    get_tardiness_weight(node_data),
+                );
+            }
+        }
     }
+    node_tardiness_stats
+}
+
+/// The end-to-end deadline is used as the soft deadline of the whole DAG.
+fn get_end_to_end_deadline_node(dag: &Graph<NodeData, i64>) -> Option<&NodeData> {
+    dag.node_weights()
+        .find(|node_data| node_data.params.contains_key("end_to_end_deadline"))
+}
+
+/// The sinks with their end-to-end deadlines if the sink chains have different ones, each of which is checked
+/// against the finish time of its own sink. Otherwise, the deadline is checked against the finish time of the whole DAG,
+/// which is the latest finish time of the sinks, and None is returned.
+pub(crate) fn get_different_sink_deadlines(
+    dag: &Graph<NodeData, i64>,
+) -> Option<Vec<(NodeIndex, i64)>> {
+    let deadlines_per_sink = dag.get_deadlines_per_sink();
+    deadlines_per_sink
+        .iter()
+        .any(|(_, end_to_end_deadline)| *end_to_end_deadline != deadlines_per_sink[0].1)
+        .then_some(deadlines_per_sink)
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGLog {
     dag_id: usize,
-    release_time: Vec<i32>,
-    finish_time: Vec<i32>,
-    response_time: Vec<i32>,
+    // Written only for the aperiodic arrivals, which may be released later than they arrive
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    arrival_time: Vec<i64>,
+    release_time: Vec<i64>,
+    finish_time: Vec<i64>,
+    response_time: Vec<i64>,
     average_response_time: f32,
-    worst_response_time: i32,
+    worst_response_time: i64,
+    tardiness_stats: TardinessStats,
+    // Analytical bound to be compared with the simulated max_tardiness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tardiness_bound: Option<f64>,
+    // Analytical bounds to be compared with the simulated worst_response_time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response_time_bound: Option<ResponseTimeBound>,
+    node_tardiness_stats: BTreeMap<usize, TardinessStats>,
+    // Range of the fluid allocation minus the actual one, tracked by proportional-share scheduling
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_lag: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_lag: Option<f64>,
+    // Time the highest-priority ready node of the DAG waits for lower-priority non-preemptive sections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    blocking_time: Option<i64>,
+    // Time the ready nodes of the DAG wait for an idle core because the DAG runs max_parallelism nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parallelism_capped_time: Option<i64>,
 }
 
 impl DAGLog {
     pub fn new(dag_id: usize) -> Self {
         Self {
             dag_id,
+            arrival_time: Default::default(),
             release_time: Default::default(),
             finish_time: Default::default(),
             response_time: Default::default(),
             average_response_time: Default::default(),
             worst_response_time: Default::default(),
+            tardiness_stats: Default::default(),
+            tardiness_bound: None,
+            response_time_bound: None,
+            node_tardiness_stats: Default::default(),
+            max_lag: None,
+            min_lag: None,
+            blocking_time: None,
+            parallelism_capped_time: None,
         }
     }
 
@@ -113,7 +258,7 @@ impl DAGLog {
         // Unequal lengths indicate that the DAG was not completed within the hyper_period, and deadline miss occurred.
         if self.release_time.len() != self.finish_time.len() {
             // Mark as a deadline miss by maximizing the response time.
-            self.finish_time.push(std::i32::MAX);
+            self.finish_time.push(i64::MAX);
         }
         self.response_time = self
             .release_time
@@ -123,22 +268,74 @@ impl DAGLog {
             .collect();
     }
 
-    pub fn calculate_average_response_time(&mut self) {
-        self.average_response_time =
-            self.response_time.iter().sum::<i32>() as f32 / self.response_time.len() as f32;
+    /// The response times of the jobs released at or after `warm_up_time`.
+    fn get_steady_response_times(&self, warm_up_time: i64) -> impl Iterator<Item = &i64> {
+        self.release_time
+            .iter()
+            .zip(self.response_time.iter())
+            .filter(move |(release_time, _)| **release_time >= warm_up_time)
+            .map(|(_, response_time)| response_time)
     }
 
-    pub fn calculate_worst_response_time(&mut self) {
-        self.worst_response_time = *self.response_time.iter().max().unwrap();
+    pub fn calculate_average_response_time(&mut self, warm_up_time: i64) {
+        // Summed as floats since the response time of an unfinished job is close to i64::MAX.
+        let (sum, count) = self
+            .get_steady_response_times(warm_up_time)
+            .fold((0.0, 0), |(sum, count), response_time| {
+                (sum + *response_time as f64, count + 1)
+            });
+        self.average_response_time = if count == 0 {
+            0.0
+        } else {
+            (sum / count as f64) as f32
+        };
+    }
+
+    pub fn calculate_worst_response_time(&mut self, warm_up_time: i64) {
+        self.worst_response_time = self
+            .get_steady_response_times(warm_up_time)
+            .max()
+            .copied()
+            .unwrap_or_default();
+    }
+
+    fn calculate_tardiness(&mut self, dag: &Graph<NodeData, i64>, job_logs: &[JobLog]) {
+        if let Some(deadline_node) = get_end_to_end_deadline_node(dag) {
+            let end_to_end_deadline = deadline_node.get_params_value("end_to_end_deadline");
+            // Jobs unfinished at the end of the schedule are excluded because their tardiness is unknown.
+            for (release_time, finish_time) in self
+                .release_time
+                .iter()
+                .zip(self.finish_time.iter())
+                .filter(|(_, finish_time)| **finish_time != i64::MAX)
+            {
+                self.tardiness_stats.add(
+                    *finish_time,
+                    release_time + end_to_end_deadline,
+                    get_tardiness_weight(deadline_node),
+                );
+            }
+        }
+        let finish_events = job_logs
+            .iter()
+            .filter_map(|job_log| match job_log.event_time {
+                JobEventTimes::FinishTime(finish_time) => Some((
+                    job_log.node_id,
+                    self.release_time[job_log.job_id],
+                    finish_time,
+                )),
+                _ => None,
+            });
+        self.node_tardiness_stats = calculate_node_tardiness_stats(dag, finish_events);
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum JobEventTimes {
-    StartTime(i32),
-    ResumeTime(i32),
-    FinishTime(i32),
-    PreemptedTime(i32),
+    StartTime(i64),
+    ResumeTime(i64),
+    FinishTime(i64),
+    PreemptedTime(i64),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -148,6 +345,9 @@ pub struct JobLog {
     node_id: usize,
     job_id: usize,
     event_time: JobEventTimes,
+    // Cache-related preemption delay added to the remaining time on resumption
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crpd_inflation: Option<i64>,
 }
 
 impl JobLog {
@@ -165,8 +365,82 @@ impl JobLog {
             node_id,
             job_id,
             event_time,
+            crpd_inflation: None,
         }
     }
+
+    /// Emit the job event to the `tracing` subscriber.
+    #[cfg(feature = "tracing")]
+    fn trace(&self) {
+        tracing::debug!(
+            core_id = self.core_id,
+            dag_id = self.dag_id,
+            node_id = self.node_id,
+            job_id = self.job_id,
+            event_time = ?self.event_time,
+            "job event"
+        );
+    }
+
+    #[cfg(feature = "file_io")]
+    fn get_event_and_time(&self) -> (&'static str, i64) {
+        match self.event_time {
+            JobEventTimes::StartTime(time) => ("start", time),
+            JobEventTimes::ResumeTime(time) => ("resume", time),
+            JobEventTimes::FinishTime(time) => ("finish", time),
+            JobEventTimes::PreemptedTime(time) => ("preempted", time),
+        }
+    }
+
+    #[cfg(feature = "file_io")]
+    fn to_csv_row(&self) -> String {
+        let (event, time) = self.get_event_and_time();
+        format!(
+            "{},{},{},{},{},{}\n",
+            self.dag_id, self.node_id, self.job_id, self.core_id, event, time
+        )
+    }
+}
+
+/// Write one row per job event so that the schedule can be loaded as a flat table.
+#[cfg(feature = "file_io")]
+fn dump_job_logs_to_csv<'a>(file_path: &str, job_logs: impl Iterator<Item = &'a JobLog>) {
+    let mut csv = String::from("dag_id,node_id,job_id,core,event,time\n");
+    for job_log in job_logs {
+        csv.push_str(&job_log.to_csv_row());
+    }
+    if let Err(err) = fs::write(file_path, csv) {
+        warn!("Failed to write to file: {}", err);
+    }
+}
+
+/// Write the job events as a Parquet table with the columns of the CSV.
+#[cfg(feature = "arrow")]
+fn dump_job_logs_to_parquet<'a>(
+    file_path: &str,
+    job_logs: impl Iterator<Item = &'a JobLog>,
+) -> Result<()> {
+    let job_logs: Vec<&JobLog> = job_logs.collect();
+    let id_column = |get_id: fn(&JobLog) -> usize| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(
+            job_logs.iter().map(|job_log| get_id(job_log) as u64),
+        ))
+    };
+    let (events, times): (Vec<&str>, Vec<i64>) = job_logs
+        .iter()
+        .map(|job_log| job_log.get_event_and_time())
+        .unzip();
+    write_columns_to_parquet(
+        file_path,
+        vec![
+            ("dag_id", id_column(|job_log| job_log.dag_id)),
+            ("node_id", id_column(|job_log| job_log.node_id)),
+            ("job_id", id_column(|job_log| job_log.job_id)),
+            ("core", id_column(|job_log| job_log.core_id)),
+            ("event", Arc::new(StringArray::from(events))),
+            ("time", Arc::new(Int64Array::from(times))),
+        ],
+    )
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -174,6 +448,11 @@ pub struct ProcessorLog {
     average_utilization: f32,
     variance_utilization: f32,
     core_logs: Vec<CoreLog>,
+    // Time spent on each DAG by each core, indexed by [core_id][dag_id]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    core_dag_time_matrix: Vec<Vec<i64>>,
+    #[serde(skip)]
+    records_core_timeline: bool,
 }
 
 impl ProcessorLog {
@@ -182,6 +461,34 @@ impl ProcessorLog {
             average_utilization: Default::default(),
             variance_utilization: Default::default(),
             core_logs: (0..num_cores).map(CoreLog::new).collect(),
+            core_dag_time_matrix: Vec::new(),
+            records_core_timeline: false,
+        }
+    }
+
+    fn calculate_core_dag_time_matrix(
+        &mut self,
+        execution_slices: &[ExecutionSlice],
+        num_dags: usize,
+    ) {
+        self.core_dag_time_matrix = vec![vec![0; num_dags]; self.core_logs.len()];
+        for slice in execution_slices.iter() {
+            self.core_dag_time_matrix[slice.core_id][slice.dag_id] +=
+                slice.end_time - slice.start_time;
+        }
+    }
+
+    /// Build the timeline of each core from the execution slices if it is enabled.
+    fn calculate_core_timelines(
+        &mut self,
+        execution_slices: &[ExecutionSlice],
+        schedule_length: i64,
+    ) {
+        if !self.records_core_timeline {
+            return;
+        }
+        for core_log in self.core_logs.iter_mut() {
+            core_log.calculate_timeline(execution_slices, schedule_length);
         }
     }
 
@@ -203,18 +510,37 @@ impl ProcessorLog {
             / self.core_logs.len() as f32;
     }
 
-    fn calculate_cores_utilization(&mut self, schedule_length: i32) {
+    fn calculate_cores_utilization(&mut self, schedule_length: i64) {
         for core_log in self.core_logs.iter_mut() {
             core_log.calculate_utilization(schedule_length);
         }
     }
 }
 
+/// An interval of the core timeline, in which the core executes a job or is idle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CoreInterval {
+    Busy {
+        start_time: i64,
+        end_time: i64,
+        dag_id: usize,
+        node_id: usize,
+        job_id: usize,
+    },
+    Idle {
+        start_time: i64,
+        end_time: i64,
+    },
+}
+
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct CoreLog {
     core_id: usize,
-    total_proc_time: i32,
+    total_proc_time: i64,
+    total_overhead_time: i64, // Dispatch, preemption, data waiting and clock drift, excluded from total_proc_time
     utilization: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeline: Option<Vec<CoreInterval>>,
 }
 
 impl CoreLog {
@@ -222,38 +548,170 @@ impl CoreLog {
         Self {
             core_id,
             total_proc_time: Default::default(),
+            total_overhead_time: Default::default(),
             utilization: Default::default(),
+            timeline: None,
         }
     }
 
-    fn calculate_utilization(&mut self, schedule_length: i32) {
+    fn calculate_utilization(&mut self, schedule_length: i64) {
         self.utilization = self.total_proc_time as f32 / schedule_length as f32;
     }
+
+    /// The gaps between the execution slices on the core, up to the schedule length, are idle intervals.
+    fn calculate_timeline(&mut self, execution_slices: &[ExecutionSlice], schedule_length: i64) {
+        let mut timeline = Vec::new();
+        let mut idle_start_time = 0;
+        for slice in execution_slices
+            .iter()
+            .filter(|slice| slice.core_id == self.core_id)
+        {
+            if idle_start_time < slice.start_time {
+                timeline.push(CoreInterval::Idle {
+                    start_time: idle_start_time,
+                    end_time: slice.start_time,
+                });
+            }
+            timeline.push(CoreInterval::Busy {
+                start_time: slice.start_time,
+                end_time: slice.end_time,
+                dag_id: slice.dag_id,
+                node_id: slice.node_id,
+                job_id: slice.job_id,
+            });
+            idle_start_time = slice.end_time;
+        }
+        if idle_start_time < schedule_length {
+            timeline.push(CoreInterval::Idle {
+                start_time: idle_start_time,
+                end_time: schedule_length,
+            });
+        }
+        self.timeline = Some(timeline);
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct LostJob {
+    dag_id: usize,
+    node_id: usize,
+    job_id: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CoreFailureLog {
+    core_id: usize,
+    failure_time: i64,
+    lost_job: Option<LostJob>, // The job running on the core when it failed
+    recovery_latency: Option<i64>, // Time until the lost job is allocated to another core
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TransferLog {
+    dag_id: usize,
+    from_node_id: usize,
+    to_node_id: usize,
+    job_id: usize,
+    start_time: i64,
+    finish_time: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DeadlineMissLog {
+    dag_id: usize,
+    job_id: usize,
+    miss_time: i64,
+    policy: DeadlineMissPolicy, // The action taken on the late job
+}
+
+/// A job finished after its absolute deadline, or unfinished although its absolute deadline has passed.
+/// The absolute deadline is the release time plus the end-to-end deadline as in the tardiness statistics.
+/// If the sink chains of the DAG have different deadlines, each missed sink is an entry.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeadlineMissEntry {
+    pub dag_id: usize,
+    pub job_id: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sink_node_id: Option<usize>,
+    pub absolute_deadline: i64,
+    pub finish_time: Option<i64>, // None if the job is unfinished
+    pub tardiness: Option<i64>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct DeadlineMissSummary {
+    total_misses: usize,
+    miss_ratio: f32, // The missed jobs over the released jobs of the DAGs with an end-to-end deadline
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ModeChangeLog {
+    from_mode: usize,
+    to_mode: usize,
+    request_time: i64,
+    switch_time: i64, // The time the jobs of the leaving DAGs have completed
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AdmissionLog {
+    dag_id: usize,
+    arrival_time: i64,
+    decision_time: i64,
+    verdict: SchedulabilityVerdict,
+}
+
+// The LET interval of a job of a node, from the read of its inputs to the publication of its outputs
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PublicationLog {
+    dag_id: usize,
+    node_id: usize,
+    job_id: usize,
+    release_time: i64,
+    publish_time: i64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ChainLatencyLog {
+    name: String,
+    max_data_age: Option<i64>,
+    max_reaction_time: Option<i64>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGSchedulerLog {
+    // The header to trace the log back to its inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    run_metadata: Option<RunMetadata>,
     dag_info: DAGInfo,
     processor_info: ProcessorInfo,
     node_logs: Vec<JobLog>,
     processor_log: ProcessorLog,
+    tardiness_stats: TardinessStats,
+    node_tardiness_stats: BTreeMap<usize, TardinessStats>,
 }
 
 impl DAGSchedulerLog {
-    pub fn new(dag: &Graph<NodeData, i32>, num_cores: usize) -> Self {
+    pub fn new(dag: &Graph<NodeData, i64>, num_cores: usize) -> Self {
         Self {
+            run_metadata: None,
             dag_info: DAGInfo::new(dag),
             processor_info: ProcessorInfo::new(num_cores),
             node_logs: Vec::new(),
             processor_log: ProcessorLog::new(num_cores),
+            tardiness_stats: Default::default(),
+            node_tardiness_stats: Default::default(),
         }
     }
 
+    pub fn write_run_metadata(&mut self, run_metadata: RunMetadata) {
+        self.run_metadata = Some(run_metadata);
+    }
+
     pub fn write_allocating_job(
         &mut self,
         node_data: &NodeData,
         core_id: usize,
-        current_time: i32,
+        current_time: i64,
     ) {
         let job_log = JobLog::new(
             core_id,
@@ -262,6 +720,8 @@ impl DAGSchedulerLog {
             0, // This is a fixed value because DAG is released only once.
             JobEventTimes::StartTime(current_time),
         );
+        #[cfg(feature = "tracing")]
+        job_log.trace();
         self.node_logs.push(job_log);
     }
 
@@ -271,7 +731,13 @@ impl DAGSchedulerLog {
         }
     }
 
-    pub fn write_finishing_job(&mut self, node_data: &NodeData, core_id: usize, current_time: i32) {
+    pub fn write_overhead_time(&mut self, core_indices: &[usize]) {
+        for core_index in core_indices {
+            self.processor_log.core_logs[*core_index].total_overhead_time += 1;
+        }
+    }
+
+    pub fn write_finishing_job(&mut self, node_data: &NodeData, core_id: usize, current_time: i64) {
         let job_log = JobLog::new(
             core_id,
             0, // This is a fixed value because DAG is only one.
@@ -279,51 +745,247 @@ impl DAGSchedulerLog {
             0, // This is a fixed value because DAG is released only once.
             JobEventTimes::FinishTime(current_time),
         );
+        #[cfg(feature = "tracing")]
+        job_log.trace();
         self.node_logs.push(job_log);
     }
 
-    pub fn calculate_utilization(&mut self, schedule_length: i32) {
+    pub fn calculate_utilization(&mut self, schedule_length: i64) {
         self.processor_log
             .calculate_cores_utilization(schedule_length);
         self.processor_log.calculate_average_utilization();
         self.processor_log.calculate_variance_utilization();
+        self.processor_log.calculate_core_timelines(
+            &get_execution_slices(self.node_logs.iter()),
+            schedule_length,
+        );
+    }
+
+    /// Record the busy and idle intervals of each core in addition to the total processing time.
+    pub fn enable_core_timeline(&mut self) {
+        self.processor_log.records_core_timeline = true;
+    }
+
+    pub fn write_execution_time_seed(&mut self, execution_time_seed: Option<u64>) {
+        self.processor_info.execution_time_seed = execution_time_seed;
     }
 
+    /// The DAG is released only once at time 0.
+    pub fn calculate_tardiness(&mut self, dag: &Graph<NodeData, i64>, schedule_length: i64) {
+        self.tardiness_stats = TardinessStats::default();
+        if let Some(deadline_node) = get_end_to_end_deadline_node(dag) {
+            self.tardiness_stats.add(
+                schedule_length,
+                deadline_node.get_params_value("end_to_end_deadline"),
+                get_tardiness_weight(deadline_node),
+            );
+        }
+        // The node logs are kept when the scheduler is reused, so only the latest finish time of each node is used.
+        let finish_events = self
+            .get_execution_intervals()
+            .into_iter()
+            .map(|(node_id, (_, finish_time))| (node_id, 0, finish_time));
+        self.node_tardiness_stats = calculate_node_tardiness_stats(dag, finish_events);
+    }
+
+    /// Returns the (start time, finish time) of each node id.
+    pub fn get_execution_intervals(&self) -> BTreeMap<usize, (i64, i64)> {
+        let mut start_times = BTreeMap::new();
+        let mut execution_intervals = BTreeMap::new();
+        for job_log in self.node_logs.iter() {
+            match job_log.event_time {
+                JobEventTimes::StartTime(start_time) => {
+                    start_times.insert(job_log.node_id, start_time);
+                }
+                JobEventTimes::FinishTime(finish_time) => {
+                    if let Some(start_time) = start_times.get(&job_log.node_id) {
+                        execution_intervals.insert(job_log.node_id, (*start_time, finish_time));
+                    }
+                }
+                _ => {}
+            }
+        }
+        execution_intervals
+    }
+
+    #[cfg(feature = "file_io")]
     pub fn dump_log_to_yaml(&self, file_path: &str) {
         dump_struct(file_path, self);
     }
+
+    /// Load a log dumped by `dump_log_to_yaml`. The sections appended to the file by the binaries are ignored.
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        load_yaml_struct(file_path)
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn dump_log_to_json(&self, file_path: &str) {
+        dump_struct_to_json(file_path, self);
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn dump_job_logs_to_csv(&self, file_path: &str) {
+        dump_job_logs_to_csv(file_path, self.node_logs.iter());
+    }
+
+    #[cfg(feature = "arrow")]
+    pub fn dump_job_logs_to_parquet(&self, file_path: &str) -> Result<()> {
+        dump_job_logs_to_parquet(file_path, self.node_logs.iter())
+    }
+}
+
+/// When and where a job of a node was executed, written into the annotated input DAG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeSchedule {
+    pub assigned_core: usize,
+    pub start_time: i64,
+    pub finish_time: i64,
+}
+
+/// A continuous execution of a job on a core, split by preemptions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionSlice {
+    pub core_id: usize,
+    pub dag_id: usize,
+    pub node_id: usize,
+    pub job_id: usize,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+fn get_execution_slices<'a>(job_logs: impl Iterator<Item = &'a JobLog>) -> Vec<ExecutionSlice> {
+    let mut execution_slices = Vec::new();
+    // (dag_id, node_id, job_id) -> (core_id, start_time)
+    let mut running_jobs = BTreeMap::new();
+    for job_log in job_logs {
+        let key = (job_log.dag_id, job_log.node_id, job_log.job_id);
+        match job_log.event_time {
+            JobEventTimes::StartTime(time) | JobEventTimes::ResumeTime(time) => {
+                running_jobs.insert(key, (job_log.core_id, time));
+            }
+            JobEventTimes::PreemptedTime(time) | JobEventTimes::FinishTime(time) => {
+                if let Some((core_id, start_time)) = running_jobs.remove(&key) {
+                    execution_slices.push(ExecutionSlice {
+                        core_id,
+                        dag_id: job_log.dag_id,
+                        node_id: job_log.node_id,
+                        job_id: job_log.job_id,
+                        start_time,
+                        end_time: time,
+                    });
+                }
+            }
+        }
+    }
+    execution_slices.sort_by_key(|slice| (slice.start_time, slice.core_id));
+    execution_slices
+}
+
+/// An event of the Chrome trace-event format, viewable in chrome://tracing or Perfetto.
+/// One time unit of the simulation is shown as one microsecond.
+#[derive(Serialize)]
+struct TraceEvent {
+    name: String,
+    ph: &'static str,
+    pid: usize,
+    tid: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ts: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<i64>,
+    args: BTreeMap<&'static str, serde_json::Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace {
+    trace_events: Vec<TraceEvent>,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DAGSetSchedulerLog {
+    // The header to trace the log back to its inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    run_metadata: Option<RunMetadata>,
     dag_set_info: DAGSetInfo,
     processor_info: ProcessorInfo,
     dag_set_log: Vec<DAGLog>,
     node_set_logs: Vec<Vec<JobLog>>,
     processor_log: ProcessorLog,
+    core_failure_logs: Vec<CoreFailureLog>,
+    transfer_logs: Vec<TransferLog>,
+    deadline_miss_logs: Vec<DeadlineMissLog>,
+    #[serde(default)]
+    deadline_misses: Vec<DeadlineMissEntry>,
+    #[serde(default)]
+    deadline_miss_summary: DeadlineMissSummary,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mode_change_logs: Vec<ModeChangeLog>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    admission_logs: Vec<AdmissionLog>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    chain_latency_logs: Vec<ChainLatencyLog>,
+    #[serde(default, skip_serializing_if = "CommunicationSemantics::is_implicit")]
+    communication_semantics: CommunicationSemantics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    publication_logs: Vec<PublicationLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    energy_log: Option<EnergyLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    warm_up_time: Option<i64>,
+    // Wall-clock time of the pre-processing inside the scheduling, reported through RunTimeLog
+    #[serde(skip)]
+    preprocessing_time: Duration,
 }
 
 impl DAGSetSchedulerLog {
-    pub fn new(dag_set: &[Graph<NodeData, i32>], num_cores: usize) -> Self {
+    pub fn new(dag_set: &[Graph<NodeData, i64>], num_cores: usize) -> Self {
         let mut dag_set_log = Vec::with_capacity(dag_set.len());
         for i in 0..dag_set.len() {
             dag_set_log.push(DAGLog::new(i));
         }
 
         Self {
+            run_metadata: None,
             dag_set_info: DAGSetInfo::new(dag_set),
             processor_info: ProcessorInfo::new(num_cores),
             dag_set_log,
             node_set_logs: vec![Vec::new(); dag_set.len()],
             processor_log: ProcessorLog::new(num_cores),
+            core_failure_logs: Vec::new(),
+            transfer_logs: Vec::new(),
+            deadline_miss_logs: Vec::new(),
+            deadline_misses: Vec::new(),
+            deadline_miss_summary: Default::default(),
+            mode_change_logs: Vec::new(),
+            admission_logs: Vec::new(),
+            chain_latency_logs: Vec::new(),
+            communication_semantics: CommunicationSemantics::default(),
+            publication_logs: Vec::new(),
+            energy_log: None,
+            warm_up_time: None,
+            preprocessing_time: Duration::ZERO,
         }
     }
 
-    pub fn write_dag_release_time(&mut self, dag_id: usize, release_time: i32) {
+    pub fn write_run_metadata(&mut self, run_metadata: RunMetadata) {
+        self.run_metadata = Some(run_metadata);
+    }
+
+    pub fn write_dag_arrival_time(&mut self, dag_id: usize, arrival_time: i64) {
+        self.dag_set_log[dag_id].arrival_time.push(arrival_time);
+    }
+
+    pub fn write_dag_release_time(&mut self, dag_id: usize, release_time: i64) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(dag_id, release_time, "dag released");
         self.dag_set_log[dag_id].release_time.push(release_time);
     }
 
-    pub fn write_dag_finish_time(&mut self, dag_id: usize, finish_time: i32) {
+    pub fn write_dag_finish_time(&mut self, dag_id: usize, finish_time: i64) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(dag_id, finish_time, "dag finished");
         self.dag_set_log[dag_id].finish_time.push(finish_time);
     }
 
@@ -332,15 +994,31 @@ impl DAGSetSchedulerLog {
         node_data: &NodeData,
         core_id: usize,
         job_id: usize,
-        current_time: i32,
+        current_time: i64,
     ) {
+        let lost_job = LostJob {
+            dag_id: node_data.get_params_value("dag_id") as usize,
+            node_id: node_data.id as usize,
+            job_id: job_id - 1,
+        };
+        if let Some(core_failure_log) = self
+            .core_failure_logs
+            .iter_mut()
+            .find(|log| log.recovery_latency.is_none() && log.lost_job.as_ref() == Some(&lost_job))
+        {
+            core_failure_log.recovery_latency = Some(current_time - core_failure_log.failure_time);
+        }
+
         if node_data.params.contains_key("is_preempted") {
-            self.write_job_event(
-                node_data,
+            let mut job_log = JobLog::new(
                 core_id,
+                lost_job.dag_id,
+                node_data.id as usize,
                 job_id - 1,
                 JobEventTimes::ResumeTime(current_time),
-            )
+            );
+            job_log.crpd_inflation = node_data.params.get("crpd").copied();
+            self.write_job_log(job_log);
         } else {
             self.write_job_event(
                 node_data,
@@ -360,7 +1038,116 @@ impl DAGSetSchedulerLog {
     ) {
         let dag_id = node_data.get_params_value("dag_id") as usize;
         let job_log = JobLog::new(core_id, dag_id, node_data.id as usize, job_id, event_time);
-        self.node_set_logs[dag_id].push(job_log);
+        self.write_job_log(job_log);
+    }
+
+    fn write_job_log(&mut self, job_log: JobLog) {
+        #[cfg(feature = "tracing")]
+        job_log.trace();
+        self.node_set_logs[job_log.dag_id].push(job_log);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_transfer(
+        &mut self,
+        dag_id: usize,
+        from_node_id: usize,
+        to_node_id: usize,
+        job_id: usize,
+        start_time: i64,
+        finish_time: i64,
+    ) {
+        self.transfer_logs.push(TransferLog {
+            dag_id,
+            from_node_id,
+            to_node_id,
+            job_id,
+            start_time,
+            finish_time,
+        });
+    }
+
+    pub fn write_core_failure(
+        &mut self,
+        core_id: usize,
+        failure_time: i64,
+        lost_job: Option<(&NodeData, usize)>,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(core_id, failure_time, "core failed");
+        let lost_job = lost_job.map(|(node_data, job_id)| LostJob {
+            dag_id: node_data.get_params_value("dag_id") as usize,
+            node_id: node_data.id as usize,
+            job_id,
+        });
+        self.core_failure_logs.push(CoreFailureLog {
+            core_id,
+            failure_time,
+            lost_job,
+            recovery_latency: None,
+        });
+    }
+
+    pub fn write_deadline_miss(
+        &mut self,
+        dag_id: usize,
+        job_id: usize,
+        miss_time: i64,
+        policy: DeadlineMissPolicy,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(dag_id, job_id, miss_time, ?policy, "deadline missed");
+        self.deadline_miss_logs.push(DeadlineMissLog {
+            dag_id,
+            job_id,
+            miss_time,
+            policy,
+        });
+    }
+
+    pub fn write_mode_change(
+        &mut self,
+        from_mode: usize,
+        to_mode: usize,
+        request_time: i64,
+        switch_time: i64,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            from_mode,
+            to_mode,
+            request_time,
+            switch_time,
+            "mode changed"
+        );
+        self.mode_change_logs.push(ModeChangeLog {
+            from_mode,
+            to_mode,
+            request_time,
+            switch_time,
+        });
+    }
+
+    pub fn write_admission(
+        &mut self,
+        dag_id: usize,
+        arrival_time: i64,
+        decision_time: i64,
+        verdict: SchedulabilityVerdict,
+    ) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            dag_id,
+            arrival_time,
+            is_admitted = verdict.is_schedulable,
+            "dag admission decided"
+        );
+        self.admission_logs.push(AdmissionLog {
+            dag_id,
+            arrival_time,
+            decision_time,
+            verdict,
+        });
     }
 
     pub fn write_processing_time(&mut self, core_indices: &[usize]) {
@@ -369,36 +1156,507 @@ impl DAGSetSchedulerLog {
         }
     }
 
-    pub fn calculate_response_time(&mut self) {
+    pub fn write_overhead_time(&mut self, core_indices: &[usize]) {
+        for core_index in core_indices {
+            self.processor_log.core_logs[*core_index].total_overhead_time += 1;
+        }
+    }
+
+    /// The jobs released before `warm_up_time` are excluded from the average and worst response times.
+    pub fn calculate_response_time(&mut self, warm_up_time: i64) {
+        if warm_up_time > 0 {
+            self.warm_up_time = Some(warm_up_time);
+        }
         for dag_log in self.dag_set_log.iter_mut() {
             dag_log.calculate_response_time();
-            dag_log.calculate_average_response_time();
-            dag_log.calculate_worst_response_time();
+            dag_log.calculate_average_response_time(warm_up_time);
+            dag_log.calculate_worst_response_time(warm_up_time);
+        }
+    }
+
+    pub fn calculate_tardiness(&mut self, dag_set: &[Graph<NodeData, i64>]) {
+        for dag in dag_set.iter() {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            self.dag_set_log[dag_id].calculate_tardiness(dag, &self.node_set_logs[dag_id]);
+        }
+    }
+
+    /// Collect the deadline misses from the release and finish times after `calculate_response_time`.
+    /// An unfinished job is a miss only if its absolute deadline has passed by `schedule_length`.
+    /// The sinks with different deadlines are checked by their own finish times.
+    pub fn calculate_deadline_misses(
+        &mut self,
+        dag_set: &[Graph<NodeData, i64>],
+        schedule_length: i64,
+    ) {
+        self.deadline_misses.clear();
+        let mut num_jobs = 0;
+        for dag in dag_set.iter() {
+            let dag_id = dag.get_dag_param("dag_id") as usize;
+            // The deadline of each sink chain, or of the whole DAG finishing at the finish time of the job
+            let deadlines: Vec<(Option<usize>, i64)> = match get_different_sink_deadlines(dag) {
+                Some(sink_deadlines) => sink_deadlines
+                    .into_iter()
+                    .map(|(sink_i, end_to_end_deadline)| {
+                        (Some(dag[sink_i].id as usize), end_to_end_deadline)
+                    })
+                    .collect(),
+                None => match get_end_to_end_deadline_node(dag) {
+                    Some(deadline_node) => {
+                        vec![(None, deadline_node.get_params_value("end_to_end_deadline"))]
+                    }
+                    None => continue,
+                },
+            };
+            let dag_log = &self.dag_set_log[dag_id];
+            num_jobs += dag_log.release_time.len();
+            for (job_id, (release_time, job_finish_time)) in dag_log
+                .release_time
+                .iter()
+                .zip(dag_log.finish_time.iter())
+                .enumerate()
+            {
+                for (sink_node_id, end_to_end_deadline) in deadlines.iter() {
+                    let absolute_deadline = release_time + end_to_end_deadline;
+                    let finish_time = match sink_node_id {
+                        Some(sink_node_id) => {
+                            self.get_node_finish_time(dag_id, *sink_node_id, job_id)
+                        }
+                        None => (*job_finish_time != i64::MAX).then_some(*job_finish_time),
+                    };
+                    let is_missed = match finish_time {
+                        Some(finish_time) => finish_time > absolute_deadline,
+                        None => absolute_deadline <= schedule_length,
+                    };
+                    if is_missed {
+                        self.deadline_misses.push(DeadlineMissEntry {
+                            dag_id,
+                            job_id,
+                            sink_node_id: *sink_node_id,
+                            absolute_deadline,
+                            finish_time,
+                            tardiness: finish_time
+                                .map(|finish_time| finish_time - absolute_deadline),
+                        });
+                    }
+                }
+            }
+        }
+        let num_missed_jobs = self
+            .deadline_misses
+            .iter()
+            .map(|deadline_miss| (deadline_miss.dag_id, deadline_miss.job_id))
+            .collect::<BTreeSet<_>>()
+            .len();
+        self.deadline_miss_summary = DeadlineMissSummary {
+            total_misses: self.deadline_misses.len(),
+            miss_ratio: if num_jobs == 0 {
+                0.0
+            } else {
+                num_missed_jobs as f32 / num_jobs as f32
+            },
+        };
+    }
+
+    /// The finish time of the node in the job, or None if it is unfinished.
+    pub(crate) fn get_node_finish_time(
+        &self,
+        dag_id: usize,
+        node_id: usize,
+        job_id: usize,
+    ) -> Option<i64> {
+        self.node_set_logs[dag_id]
+            .iter()
+            .rev()
+            .find_map(|job_log| match job_log.event_time {
+                JobEventTimes::FinishTime(finish_time)
+                    if job_log.node_id == node_id && job_log.job_id == job_id =>
+                {
+                    Some(finish_time)
+                }
+                _ => None,
+            })
+    }
+
+    /// Whether the deadline miss of the job has been written.
+    pub(crate) fn has_deadline_miss_log(&self, dag_id: usize, job_id: usize) -> bool {
+        self.deadline_miss_logs.iter().any(|deadline_miss_log| {
+            deadline_miss_log.dag_id == dag_id && deadline_miss_log.job_id == job_id
+        })
+    }
+
+    pub fn get_deadline_misses(&self) -> &[DeadlineMissEntry] {
+        &self.deadline_misses
+    }
+
+    pub fn write_execution_time_seed(&mut self, execution_time_seed: Option<u64>) {
+        self.processor_info.execution_time_seed = execution_time_seed;
+    }
+
+    pub fn write_preprocessing_time(&mut self, preprocessing_time: Duration) {
+        self.preprocessing_time += preprocessing_time;
+    }
+
+    pub fn get_preprocessing_time(&self) -> Duration {
+        self.preprocessing_time
+    }
+
+    pub fn get_number_of_cores(&self) -> usize {
+        self.processor_info.number_of_cores
+    }
+
+    pub fn get_number_of_dags(&self) -> usize {
+        self.dag_set_log.len()
+    }
+
+    pub fn write_energy_log(&mut self, energy_log: EnergyLog) {
+        self.energy_log = Some(energy_log);
+    }
+
+    pub fn write_chain_latency(
+        &mut self,
+        name: &str,
+        max_data_age: Option<i64>,
+        max_reaction_time: Option<i64>,
+    ) {
+        self.chain_latency_logs.push(ChainLatencyLog {
+            name: name.to_string(),
+            max_data_age,
+            max_reaction_time,
+        });
+    }
+
+    pub fn write_communication_semantics(
+        &mut self,
+        communication_semantics: CommunicationSemantics,
+    ) {
+        self.communication_semantics = communication_semantics;
+    }
+
+    pub fn get_communication_semantics(&self) -> CommunicationSemantics {
+        self.communication_semantics
+    }
+
+    pub fn write_publication(
+        &mut self,
+        dag_id: usize,
+        node_id: usize,
+        job_id: usize,
+        release_time: i64,
+        publish_time: i64,
+    ) {
+        self.publication_logs.push(PublicationLog {
+            dag_id,
+            node_id,
+            job_id,
+            release_time,
+            publish_time,
+        });
+    }
+
+    /// Returns the (release time, publish time) of the published jobs of the node in the job order.
+    pub fn get_let_intervals(&self, dag_id: usize, node_id: usize) -> Vec<(i64, i64)> {
+        self.publication_logs
+            .iter()
+            .filter(|log| log.dag_id == dag_id && log.node_id == node_id)
+            .map(|log| (log.job_id, (log.release_time, log.publish_time)))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect()
+    }
+
+    /// Returns the (start time, finish time) of the finished jobs of the node in the job order.
+    pub fn get_job_intervals(&self, dag_id: usize, node_id: usize) -> Vec<(i64, i64)> {
+        let mut start_times = BTreeMap::new();
+        let mut job_intervals = BTreeMap::new();
+        for job_log in self.node_set_logs[dag_id]
+            .iter()
+            .filter(|job_log| job_log.node_id == node_id)
+        {
+            match job_log.event_time {
+                JobEventTimes::StartTime(start_time) => {
+                    start_times.insert(job_log.job_id, start_time);
+                }
+                JobEventTimes::FinishTime(finish_time) => {
+                    if let Some(start_time) = start_times.get(&job_log.job_id) {
+                        job_intervals.insert(job_log.job_id, (*start_time, finish_time));
+                    }
+                }
+                _ => {}
+            }
+        }
+        job_intervals.into_values().collect()
+    }
+
+    /// Returns the schedule of each node of the job of the DAG, keyed by node id.
+    /// Only the nodes that finished are included. A preempted node is assigned to the core on which it finished.
+    pub fn get_node_schedules(
+        &self,
+        dag_id: usize,
+        job_id: usize,
+    ) -> BTreeMap<usize, NodeSchedule> {
+        let mut start_times = BTreeMap::new();
+        let mut node_schedules = BTreeMap::new();
+        for job_log in self.node_set_logs[dag_id]
+            .iter()
+            .filter(|job_log| job_log.job_id == job_id)
+        {
+            match job_log.event_time {
+                JobEventTimes::StartTime(start_time) => {
+                    start_times.insert(job_log.node_id, start_time);
+                }
+                JobEventTimes::FinishTime(finish_time) => {
+                    if let Some(start_time) = start_times.get(&job_log.node_id) {
+                        node_schedules.insert(
+                            job_log.node_id,
+                            NodeSchedule {
+                                assigned_core: job_log.core_id,
+                                start_time: *start_time,
+                                finish_time,
+                            },
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        node_schedules
+    }
+
+    /// Reconstruct the execution slices from the start, preempt, resume and finish events.
+    /// A job still running at the end of the log has no slice for its last execution.
+    pub fn get_execution_slices(&self) -> Vec<ExecutionSlice> {
+        get_execution_slices(self.node_set_logs.iter().flatten())
+    }
+
+    /// The schedule in the Chrome trace-event format with one track per core.
+    fn get_chrome_trace(&self) -> ChromeTrace {
+        let mut trace_events: Vec<TraceEvent> = (0..self.processor_info.number_of_cores)
+            .map(|core_id| TraceEvent {
+                name: "thread_name".to_string(),
+                ph: "M",
+                pid: 0,
+                tid: core_id,
+                ts: None,
+                dur: None,
+                args: BTreeMap::from([("name", format!("Core {}", core_id).into())]),
+            })
+            .collect();
+        trace_events.extend(
+            self.get_execution_slices()
+                .into_iter()
+                .map(|slice| TraceEvent {
+                    name: format!(
+                        "DAG {} node {} job {}",
+                        slice.dag_id, slice.node_id, slice.job_id
+                    ),
+                    ph: "X",
+                    pid: 0,
+                    tid: slice.core_id,
+                    ts: Some(slice.start_time),
+                    dur: Some(slice.end_time - slice.start_time),
+                    args: BTreeMap::from([
+                        ("dag_id", slice.dag_id.into()),
+                        ("node_id", slice.node_id.into()),
+                        ("job_id", slice.job_id.into()),
+                    ]),
+                }),
+        );
+        ChromeTrace { trace_events }
+    }
+
+    /// Write the schedule in the Chrome trace-event format with one track per core.
+    #[cfg(feature = "file_io")]
+    pub fn dump_chrome_trace(&self, file_path: &str) {
+        dump_struct_to_json(file_path, &self.get_chrome_trace());
+    }
+
+    /// The JSON written by `dump_chrome_trace`, for the environments without a file system.
+    pub fn to_chrome_trace_json(&self) -> String {
+        serde_json::to_string_pretty(&self.get_chrome_trace()).expect("Failed to serialize.")
+    }
+
+    pub fn write_blocking_time(&mut self, dag_id: usize) {
+        *self.dag_set_log[dag_id].blocking_time.get_or_insert(0) += 1;
+    }
+
+    pub fn write_parallelism_capped_time(&mut self, dag_id: usize) {
+        *self.dag_set_log[dag_id]
+            .parallelism_capped_time
+            .get_or_insert(0) += 1;
+    }
+
+    pub fn write_lag(&mut self, dag_id: usize, lag: f64) {
+        let dag_log = &mut self.dag_set_log[dag_id];
+        dag_log.max_lag = Some(dag_log.max_lag.map_or(lag, |max_lag| max_lag.max(lag)));
+        dag_log.min_lag = Some(dag_log.min_lag.map_or(lag, |min_lag| min_lag.min(lag)));
+    }
+
+    /// Indexed by dag_id.
+    pub fn get_worst_response_times(&self) -> Vec<i64> {
+        self.dag_set_log
+            .iter()
+            .map(|dag_log| dag_log.worst_response_time)
+            .collect()
+    }
+
+    /// Indexed by dag_id.
+    pub fn get_response_times(&self) -> Vec<Vec<i64>> {
+        self.dag_set_log
+            .iter()
+            .map(|dag_log| dag_log.response_time.clone())
+            .collect()
+    }
+
+    /// Whether each DAG has missed a deadline, indexed by dag_id.
+    pub fn get_deadline_miss_flags(&self) -> Vec<bool> {
+        let mut deadline_miss_flags = vec![false; self.dag_set_log.len()];
+        for deadline_miss in self.deadline_misses.iter() {
+            deadline_miss_flags[deadline_miss.dag_id] = true;
         }
+        deadline_miss_flags
     }
 
-    pub fn calculate_utilization(&mut self, schedule_length: i32) {
+    /// `tardiness_bounds` are indexed by dag_id.
+    pub fn write_tardiness_bounds(&mut self, tardiness_bounds: &[f64]) {
+        for (dag_log, tardiness_bound) in self.dag_set_log.iter_mut().zip(tardiness_bounds) {
+            dag_log.tardiness_bound = Some(*tardiness_bound);
+        }
+    }
+
+    /// `response_time_bounds` are indexed by dag_id.
+    pub fn write_response_time_bounds(&mut self, response_time_bounds: &[ResponseTimeBound]) {
+        for (dag_log, response_time_bound) in self.dag_set_log.iter_mut().zip(response_time_bounds)
+        {
+            dag_log.response_time_bound = Some(*response_time_bound);
+        }
+    }
+
+    pub fn calculate_utilization(&mut self, schedule_length: i64) {
         self.processor_log
             .calculate_cores_utilization(schedule_length);
         self.processor_log.calculate_average_utilization();
         self.processor_log.calculate_variance_utilization();
+        let execution_slices = self.get_execution_slices();
+        self.processor_log
+            .calculate_core_timelines(&execution_slices, schedule_length);
+        self.processor_log
+            .calculate_core_dag_time_matrix(&execution_slices, self.dag_set_log.len());
+    }
+
+    /// Returns the time spent on each DAG by each core, indexed by [core_id][dag_id].
+    /// A DAG spread by a global scheduler has its time split across the cores.
+    pub fn get_core_dag_time_matrix(&self) -> &[Vec<i64>] {
+        &self.processor_log.core_dag_time_matrix
+    }
+
+    /// Record the busy and idle intervals of each core in addition to the total processing time.
+    pub fn enable_core_timeline(&mut self) {
+        self.processor_log.records_core_timeline = true;
     }
 
+    /// Returns None if the core timeline is not enabled.
+    pub fn get_core_timeline(&self, core_id: usize) -> Option<&[CoreInterval]> {
+        self.processor_log.core_logs[core_id].timeline.as_deref()
+    }
+
+    #[cfg(feature = "file_io")]
     pub fn dump_log_to_yaml(&self, file_path: &str) {
         dump_struct(file_path, self);
     }
+
+    /// Load a log dumped by `dump_log_to_yaml`. The sections appended to the file by the binaries are ignored.
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        load_yaml_struct(file_path)
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn dump_log_to_json(&self, file_path: &str) {
+        dump_struct_to_json(file_path, self);
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn dump_job_logs_to_csv(&self, file_path: &str) {
+        dump_job_logs_to_csv(file_path, self.node_set_logs.iter().flatten());
+    }
+
+    #[cfg(feature = "arrow")]
+    pub fn dump_job_logs_to_parquet(&self, file_path: &str) -> Result<()> {
+        dump_job_logs_to_parquet(file_path, self.node_set_logs.iter().flatten())
+    }
+
+    /// Write one row per DAG with its response times, tardiness and deadline misses.
+    #[cfg(feature = "arrow")]
+    pub fn dump_dag_summaries_to_parquet(&self, file_path: &str) -> Result<()> {
+        let dag_logs = &self.dag_set_log;
+        let mut number_of_deadline_misses = vec![0; dag_logs.len()];
+        for deadline_miss in self.deadline_misses.iter() {
+            number_of_deadline_misses[deadline_miss.dag_id] += 1;
+        }
+        let i64_column = |get_value: fn(&DAGLog) -> i64| -> ArrayRef {
+            Arc::new(Int64Array::from_iter_values(dag_logs.iter().map(get_value)))
+        };
+        write_columns_to_parquet(
+            file_path,
+            vec![
+                (
+                    "dag_id",
+                    Arc::new(UInt64Array::from_iter_values(
+                        dag_logs.iter().map(|dag_log| dag_log.dag_id as u64),
+                    )),
+                ),
+                (
+                    "number_of_jobs",
+                    Arc::new(UInt64Array::from_iter_values(
+                        dag_logs
+                            .iter()
+                            .map(|dag_log| dag_log.response_time.len() as u64),
+                    )),
+                ),
+                (
+                    "average_response_time",
+                    Arc::new(Float32Array::from_iter_values(
+                        dag_logs.iter().map(|dag_log| dag_log.average_response_time),
+                    )),
+                ),
+                (
+                    "worst_response_time",
+                    i64_column(|dag_log| dag_log.worst_response_time),
+                ),
+                (
+                    "total_tardiness",
+                    i64_column(|dag_log| dag_log.tardiness_stats.total_tardiness),
+                ),
+                (
+                    "max_tardiness",
+                    i64_column(|dag_log| dag_log.tardiness_stats.max_tardiness),
+                ),
+                (
+                    "weighted_tardiness",
+                    i64_column(|dag_log| dag_log.tardiness_stats.weighted_tardiness),
+                ),
+                (
+                    "number_of_deadline_misses",
+                    Arc::new(UInt64Array::from(number_of_deadline_misses)),
+                ),
+            ],
+        )
+    }
 }
 
+#[cfg(feature = "file_io")]
 #[derive(Serialize, Deserialize)]
 struct DAGSchedulerResultInfo {
-    schedule_length: i32,
+    schedule_length: i64,
     period_factor: f32,
     result: bool,
 }
 
+#[cfg(feature = "file_io")]
 pub fn dump_dag_scheduler_result_to_yaml(
     file_path: &str,
-    schedule_length: i32,
+    schedule_length: i64,
     period_factor: f32,
     result: bool,
 ) {
@@ -410,12 +1668,149 @@ pub fn dump_dag_scheduler_result_to_yaml(
     dump_struct(file_path, &result_info);
 }
 
+#[cfg(feature = "file_io")]
 #[derive(Serialize, Deserialize)]
 struct DAGSetSchedulerResultInfo {
     result: bool,
 }
 
+#[cfg(feature = "file_io")]
 pub fn dump_dag_set_scheduler_result_to_yaml(file_path: &str, result: bool) {
     let result_info = DAGSetSchedulerResultInfo { result };
     dump_struct(file_path, &result_info);
 }
+
+/// Wall-clock time of a run in seconds, split into the pre-processing of the algorithm
+/// (e.g., decomposition, priority assignment and minimum-core search) and the simulation.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RunTimeLog {
+    preprocessing_time: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    simulation_time: Option<f64>, // None for the analyses without a simulation
+}
+
+impl RunTimeLog {
+    pub fn measure_preprocessing<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start_time = Instant::now();
+        let result = f();
+        self.preprocessing_time += start_time.elapsed().as_secs_f64();
+        result
+    }
+
+    pub fn measure_simulation<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let start_time = Instant::now();
+        let result = f();
+        *self.simulation_time.get_or_insert(0.0) += start_time.elapsed().as_secs_f64();
+        result
+    }
+
+    /// Count the pre-processing done inside the scheduling, such as the minimum-core search of DynFed,
+    /// as pre-processing instead of simulation.
+    pub fn move_to_preprocessing(&mut self, preprocessing_time: Duration) {
+        let preprocessing_time = preprocessing_time.as_secs_f64();
+        self.preprocessing_time += preprocessing_time;
+        if let Some(simulation_time) = self.simulation_time.as_mut() {
+            *simulation_time = (*simulation_time - preprocessing_time).max(0.0);
+        }
+    }
+
+    pub fn get_preprocessing_time(&self) -> f64 {
+        self.preprocessing_time
+    }
+
+    pub fn get_simulation_time(&self) -> Option<f64> {
+        self.simulation_time
+    }
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize)]
+struct RunTimeInfo<'a> {
+    run_time_log: &'a RunTimeLog,
+}
+
+#[cfg(feature = "file_io")]
+pub fn dump_run_time_to_yaml(file_path: &str, run_time_log: &RunTimeLog) {
+    dump_struct(file_path, &RunTimeInfo { run_time_log });
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize)]
+struct SchedulabilityTestInfo<'a> {
+    schedulability_tests: &'a SchedulabilityVerdicts,
+}
+
+/// Dump the analytic verdicts to be compared with the simulated result.
+#[cfg(feature = "file_io")]
+pub fn dump_schedulability_verdicts_to_yaml(
+    file_path: &str,
+    schedulability_verdicts: &SchedulabilityVerdicts,
+) {
+    dump_struct(
+        file_path,
+        &SchedulabilityTestInfo {
+            schedulability_tests: schedulability_verdicts,
+        },
+    );
+}
+
+/// Output format of the binaries.
+/// The log is written to the YAML file regardless of the format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Nothing is printed.
+    Yaml,
+    /// The whole log is also written to a JSON file, and the summary is printed as a single JSON object
+    /// so that wrappers do not need to parse the YAML file.
+    Json,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "yaml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown output format: {}",
+                name
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize, Deserialize)]
+struct ResultSummary {
+    result: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule_length: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    worst_response_times: Vec<i64>,
+    log_file_path: String,
+}
+
+/// If the format is JSON, write the YAML log including the appended sections to a JSON file
+/// and print the summary on stdout.
+/// `schedule_length` and `worst_response_times` are omitted for an analysis without simulation.
+#[cfg(feature = "file_io")]
+pub fn print_result_summary(
+    output_format: OutputFormat,
+    file_path: &str,
+    result: bool,
+    schedule_length: Option<i64>,
+    worst_response_times: &[i64],
+) {
+    if output_format != OutputFormat::Json {
+        return;
+    }
+    let result_summary = ResultSummary {
+        result,
+        schedule_length,
+        worst_response_times: worst_response_times.to_vec(),
+        log_file_path: convert_yaml_to_json(file_path),
+    };
+    println!(
+        "{}",
+        serde_json::to_string(&result_summary).expect("Failed to serialize.")
+    );
+}
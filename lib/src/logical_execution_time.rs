@@ -0,0 +1,92 @@
+//! Logical execution time (LET) semantics of the DAG set scheduler.
+//! Under LET, a node reads its inputs when it is released and publishes its outputs
+//! `logical_execution_time` after the release, regardless of when it actually finishes in between.
+//! The successors are released when all their predecessors have published, and a DAG job finishes
+//! when its sinks publish, so the data flow, including the end-to-end and chain latencies, is free of scheduling jitter.
+//! A node finishing after the end of its LET interval publishes on finish, and the overrun is reported.
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::NodeData;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommunicationSemantics {
+    /// A node reads its inputs when it starts and writes its outputs when it finishes.
+    #[default]
+    Implicit,
+    LogicalExecutionTime,
+}
+
+impl CommunicationSemantics {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "implicit" => Ok(Self::Implicit),
+            "let" => Ok(Self::LogicalExecutionTime),
+            _ => Err(SchedSimError::Validation(format!(
+                "Unknown communication semantics: {}",
+                name
+            ))),
+        }
+    }
+
+    pub fn is_implicit(&self) -> bool {
+        *self == Self::Implicit
+    }
+}
+
+/// Set the time at which the node released at `release_time` publishes its outputs.
+pub fn set_publish_time(node_data: &mut NodeData, release_time: i64) {
+    let publish_time = release_time + node_data.get_params_value("logical_execution_time");
+    node_data
+        .params
+        .insert("publish_time".to_string(), publish_time);
+}
+
+/// Return an error if a node has no `logical_execution_time` or a negative one.
+pub fn validate_logical_execution_times(dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        for node in dag.node_weights() {
+            match node.params.get("logical_execution_time") {
+                Some(logical_execution_time) if *logical_execution_time >= 0 => {}
+                _ => {
+                    return Err(SchedSimError::Validation(format!(
+                    "Node {} of DAG {} must have a non-negative logical_execution_time under LET.",
+                    node.id, dag_id
+                )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    #[test]
+    fn test_set_publish_time_normal() {
+        let mut node_data = create_node(0, "logical_execution_time", 20);
+        set_publish_time(&mut node_data, 30);
+        assert_eq!(node_data.get_params_value("publish_time"), 50);
+    }
+
+    #[test]
+    fn test_validate_logical_execution_times_missing() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        dag.add_node(create_node(0, "execution_time", 10));
+        assert!(validate_logical_execution_times(&[dag]).is_err());
+    }
+
+    #[test]
+    fn test_communication_semantics_from_name_unknown() {
+        assert!(CommunicationSemantics::from_name("unknown").is_err());
+    }
+}
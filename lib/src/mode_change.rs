@@ -0,0 +1,241 @@
+//! Mode changes of a DAG set.
+//! Each mode activates a subset of the DAGs, and a mode-change request at a configured time switches to another mode.
+//! When a request is accepted, the DAGs leaving the mode are no longer released,
+//! and the mode is switched once their unfinished jobs have completed.
+//! The DAGs entering the mode are released only after the switch, while the DAGs in both modes are released throughout.
+//! The arrivals of an inactive DAG are skipped, so the DAG keeps its release phase when it becomes active again.
+//!
+//! Example of a mode schedule file:
+//!
+//! ```yaml
+//! initial_mode: 0
+//! modes:
+//!   - [0, 1]
+//!   - [1, 2]
+//! requests:
+//!   - time: 100
+//!     mode: 1
+//! ```
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::NodeData;
+#[cfg(feature = "file_io")]
+use crate::util::load_yaml_struct;
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeSet, VecDeque};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModeChangeRequest {
+    pub time: i64,
+    pub mode: usize,
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Deserialize)]
+struct ModeScheduleFile {
+    #[serde(default)]
+    initial_mode: usize,
+    modes: Vec<BTreeSet<usize>>,
+    #[serde(default)]
+    requests: Vec<ModeChangeRequest>,
+}
+
+fn check_modes(
+    modes: &[BTreeSet<usize>],
+    initial_mode: usize,
+    requests: &[ModeChangeRequest],
+) -> Result<()> {
+    if initial_mode >= modes.len() {
+        return Err(SchedSimError::Validation(format!(
+            "The initial mode does not exist: {}",
+            initial_mode
+        )));
+    }
+    if let Some(request) = requests.iter().find(|request| request.mode >= modes.len()) {
+        return Err(SchedSimError::Validation(format!(
+            "The requested mode does not exist: {} at {}",
+            request.mode, request.time
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModeSchedule {
+    modes: Vec<BTreeSet<usize>>,
+    current_mode: usize,
+    requests: VecDeque<ModeChangeRequest>,
+    // The request waiting for the jobs of the leaving DAGs to complete.
+    pending_request: Option<ModeChangeRequest>,
+}
+
+impl ModeSchedule {
+    pub fn new(
+        modes: Vec<BTreeSet<usize>>,
+        initial_mode: usize,
+        mut requests: Vec<ModeChangeRequest>,
+    ) -> Self {
+        check_modes(&modes, initial_mode, &requests).unwrap_or_else(|err| panic!("{}", err));
+        requests.sort_by_key(|request| request.time);
+        Self {
+            modes,
+            current_mode: initial_mode,
+            requests: requests.into(),
+            pending_request: None,
+        }
+    }
+
+    /// load yaml file and return a mode schedule
+    ///
+    /// # Arguments
+    ///
+    /// *  `file_path` - yaml file path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lib::mode_change::ModeSchedule;
+    ///
+    /// let mode_schedule = ModeSchedule::from_yaml_file("tests/sample_mode_schedules/two_modes.yaml").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        let mode_schedule_file: ModeScheduleFile = load_yaml_struct(file_path)?;
+        check_modes(
+            &mode_schedule_file.modes,
+            mode_schedule_file.initial_mode,
+            &mode_schedule_file.requests,
+        )?;
+        Ok(Self::new(
+            mode_schedule_file.modes,
+            mode_schedule_file.initial_mode,
+            mode_schedule_file.requests,
+        ))
+    }
+
+    /// Return an error if a mode contains a DAG that is not in the DAG set.
+    pub fn validate(&self, dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+        for (mode, dag_ids) in self.modes.iter().enumerate() {
+            if let Some(dag_id) = dag_ids.iter().find(|dag_id| **dag_id >= dag_set.len()) {
+                return Err(SchedSimError::Validation(format!(
+                    "Mode {} contains a non-existent DAG: {}",
+                    mode, dag_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_current_mode(&self) -> usize {
+        self.current_mode
+    }
+
+    /// A DAG is released only if it is in the current mode and is not leaving it.
+    pub fn is_dag_active(&self, dag_id: usize) -> bool {
+        self.modes[self.current_mode].contains(&dag_id)
+            && self
+                .pending_request
+                .is_none_or(|request| self.modes[request.mode].contains(&dag_id))
+    }
+
+    /// Accept the next request made by `current_time` if no mode change is in progress,
+    /// and switch the mode if all the leaving DAGs are waiting for their next release.
+    /// Returns the previous mode and the request if the mode is switched.
+    pub fn switch_mode(
+        &mut self,
+        current_time: i64,
+        is_dag_waiting: impl Fn(usize) -> bool,
+    ) -> Option<(usize, ModeChangeRequest)> {
+        if self.pending_request.is_none()
+            && self
+                .requests
+                .front()
+                .is_some_and(|request| request.time <= current_time)
+        {
+            self.pending_request = self.requests.pop_front();
+        }
+        let request = self.pending_request?;
+        let can_switch = self.modes[self.current_mode]
+            .difference(&self.modes[request.mode])
+            .all(|dag_id| is_dag_waiting(*dag_id));
+        if !can_switch {
+            return None;
+        }
+        let previous_mode = self.current_mode;
+        self.current_mode = request.mode;
+        self.pending_request = None;
+        Some((previous_mode, request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_two_mode_schedule(requests: Vec<ModeChangeRequest>) -> ModeSchedule {
+        ModeSchedule::new(
+            vec![BTreeSet::from([0, 1]), BTreeSet::from([1, 2])],
+            0,
+            requests,
+        )
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_normal() {
+        let mode_schedule =
+            ModeSchedule::from_yaml_file("tests/sample_mode_schedules/two_modes.yaml").unwrap();
+        assert_eq!(
+            mode_schedule,
+            create_two_mode_schedule(vec![ModeChangeRequest { time: 150, mode: 1 }])
+        );
+    }
+
+    #[test]
+    fn test_switch_mode_after_leaving_jobs_complete() {
+        let mut mode_schedule =
+            create_two_mode_schedule(vec![ModeChangeRequest { time: 10, mode: 1 }]);
+
+        assert_eq!(mode_schedule.switch_mode(5, |_| true), None);
+        assert!(mode_schedule.is_dag_active(0));
+
+        // DAG 0 is leaving but still has an unfinished job.
+        assert_eq!(mode_schedule.switch_mode(10, |dag_id| dag_id != 0), None);
+        assert!(!mode_schedule.is_dag_active(0));
+        assert!(mode_schedule.is_dag_active(1));
+        assert!(!mode_schedule.is_dag_active(2));
+
+        assert_eq!(
+            mode_schedule.switch_mode(12, |_| true),
+            Some((0, ModeChangeRequest { time: 10, mode: 1 }))
+        );
+        assert_eq!(mode_schedule.get_current_mode(), 1);
+        assert!(!mode_schedule.is_dag_active(0));
+        assert!(mode_schedule.is_dag_active(2));
+    }
+
+    #[test]
+    fn test_switch_mode_queued_requests() {
+        let mut mode_schedule = create_two_mode_schedule(vec![
+            ModeChangeRequest { time: 20, mode: 0 },
+            ModeChangeRequest { time: 10, mode: 1 },
+        ]);
+
+        assert_eq!(mode_schedule.switch_mode(20, |dag_id| dag_id != 0), None);
+        assert_eq!(
+            mode_schedule.switch_mode(20, |_| true),
+            Some((0, ModeChangeRequest { time: 10, mode: 1 }))
+        );
+        assert_eq!(
+            mode_schedule.switch_mode(20, |_| true),
+            Some((1, ModeChangeRequest { time: 20, mode: 0 }))
+        );
+        assert_eq!(mode_schedule.switch_mode(20, |_| true), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_no_requested_mode() {
+        create_two_mode_schedule(vec![ModeChangeRequest { time: 10, mode: 2 }]);
+    }
+}
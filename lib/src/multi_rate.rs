@@ -0,0 +1,330 @@
+//! Multi-rate DAGs, whose source nodes carry different periods, e.g., the 10 ms camera and the 40 ms radar
+//! of an automotive perception DAG, without splitting them into artificial separate DAGs.
+//! A non-source node runs at the rate of its predecessors, or at its own `period` if it is given,
+//! which it must be if its predecessors have different rates.
+//!
+//! A multi-rate DAG is unrolled over its hyper period into a single-rate DAG, whose job is the hyper period:
+//! a node of period `p` becomes `hyper_period / p` instances, the k-th of which is released `k * p` after the job arrival.
+//! On an edge crossing a rate boundary, each consumer instance reads the latest producer instance released by its own release.
+//! So consecutive instances of a faster consumer read the same producer instance (oversampling),
+//! and the producer instances between the reads of a slower consumer are not read (undersampling).
+//! The end-to-end deadline of an instance is relative to its own release, and a sink without one has its implicit deadline.
+use crate::{
+    error::{Result, SchedSimError},
+    graph_extension::{GraphExtension, NodeData},
+};
+use num_integer::lcm;
+use petgraph::{algo::toposort, graph::NodeIndex, visit::EdgeRef, Graph};
+use std::collections::{BTreeSet, HashMap};
+
+/// Whether the nodes of the DAG carry different periods.
+pub fn is_multi_rate_dag(dag: &Graph<NodeData, i64>) -> bool {
+    dag.node_weights()
+        .filter_map(|node| node.params.get("period"))
+        .collect::<BTreeSet<_>>()
+        .len()
+        > 1
+}
+
+/// Whether the DAG is unrolled from a multi-rate DAG, i.e., its nodes are released by their offsets.
+pub fn is_unrolled_multi_rate_dag(dag: &Graph<NodeData, i64>) -> bool {
+    dag.node_weights()
+        .any(|node| node.params.contains_key("release_offset"))
+}
+
+fn get_node_periods(dag: &Graph<NodeData, i64>) -> Result<HashMap<NodeIndex, i64>> {
+    let mut node_periods = HashMap::new();
+    for node_i in toposort(dag, None).unwrap() {
+        let pre_periods: BTreeSet<i64> = dag
+            .get_pre_nodes(node_i)
+            .unwrap_or_default()
+            .iter()
+            .map(|pre_node_i| node_periods[pre_node_i])
+            .collect();
+        let period = match (dag[node_i].params.get("period"), pre_periods.len()) {
+            (Some(period), _) => *period,
+            (None, 1) => *pre_periods.first().unwrap(),
+            (None, 0) => {
+                return Err(SchedSimError::Validation(format!(
+                    "Source node {} of the multi-rate DAG has no period.",
+                    dag[node_i].id
+                )))
+            }
+            (None, _) => {
+                return Err(SchedSimError::Validation(format!(
+                    "Node {} of the multi-rate DAG has predecessors of different periods, so it must have its own period.",
+                    dag[node_i].id
+                )))
+            }
+        };
+        if period <= 0 {
+            return Err(SchedSimError::Validation(format!(
+                "Node {} of the multi-rate DAG has a non-positive period {}.",
+                dag[node_i].id, period
+            )));
+        }
+        node_periods.insert(node_i, period);
+    }
+    Ok(node_periods)
+}
+
+/// Unroll the multi-rate DAG over its hyper period into a single-rate DAG.
+/// Each instance keeps the id of its node as `original_node_id` and its index in the hyper period as `instance_index`,
+/// and the ids are renumbered in the order of the nodes and their instances.
+/// The head source keeps the `offset` of the DAG and gets the hyper period as its `period`.
+pub fn unroll_multi_rate_dag(dag: &Graph<NodeData, i64>) -> Result<Graph<NodeData, i64>> {
+    let node_periods = get_node_periods(dag)?;
+    let hyper_period = node_periods
+        .values()
+        .fold(1, |hyper_period, period| lcm(hyper_period, *period));
+    let source_nodes = dag.get_source_nodes();
+    let head_offset = source_nodes
+        .iter()
+        .find_map(|source_i| dag[*source_i].params.get("offset").copied());
+
+    let mut unrolled_dag = Graph::<NodeData, i64>::new();
+    let mut instance_indices = HashMap::new();
+    let mut instance_periods = Vec::new();
+    for node_i in dag.node_indices() {
+        let period = node_periods[&node_i];
+        for instance_index in 0..hyper_period / period {
+            let release_offset = instance_index * period;
+            let mut node_data = dag[node_i].clone();
+            node_data.id = unrolled_dag.node_count() as i64;
+            node_data.params.remove("period");
+            node_data.params.remove("offset");
+            node_data
+                .params
+                .insert("original_node_id".to_string(), dag[node_i].id);
+            node_data
+                .params
+                .insert("instance_index".to_string(), instance_index);
+            node_data
+                .params
+                .insert("release_offset".to_string(), release_offset);
+            if let Some(end_to_end_deadline) = dag[node_i].params.get("end_to_end_deadline") {
+                node_data.params.insert(
+                    "end_to_end_deadline".to_string(),
+                    release_offset + end_to_end_deadline,
+                );
+            }
+            instance_indices.insert(
+                (node_i, instance_index),
+                unrolled_dag.add_node_with_id_consistency(node_data),
+            );
+            instance_periods.push(period);
+        }
+    }
+
+    let head_i = instance_indices[&(source_nodes[0], 0)];
+    unrolled_dag[head_i]
+        .params
+        .insert("period".to_string(), hyper_period);
+    if let Some(head_offset) = head_offset {
+        unrolled_dag[head_i]
+            .params
+            .insert("offset".to_string(), head_offset);
+    }
+
+    for edge in dag.edge_references() {
+        let producer_period = node_periods[&edge.source()];
+        let consumer_period = node_periods[&edge.target()];
+        for consumer_instance_index in 0..hyper_period / consumer_period {
+            // The latest producer instance released by the release of the consumer instance.
+            let producer_instance_index =
+                consumer_instance_index * consumer_period / producer_period;
+            unrolled_dag.add_edge(
+                instance_indices[&(edge.source(), producer_instance_index)],
+                instance_indices[&(edge.target(), consumer_instance_index)],
+                *edge.weight(),
+            );
+        }
+    }
+
+    // An instance read by no consumer is also a sink, e.g., of undersampling.
+    for sink_i in unrolled_dag.get_sink_nodes() {
+        let params = &mut unrolled_dag[sink_i].params;
+        if !params.contains_key("end_to_end_deadline") {
+            params.insert(
+                "end_to_end_deadline".to_string(),
+                params["release_offset"] + instance_periods[sink_i.index()],
+            );
+        }
+    }
+    Ok(unrolled_dag)
+}
+
+/// Unroll the multi-rate DAGs of the set in place, and leave the single-rate DAGs as they are.
+pub fn unroll_multi_rate_dag_set(dag_set: &mut [Graph<NodeData, i64>]) -> Result<()> {
+    for dag in dag_set.iter_mut() {
+        if is_multi_rate_dag(dag) {
+            *dag = unroll_multi_rate_dag(dag)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set the release times of the instances of the job arrived at `arrival_time`.
+pub fn set_release_times(dag: &mut Graph<NodeData, i64>, arrival_time: i64) {
+    for node in dag.node_weights_mut() {
+        if let Some(&release_offset) = node.params.get("release_offset") {
+            node.params
+                .insert("release_time".to_string(), arrival_time + release_offset);
+        }
+    }
+}
+
+/// The source nodes released at the job arrival.
+pub fn get_head_source_nodes(dag: &Graph<NodeData, i64>) -> Vec<NodeData> {
+    dag.get_source_nodes()
+        .into_iter()
+        .filter(|source_i| {
+            dag[*source_i]
+                .params
+                .get("release_offset")
+                .is_none_or(|release_offset| *release_offset == 0)
+        })
+        .map(|source_i| dag[source_i].clone())
+        .collect()
+}
+
+/// The instances released later than the job arrival whose release times are `current_time`,
+/// and whose predecessors have finished by then.
+pub fn get_offset_released_nodes(dag: &Graph<NodeData, i64>, current_time: i64) -> Vec<NodeData> {
+    dag.node_indices()
+        .filter(|node_i| {
+            let params = &dag[*node_i].params;
+            params
+                .get("release_offset")
+                .is_some_and(|release_offset| *release_offset > 0)
+                && params.get("release_time") == Some(&current_time)
+                && dag.is_node_ready(*node_i)
+        })
+        .map(|node_i| dag[node_i].clone())
+        .collect()
+}
+
+/// Whether a node whose predecessors finish at `current_time` is released now.
+/// An instance whose release time is `current_time` or later is released by `get_offset_released_nodes`.
+pub fn is_released_on_ready(node_data: &NodeData, current_time: i64) -> bool {
+    node_data
+        .params
+        .get("release_time")
+        .is_none_or(|release_time| *release_time < current_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dag_creator::create_dag_from_yaml_str,
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+    };
+
+    fn get_edges(dag: &Graph<NodeData, i64>) -> Vec<(usize, usize)> {
+        dag.edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unroll_multi_rate_dag_undersampling() {
+        // The 10 ms node 0 and the 20 ms node 1 are fused by the 20 ms node 2.
+        let dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 10}, {id: 1, execution_time: 4, period: 20}, {id: 2, execution_time: 3, period: 20}]\nlinks: [{source: 0, target: 2}, {source: 1, target: 2}]",
+            false,
+        )
+        .unwrap();
+        assert!(is_multi_rate_dag(&dag));
+
+        let unrolled_dag = unroll_multi_rate_dag(&dag).unwrap();
+        assert!(is_unrolled_multi_rate_dag(&unrolled_dag));
+        assert_eq!(unrolled_dag.node_count(), 4);
+        assert_eq!(unrolled_dag.get_head_period(), Some(20));
+        assert_eq!(
+            unrolled_dag
+                .node_weights()
+                .map(|node| (
+                    node.get_params_value("original_node_id"),
+                    node.get_params_value("release_offset")
+                ))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (0, 10), (1, 0), (2, 0)]
+        );
+        // The second instance of node 0 is not read.
+        assert_eq!(get_edges(&unrolled_dag), vec![(0, 3), (2, 3)]);
+        assert_eq!(
+            unrolled_dag.get_deadlines_per_sink(),
+            vec![(NodeIndex::new(1), 20), (NodeIndex::new(3), 20)]
+        );
+    }
+
+    #[test]
+    fn test_unroll_multi_rate_dag_oversampling() {
+        let dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 20}, {id: 1, execution_time: 1, period: 10, end_to_end_deadline: 5}]\nlinks: [{source: 0, target: 1, communication_time: 3}]",
+            false,
+        )
+        .unwrap();
+        let unrolled_dag = unroll_multi_rate_dag(&dag).unwrap();
+
+        // Both instances of node 1 read the only instance of node 0.
+        assert_eq!(get_edges(&unrolled_dag), vec![(0, 1), (0, 2)]);
+        assert_eq!(
+            unrolled_dag.get_deadlines_per_sink(),
+            vec![(NodeIndex::new(1), 5), (NodeIndex::new(2), 15)]
+        );
+    }
+
+    #[test]
+    fn test_unroll_multi_rate_dag_ambiguous_rate() {
+        let dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 10}, {id: 1, execution_time: 4, period: 20}, {id: 2, execution_time: 3}]\nlinks: [{source: 0, target: 2}, {source: 1, target: 2}]",
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            unroll_multi_rate_dag(&dag),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_unroll_multi_rate_dag_set_single_rate() {
+        let dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 10}, {id: 1, execution_time: 4}]\nlinks: [{source: 0, target: 1}]",
+            false,
+        )
+        .unwrap();
+        let mut dag_set = vec![dag.clone()];
+        unroll_multi_rate_dag_set(&mut dag_set).unwrap();
+        assert_eq!(dag_set[0].node_count(), dag.node_count());
+        assert!(!is_unrolled_multi_rate_dag(&dag_set[0]));
+    }
+
+    #[test]
+    fn test_schedule_unrolled_multi_rate_dag() {
+        let mut dag = create_dag_from_yaml_str(
+            "nodes: [{id: 0, execution_time: 2, period: 10}, {id: 1, execution_time: 4, period: 20}, {id: 2, execution_time: 3, period: 20}]\nlinks: [{source: 0, target: 2}, {source: 1, target: 2}]",
+            false,
+        )
+        .unwrap();
+        dag = unroll_multi_rate_dag(&dag).unwrap();
+        dag.set_dag_param("dag_id", 0);
+
+        let mut global_edf_scheduler =
+            GlobalEDFScheduler::new(&[dag], &HomogeneousProcessor::new(1));
+        let schedule_outcome = global_edf_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        // The second instance of node 0 is released at 10, after the others have finished at 9.
+        assert_eq!(schedule_outcome.log.get_node_finish_time(0, 3, 0), Some(9));
+        assert_eq!(schedule_outcome.log.get_node_finish_time(0, 1, 0), Some(12));
+        assert_eq!(schedule_outcome.worst_response_times, vec![12]);
+        assert_eq!(schedule_outcome.deadline_miss_flags, vec![false]);
+    }
+}
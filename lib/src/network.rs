@@ -0,0 +1,403 @@
+//! Distributed platform of multiple processors, such as edge devices and a cloud server, connected by network links.
+//! The cores of all processors are numbered consecutively from processor 0 and scheduled as one processor,
+//! and a node with `processor_id` is only allocated to the cores of that processor.
+//!
+//! The edge weight (`communication_time`) is the amount of data, which takes one time unit per unit on the interconnect of a processor.
+//! A link transfers `bandwidth` units per time unit after its `latency`, and transfers in the same direction are serialized.
+//!
+//! For partitioned scheduling, the end-to-end deadline of a DAG can be decomposed into the sub-deadlines of the nodes,
+//! which the local schedulers use as `int_scaled_node_relative_deadline`.
+//!
+//! Example of a network file:
+//!
+//! ```yaml
+//! numbers_of_cores: [2, 4]
+//! links:
+//!   - from: 0
+//!     to: 1
+//!     latency: 10
+//!     bandwidth: 2
+//! ```
+use crate::error::{Result, SchedSimError};
+use crate::{
+    graph_extension::{GraphExtension, NodeData},
+    interconnect::Interconnect,
+};
+use petgraph::{visit::EdgeRef, visit::Topo, Direction::Incoming, Graph};
+use std::collections::BTreeMap;
+#[cfg(feature = "file_io")]
+use {crate::util::load_yaml_struct, serde_derive::Deserialize};
+
+// `DEADLINE_FACTOR` is used to scale the sub-deadline of a node to an integer type.
+const DEADLINE_FACTOR: f64 = 100000.0;
+
+#[derive(Clone, Debug, PartialEq)]
+struct NetworkLink {
+    latency: i64,
+    bandwidth: i64,
+    channel: Interconnect,
+}
+
+impl NetworkLink {
+    fn get_transfer_time(&self, data_size: i64) -> i64 {
+        self.latency + (data_size + self.bandwidth - 1) / self.bandwidth
+    }
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Deserialize)]
+struct NetworkLinkSpec {
+    from: usize,
+    to: usize,
+    latency: i64,
+    bandwidth: i64,
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Deserialize)]
+struct NetworkSpec {
+    numbers_of_cores: Vec<usize>,
+    #[serde(default)]
+    links: Vec<NetworkLinkSpec>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Network {
+    core_processor_ids: Vec<usize>,
+    // Keyed by (from processor_id, to processor_id)
+    links: BTreeMap<(usize, usize), NetworkLink>,
+}
+
+impl Network {
+    /// `numbers_of_cores[i]` is the number of cores of processor i.
+    pub fn new(numbers_of_cores: &[usize]) -> Self {
+        Self {
+            core_processor_ids: numbers_of_cores
+                .iter()
+                .enumerate()
+                .flat_map(|(processor_id, number_of_cores)| {
+                    std::iter::repeat_n(processor_id, *number_of_cores)
+                })
+                .collect(),
+            links: BTreeMap::new(),
+        }
+    }
+
+    /// # Example
+    ///
+    /// ```
+    /// use lib::network::Network;
+    ///
+    /// let network = Network::from_yaml_file("tests/sample_networks/edge_cloud.yaml").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        let network_spec: NetworkSpec = load_yaml_struct(file_path)?;
+        let mut network = Self::new(&network_spec.numbers_of_cores);
+        for link in network_spec.links {
+            network.check_link(link.from, link.to, link.bandwidth)?;
+            network.add_link(link.from, link.to, link.latency, link.bandwidth);
+        }
+        Ok(network)
+    }
+
+    fn check_link(
+        &self,
+        processor_id_a: usize,
+        processor_id_b: usize,
+        bandwidth: i64,
+    ) -> Result<()> {
+        let number_of_processors = self.get_number_of_processors();
+        if processor_id_a >= number_of_processors || processor_id_b >= number_of_processors {
+            return Err(SchedSimError::Validation(format!(
+                "The link between processors {} and {} is out of the {} processors.",
+                processor_id_a, processor_id_b, number_of_processors
+            )));
+        }
+        if bandwidth <= 0 {
+            return Err(SchedSimError::Validation(
+                "The bandwidth of a link must be positive.".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Connect the processors in both directions. Each direction has its own channel.
+    pub fn add_link(
+        &mut self,
+        processor_id_a: usize,
+        processor_id_b: usize,
+        latency: i64,
+        bandwidth: i64,
+    ) {
+        self.check_link(processor_id_a, processor_id_b, bandwidth)
+            .unwrap_or_else(|err| panic!("{}", err));
+        for key in [
+            (processor_id_a, processor_id_b),
+            (processor_id_b, processor_id_a),
+        ] {
+            self.links.insert(
+                key,
+                NetworkLink {
+                    latency,
+                    bandwidth,
+                    channel: Interconnect::new(1),
+                },
+            );
+        }
+    }
+
+    pub fn get_number_of_cores(&self) -> usize {
+        self.core_processor_ids.len()
+    }
+
+    pub fn get_number_of_processors(&self) -> usize {
+        self.core_processor_ids
+            .last()
+            .map_or(0, |processor_id| processor_id + 1)
+    }
+
+    pub fn get_processor_id(&self, core_id: usize) -> usize {
+        self.core_processor_ids[core_id]
+    }
+
+    /// Returns the `allowed_cores` bitmask of the cores of the processor.
+    pub fn get_processor_core_mask(&self, processor_id: usize) -> i64 {
+        self.core_processor_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| **id == processor_id)
+            .fold(0, |mask, (core_id, _)| mask | 1 << core_id)
+    }
+
+    /// Restrict the nodes with `processor_id` to the cores of the processor.
+    pub fn assign_nodes_to_processors(&self, dag_set: &mut [Graph<NodeData, i64>]) {
+        if self.get_number_of_cores() > 63 {
+            panic!("The assignment to processors supports up to 63 cores.");
+        }
+        for dag in dag_set.iter_mut() {
+            for node_i in dag.node_indices() {
+                if let Some(processor_id) = dag[node_i].params.get("processor_id").copied() {
+                    if processor_id as usize >= self.get_number_of_processors() {
+                        panic!(
+                            "Node {} is assigned to the nonexistent processor {}.",
+                            dag[node_i].id, processor_id
+                        );
+                    }
+                    let core_mask = self.get_processor_core_mask(processor_id as usize);
+                    if dag[node_i].params.contains_key("allowed_cores") {
+                        dag.update_param(node_i, "allowed_cores", core_mask);
+                    } else {
+                        dag.add_param(node_i, "allowed_cores", core_mask);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reserve the link for a transfer of `data_size` from `from_core_id` to `to_core_id` which can start at `ready_time`.
+    /// Returns the (start time, finish time) of the transfer, or None if both cores belong to the same processor.
+    pub fn reserve_transfer(
+        &mut self,
+        from_core_id: usize,
+        to_core_id: usize,
+        ready_time: i64,
+        data_size: i64,
+    ) -> Option<(i64, i64)> {
+        let from_processor_id = self.get_processor_id(from_core_id);
+        let to_processor_id = self.get_processor_id(to_core_id);
+        if from_processor_id == to_processor_id {
+            return None;
+        }
+        let transfer_time = self
+            .get_link(from_processor_id, to_processor_id)
+            .get_transfer_time(data_size);
+        let link = self
+            .links
+            .get_mut(&(from_processor_id, to_processor_id))
+            .unwrap();
+        Some(link.channel.reserve_transfer(ready_time, transfer_time))
+    }
+
+    fn get_link(&self, from_processor_id: usize, to_processor_id: usize) -> &NetworkLink {
+        self.links
+            .get(&(from_processor_id, to_processor_id))
+            .unwrap_or_else(|| {
+                panic!(
+                    "No link from processor {} to processor {}.",
+                    from_processor_id, to_processor_id
+                )
+            })
+    }
+
+    /// Returns the transfer time of `data_size` without contention, or 0 within a processor.
+    pub fn get_link_delay(
+        &self,
+        from_processor_id: usize,
+        to_processor_id: usize,
+        data_size: i64,
+    ) -> i64 {
+        if from_processor_id == to_processor_id {
+            0
+        } else {
+            self.get_link(from_processor_id, to_processor_id)
+                .get_transfer_time(data_size)
+        }
+    }
+
+    /// Split the end-to-end deadline of the DAG whose nodes all have `processor_id` into the sub-deadlines of the nodes.
+    /// The sub-deadline of a node is proportional to the longest path up to its finish, in which the link delays count as well as the WCETs.
+    /// Thus the nodes executed on one processor between link transfers form a segment,
+    /// and the share of each link delay is left between the segments.
+    pub fn decompose_end_to_end_deadline(&self, dag: &mut Graph<NodeData, i64>) {
+        let end_to_end_deadline = dag.get_end_to_end_deadline().unwrap();
+        let processor_ids: Vec<usize> = dag
+            .node_indices()
+            .map(|node_i| {
+                *dag[node_i].params.get("processor_id").unwrap_or_else(|| {
+                    panic!("Node {} is not assigned to a processor.", dag[node_i].id)
+                }) as usize
+            })
+            .collect();
+
+        let mut finish_positions = vec![0; dag.node_count()];
+        let mut topo_order = Topo::new(&*dag);
+        while let Some(node_i) = topo_order.next(&*dag) {
+            let start_position = dag
+                .edges_directed(node_i, Incoming)
+                .map(|edge| {
+                    let pre_idx = edge.source().index();
+                    finish_positions[pre_idx]
+                        + self.get_link_delay(
+                            processor_ids[pre_idx],
+                            processor_ids[node_i.index()],
+                            *edge.weight(),
+                        )
+                })
+                .max()
+                .unwrap_or(0);
+            finish_positions[node_i.index()] =
+                start_position + dag[node_i].get_params_value("execution_time");
+        }
+
+        let span = *finish_positions.iter().max().unwrap();
+        for node_i in dag.node_indices() {
+            let sub_deadline =
+                end_to_end_deadline as f64 * finish_positions[node_i.index()] as f64 / span as f64;
+            dag[node_i].params.insert(
+                "int_scaled_node_relative_deadline".to_string(),
+                (sub_deadline * DEADLINE_FACTOR) as i64,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graph::NodeIndex;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_normal() {
+        let network = Network::from_yaml_file("tests/sample_networks/edge_cloud.yaml").unwrap();
+        let mut expected_network = Network::new(&[2, 4]);
+        expected_network.add_link(0, 1, 10, 2);
+        assert_eq!(network, expected_network);
+    }
+
+    #[test]
+    fn test_get_processor_id_normal() {
+        let network = Network::new(&[2, 3]);
+        assert_eq!(network.get_number_of_cores(), 5);
+        assert_eq!(network.get_number_of_processors(), 2);
+        assert_eq!(network.get_processor_id(1), 0);
+        assert_eq!(network.get_processor_id(2), 1);
+        assert_eq!(network.get_processor_core_mask(1), 0b11100);
+    }
+
+    #[test]
+    fn test_assign_nodes_to_processors_normal() {
+        let network = Network::new(&[1, 2]);
+        let mut dag = Graph::<NodeData, i64>::new();
+        dag.add_node(create_node(0, "processor_id", 1));
+        dag.add_node(create_node(1, "execution_time", 1));
+        let mut dag_set = vec![dag];
+
+        network.assign_nodes_to_processors(&mut dag_set);
+        assert_eq!(
+            dag_set[0][NodeIndex::new(0)].get_params_value("allowed_cores"),
+            0b110
+        );
+        assert!(!dag_set[0][NodeIndex::new(1)]
+            .params
+            .contains_key("allowed_cores"));
+    }
+
+    #[test]
+    fn test_reserve_transfer_normal() {
+        let mut network = Network::new(&[2, 2]);
+        network.add_link(0, 1, 3, 2);
+
+        assert_eq!(network.reserve_transfer(0, 1, 0, 5), None);
+        // latency 3 + ceil(5 / 2)
+        assert_eq!(network.reserve_transfer(0, 2, 0, 5), Some((0, 6)));
+        // The same direction is serialized, and the opposite direction is not.
+        assert_eq!(network.reserve_transfer(1, 3, 2, 2), Some((6, 10)));
+        assert_eq!(network.reserve_transfer(3, 0, 2, 2), Some((2, 6)));
+    }
+
+    #[test]
+    fn test_decompose_end_to_end_deadline_normal() {
+        let mut network = Network::new(&[1, 1]);
+        network.add_link(0, 1, 2, 2);
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        let n1 = dag.add_node(create_node(1, "execution_time", 3));
+        let n2 = dag.add_node(create_node(2, "execution_time", 5));
+        dag.add_param(n0, "end_to_end_deadline", 28);
+        dag.add_param(n0, "processor_id", 0);
+        dag.add_param(n1, "processor_id", 0);
+        dag.add_param(n2, "processor_id", 1);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n2, 4);
+
+        // The path spans 2 + 3 + (2 + 4 / 2) + 5 = 14, which is half of the end-to-end deadline.
+        network.decompose_end_to_end_deadline(&mut dag);
+        assert_eq!(
+            dag[n0].get_params_value("int_scaled_node_relative_deadline"),
+            400000
+        );
+        assert_eq!(
+            dag[n1].get_params_value("int_scaled_node_relative_deadline"),
+            1000000
+        );
+        assert_eq!(
+            dag[n2].get_params_value("int_scaled_node_relative_deadline"),
+            2800000
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decompose_end_to_end_deadline_no_processor_id() {
+        let network = Network::new(&[1]);
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 2));
+        dag.add_param(n0, "end_to_end_deadline", 10);
+        network.decompose_end_to_end_deadline(&mut dag);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reserve_transfer_no_link() {
+        let mut network = Network::new(&[1, 1]);
+        network.reserve_transfer(0, 1, 0, 1);
+    }
+}
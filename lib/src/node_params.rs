@@ -0,0 +1,90 @@
+//! Typed params of a node.
+//! The well-known params are fields, so a missing one is None instead of a panic of `params["key"]`,
+//! and the others, e.g., the ones written by the analyses, are kept as they are in `extra`.
+//! The conversions from and to the BTreeMap of NodeData are lossless, so the lookups can be migrated one by one.
+use std::collections::BTreeMap;
+
+const EXECUTION_TIME: &str = "execution_time";
+const PERIOD: &str = "period";
+const OFFSET: &str = "offset";
+const END_TO_END_DEADLINE: &str = "end_to_end_deadline";
+const PRIORITY: &str = "priority";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeParams {
+    pub execution_time: Option<i64>,
+    pub period: Option<i64>,
+    pub offset: Option<i64>,
+    pub end_to_end_deadline: Option<i64>,
+    pub priority: Option<i64>,
+    // The params other than the fields above
+    pub extra: BTreeMap<String, i64>,
+}
+
+impl From<BTreeMap<String, i64>> for NodeParams {
+    fn from(mut params: BTreeMap<String, i64>) -> Self {
+        Self {
+            execution_time: params.remove(EXECUTION_TIME),
+            period: params.remove(PERIOD),
+            offset: params.remove(OFFSET),
+            end_to_end_deadline: params.remove(END_TO_END_DEADLINE),
+            priority: params.remove(PRIORITY),
+            extra: params,
+        }
+    }
+}
+
+impl From<&BTreeMap<String, i64>> for NodeParams {
+    fn from(params: &BTreeMap<String, i64>) -> Self {
+        Self::from(params.clone())
+    }
+}
+
+impl From<NodeParams> for BTreeMap<String, i64> {
+    fn from(node_params: NodeParams) -> Self {
+        let mut params = node_params.extra;
+        for (key, value) in [
+            (EXECUTION_TIME, node_params.execution_time),
+            (PERIOD, node_params.period),
+            (OFFSET, node_params.offset),
+            (END_TO_END_DEADLINE, node_params.end_to_end_deadline),
+            (PRIORITY, node_params.priority),
+        ] {
+            if let Some(value) = value {
+                params.insert(key.to_string(), value);
+            }
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_params() -> BTreeMap<String, i64> {
+        let mut params = BTreeMap::new();
+        params.insert("execution_time".to_string(), 10);
+        params.insert("period".to_string(), 100);
+        params.insert("earliest_start_time".to_string(), 5);
+        params
+    }
+
+    #[test]
+    fn test_node_params_from_params_normal() {
+        let node_params = NodeParams::from(&create_params());
+        assert_eq!(node_params.execution_time, Some(10));
+        assert_eq!(node_params.period, Some(100));
+        assert_eq!(node_params.offset, None);
+        assert_eq!(node_params.end_to_end_deadline, None);
+        assert_eq!(node_params.priority, None);
+        assert_eq!(node_params.extra.len(), 1);
+        assert_eq!(node_params.extra["earliest_start_time"], 5);
+    }
+
+    #[test]
+    fn test_node_params_round_trip() {
+        let params = create_params();
+        assert_eq!(BTreeMap::from(NodeParams::from(&params)), params);
+    }
+}
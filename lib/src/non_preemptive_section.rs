@@ -0,0 +1,126 @@
+//! Non-preemptive sections spanning multiple nodes.
+//! A chain of nodes in a DAG with the same `non_preemptive_section` parameter models a critical region that must not be split.
+//! Following the priority ceiling, a node in a section is never preempted, even under a preemptive policy,
+//! and a node that continues a started section is allocated before the other ready nodes.
+//! The time a higher-priority node waits for a section is reported as blocking time.
+use crate::error::{Result, SchedSimError};
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::{graph::NodeIndex, Graph};
+use std::collections::BTreeMap;
+
+pub fn is_in_non_preemptive_section(node_data: &NodeData) -> bool {
+    node_data.params.contains_key("non_preemptive_section")
+}
+
+/// Whether the node follows another node of the same section, i.e., it is not the head of the section.
+pub fn is_section_continuation(dag: &Graph<NodeData, i64>, node_data: &NodeData) -> bool {
+    let Some(section_id) = node_data.params.get("non_preemptive_section") else {
+        return false;
+    };
+    dag.get_pre_nodes(NodeIndex::new(node_data.id as usize))
+        .unwrap_or_default()
+        .iter()
+        .any(|pre_node| dag[*pre_node].params.get("non_preemptive_section") == Some(section_id))
+}
+
+/// Return an error if the nodes of a section do not form a chain.
+/// Each node except the head must have exactly one predecessor, which is in the same section,
+/// so that it becomes ready as soon as the previous node of the section completes.
+pub fn validate_non_preemptive_sections(dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let mut sections: BTreeMap<i64, Vec<NodeIndex>> = BTreeMap::new();
+        for node_i in dag.node_indices() {
+            if let Some(section_id) = dag[node_i].params.get("non_preemptive_section") {
+                sections.entry(*section_id).or_default().push(node_i);
+            }
+        }
+
+        for (section_id, nodes) in sections {
+            let is_in_section = |node_i: &NodeIndex| nodes.contains(node_i);
+            let mut num_heads = 0;
+            for node_i in nodes.iter() {
+                let pre_nodes = dag.get_pre_nodes(*node_i).unwrap_or_default();
+                let num_suc_nodes_in_section = dag
+                    .get_suc_nodes(*node_i)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|suc_node| is_in_section(suc_node))
+                    .count();
+                if !pre_nodes.iter().any(is_in_section) {
+                    num_heads += 1;
+                } else if pre_nodes.len() != 1 {
+                    return Err(SchedSimError::Validation(format!(
+                        "Section {} of DAG {} joins at node {}",
+                        section_id,
+                        dag_id,
+                        node_i.index()
+                    )));
+                }
+                if num_suc_nodes_in_section > 1 {
+                    return Err(SchedSimError::Validation(format!(
+                        "Section {} of DAG {} branches at node {}",
+                        section_id,
+                        dag_id,
+                        node_i.index()
+                    )));
+                }
+            }
+            if num_heads != 1 {
+                return Err(SchedSimError::Validation(format!(
+                    "Section {} of DAG {} must have exactly one head: {}",
+                    section_id, dag_id, num_heads
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_chain_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 1));
+        let n1 = dag.add_node(create_node(1, "execution_time", 1));
+        let n2 = dag.add_node(create_node(2, "execution_time", 1));
+        let n3 = dag.add_node(create_node(3, "execution_time", 1));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n2, 1);
+        dag.add_edge(n0, n3, 1);
+        dag.add_param(n1, "non_preemptive_section", 0);
+        dag.add_param(n2, "non_preemptive_section", 0);
+        dag
+    }
+
+    #[test]
+    fn test_is_section_continuation_normal() {
+        let dag = create_chain_dag();
+
+        assert!(!is_section_continuation(&dag, &dag[NodeIndex::new(0)]));
+        assert!(!is_section_continuation(&dag, &dag[NodeIndex::new(1)]));
+        assert!(is_section_continuation(&dag, &dag[NodeIndex::new(2)]));
+        validate_non_preemptive_sections(&[dag]).unwrap();
+    }
+
+    #[test]
+    fn test_validate_non_preemptive_sections_two_heads() {
+        let mut dag = create_chain_dag();
+        dag.add_param(NodeIndex::new(3), "non_preemptive_section", 0);
+        assert!(validate_non_preemptive_sections(&[dag]).is_err());
+    }
+
+    #[test]
+    fn test_validate_non_preemptive_sections_join() {
+        let mut dag = create_chain_dag();
+        dag.add_edge(NodeIndex::new(3), NodeIndex::new(2), 1);
+        assert!(validate_non_preemptive_sections(&[dag]).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! Parquet export of the logs, enabled by the `arrow` feature, so that a campaign of millions of job events
+//! is loaded by pandas or Polars without parsing YAML, e.g., `pl.scan_parquet("outputs/**/*-jobs.parquet")`.
+//! The tables are built by the logs, which own the fields, and are written here.
+use crate::error::{Result, SchedSimError};
+use arrow::{array::ArrayRef, record_batch::RecordBatch};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use std::{fs::File, io};
+
+/// Write the named columns as a table. The columns must have the same length.
+pub fn write_columns_to_parquet(file_path: &str, columns: Vec<(&str, ArrayRef)>) -> Result<()> {
+    let record_batch =
+        RecordBatch::try_from_iter(columns).expect("The columns of a table have the same length.");
+    let file = File::create(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+    let writer_properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, record_batch.schema(), Some(writer_properties))
+        .map_err(|err| SchedSimError::io(file_path, io::Error::other(err)))?;
+    writer
+        .write(&record_batch)
+        .and_then(|()| writer.close().map(|_| ()))
+        .map_err(|err| SchedSimError::io(file_path, io::Error::other(err)))
+}
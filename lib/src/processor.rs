@@ -6,7 +6,60 @@ pub trait ProcessorBase {
     fn process(&mut self) -> Vec<ProcessResult>;
     fn get_number_of_cores(&self) -> usize;
     fn get_idle_core_index(&self) -> Option<usize>;
+    fn get_idle_core_index_with_affinity(&self, node_data: &NodeData) -> Option<usize>;
     fn get_idle_core_num(&self) -> usize;
+    fn get_processing_node(&self, core_id: usize) -> Option<&NodeData>;
     fn preempt(&mut self, core_id: usize) -> Option<NodeData>;
-    fn get_max_value_and_index(&self, key: &str) -> Option<(i32, usize)>;
+    fn fail_core(&mut self, core_id: usize) -> Option<NodeData>;
+    fn set_overhead(&mut self, dispatch_overhead: i64, preemption_overhead: i64);
+    // Drift of the core clock in parts per million, which delays the observed completions
+    fn set_clock_drift(&mut self, core_id: usize, clock_drift_ppm: i64);
+    fn delay_core(&mut self, core_id: usize, delay_time: i64);
+    // Draw the actual execution time of each job from the seeded RNG
+    fn set_execution_time_seed(&mut self, seed: u64);
+    fn get_execution_time_seed(&self) -> Option<u64>;
+    // Per-core dispatch queues for partitioned scheduling, which are optional for a processor
+    fn enqueue_to_core(&mut self, _core_id: usize, _node_data: &NodeData) {
+        panic!("The processor does not have per-core dispatch queues");
+    }
+    fn dequeue_from_core(&mut self, _core_id: usize) -> Option<NodeData> {
+        None
+    }
+    fn get_queued_head(&self, _core_id: usize) -> Option<&NodeData> {
+        None
+    }
+    fn get_queued_node_num(&self, _core_id: usize) -> usize {
+        0
+    }
+    // Without queued nodes, the backlog is bounded by the execution time of the processing node
+    fn get_backlog_time(&self, core_id: usize) -> i64 {
+        self.get_processing_node(core_id)
+            .map_or(0, |node_data| node_data.get_params_value("execution_time"))
+    }
+    // Allocate the head of the dispatch queue to each idle core, and return the allocated nodes.
+    // A node whose allocation fails stays at the head of the queue.
+    fn dispatch_queued_nodes(&mut self) -> Vec<(usize, NodeData)> {
+        let mut dispatched_nodes = Vec::new();
+        for core_id in 0..self.get_number_of_cores() {
+            if self.get_processing_node(core_id).is_some() {
+                continue;
+            }
+            let Some(node_data) = self.get_queued_head(core_id).cloned() else {
+                continue;
+            };
+            if self.allocate_specific_core(core_id, &node_data) {
+                self.dequeue_from_core(core_id);
+                dispatched_nodes.push((core_id, node_data));
+            }
+        }
+        dispatched_nodes
+    }
+    fn get_max_value_and_index(&self, key: &str) -> Option<(i64, usize)>;
+    fn get_max_value_and_index_with_affinity(
+        &self,
+        key: &str,
+        node_data: &NodeData,
+    ) -> Option<(i64, usize)>;
+    // Speed of the core relative to the execution times of the nodes, which are those on a core of speed 1
+    fn get_core_speed(&self, core_id: usize) -> f64;
 }
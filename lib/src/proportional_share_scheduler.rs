@@ -0,0 +1,552 @@
+//! Quantum-based proportional-share scheduler approximating Pfair (PD2-lite).
+//! Each DAG is given the weight of its utilization, and the cores are reassigned at every quantum boundary.
+//! The job of a DAG is divided into subtasks of one quantum, and the k-th subtask has the pseudo-deadline
+//! `release_time + ceil(k * quantum / weight)`. The cores go to the DAGs with the earliest pseudo-deadlines,
+//! and ties are broken by the larger lag, which is the fluid allocation `weight * (t - release_time)`
+//! minus the time actually allocated. The range of the lag of each DAG is written to its DAGLog.
+use crate::dag_set_scheduler::{
+    validate_max_parallelisms, DAGSetSchedulerBase, DAGState, DAGStateManager, DAGStateManagerBase,
+    DeadlineMissPolicy, PreemptiveType, ScheduleOutcome,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    admission_control::OnlineAdmission,
+    aperiodic_arrival::AperiodicArrivalTrace,
+    core::ProcessResult,
+    diagnostics::DiagnosticCollector,
+    error::{Result, SchedSimError},
+    event_script::EventScript,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    interconnect::Interconnect,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    logical_execution_time::CommunicationSemantics,
+    mode_change::ModeSchedule,
+    network::Network,
+    non_preemptive_section::{
+        is_in_non_preemptive_section, is_section_continuation, validate_non_preemptive_sections,
+    },
+    processor::ProcessorBase,
+    simulation_horizon::SimulationHorizon,
+    sporadic_release::{validate_jitters, ArrivalTrace, ReleaseJitter},
+    util::{get_overhead_core_indices, get_process_core_indices},
+};
+use petgraph::graph::Graph;
+use std::{cmp::Ordering, collections::VecDeque};
+
+const DEFAULT_QUANTUM: i64 = 1;
+
+/// The progress of the current job of a DAG against its fluid schedule.
+#[derive(Clone, Default)]
+struct DAGShare {
+    weight: f64,
+    release_count: i64,
+    release_time: i64,
+    allocated_time: i64,
+}
+
+impl DAGShare {
+    fn new(dag: &Graph<NodeData, i64>) -> Self {
+        Self {
+            weight: dag.get_volume() as f64 / dag.get_head_period().unwrap() as f64,
+            ..Default::default()
+        }
+    }
+
+    fn get_lag(&self, current_time: i64) -> f64 {
+        self.weight * (current_time - self.release_time) as f64 - self.allocated_time as f64
+    }
+
+    /// The pseudo-deadline of the subtask after the `num_assigned_cores` subtasks assigned in this quantum.
+    fn get_pseudo_deadline(&self, quantum: i64, num_assigned_cores: usize) -> i64 {
+        let subtask_index = self.allocated_time / quantum + num_assigned_cores as i64 + 1;
+        self.release_time + ((subtask_index * quantum) as f64 / self.weight).ceil() as i64
+    }
+}
+
+pub struct ProportionalShareScheduler {
+    dag_set: Vec<Graph<NodeData, i64>>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i64,
+    event_script: EventScript,
+    interconnect: Option<Interconnect>,
+    network: Option<Network>,
+    arrival_trace: Option<ArrivalTrace>,
+    aperiodic_arrival_trace: Option<AperiodicArrivalTrace>,
+    release_jitter: ReleaseJitter,
+    deadline_miss_policy: DeadlineMissPolicy,
+    simulation_horizon: SimulationHorizon,
+    mode_schedule: Option<ModeSchedule>,
+    online_admission: Option<OnlineAdmission>,
+    communication_semantics: CommunicationSemantics,
+    quantum: i64,
+}
+
+impl ProportionalShareScheduler {
+    pub fn set_quantum(&mut self, quantum: i64) -> Result<()> {
+        if quantum <= 0 {
+            return Err(SchedSimError::Validation(format!(
+                "The quantum must be positive: {}",
+                quantum
+            )));
+        }
+        self.quantum = quantum;
+        Ok(())
+    }
+
+    pub fn get_quantum(&self) -> i64 {
+        self.quantum
+    }
+
+    fn get_running_core_ids(&self, dag_id: usize) -> Vec<usize> {
+        (0..self.processor.get_number_of_cores())
+            .filter(|&core_id| {
+                self.processor
+                    .get_processing_node(core_id)
+                    .is_some_and(|node_data| {
+                        node_data.get_params_value("dag_id") as usize == dag_id
+                    })
+            })
+            .collect()
+    }
+
+    /// Decide the number of cores of each DAG in this quantum.
+    /// One core at a time goes to the DAG with the earliest pseudo-deadline among those with unassigned nodes.
+    fn calculate_slot_shares(&self, shares: &[DAGShare], demands: &[usize]) -> Vec<usize> {
+        let mut slot_shares = vec![0; shares.len()];
+        for _ in 0..self.processor.get_number_of_cores() {
+            let selected_dag_id = (0..shares.len())
+                .filter(|&dag_id| slot_shares[dag_id] < demands[dag_id])
+                .min_by(|&a, &b| {
+                    let pseudo_deadline = |dag_id: usize| {
+                        shares[dag_id].get_pseudo_deadline(self.quantum, slot_shares[dag_id])
+                    };
+                    pseudo_deadline(a)
+                        .cmp(&pseudo_deadline(b))
+                        .then_with(|| {
+                            let lag_b = shares[b].get_lag(self.current_time);
+                            lag_b
+                                .partial_cmp(&shares[a].get_lag(self.current_time))
+                                .unwrap_or(Ordering::Equal)
+                        })
+                        .then(a.cmp(&b))
+                });
+            match selected_dag_id {
+                Some(dag_id) => slot_shares[dag_id] += 1,
+                None => break,
+            }
+        }
+        slot_shares
+    }
+
+    /// Allocate the nodes continuing started non-preemptive sections, which wait at the front of the ready queues,
+    /// to the idle cores regardless of the slot shares and the quantum boundaries.
+    fn allocate_section_continuations(
+        &mut self,
+        ready_queues: &mut [VecDeque<NodeData>],
+        managers: &[DAGStateManager],
+    ) {
+        for (dag_id, ready_queue) in ready_queues.iter_mut().enumerate() {
+            while let Some(node_data) = ready_queue.front() {
+                if !is_section_continuation(&self.dag_set[dag_id], node_data) {
+                    break;
+                }
+                let Some(idle_core_i) = self.processor.get_idle_core_index_with_affinity(node_data)
+                else {
+                    break;
+                };
+                let node_data = ready_queue.pop_front().unwrap();
+                self.allocate_node(
+                    &node_data,
+                    idle_core_i,
+                    managers[dag_id].get_release_count() as usize,
+                );
+            }
+        }
+    }
+
+    /// Preempt the nodes of the DAGs running on more cores than their slot shares,
+    /// and then allocate the ready nodes of the DAGs running on fewer cores.
+    /// The nodes in non-preemptive sections run to completion, even beyond the slot shares.
+    /// The demand of a DAG, and hence its slot share, is capped by its `max_parallelism`.
+    fn reassign_cores(
+        &mut self,
+        ready_queues: &mut [VecDeque<NodeData>],
+        managers: &[DAGStateManager],
+        shares: &[DAGShare],
+    ) {
+        let running_core_ids: Vec<Vec<usize>> = (0..ready_queues.len())
+            .map(|dag_id| self.get_running_core_ids(dag_id))
+            .collect();
+        let demands: Vec<usize> = running_core_ids
+            .iter()
+            .zip(ready_queues.iter())
+            .zip(self.dag_set.iter())
+            .map(|((core_ids, ready_queue), dag)| {
+                let demand = core_ids.len() + ready_queue.len();
+                // max_parallelism is a DAG parameter set on every node.
+                match dag
+                    .node_weights()
+                    .next()
+                    .unwrap()
+                    .params
+                    .get("max_parallelism")
+                {
+                    Some(max_parallelism) => demand.min(*max_parallelism as usize),
+                    None => demand,
+                }
+            })
+            .collect();
+        let slot_shares = self.calculate_slot_shares(shares, &demands);
+
+        for (dag_id, core_ids) in running_core_ids.iter().enumerate() {
+            let preemptable_core_ids: Vec<usize> = core_ids
+                .iter()
+                .copied()
+                .filter(|&core_id| {
+                    !self
+                        .processor
+                        .get_processing_node(core_id)
+                        .is_some_and(is_in_non_preemptive_section)
+                })
+                .collect();
+            let num_excess_cores = core_ids.len().saturating_sub(slot_shares[dag_id]);
+            for &core_id in preemptable_core_ids
+                .iter()
+                .skip(preemptable_core_ids.len().saturating_sub(num_excess_cores))
+            {
+                let preempted_node_data = self.processor.preempt(core_id).unwrap();
+                self.log.write_job_event(
+                    &preempted_node_data,
+                    core_id,
+                    (managers[dag_id].get_release_count() - 1) as usize,
+                    JobEventTimes::PreemptedTime(self.current_time),
+                );
+                ready_queues[dag_id].push_front(preempted_node_data);
+            }
+        }
+
+        for (dag_id, ready_queue) in ready_queues.iter_mut().enumerate() {
+            let mut num_unassigned_cores =
+                slot_shares[dag_id].saturating_sub(running_core_ids[dag_id].len());
+            let mut queue_index = 0;
+            while num_unassigned_cores > 0 && queue_index < ready_queue.len() {
+                match self
+                    .processor
+                    .get_idle_core_index_with_affinity(&ready_queue[queue_index])
+                {
+                    Some(idle_core_i) => {
+                        let node_data = ready_queue.remove(queue_index).unwrap();
+                        self.allocate_node(
+                            &node_data,
+                            idle_core_i,
+                            managers[dag_id].get_release_count() as usize,
+                        );
+                        num_unassigned_cores -= 1;
+                    }
+                    None => queue_index += 1,
+                }
+            }
+        }
+    }
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for ProportionalShareScheduler {
+    fn new(dag_set: &[Graph<NodeData, i64>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            event_script: EventScript::default(),
+            interconnect: None,
+            network: None,
+            arrival_trace: None,
+            aperiodic_arrival_trace: None,
+            release_jitter: ReleaseJitter::default(),
+            deadline_miss_policy: DeadlineMissPolicy::default(),
+            simulation_horizon: SimulationHorizon::default(),
+            mode_schedule: None,
+            online_admission: None,
+            communication_semantics: CommunicationSemantics::default(),
+            quantum: DEFAULT_QUANTUM,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The nodes are preempted whenever their DAG loses its share, so `PreemptiveType` is ignored.
+    fn schedule(&mut self, _: PreemptiveType) -> Result<ScheduleOutcome> {
+        // The outputs are published under LET only by the default schedule.
+        if self.communication_semantics == CommunicationSemantics::LogicalExecutionTime {
+            return Err(SchedSimError::Validation(
+                "The proportional-share scheduler does not support the logical execution time."
+                    .to_string(),
+            ));
+        }
+        let diagnostic_collector = DiagnosticCollector::new();
+        self.log
+            .write_execution_time_seed(self.processor.get_execution_time_seed());
+        self.log
+            .write_communication_semantics(self.communication_semantics);
+        if let Some(arrival_trace) = &self.arrival_trace {
+            arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(aperiodic_arrival_trace) = &self.aperiodic_arrival_trace {
+            aperiodic_arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(mode_schedule) = &self.mode_schedule {
+            mode_schedule.validate(&self.dag_set)?;
+        }
+        validate_jitters(&self.dag_set)?;
+        validate_non_preemptive_sections(&self.dag_set)?;
+        validate_max_parallelisms(&self.dag_set)?;
+
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queues = vec![VecDeque::new(); self.dag_set.len()];
+        let mut shares: Vec<DAGShare> = self.dag_set.iter().map(DAGShare::new).collect();
+        let end_time = self.get_end_time()?;
+        while self.current_time < end_time {
+            self.handle_mode_changes(&managers);
+
+            // Handle scripted events and release DAGs
+            let mut ready_nodes = self.handle_scripted_events(&mut managers);
+            ready_nodes.extend(self.release_dags(&mut managers));
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            for ready_node in ready_nodes {
+                ready_queues[ready_node.get_params_value("dag_id") as usize].push_back(ready_node);
+            }
+            // A new job starts the fluid schedule over.
+            for (share, manager) in shares.iter_mut().zip(managers.iter()) {
+                if share.release_count != manager.get_release_count() {
+                    share.release_count = manager.get_release_count();
+                    share.release_time = self.current_time;
+                    share.allocated_time = 0;
+                }
+            }
+
+            self.allocate_section_continuations(&mut ready_queues, &managers);
+            if self.current_time % self.quantum == 0 {
+                for (dag_id, manager) in managers.iter().enumerate() {
+                    if manager.get_dag_state() != DAGState::Waiting {
+                        self.log
+                            .write_lag(dag_id, shares[dag_id].get_lag(self.current_time));
+                    }
+                }
+                self.reassign_cores(&mut ready_queues, &managers, &shares);
+            }
+            self.write_parallelism_capped_times(ready_queues.iter().flatten());
+
+            // Process unit time
+            let running_dag_ids: Vec<Option<usize>> = (0..self.processor.get_number_of_cores())
+                .map(|core_id| {
+                    self.processor
+                        .get_processing_node(core_id)
+                        .map(|node_data| node_data.get_params_value("dag_id") as usize)
+                })
+                .collect();
+            let process_result = self.process_unit_time();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            for core_id in indices.iter() {
+                if let Some(dag_id) = running_dag_ids[*core_id] {
+                    shares[dag_id].allocated_time += 1;
+                }
+            }
+            self.log.write_processing_time(&indices);
+            let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+            self.log.write_overhead_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let mut ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    self.apply_wcet_overruns(&mut ready_nodes, &managers);
+                    let dag_id = node_data.get_params_value("dag_id") as usize;
+                    for ready_node in ready_nodes {
+                        // A started section continues ahead of the queue.
+                        if is_section_continuation(&self.dag_set[dag_id], &ready_node) {
+                            ready_queues[dag_id].push_front(ready_node);
+                        } else {
+                            ready_queues[dag_id].push_back(ready_node);
+                        }
+                    }
+                }
+            }
+
+            // Handle deadline misses
+            let missed_dag_ids = self.handle_deadline_misses(&mut managers);
+            match self.deadline_miss_policy {
+                DeadlineMissPolicy::Abort if !missed_dag_ids.is_empty() => break,
+                DeadlineMissPolicy::KillJob => {
+                    for dag_id in missed_dag_ids {
+                        ready_queues[dag_id].clear();
+                        self.kill_job(dag_id, &mut managers[dag_id]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.calculate_log();
+        Ok(self.get_schedule_outcome(diagnostic_collector.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "file_io")]
+    use crate::util::load_yaml;
+    use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::fs::remove_file;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_single_node_dag(dag_id: i64, execution_time: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n0, "end_to_end_deadline", 100);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_proportional_share_normal() {
+        // The weights are 0.3 and 0.6.
+        let dag_set = vec![create_single_node_dag(0, 30), create_single_node_dag(1, 60)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut proportional_share_scheduler =
+            ProportionalShareScheduler::new(&dag_set, &processor);
+        proportional_share_scheduler.set_quantum(10).unwrap();
+        let time = proportional_share_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
+        assert_eq!(time, 100);
+
+        let file_path = proportional_share_scheduler.dump_log("../lib/tests", "pfair_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let dag_set_log = &yaml_docs[0]["dag_set_log"];
+
+        // DAG 0: 10-20, 40-50, 70-80 and DAG 1: 0-10, 20-40, 50-70, 80-90
+        assert_eq!(dag_set_log[0]["finish_time"][0].as_i64().unwrap(), 80);
+        assert_eq!(dag_set_log[1]["finish_time"][0].as_i64().unwrap(), 90);
+        let node_logs = yaml_docs[0]["node_set_logs"][0].as_vec().unwrap();
+        assert_eq!(node_logs.len(), 6);
+
+        assert!((dag_set_log[0]["max_lag"].as_f64().unwrap() - 3.0).abs() < 1e-6);
+        assert!((dag_set_log[0]["min_lag"].as_f64().unwrap() + 5.0).abs() < 1e-6);
+        assert!((dag_set_log[1]["max_lag"].as_f64().unwrap() - 2.0).abs() < 1e-6);
+        assert!((dag_set_log[1]["min_lag"].as_f64().unwrap() + 8.0).abs() < 1e-6);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_proportional_share_parallel_dag() {
+        // The weight of DAG 0 is 1.21, so it is given two cores in some quanta.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(source, "period", 100);
+        dag.add_param(source, "end_to_end_deadline", 100);
+        for node_id in 1..4 {
+            let node = dag.add_node(create_node(node_id, "execution_time", 40));
+            dag.add_edge(source, node, 0);
+        }
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag];
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut proportional_share_scheduler =
+            ProportionalShareScheduler::new(&dag_set, &processor);
+        proportional_share_scheduler.set_quantum(10).unwrap();
+        let shares = vec![DAGShare::new(&dag_set[0])];
+        assert!((shares[0].weight - 1.21).abs() < 1e-6);
+        assert_eq!(
+            proportional_share_scheduler.calculate_slot_shares(&shares, &[3]),
+            vec![2]
+        );
+        assert_eq!(
+            proportional_share_scheduler.calculate_slot_shares(&shares, &[1]),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_proportional_share_non_preemptive_section() {
+        // The chain of DAG 0 is a section, so it is neither preempted nor interrupted by DAG 1 at 20.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 20));
+        dag.add_edge(n0, n1, 0);
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n1, "end_to_end_deadline", 100);
+        dag.add_param(n0, "non_preemptive_section", 0);
+        dag.add_param(n1, "non_preemptive_section", 0);
+        dag.set_dag_param("dag_id", 0);
+        let dag_set = vec![dag, create_single_node_dag(1, 60)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut proportional_share_scheduler =
+            ProportionalShareScheduler::new(&dag_set, &processor);
+        proportional_share_scheduler.set_quantum(10).unwrap();
+        let schedule_outcome = proportional_share_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        // DAG 0: 10-40 and DAG 1: 0-10, 40-90
+        assert_eq!(schedule_outcome.worst_response_times, vec![40, 90]);
+    }
+
+    #[test]
+    fn test_proportional_share_max_parallelism() {
+        // The two branches run one after the other even though two cores are idle.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(source, "period", 100);
+        dag.add_param(source, "end_to_end_deadline", 100);
+        for node_id in 1..3 {
+            let node = dag.add_node(create_node(node_id, "execution_time", 10));
+            dag.add_edge(source, node, 0);
+        }
+        dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("max_parallelism", 1);
+        let processor = HomogeneousProcessor::new(2);
+
+        let schedule_outcome = ProportionalShareScheduler::new(&[dag], &processor)
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        assert_eq!(schedule_outcome.worst_response_times, vec![21]);
+    }
+
+    #[test]
+    fn test_proportional_share_set_quantum_zero() {
+        let dag_set = vec![create_single_node_dag(0, 30)];
+        let processor = HomogeneousProcessor::new(1);
+        assert!(ProportionalShareScheduler::new(&dag_set, &processor)
+            .set_quantum(0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_proportional_share_logical_execution_time() {
+        let dag_set = vec![create_single_node_dag(0, 30)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut scheduler = ProportionalShareScheduler::new(&dag_set, &processor);
+        scheduler.set_communication_semantics(CommunicationSemantics::LogicalExecutionTime);
+        assert!(matches!(
+            scheduler.schedule(PreemptiveType::NonPreemptive),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+//! Python bindings of the DAG construction and the schedulers, so that the analysis notebooks drive the simulations
+//! and read their results without running the binaries and parsing their YAML logs.
+//! They are enabled by the `pyo3` feature, and built as the `sched_sim` module with maturin from this directory.
+//!
+//! ```python
+//! import sched_sim
+//!
+//! dag = sched_sim.Dag()
+//! dag.add_node({"execution_time": 10, "period": 100})
+//! dag.add_node({"execution_time": 5})
+//! dag.add_edge(0, 1)
+//! result = sched_sim.schedule_dag_set("global_edf", [dag], 2)
+//! print(result.schedule_length, result.worst_response_times, result.log["processor_info"])
+//! ```
+use crate::{
+    dag_creator,
+    dag_scheduler::DAGSchedulerBase,
+    dag_set_scheduler::PreemptiveType,
+    error::SchedSimError,
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    graph_extension::{GraphExtension, NodeData},
+    homogeneous::HomogeneousProcessor,
+    processor::ProcessorBase,
+    scheduler_factory::SchedulerFactory,
+};
+use petgraph::{graph::NodeIndex, Graph};
+use pyo3::{
+    exceptions::{PyIOError, PyValueError},
+    prelude::*,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+impl From<SchedSimError> for PyErr {
+    fn from(err: SchedSimError) -> PyErr {
+        match err {
+            SchedSimError::Io { .. } => PyIOError::new_err(err.to_string()),
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// The log is passed to Python as JSON, and `log` of the results decodes it into a dict.
+fn to_log_json<T: Serialize>(log: &T) -> String {
+    serde_json::to_string(log).expect("The log is always serializable to JSON.")
+}
+
+fn decode_log_json<'py>(py: Python<'py>, log_json: &str) -> PyResult<Bound<'py, PyAny>> {
+    py.import("json")?.call_method1("loads", (log_json,))
+}
+
+#[pyclass(name = "Dag")]
+#[derive(Clone)]
+pub struct PyDag {
+    pub dag: Graph<NodeData, i64>,
+}
+
+#[pymethods]
+impl PyDag {
+    #[new]
+    pub fn new() -> Self {
+        Self { dag: Graph::new() }
+    }
+
+    #[staticmethod]
+    pub fn from_yaml(file_path: &str) -> PyResult<Self> {
+        create_dag_from_yaml(file_path)
+    }
+
+    /// Add a node with the next id, and return the id.
+    pub fn add_node(&mut self, params: BTreeMap<String, i64>) -> i64 {
+        let node_id = self.dag.node_count() as i64;
+        self.dag
+            .add_node_with_id_consistency(NodeData::new(node_id, params));
+        node_id
+    }
+
+    #[pyo3(signature = (source_id, target_id, communication_time = 0))]
+    pub fn add_edge(
+        &mut self,
+        source_id: usize,
+        target_id: usize,
+        communication_time: i64,
+    ) -> PyResult<()> {
+        for node_id in [source_id, target_id] {
+            if node_id >= self.dag.node_count() {
+                return Err(PyValueError::new_err(format!(
+                    "The DAG has no node {}.",
+                    node_id
+                )));
+            }
+        }
+        self.dag.add_edge(
+            NodeIndex::new(source_id),
+            NodeIndex::new(target_id),
+            communication_time,
+        );
+        Ok(())
+    }
+
+    pub fn get_node_params(&self, node_id: usize) -> PyResult<BTreeMap<String, i64>> {
+        self.dag
+            .node_weight(NodeIndex::new(node_id))
+            .map(|node| node.params.clone())
+            .ok_or_else(|| PyValueError::new_err(format!("The DAG has no node {}.", node_id)))
+    }
+
+    pub fn get_node_count(&self) -> usize {
+        self.dag.node_count()
+    }
+
+    pub fn get_edge_count(&self) -> usize {
+        self.dag.edge_count()
+    }
+
+    pub fn get_volume(&self) -> i64 {
+        self.dag.get_volume()
+    }
+
+    pub fn get_critical_path(&self) -> Vec<i64> {
+        self.dag
+            .get_critical_path()
+            .iter()
+            .map(|node_i| self.dag[*node_i].id)
+            .collect()
+    }
+
+    pub fn get_end_to_end_deadline(&self) -> Option<i64> {
+        self.dag.get_end_to_end_deadline()
+    }
+
+    pub fn get_head_period(&self) -> Option<i64> {
+        self.dag.get_head_period()
+    }
+}
+
+impl Default for PyDag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[pyclass(name = "ScheduleResult")]
+pub struct PyScheduleResult {
+    #[pyo3(get)]
+    pub schedule_length: i64,
+    #[pyo3(get)]
+    pub response_times: Vec<Vec<i64>>,
+    #[pyo3(get)]
+    pub worst_response_times: Vec<i64>,
+    #[pyo3(get)]
+    pub deadline_miss_flags: Vec<bool>,
+    log_json: String,
+}
+
+#[pymethods]
+impl PyScheduleResult {
+    pub fn has_deadline_miss(&self) -> bool {
+        self.deadline_miss_flags.iter().any(|flag| *flag)
+    }
+
+    /// The log of the DAG set scheduler as the dict of its YAML log.
+    #[getter]
+    pub fn log<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        decode_log_json(py, &self.log_json)
+    }
+}
+
+#[pyclass(name = "DagScheduleResult")]
+pub struct PyDagScheduleResult {
+    #[pyo3(get)]
+    pub schedule_length: i64,
+    /// The node ids in the order of their starts.
+    #[pyo3(get)]
+    pub execution_order: Vec<i64>,
+    log_json: String,
+}
+
+#[pymethods]
+impl PyDagScheduleResult {
+    /// The log of the DAG scheduler as the dict of its YAML log.
+    #[getter]
+    pub fn log<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        decode_log_json(py, &self.log_json)
+    }
+}
+
+#[pyfunction]
+pub fn create_dag_from_yaml(file_path: &str) -> PyResult<PyDag> {
+    Ok(PyDag {
+        dag: dag_creator::create_dag_from_yaml(file_path, false)?,
+    })
+}
+
+#[pyfunction]
+pub fn create_dag_set_from_dir(dir_path: &str) -> PyResult<Vec<PyDag>> {
+    Ok(dag_creator::create_dag_set_from_dir(dir_path)?
+        .into_iter()
+        .map(|dag| PyDag { dag })
+        .collect())
+}
+
+/// The names of the schedulers of `schedule_dag_set`.
+#[pyfunction]
+pub fn get_scheduler_names() -> Vec<String> {
+    SchedulerFactory::default()
+        .get_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Simulate the DAG set with the scheduler named `scheduler_name` as the binaries do,
+/// i.e., the period is considered as the end-to-end deadline.
+/// The scheduling is preemptive by the node parameter `preemptive_key` if it is given.
+#[pyfunction]
+#[pyo3(signature = (scheduler_name, dag_set, number_of_cores, preemptive_key = None, num_hyper_periods = 1))]
+pub fn schedule_dag_set(
+    scheduler_name: &str,
+    dag_set: Vec<PyDag>,
+    number_of_cores: usize,
+    preemptive_key: Option<String>,
+    num_hyper_periods: i64,
+) -> PyResult<PyScheduleResult> {
+    // The DAGs are numbered in the order of the list, as create_dag_set_from_dir numbers the files.
+    let dag_set: Vec<Graph<NodeData, i64>> = dag_set
+        .into_iter()
+        .enumerate()
+        .map(|(dag_id, mut dag)| {
+            dag.dag.set_dag_param("dag_id", dag_id as i64);
+            dag.dag
+        })
+        .collect();
+    let preemptive_type = match preemptive_key {
+        Some(key) => PreemptiveType::Preemptive { key },
+        None => PreemptiveType::NonPreemptive,
+    };
+    let schedule_outcome = SchedulerFactory::default().simulate(
+        scheduler_name,
+        &dag_set,
+        number_of_cores,
+        preemptive_type,
+        num_hyper_periods,
+    )?;
+    Ok(PyScheduleResult {
+        schedule_length: schedule_outcome.schedule_length,
+        response_times: schedule_outcome.response_times,
+        worst_response_times: schedule_outcome.worst_response_times,
+        deadline_miss_flags: schedule_outcome.deadline_miss_flags,
+        log_json: to_log_json(&schedule_outcome.log),
+    })
+}
+
+/// Schedule a single DAG with the fixed-priority scheduler by the node parameter `priority`.
+#[pyfunction]
+pub fn schedule_dag(dag: PyDag, number_of_cores: usize) -> PyDagScheduleResult {
+    let homogeneous_processor = HomogeneousProcessor::new(number_of_cores);
+    let mut fixed_priority_scheduler =
+        FixedPriorityScheduler::new(&dag.dag, &homogeneous_processor);
+    let (schedule_length, execution_order) = fixed_priority_scheduler.schedule();
+    PyDagScheduleResult {
+        schedule_length,
+        execution_order: execution_order
+            .iter()
+            .map(|node_i| dag.dag[*node_i].id)
+            .collect(),
+        log_json: to_log_json(&fixed_priority_scheduler.get_log()),
+    }
+}
+
+#[pymodule]
+fn sched_sim(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyDag>()?;
+    module.add_class::<PyScheduleResult>()?;
+    module.add_class::<PyDagScheduleResult>()?;
+    module.add_function(wrap_pyfunction!(create_dag_from_yaml, module)?)?;
+    module.add_function(wrap_pyfunction!(create_dag_set_from_dir, module)?)?;
+    module.add_function(wrap_pyfunction!(get_scheduler_names, module)?)?;
+    module.add_function(wrap_pyfunction!(schedule_dag_set, module)?)?;
+    module.add_function(wrap_pyfunction!(schedule_dag, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_chain_dag() -> PyDag {
+        let mut dag = PyDag::new();
+        dag.add_node(BTreeMap::from([
+            ("execution_time".to_string(), 10),
+            ("period".to_string(), 100),
+        ]));
+        dag.add_node(BTreeMap::from([("execution_time".to_string(), 5)]));
+        dag.add_edge(0, 1, 0).unwrap();
+        dag
+    }
+
+    #[test]
+    fn test_py_dag_normal() {
+        let dag = create_chain_dag();
+        assert_eq!(dag.get_node_count(), 2);
+        assert_eq!(dag.get_edge_count(), 1);
+        assert_eq!(dag.get_volume(), 15);
+        assert_eq!(dag.get_critical_path(), vec![0, 1]);
+        assert_eq!(dag.get_head_period(), Some(100));
+        assert_eq!(dag.get_node_params(1).unwrap()["execution_time"], 5);
+    }
+
+    #[test]
+    fn test_schedule_dag_set_normal() {
+        let result = schedule_dag_set("global_edf", vec![create_chain_dag()], 2, None, 1).unwrap();
+        // The simulation runs until the end of the hyper period.
+        assert_eq!(result.schedule_length, 100);
+        assert_eq!(result.worst_response_times, vec![15]);
+        assert!(!result.has_deadline_miss());
+        assert!(result.log_json.contains("\"processor_info\""));
+    }
+
+    #[test]
+    fn test_schedule_dag_normal() {
+        let mut dag = create_chain_dag();
+        for node_id in 0..2 {
+            dag.dag
+                .add_param(NodeIndex::new(node_id), "priority", node_id as i64);
+        }
+        let result = schedule_dag(dag, 1);
+        assert_eq!(result.schedule_length, 15);
+        assert_eq!(result.execution_order, vec![0, 1]);
+    }
+}
@@ -0,0 +1,266 @@
+//! Transitive reachability between the nodes of a DAG.
+//! Each node has a bitset of the nodes reachable from it, so a query costs O(V/64) instead of a graph search.
+//! The matrix is not updated when the DAG is mutated, so the callers querying a DAG many times build it once and hold it.
+use crate::graph_extension::NodeData;
+use petgraph::{
+    algo::toposort,
+    graph::{Graph, NodeIndex},
+    Direction::Outgoing,
+};
+
+const BITS_PER_BLOCK: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReachabilityMatrix {
+    num_nodes: usize,
+    // The j-th bit of the i-th bitset is set when node j is reachable from node i.
+    des_bitsets: Vec<Vec<u64>>,
+}
+
+impl ReachabilityMatrix {
+    pub fn new(dag: &Graph<NodeData, i64>) -> Self {
+        let num_nodes = dag.node_count();
+        let num_blocks = num_nodes.div_ceil(BITS_PER_BLOCK);
+        let mut des_bitsets = vec![vec![0; num_blocks]; num_nodes];
+
+        // Visit the nodes in reverse topological order so that the successors are already computed.
+        let sorted_nodes = toposort(dag, None).unwrap();
+        for node_i in sorted_nodes.into_iter().rev() {
+            let mut des_bitset = vec![0; num_blocks];
+            for suc_node_i in dag.neighbors_directed(node_i, Outgoing) {
+                let suc_index = suc_node_i.index();
+                des_bitset[suc_index / BITS_PER_BLOCK] |= 1 << (suc_index % BITS_PER_BLOCK);
+                for (block, suc_block) in des_bitset.iter_mut().zip(&des_bitsets[suc_index]) {
+                    *block |= suc_block;
+                }
+            }
+            des_bitsets[node_i.index()] = des_bitset;
+        }
+
+        Self {
+            num_nodes,
+            des_bitsets,
+        }
+    }
+
+    pub fn is_reachable(&self, from_node_i: NodeIndex, to_node_i: NodeIndex) -> bool {
+        let to_index = to_node_i.index();
+        (self.des_bitsets[from_node_i.index()][to_index / BITS_PER_BLOCK]
+            >> (to_index % BITS_PER_BLOCK))
+            & 1
+            == 1
+    }
+
+    /// The ancestors of the node in the order of NodeIndex.
+    pub fn get_anc_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let anc_nodes: Vec<_> = (0..self.num_nodes)
+            .map(NodeIndex::new)
+            .filter(|&node| self.is_reachable(node, node_i))
+            .collect();
+        Some(anc_nodes).filter(|nodes| !nodes.is_empty())
+    }
+
+    /// The descendants of the node in the order of NodeIndex.
+    pub fn get_des_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let des_nodes: Vec<_> = self.des_bitsets[node_i.index()]
+            .iter()
+            .enumerate()
+            .flat_map(|(block_index, block)| {
+                (0..BITS_PER_BLOCK)
+                    .filter(move |bit| (block >> bit) & 1 == 1)
+                    .map(move |bit| NodeIndex::new(block_index * BITS_PER_BLOCK + bit))
+            })
+            .collect();
+        Some(des_nodes).filter(|nodes| !nodes.is_empty())
+    }
+
+    pub fn get_parallel_process_nodes(&self, node_i: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let parallel_process_nodes: Vec<_> = (0..self.num_nodes)
+            .map(NodeIndex::new)
+            .filter(|&node| {
+                node != node_i
+                    && !self.is_reachable(node, node_i)
+                    && !self.is_reachable(node_i, node)
+            })
+            .collect();
+
+        Some(parallel_process_nodes).filter(|nodes| !nodes.is_empty())
+    }
+
+    /// The size of the largest set of nodes no two of which are reachable from each other,
+    /// i.e., the most nodes that can run in parallel.
+    /// By Dilworth's theorem, it is the number of nodes minus the maximum matching of the bipartite graph
+    /// whose edges are the reachable pairs, which is found by augmenting paths in O(V^3).
+    pub fn get_max_antichain_size(&self) -> usize {
+        // The node matched to each node on the right side of the bipartite graph
+        let mut matched_nodes = vec![None; self.num_nodes];
+        let mut matching_size = 0;
+        for node_index in 0..self.num_nodes {
+            let mut visited = vec![false; self.num_nodes];
+            if self.find_augmenting_path(node_index, &mut visited, &mut matched_nodes) {
+                matching_size += 1;
+            }
+        }
+        self.num_nodes - matching_size
+    }
+
+    fn find_augmenting_path(
+        &self,
+        node_index: usize,
+        visited: &mut [bool],
+        matched_nodes: &mut [Option<usize>],
+    ) -> bool {
+        for des_index in 0..self.num_nodes {
+            if visited[des_index]
+                || !self.is_reachable(NodeIndex::new(node_index), NodeIndex::new(des_index))
+            {
+                continue;
+            }
+            visited[des_index] = true;
+            let is_augmented = match matched_nodes[des_index] {
+                Some(matched_index) => {
+                    self.find_augmenting_path(matched_index, visited, matched_nodes)
+                }
+                None => true,
+            };
+            if is_augmented {
+                matched_nodes[des_index] = Some(node_index);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    #[test]
+    fn test_is_reachable_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        let n3 = dag.add_node(create_node(3, "execution_time", 0));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n0, n2, 1);
+
+        let reachability_matrix = ReachabilityMatrix::new(&dag);
+        assert!(reachability_matrix.is_reachable(n0, n3));
+        assert!(!reachability_matrix.is_reachable(n3, n0));
+        assert!(!reachability_matrix.is_reachable(n2, n3));
+        assert!(!reachability_matrix.is_reachable(n0, n0));
+    }
+
+    #[test]
+    fn test_is_reachable_over_one_block() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let chain: Vec<_> = (0..100)
+            .map(|id| dag.add_node(create_node(id, "execution_time", 0)))
+            .collect();
+        for window in chain.windows(2) {
+            dag.add_edge(window[0], window[1], 1);
+        }
+
+        let reachability_matrix = ReachabilityMatrix::new(&dag);
+        assert!(reachability_matrix.is_reachable(chain[0], chain[99]));
+        assert!(reachability_matrix.is_reachable(chain[63], chain[64]));
+        assert!(!reachability_matrix.is_reachable(chain[70], chain[10]));
+        assert_eq!(
+            reachability_matrix.get_parallel_process_nodes(chain[50]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_anc_and_des_nodes_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        let n3 = dag.add_node(create_node(3, "execution_time", 0));
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n2, n3, 1);
+        dag.add_edge(n1, n3, 1);
+
+        let reachability_matrix = ReachabilityMatrix::new(&dag);
+        assert_eq!(
+            reachability_matrix.get_anc_nodes(n3),
+            Some(vec![n0, n1, n2])
+        );
+        assert_eq!(reachability_matrix.get_anc_nodes(n0), None);
+        assert_eq!(reachability_matrix.get_des_nodes(n0), Some(vec![n2, n3]));
+        assert_eq!(reachability_matrix.get_des_nodes(n3), None);
+    }
+
+    #[test]
+    fn test_new_after_mutation() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        dag.add_edge(n0, n1, 1);
+        assert!(!ReachabilityMatrix::new(&dag).is_reachable(n0, n2));
+
+        dag.add_edge(n1, n2, 1);
+        assert!(ReachabilityMatrix::new(&dag).is_reachable(n0, n2));
+    }
+
+    #[test]
+    fn test_get_parallel_process_nodes_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        let n3 = dag.add_node(create_node(3, "execution_time", 0));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+
+        let reachability_matrix = ReachabilityMatrix::new(&dag);
+        assert_eq!(
+            reachability_matrix.get_parallel_process_nodes(n2),
+            Some(vec![n1, n3])
+        );
+        assert_eq!(reachability_matrix.get_parallel_process_nodes(n0), None);
+    }
+
+    #[test]
+    fn test_get_max_antichain_size_normal() {
+        // n2 is parallel to n1, n3 and n4, and n3 and n4 are parallel to each other.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 0));
+        let n1 = dag.add_node(create_node(1, "execution_time", 0));
+        let n2 = dag.add_node(create_node(2, "execution_time", 0));
+        let n3 = dag.add_node(create_node(3, "execution_time", 0));
+        let n4 = dag.add_node(create_node(4, "execution_time", 0));
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n1, n4, 1);
+        assert_eq!(ReachabilityMatrix::new(&dag).get_max_antichain_size(), 3);
+
+        // A chain has no parallelism, and an empty DAG has none to exploit.
+        let mut chain_dag = Graph::<NodeData, i64>::new();
+        let c0 = chain_dag.add_node(create_node(0, "execution_time", 0));
+        let c1 = chain_dag.add_node(create_node(1, "execution_time", 0));
+        chain_dag.add_edge(c0, c1, 1);
+        assert_eq!(
+            ReachabilityMatrix::new(&chain_dag).get_max_antichain_size(),
+            1
+        );
+        assert_eq!(
+            ReachabilityMatrix::new(&Graph::<NodeData, i64>::new()).get_max_antichain_size(),
+            0
+        );
+    }
+}
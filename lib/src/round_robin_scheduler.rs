@@ -0,0 +1,397 @@
+//! Global round-robin scheduler.
+//! The ready nodes of all DAGs wait in a single FIFO queue regardless of their deadlines.
+//! A node runs for at most `quantum` time units at a time, and then it is preempted and appended to the queue
+//! if another node is waiting. It is a baseline for fairness comparisons.
+use crate::dag_set_scheduler::{
+    validate_max_parallelisms, DAGSetSchedulerBase, DAGStateManager, DAGStateManagerBase,
+    DeadlineMissPolicy, PreemptiveType, ScheduleOutcome,
+};
+use crate::getset_dag_set_scheduler;
+use crate::{
+    admission_control::OnlineAdmission,
+    aperiodic_arrival::AperiodicArrivalTrace,
+    core::ProcessResult,
+    diagnostics::DiagnosticCollector,
+    error::{Result, SchedSimError},
+    event_script::EventScript,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    interconnect::Interconnect,
+    log::{DAGSetSchedulerLog, JobEventTimes},
+    logical_execution_time::CommunicationSemantics,
+    mode_change::ModeSchedule,
+    network::Network,
+    non_preemptive_section::{
+        is_in_non_preemptive_section, is_section_continuation, validate_non_preemptive_sections,
+    },
+    processor::ProcessorBase,
+    simulation_horizon::SimulationHorizon,
+    sporadic_release::{validate_jitters, ArrivalTrace, ReleaseJitter},
+    util::{get_overhead_core_indices, get_process_core_indices},
+};
+use petgraph::graph::Graph;
+use std::collections::VecDeque;
+
+const DEFAULT_QUANTUM: i64 = 10;
+
+pub struct RoundRobinScheduler {
+    dag_set: Vec<Graph<NodeData, i64>>,
+    processor: HomogeneousProcessor,
+    log: DAGSetSchedulerLog,
+    current_time: i64,
+    event_script: EventScript,
+    interconnect: Option<Interconnect>,
+    network: Option<Network>,
+    arrival_trace: Option<ArrivalTrace>,
+    aperiodic_arrival_trace: Option<AperiodicArrivalTrace>,
+    release_jitter: ReleaseJitter,
+    deadline_miss_policy: DeadlineMissPolicy,
+    simulation_horizon: SimulationHorizon,
+    mode_schedule: Option<ModeSchedule>,
+    online_admission: Option<OnlineAdmission>,
+    communication_semantics: CommunicationSemantics,
+    quantum: i64,
+}
+
+impl RoundRobinScheduler {
+    pub fn set_quantum(&mut self, quantum: i64) -> Result<()> {
+        if quantum <= 0 {
+            return Err(SchedSimError::Validation(format!(
+                "The quantum must be positive: {}",
+                quantum
+            )));
+        }
+        self.quantum = quantum;
+        Ok(())
+    }
+
+    pub fn get_quantum(&self) -> i64 {
+        self.quantum
+    }
+
+    /// Allocate the nodes in the queue order to the idle cores allowed by their affinity.
+    /// The nodes of a DAG running as many nodes as its `max_parallelism` are skipped.
+    fn allocate_ready_nodes(
+        &mut self,
+        ready_queue: &mut VecDeque<NodeData>,
+        managers: &[DAGStateManager],
+        elapsed_times: &mut [i64],
+    ) {
+        while self.processor.get_idle_core_num() > 0 {
+            let Some((queue_index, idle_core_i)) = ready_queue
+                .iter()
+                .enumerate()
+                .filter(|(_, node_data)| !self.is_parallelism_capped(node_data))
+                .find_map(|(queue_index, node_data)| {
+                    self.processor
+                        .get_idle_core_index_with_affinity(node_data)
+                        .map(|idle_core_i| (queue_index, idle_core_i))
+                })
+            else {
+                break;
+            };
+            let node_data = ready_queue.remove(queue_index).unwrap();
+            let dag_id = node_data.get_params_value("dag_id") as usize;
+            self.allocate_node(
+                &node_data,
+                idle_core_i,
+                managers[dag_id].get_release_count() as usize,
+            );
+            elapsed_times[idle_core_i] = 0;
+        }
+    }
+
+    /// Preempt the nodes that have used up the quantum while other nodes are waiting.
+    /// The nodes in non-preemptive sections run to completion.
+    fn preempt_expired_nodes(
+        &mut self,
+        ready_queue: &mut VecDeque<NodeData>,
+        managers: &[DAGStateManager],
+        elapsed_times: &[i64],
+    ) {
+        for (core_id, elapsed_time) in elapsed_times.iter().enumerate() {
+            if ready_queue.is_empty() {
+                break;
+            }
+            if *elapsed_time < self.quantum
+                || self
+                    .processor
+                    .get_processing_node(core_id)
+                    .is_some_and(is_in_non_preemptive_section)
+            {
+                continue;
+            }
+            if let Some(preempted_node_data) = self.processor.preempt(core_id) {
+                let dag_id = preempted_node_data.get_params_value("dag_id") as usize;
+                self.log.write_job_event(
+                    &preempted_node_data,
+                    core_id,
+                    (managers[dag_id].get_release_count() - 1) as usize,
+                    JobEventTimes::PreemptedTime(self.current_time),
+                );
+                ready_queue.push_back(preempted_node_data);
+            }
+        }
+    }
+}
+
+impl DAGSetSchedulerBase<HomogeneousProcessor> for RoundRobinScheduler {
+    fn new(dag_set: &[Graph<NodeData, i64>], processor: &HomogeneousProcessor) -> Self {
+        Self {
+            dag_set: dag_set.to_vec(),
+            processor: processor.clone(),
+            log: DAGSetSchedulerLog::new(dag_set, processor.get_number_of_cores()),
+            current_time: 0,
+            event_script: EventScript::default(),
+            interconnect: None,
+            network: None,
+            arrival_trace: None,
+            aperiodic_arrival_trace: None,
+            release_jitter: ReleaseJitter::default(),
+            deadline_miss_policy: DeadlineMissPolicy::default(),
+            simulation_horizon: SimulationHorizon::default(),
+            mode_schedule: None,
+            online_admission: None,
+            communication_semantics: CommunicationSemantics::default(),
+            quantum: DEFAULT_QUANTUM,
+        }
+    }
+
+    getset_dag_set_scheduler!(HomogeneousProcessor);
+
+    /// The nodes are always preempted at the quantum expiry, so `PreemptiveType` is ignored.
+    fn schedule(&mut self, _: PreemptiveType) -> Result<ScheduleOutcome> {
+        // The outputs are published under LET only by the default schedule.
+        if self.communication_semantics == CommunicationSemantics::LogicalExecutionTime {
+            return Err(SchedSimError::Validation(
+                "The round-robin scheduler does not support the logical execution time."
+                    .to_string(),
+            ));
+        }
+        let diagnostic_collector = DiagnosticCollector::new();
+        self.log
+            .write_execution_time_seed(self.processor.get_execution_time_seed());
+        self.log
+            .write_communication_semantics(self.communication_semantics);
+        if let Some(arrival_trace) = &self.arrival_trace {
+            arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(aperiodic_arrival_trace) = &self.aperiodic_arrival_trace {
+            aperiodic_arrival_trace.validate(&self.dag_set)?;
+        }
+        if let Some(mode_schedule) = &self.mode_schedule {
+            mode_schedule.validate(&self.dag_set)?;
+        }
+        validate_jitters(&self.dag_set)?;
+        validate_non_preemptive_sections(&self.dag_set)?;
+        validate_max_parallelisms(&self.dag_set)?;
+
+        let mut managers = vec![DAGStateManager::default(); self.dag_set.len()];
+        let mut ready_queue = VecDeque::new();
+        // The time each core has processed the current node since it was allocated.
+        let mut elapsed_times = vec![0; self.processor.get_number_of_cores()];
+        let end_time = self.get_end_time()?;
+        while self.current_time < end_time {
+            self.handle_mode_changes(&managers);
+
+            // Handle scripted events
+            let mut ready_nodes = self.handle_scripted_events(&mut managers);
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            ready_queue.extend(ready_nodes);
+
+            // Release DAGs
+            let mut ready_nodes = self.release_dags(&mut managers);
+            self.apply_wcet_overruns(&mut ready_nodes, &managers);
+            ready_queue.extend(ready_nodes);
+
+            self.preempt_expired_nodes(&mut ready_queue, &managers, &elapsed_times);
+            self.allocate_ready_nodes(&mut ready_queue, &managers, &mut elapsed_times);
+            self.write_parallelism_capped_times(ready_queue.iter());
+
+            // Process unit time
+            let process_result = self.process_unit_time();
+            let indices: Vec<usize> = get_process_core_indices(&process_result);
+            for core_id in indices.iter() {
+                elapsed_times[*core_id] += 1;
+            }
+            self.log.write_processing_time(&indices);
+            let indices: Vec<usize> = get_overhead_core_indices(&process_result);
+            self.log.write_overhead_time(&indices);
+
+            // Post-process on completion of node execution
+            for (core_id, result) in process_result.iter().enumerate() {
+                if let ProcessResult::Done(node_data) = result {
+                    let mut ready_nodes =
+                        self.post_process_on_node_completion(node_data, core_id, &mut managers);
+                    self.apply_wcet_overruns(&mut ready_nodes, &managers);
+                    let dag = &self.dag_set[node_data.get_params_value("dag_id") as usize];
+                    for ready_node in ready_nodes {
+                        // A started section continues ahead of the queue.
+                        if is_section_continuation(dag, &ready_node) {
+                            ready_queue.push_front(ready_node);
+                        } else {
+                            ready_queue.push_back(ready_node);
+                        }
+                    }
+                }
+            }
+
+            // Handle deadline misses
+            let missed_dag_ids = self.handle_deadline_misses(&mut managers);
+            match self.deadline_miss_policy {
+                DeadlineMissPolicy::Abort if !missed_dag_ids.is_empty() => break,
+                DeadlineMissPolicy::KillJob => {
+                    for dag_id in missed_dag_ids {
+                        ready_queue.retain(|node_data| {
+                            node_data.get_params_value("dag_id") as usize != dag_id
+                        });
+                        self.kill_job(dag_id, &mut managers[dag_id]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.calculate_log();
+        Ok(self.get_schedule_outcome(diagnostic_collector.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    #[cfg(feature = "file_io")]
+    use crate::util::load_yaml;
+    use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::fs::remove_file;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_single_node_dag(dag_id: i64, execution_time: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", execution_time));
+        dag.add_param(n0, "period", 100);
+        dag.add_param(n0, "end_to_end_deadline", 100);
+        dag.set_dag_param("dag_id", dag_id);
+        dag
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_round_robin_time_slicing() {
+        let dag_set = vec![create_single_node_dag(0, 25), create_single_node_dag(1, 25)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut round_robin_scheduler = RoundRobinScheduler::new(&dag_set, &processor);
+        round_robin_scheduler.set_quantum(10).unwrap();
+        let time = round_robin_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap()
+            .schedule_length;
+        assert_eq!(time, 100);
+
+        let file_path = round_robin_scheduler.dump_log("../lib/tests", "round_robin_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        // A: 0-10, 20-30, 40-45 and B: 10-20, 30-40, 45-50
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            45
+        );
+        assert_eq!(
+            yaml_doc["dag_set_log"][1]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            50
+        );
+        let node_logs = yaml_doc["node_set_logs"][0].as_vec().unwrap();
+        assert_eq!(node_logs.len(), 6);
+        // Preempted at 10 and resumed at 20.
+        assert_eq!(node_logs[1]["event_time"].as_str().unwrap(), "10");
+        assert_eq!(node_logs[2]["event_time"].as_str().unwrap(), "20");
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_round_robin_no_preemption_without_waiting_nodes() {
+        let dag_set = vec![create_single_node_dag(0, 25)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut round_robin_scheduler = RoundRobinScheduler::new(&dag_set, &processor);
+        round_robin_scheduler.set_quantum(10).unwrap();
+        round_robin_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+
+        let file_path =
+            round_robin_scheduler.dump_log("../lib/tests", "round_robin_single_dag_test");
+        let yaml_docs = load_yaml(&file_path).unwrap();
+        let yaml_doc = &yaml_docs[0];
+
+        assert_eq!(
+            yaml_doc["dag_set_log"][0]["finish_time"][0]
+                .as_i64()
+                .unwrap(),
+            25
+        );
+        assert_eq!(yaml_doc["node_set_logs"][0].as_vec().unwrap().len(), 2);
+
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_round_robin_set_quantum_zero() {
+        let dag_set = vec![create_single_node_dag(0, 25)];
+        let processor = HomogeneousProcessor::new(1);
+        assert!(RoundRobinScheduler::new(&dag_set, &processor)
+            .set_quantum(0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_round_robin_logical_execution_time() {
+        let dag_set = vec![create_single_node_dag(0, 30)];
+        let processor = HomogeneousProcessor::new(1);
+
+        let mut scheduler = RoundRobinScheduler::new(&dag_set, &processor);
+        scheduler.set_communication_semantics(CommunicationSemantics::LogicalExecutionTime);
+        assert!(matches!(
+            scheduler.schedule(PreemptiveType::NonPreemptive),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_round_robin_max_parallelism() {
+        // The two branches run one after the other even though two cores are idle.
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(source, "period", 100);
+        dag.add_param(source, "end_to_end_deadline", 100);
+        for node_id in 1..3 {
+            let node = dag.add_node(create_node(node_id, "execution_time", 10));
+            dag.add_edge(source, node, 0);
+        }
+        dag.set_dag_param("dag_id", 0);
+        dag.set_dag_param("max_parallelism", 1);
+        let processor = HomogeneousProcessor::new(2);
+
+        let mut round_robin_scheduler = RoundRobinScheduler::new(&[dag], &processor);
+        round_robin_scheduler.set_quantum(100).unwrap();
+        let schedule_outcome = round_robin_scheduler
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        assert_eq!(schedule_outcome.worst_response_times, vec![21]);
+    }
+}
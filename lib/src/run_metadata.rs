@@ -0,0 +1,210 @@
+//! The configuration of a run written as the header of its log, so that a timestamped log can be traced back
+//! to the inputs that produced it and be replayed.
+//! The input files are recorded with their hashes. The hash is FNV-1a, which is not cryptographic
+//! but detects an input modified since the run.
+//! The standard input, i.e., the path `-`, is recorded without a hash because it is consumed by the run,
+//! and the replayed run reads it again.
+//! `config` is the configuration of the binary, e.g., its arguments, which the binary deserializes to replay the run.
+use serde_derive::{Deserialize, Serialize};
+use std::env;
+#[cfg(feature = "file_io")]
+use {
+    crate::{
+        dag_creator::get_yaml_paths_from_dir,
+        error::{Result, SchedSimError},
+        log::dump_struct,
+        util::STDIN_FILE_PATH,
+    },
+    std::{fs, path::Path},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputFile {
+    pub path: String,
+    pub hash: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub crate_version: String,
+    pub algorithm: String,
+    pub number_of_cores: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_time_seed: Option<u64>,
+    // The relative paths of the command line and the input files are resolved from it.
+    pub working_dir_path: String,
+    pub command_line: Vec<String>,
+    pub input_files: Vec<InputFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_yaml::Value>,
+}
+
+#[cfg(feature = "file_io")]
+#[derive(Serialize, Deserialize)]
+struct RunMetadataSection {
+    run_metadata: RunMetadata,
+}
+
+#[cfg(feature = "file_io")]
+fn calculate_file_hash(file_path: &str) -> Result<String> {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let file_content = fs::read(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in file_content {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+impl RunMetadata {
+    /// The version of this crate, the working directory and the command line are those of the current process.
+    pub fn new(algorithm: &str, number_of_cores: usize) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            algorithm: algorithm.to_string(),
+            number_of_cores,
+            execution_time_seed: None,
+            working_dir_path: env::current_dir()
+                .map(|dir_path| dir_path.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            command_line: env::args().collect(),
+            input_files: Vec::new(),
+            config: None,
+        }
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn add_input_file(&mut self, file_path: &str) -> Result<()> {
+        let hash = if file_path == STDIN_FILE_PATH {
+            String::new()
+        } else {
+            calculate_file_hash(file_path)?
+        };
+        self.input_files.push(InputFile {
+            path: file_path.to_string(),
+            hash,
+        });
+        Ok(())
+    }
+
+    /// Add the DAG files in the directory in the order of their dag_ids, i.e., their paths.
+    #[cfg(feature = "file_io")]
+    pub fn add_input_dir(&mut self, dir_path: &str) -> Result<()> {
+        let mut file_paths = get_yaml_paths_from_dir(dir_path)?;
+        file_paths.sort();
+        for file_path in file_paths.iter() {
+            self.add_input_file(file_path)?;
+        }
+        Ok(())
+    }
+
+    /// Write the metadata as a section of the log. Write it first to make it the header.
+    #[cfg(feature = "file_io")]
+    pub fn dump_to_yaml(&self, file_path: &str) {
+        dump_struct(
+            file_path,
+            &RunMetadataSection {
+                run_metadata: self.clone(),
+            },
+        );
+    }
+
+    /// Load the metadata in the header of a log.
+    #[cfg(feature = "file_io")]
+    pub fn from_log_file(file_path: &str) -> Result<Self> {
+        let file_content =
+            fs::read_to_string(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+        let run_metadata_section: RunMetadataSection = serde_yaml::from_str(&file_content)
+            .map_err(|err| SchedSimError::parse(file_path, err))?;
+        Ok(run_metadata_section.run_metadata)
+    }
+
+    /// Return an error if an input file has been removed or modified since the run.
+    #[cfg(feature = "file_io")]
+    pub fn verify_input_files(&self) -> Result<()> {
+        for input_file in self.input_files.iter() {
+            if input_file.path == STDIN_FILE_PATH {
+                continue;
+            }
+            let file_path = Path::new(&self.working_dir_path).join(&input_file.path);
+            let file_path = file_path.to_string_lossy();
+            if calculate_file_hash(&file_path)? != input_file.hash {
+                return Err(SchedSimError::Validation(format!(
+                    "The input file has been modified since the run: {}",
+                    input_file.path
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Verify the inputs recorded in the header of the log, and re-run `run` with the metadata.
+/// `run` is given the metadata rather than run on the command line, because the binary is the one
+/// that knows how to restore itself from `config`.
+#[cfg(feature = "file_io")]
+pub fn replay<R>(log_file_path: &str, run: impl FnOnce(&RunMetadata) -> Result<R>) -> Result<R> {
+    let run_metadata = RunMetadata::from_log_file(log_file_path)?;
+    run_metadata.verify_input_files()?;
+    run(&run_metadata)
+}
+
+#[cfg(all(test, feature = "file_io"))]
+mod tests {
+    use super::*;
+    use crate::util::{append_info_to_yaml, create_yaml};
+
+    #[test]
+    fn test_run_metadata_round_trip() {
+        let mut run_metadata = RunMetadata::new("gedf", 4);
+        run_metadata.execution_time_seed = Some(7);
+        run_metadata
+            .add_input_dir("tests/sample_dags/multiple_yaml")
+            .unwrap();
+        let file_path = create_yaml("../lib/tests", "run_metadata_round_trip_test");
+        run_metadata.dump_to_yaml(&file_path);
+        append_info_to_yaml(&file_path, "result: true\n");
+
+        let loaded_run_metadata = RunMetadata::from_log_file(&file_path).unwrap();
+        assert_eq!(loaded_run_metadata, run_metadata);
+        assert_eq!(loaded_run_metadata.input_files.len(), 2);
+        assert!(loaded_run_metadata.input_files[0]
+            .path
+            .ends_with("dag_0.yaml"));
+        assert!(loaded_run_metadata.verify_input_files().is_ok());
+        assert_eq!(
+            replay(&file_path, |run_metadata| Ok(run_metadata.number_of_cores)).unwrap(),
+            4
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_input_files_modified() {
+        let input_file_path = "../lib/tests/run_metadata_modified_input.yaml";
+        fs::write(input_file_path, "nodes: []\n").unwrap();
+        let mut run_metadata = RunMetadata::new("gedf", 4);
+        run_metadata.add_input_file(input_file_path).unwrap();
+        assert!(run_metadata.verify_input_files().is_ok());
+
+        fs::write(input_file_path, "nodes: [0]\n").unwrap();
+        assert!(matches!(
+            run_metadata.verify_input_files(),
+            Err(SchedSimError::Validation(_))
+        ));
+
+        fs::remove_file(input_file_path).unwrap();
+    }
+
+    #[test]
+    fn test_add_input_file_stdin() {
+        let mut run_metadata = RunMetadata::new("cpc", 4);
+        run_metadata.add_input_file(STDIN_FILE_PATH).unwrap();
+        assert_eq!(run_metadata.input_files[0].hash, "");
+        assert!(run_metadata.verify_input_files().is_ok());
+    }
+}
@@ -0,0 +1,255 @@
+//! Analytic schedulability tests of DAG sets on identical cores.
+//! Each test is a pure function of the DAG set and the number of cores, and returns the verdict with the metric deciding it,
+//! so that the analytic verdict can be reported next to the simulated one.
+use crate::{
+    analysis::calculate_response_time_bounds,
+    graph_extension::{GraphExtension, NodeData},
+};
+use petgraph::Graph;
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SchedulabilityVerdict {
+    pub is_schedulable: bool,
+    // The test passes if binding_value does not exceed limit.
+    pub binding_metric: String,
+    pub binding_value: f64,
+    pub limit: f64,
+}
+
+impl SchedulabilityVerdict {
+    pub(crate) fn new(binding_metric: &str, binding_value: f64, limit: f64) -> Self {
+        Self {
+            is_schedulable: binding_value <= limit,
+            binding_metric: binding_metric.to_string(),
+            binding_value,
+            limit,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SchedulabilityVerdicts {
+    pub federated: SchedulabilityVerdict,
+    pub global_edf: SchedulabilityVerdict,
+    pub global_fixed_priority: SchedulabilityVerdict,
+}
+
+struct DAGTaskParams {
+    volume: i64,
+    critical_path_length: i64,
+    // min(end-to-end deadline, period)
+    deadline: i64,
+}
+
+impl DAGTaskParams {
+    fn new(dag: &Graph<NodeData, i64>) -> Self {
+        let critical_path = dag.get_critical_path();
+        Self {
+            volume: dag.get_volume(),
+            critical_path_length: dag.get_total_wcet_from_nodes(&critical_path),
+            deadline: dag
+                .get_end_to_end_deadline()
+                .unwrap()
+                .min(dag.get_head_period().unwrap()),
+        }
+    }
+
+    fn get_density(&self) -> f64 {
+        self.volume as f64 / self.deadline as f64
+    }
+
+    fn get_critical_path_ratio(&self) -> f64 {
+        self.critical_path_length as f64 / self.deadline as f64
+    }
+}
+
+fn get_max_critical_path_ratio(dag_task_params: &[DAGTaskParams]) -> f64 {
+    dag_task_params
+        .iter()
+        .map(DAGTaskParams::get_critical_path_ratio)
+        .fold(0.0, f64::max)
+}
+
+/// Federated scheduling of Li et al.
+/// A DAG with a density above 1 gets ceil((vol - len) / (D - len)) dedicated cores,
+/// and the other DAGs are executed sequentially and partitioned onto the remaining cores by first-fit decreasing density.
+/// The binding metric is the number of required cores, or the critical path ratio if a critical path exceeds its deadline.
+pub fn test_federated(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> SchedulabilityVerdict {
+    let dag_task_params: Vec<DAGTaskParams> = dag_set.iter().map(DAGTaskParams::new).collect();
+    let max_critical_path_ratio = get_max_critical_path_ratio(&dag_task_params);
+    if max_critical_path_ratio > 1.0 {
+        return SchedulabilityVerdict::new("critical_path_ratio", max_critical_path_ratio, 1.0);
+    }
+
+    let mut dedicated_cores = 0;
+    let mut light_densities = Vec::new();
+    for params in dag_task_params.iter() {
+        if params.get_density() > 1.0 {
+            dedicated_cores += ((params.volume - params.critical_path_length) as f64
+                / (params.deadline - params.critical_path_length) as f64)
+                .ceil() as usize;
+        } else {
+            light_densities.push(params.get_density());
+        }
+    }
+    light_densities.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    let mut core_densities: Vec<f64> = Vec::new();
+    for density in light_densities {
+        match core_densities
+            .iter_mut()
+            .find(|core_density| **core_density + density <= 1.0)
+        {
+            Some(core_density) => *core_density += density,
+            None => core_densities.push(density),
+        }
+    }
+
+    SchedulabilityVerdict::new(
+        "required_cores",
+        (dedicated_cores + core_densities.len()) as f64,
+        number_of_cores as f64,
+    )
+}
+
+/// Density test of Baruah et al. for global EDF.
+/// The DAG set is schedulable if the total density does not exceed m - (m - 1) max(len / D).
+pub fn test_global_edf(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> SchedulabilityVerdict {
+    test_global_edf_of_dags(dag_set.iter(), number_of_cores)
+}
+
+/// The density test over borrowed DAGs, which the admission control applies without cloning the admitted DAGs.
+pub(crate) fn test_global_edf_of_dags<'a>(
+    dags: impl Iterator<Item = &'a Graph<NodeData, i64>>,
+    number_of_cores: usize,
+) -> SchedulabilityVerdict {
+    let dag_task_params: Vec<DAGTaskParams> = dags.map(DAGTaskParams::new).collect();
+    let total_density: f64 = dag_task_params.iter().map(DAGTaskParams::get_density).sum();
+    let number_of_cores = number_of_cores as f64;
+    SchedulabilityVerdict::new(
+        "total_density",
+        total_density,
+        number_of_cores - (number_of_cores - 1.0) * get_max_critical_path_ratio(&dag_task_params),
+    )
+}
+
+/// Response-time analysis of Melani et al. for global fixed-priority scheduling with deadline-monotonic priorities.
+/// The binding metric is the largest response time bound relative to the end-to-end deadline,
+/// which is infinite if the analysis does not converge within the deadline.
+pub fn test_global_fixed_priority(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> SchedulabilityVerdict {
+    let max_response_time_ratio = calculate_response_time_bounds(dag_set, number_of_cores)
+        .iter()
+        .zip(dag_set)
+        .map(
+            |(response_time_bound, dag)| match response_time_bound.melani_bound {
+                Some(melani_bound) => {
+                    melani_bound as f64 / dag.get_end_to_end_deadline().unwrap() as f64
+                }
+                None => f64::INFINITY,
+            },
+        )
+        .fold(0.0, f64::max);
+    SchedulabilityVerdict::new("response_time_ratio", max_response_time_ratio, 1.0)
+}
+
+pub fn test_all(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> SchedulabilityVerdicts {
+    SchedulabilityVerdicts {
+        federated: test_federated(dag_set, number_of_cores),
+        global_edf: test_global_edf(dag_set, number_of_cores),
+        global_fixed_priority: test_global_fixed_priority(dag_set, number_of_cores),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag(execution_times: &[i64], period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(source, "period", period);
+        dag.add_param(source, "end_to_end_deadline", period);
+        for (id, execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node = dag.add_node(create_node(id as i64, "execution_time", *execution_time));
+            dag.add_edge(source, node, 0);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_federated_normal() {
+        // Density 1.6 with ceil((80 - 30) / (50 - 30)) = 3 dedicated cores, and densities 0.6 and 0.5 on two cores.
+        let dag_set = vec![
+            create_dag(&[10, 10, 20, 20, 20], 50),
+            create_dag(&[30, 30], 100),
+            create_dag(&[50], 100),
+        ];
+
+        let verdict = test_federated(&dag_set, 5);
+        assert!(verdict.is_schedulable);
+        assert_eq!(verdict.binding_metric, "required_cores");
+        assert_eq!(verdict.binding_value, 5.0);
+        assert!(!test_federated(&dag_set, 4).is_schedulable);
+    }
+
+    #[test]
+    fn test_federated_long_critical_path() {
+        let dag_set = vec![create_dag(&[60, 60], 100)];
+
+        let verdict = test_federated(&dag_set, 4);
+        assert!(!verdict.is_schedulable);
+        assert_eq!(verdict.binding_metric, "critical_path_ratio");
+        assert_eq!(verdict.binding_value, 1.2);
+    }
+
+    #[test]
+    fn test_global_edf_normal() {
+        // Total density 0.7 + 0.6, and the largest critical path ratio is 40 / 100.
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 100),
+            create_dag(&[20, 20, 20], 100),
+        ];
+
+        let verdict = test_global_edf(&dag_set, 2);
+        assert!(verdict.is_schedulable);
+        assert!((verdict.binding_value - 1.3).abs() < 1e-9);
+        assert!((verdict.limit - 1.6).abs() < 1e-9);
+        assert!(!test_global_edf(&dag_set, 1).is_schedulable);
+    }
+
+    #[test]
+    fn test_global_fixed_priority_normal() {
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 150),
+            create_dag(&[20, 20, 20], 100),
+        ];
+
+        // The response time bounds are 80 and 50.
+        let verdict = test_global_fixed_priority(&dag_set, 2);
+        assert!(verdict.is_schedulable);
+        assert!((verdict.binding_value - 80.0 / 150.0).abs() < 1e-9);
+
+        let verdict = test_global_fixed_priority(&dag_set, 1);
+        assert!(!verdict.is_schedulable);
+        assert_eq!(verdict.binding_value, f64::INFINITY);
+    }
+}
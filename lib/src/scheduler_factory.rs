@@ -0,0 +1,219 @@
+//! Creation of the DAG set schedulers by name, so that a harness can select the algorithm at runtime.
+//! DAGSetSchedulerBase is not object safe because of its constructor and generic methods,
+//! so the factory returns the object-safe DynDAGSetScheduler implemented for every DAG set scheduler.
+//! The schedulers outside this crate, e.g., DynFed of its paper crate, are added with `register`.
+//! The single DAG schedulers such as FixedPriorityScheduler schedule one DAG and are not created here.
+use crate::{
+    admission_control::OnlineAdmission,
+    dag_set_scheduler::{DAGSetSchedulerBase, DeadlineMissPolicy, PreemptiveType, ScheduleOutcome},
+    error::{Result, SchedSimError},
+    event_script::EventScript,
+    global_edf_scheduler::GlobalEDFScheduler,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    log::DAGSetSchedulerLog,
+    logical_execution_time::CommunicationSemantics,
+    network::Network,
+    processor::ProcessorBase,
+    proportional_share_scheduler::ProportionalShareScheduler,
+    round_robin_scheduler::RoundRobinScheduler,
+    simulation_horizon::{HorizonEnd, SimulationHorizon},
+    sporadic_release::{ArrivalTrace, ReleaseJitter},
+    util::adjust_to_implicit_deadline,
+};
+use petgraph::Graph;
+use std::collections::BTreeMap;
+
+/// The part of DAGSetSchedulerBase used to configure and run a scheduler behind a Box.
+pub trait DynDAGSetScheduler<T: ProcessorBase + Clone> {
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> Result<ScheduleOutcome>;
+    #[cfg(feature = "file_io")]
+    fn dump_log(&mut self, dir_path: &str, alg_name: &str) -> String;
+    fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog;
+    fn get_processor_mut(&mut self) -> &mut T;
+    fn set_event_script(&mut self, event_script: EventScript);
+    fn set_network(&mut self, network: Network);
+    fn set_arrival_trace(&mut self, arrival_trace: ArrivalTrace);
+    fn set_release_jitter(&mut self, release_jitter: ReleaseJitter);
+    fn set_deadline_miss_policy(&mut self, deadline_miss_policy: DeadlineMissPolicy);
+    fn set_simulation_horizon(&mut self, simulation_horizon: SimulationHorizon);
+    fn set_online_admission(&mut self, online_admission: OnlineAdmission) -> Result<()>;
+    fn set_communication_semantics(&mut self, communication_semantics: CommunicationSemantics);
+}
+
+impl<T: ProcessorBase + Clone, S: DAGSetSchedulerBase<T>> DynDAGSetScheduler<T> for S {
+    fn schedule(&mut self, preemptive_type: PreemptiveType) -> Result<ScheduleOutcome> {
+        DAGSetSchedulerBase::schedule(self, preemptive_type)
+    }
+    #[cfg(feature = "file_io")]
+    fn dump_log(&mut self, dir_path: &str, alg_name: &str) -> String {
+        DAGSetSchedulerBase::dump_log(self, dir_path, alg_name)
+    }
+    fn get_log_mut(&mut self) -> &mut DAGSetSchedulerLog {
+        DAGSetSchedulerBase::get_log_mut(self)
+    }
+    fn get_processor_mut(&mut self) -> &mut T {
+        DAGSetSchedulerBase::get_processor_mut(self)
+    }
+    fn set_event_script(&mut self, event_script: EventScript) {
+        DAGSetSchedulerBase::set_event_script(self, event_script);
+    }
+    fn set_network(&mut self, network: Network) {
+        DAGSetSchedulerBase::set_network(self, network);
+    }
+    fn set_arrival_trace(&mut self, arrival_trace: ArrivalTrace) {
+        DAGSetSchedulerBase::set_arrival_trace(self, arrival_trace);
+    }
+    fn set_release_jitter(&mut self, release_jitter: ReleaseJitter) {
+        DAGSetSchedulerBase::set_release_jitter(self, release_jitter);
+    }
+    fn set_deadline_miss_policy(&mut self, deadline_miss_policy: DeadlineMissPolicy) {
+        DAGSetSchedulerBase::set_deadline_miss_policy(self, deadline_miss_policy);
+    }
+    fn set_simulation_horizon(&mut self, simulation_horizon: SimulationHorizon) {
+        DAGSetSchedulerBase::set_simulation_horizon(self, simulation_horizon);
+    }
+    fn set_online_admission(&mut self, online_admission: OnlineAdmission) -> Result<()> {
+        DAGSetSchedulerBase::set_online_admission(self, online_admission)
+    }
+    fn set_communication_semantics(&mut self, communication_semantics: CommunicationSemantics) {
+        DAGSetSchedulerBase::set_communication_semantics(self, communication_semantics);
+    }
+}
+
+pub type SchedulerConstructor = fn(
+    &[Graph<NodeData, i64>],
+    &HomogeneousProcessor,
+) -> Box<dyn DynDAGSetScheduler<HomogeneousProcessor>>;
+
+/// The constructor of S to register.
+pub fn create_boxed_scheduler<S: DAGSetSchedulerBase<HomogeneousProcessor> + 'static>(
+    dag_set: &[Graph<NodeData, i64>],
+    processor: &HomogeneousProcessor,
+) -> Box<dyn DynDAGSetScheduler<HomogeneousProcessor>> {
+    Box::new(S::new(dag_set, processor))
+}
+
+pub struct SchedulerFactory {
+    constructors: BTreeMap<String, SchedulerConstructor>,
+}
+
+impl Default for SchedulerFactory {
+    /// The factory of the DAG set schedulers of this crate.
+    fn default() -> Self {
+        let mut factory = Self {
+            constructors: BTreeMap::new(),
+        };
+        factory.register("global_edf", create_boxed_scheduler::<GlobalEDFScheduler>);
+        factory.register("round_robin", create_boxed_scheduler::<RoundRobinScheduler>);
+        factory.register(
+            "proportional_share",
+            create_boxed_scheduler::<ProportionalShareScheduler>,
+        );
+        factory
+    }
+}
+
+impl SchedulerFactory {
+    /// Add a scheduler, or replace the one with the same name.
+    pub fn register(&mut self, name: &str, constructor: SchedulerConstructor) {
+        self.constructors.insert(name.to_string(), constructor);
+    }
+
+    /// The names of the registered schedulers in the alphabetical order.
+    pub fn get_names(&self) -> Vec<&str> {
+        self.constructors.keys().map(String::as_str).collect()
+    }
+
+    pub fn create(
+        &self,
+        name: &str,
+        dag_set: &[Graph<NodeData, i64>],
+        processor: &HomogeneousProcessor,
+    ) -> Result<Box<dyn DynDAGSetScheduler<HomogeneousProcessor>>> {
+        let constructor = self.constructors.get(name).ok_or_else(|| {
+            SchedSimError::Validation(format!(
+                "Unknown scheduler: {}. The schedulers are {}.",
+                name,
+                self.get_names().join(", ")
+            ))
+        })?;
+        Ok(constructor(dag_set, processor))
+    }
+
+    /// Simulate the DAG set for the hyper periods on homogeneous cores with the scheduler named `name` as the binaries do,
+    /// i.e., the period is considered as the end-to-end deadline.
+    /// This is the whole run for the bindings, which have no command line arguments to configure the scheduler.
+    pub fn simulate(
+        &self,
+        name: &str,
+        dag_set: &[Graph<NodeData, i64>],
+        number_of_cores: usize,
+        preemptive_type: PreemptiveType,
+        num_hyper_periods: i64,
+    ) -> Result<ScheduleOutcome> {
+        let mut dag_set = dag_set.to_vec();
+        adjust_to_implicit_deadline(&mut dag_set);
+        let mut scheduler =
+            self.create(name, &dag_set, &HomogeneousProcessor::new(number_of_cores))?;
+        scheduler.set_simulation_horizon(SimulationHorizon::new(
+            HorizonEnd::HyperPeriods(num_hyper_periods),
+            0,
+        ));
+        scheduler.schedule(preemptive_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dag_builder::DagBuilder;
+    use crate::graph_extension::GraphExtension;
+
+    fn create_dag_set() -> Vec<Graph<NodeData, i64>> {
+        let mut builder = DagBuilder::new();
+        builder.node(0).wcet(10).period(50).end_to_end_deadline(50);
+        builder.node(1).wcet(20);
+        builder.edge(0, 1);
+        let mut dag = builder.build().unwrap();
+        dag.set_dag_param("dag_id", 0);
+        vec![dag]
+    }
+
+    #[test]
+    fn test_scheduler_factory_create_normal() {
+        let factory = SchedulerFactory::default();
+        assert_eq!(
+            factory.get_names(),
+            vec!["global_edf", "proportional_share", "round_robin"]
+        );
+        let dag_set = create_dag_set();
+        let processor = HomogeneousProcessor::new(2);
+        for name in factory.get_names() {
+            let mut scheduler = factory.create(name, &dag_set, &processor).unwrap();
+            let schedule_outcome = scheduler.schedule(PreemptiveType::NonPreemptive).unwrap();
+            assert!(!schedule_outcome.has_deadline_miss(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_scheduler_factory_create_registered() {
+        let mut factory = SchedulerFactory::default();
+        factory.register("gedf", create_boxed_scheduler::<GlobalEDFScheduler>);
+        let schedule_outcome = factory
+            .create("gedf", &create_dag_set(), &HomogeneousProcessor::new(2))
+            .unwrap()
+            .schedule(PreemptiveType::NonPreemptive)
+            .unwrap();
+        assert_eq!(schedule_outcome.schedule_length, 50);
+    }
+
+    #[test]
+    fn test_scheduler_factory_create_unknown() {
+        let factory = SchedulerFactory::default();
+        assert!(matches!(
+            factory.create("unknown", &create_dag_set(), &HomogeneousProcessor::new(2)),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+}
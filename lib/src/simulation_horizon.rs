@@ -0,0 +1,131 @@
+//! Length of a simulation run.
+//! By default the DAG set is simulated for one hyper period, which cuts off the jobs released late in it.
+//! The run can be extended to several hyper periods or to an explicit end time,
+//! and the jobs released during the warm-up are excluded from the response time statistics.
+use crate::{
+    error::{Result, SchedSimError},
+    graph_extension::NodeData,
+    util::get_hyper_period,
+};
+use petgraph::Graph;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HorizonEnd {
+    HyperPeriods(i64),
+    EndTime(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationHorizon {
+    end: HorizonEnd,
+    warm_up_time: i64,
+}
+
+impl Default for SimulationHorizon {
+    fn default() -> Self {
+        Self {
+            end: HorizonEnd::HyperPeriods(1),
+            warm_up_time: 0,
+        }
+    }
+}
+
+impl SimulationHorizon {
+    pub fn new(end: HorizonEnd, warm_up_time: i64) -> Self {
+        match end {
+            HorizonEnd::HyperPeriods(num_hyper_periods) if num_hyper_periods <= 0 => {
+                panic!(
+                    "The number of hyper periods must be positive: {}",
+                    num_hyper_periods
+                )
+            }
+            HorizonEnd::EndTime(end_time) if end_time <= 0 => {
+                panic!("The end time must be positive: {}", end_time)
+            }
+            _ => {}
+        }
+        if warm_up_time < 0 {
+            panic!("The warm-up time must not be negative: {}", warm_up_time);
+        }
+        Self { end, warm_up_time }
+    }
+
+    pub fn get_end_time(&self, dag_set: &[Graph<NodeData, i64>]) -> Result<i64> {
+        match self.end {
+            HorizonEnd::HyperPeriods(num_hyper_periods) => get_hyper_period(dag_set)?
+                .checked_mul(num_hyper_periods)
+                .ok_or_else(|| {
+                    SchedSimError::Validation("The simulation horizon overflows i64.".to_string())
+                }),
+            HorizonEnd::EndTime(end_time) => Ok(end_time),
+        }
+    }
+
+    pub fn get_warm_up_time(&self) -> i64 {
+        self.warm_up_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_extension::GraphExtension;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag_with_period(period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(source, "period", period);
+        dag
+    }
+
+    #[test]
+    fn test_get_end_time_normal() {
+        let dag_set = vec![create_dag_with_period(10), create_dag_with_period(15)];
+
+        assert_eq!(
+            SimulationHorizon::default().get_end_time(&dag_set).unwrap(),
+            30
+        );
+        assert_eq!(
+            SimulationHorizon::new(HorizonEnd::HyperPeriods(3), 0)
+                .get_end_time(&dag_set)
+                .unwrap(),
+            90
+        );
+        assert_eq!(
+            SimulationHorizon::new(HorizonEnd::EndTime(45), 0)
+                .get_end_time(&dag_set)
+                .unwrap(),
+            45
+        );
+    }
+
+    #[test]
+    fn test_get_end_time_overflow() {
+        let dag_set = vec![create_dag_with_period(i64::MAX / 2)];
+
+        assert!(matches!(
+            SimulationHorizon::new(HorizonEnd::HyperPeriods(3), 0).get_end_time(&dag_set),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_no_hyper_period() {
+        SimulationHorizon::new(HorizonEnd::HyperPeriods(0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_negative_warm_up_time() {
+        SimulationHorizon::new(HorizonEnd::EndTime(100), -1);
+    }
+}
@@ -0,0 +1,337 @@
+//! Sporadic release model.
+//! Without an arrival trace, each DAG is released strictly periodically at `offset + period * count`.
+//! With an arrival trace, the DAG is released at the arrival times in the trace instead,
+//! and consecutive arrivals must be separated by at least the period.
+//! The trace is either read from a YAML file or drawn from an inter-arrival distribution.
+//!
+//! A source node with `jitter` delays the release of each job by up to `jitter` after its arrival,
+//! while the absolute deadline stays relative to the arrival.
+//! The delays are drawn from a seeded RNG keyed by the job or given as a trace in the same format as the arrival trace.
+//!
+//! Example of an arrival trace file (dag_id: arrival times):
+//!
+//! ```yaml
+//! 0: [0, 120, 250]
+//! 1: [10, 110, 230]
+//! ```
+#[cfg(feature = "file_io")]
+use crate::util::load_yaml_struct;
+use crate::{
+    error::{Result, SchedSimError},
+    graph_extension::{GraphExtension, NodeData},
+    job_rng::{create_job_rng, RandomStream},
+};
+use petgraph::Graph;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, Exp};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The inter-arrival time is the period plus a delay drawn from the distribution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InterArrivalDistribution {
+    /// The delay is uniform over [0, max_delay].
+    Uniform { max_delay: i64 },
+    /// The delay is exponential with mean `mean_delay`, rounded to the time unit.
+    Exponential { mean_delay: f64 },
+}
+
+impl InterArrivalDistribution {
+    fn draw_delay(&self, rng: &mut StdRng) -> i64 {
+        match self {
+            Self::Uniform { max_delay } => rng.gen_range(0..=*max_delay),
+            Self::Exponential { mean_delay } => {
+                Exp::new(1.0 / mean_delay).unwrap().sample(rng).round() as i64
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ArrivalTrace {
+    arrival_times: BTreeMap<usize, Vec<i64>>,
+}
+
+impl ArrivalTrace {
+    pub fn new(arrival_times: BTreeMap<usize, Vec<i64>>) -> Self {
+        Self { arrival_times }
+    }
+
+    /// load yaml file and return an arrival trace
+    ///
+    /// # Arguments
+    ///
+    /// *  `file_path` - yaml file path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lib::sporadic_release::ArrivalTrace;
+    ///
+    /// let arrival_trace = ArrivalTrace::from_yaml_file("tests/sample_arrival_traces/sporadic.yaml").unwrap();
+    /// ```
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        load_yaml_struct(file_path)
+    }
+
+    /// Draw the arrival times before `end_time` for each DAG, starting at its offset.
+    /// The same `seed` always yields the same arrivals.
+    pub fn generate(
+        dag_set: &[Graph<NodeData, i64>],
+        end_time: i64,
+        distribution: &InterArrivalDistribution,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut arrival_times = BTreeMap::new();
+        for (dag_id, dag) in dag_set.iter().enumerate() {
+            let period = dag.get_head_period().unwrap();
+            let mut dag_arrival_times = Vec::new();
+            let mut arrival_time = dag.get_head_offset();
+            while arrival_time < end_time {
+                dag_arrival_times.push(arrival_time);
+                arrival_time += period + distribution.draw_delay(&mut rng);
+            }
+            arrival_times.insert(dag_id, dag_arrival_times);
+        }
+        Self { arrival_times }
+    }
+
+    /// Returns the `release_count`-th arrival time of the DAG, or None after the last arrival.
+    pub fn get_arrival_time(&self, dag_id: usize, release_count: usize) -> Option<i64> {
+        self.arrival_times.get(&dag_id)?.get(release_count).copied()
+    }
+
+    /// Return an error if any inter-arrival time is shorter than the period of the DAG.
+    pub fn validate(&self, dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+        for (dag_id, dag_arrival_times) in self.arrival_times.iter() {
+            let period = dag_set[*dag_id].get_head_period().unwrap();
+            for arrivals in dag_arrival_times.windows(2) {
+                if arrivals[1] - arrivals[0] < period {
+                    return Err(SchedSimError::Validation(format!(
+                        "The inter-arrival time of DAG {} is shorter than the period: {} -> {}",
+                        dag_id, arrivals[0], arrivals[1]
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The release delay of each job of the DAGs with `jitter`.
+#[derive(Clone, Debug)]
+pub struct ReleaseJitter {
+    delays: BTreeMap<usize, Vec<i64>>,
+    // None if the delays are given as a trace.
+    seed: Option<u64>,
+}
+
+impl Default for ReleaseJitter {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+impl ReleaseJitter {
+    /// The delays are drawn uniformly over [0, jitter] as the jobs arrive.
+    /// The delay of a job depends only on the seed, the DAG and the job, so every scheduler sees the same delays.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            delays: BTreeMap::new(),
+            seed: Some(seed),
+        }
+    }
+
+    /// The jobs beyond the end of the trace are released without delay.
+    pub fn from_trace(delays: BTreeMap<usize, Vec<i64>>) -> Self {
+        Self { delays, seed: None }
+    }
+
+    #[cfg(feature = "file_io")]
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        Ok(Self::from_trace(load_yaml_struct(file_path)?))
+    }
+
+    /// Returns the release delay of the `release_count`-th job of the DAG.
+    /// The same job always gets the same delay, because the release is checked at every time unit.
+    pub fn get_delay(&mut self, dag_id: usize, release_count: usize, jitter: i64) -> i64 {
+        let dag_delays = self.delays.entry(dag_id).or_default();
+        if let Some(seed) = self.seed {
+            while dag_delays.len() <= release_count {
+                let mut rng = create_job_rng(
+                    seed,
+                    RandomStream::ReleaseJitter,
+                    dag_id,
+                    0,
+                    dag_delays.len(),
+                );
+                dag_delays.push(rng.gen_range(0..=jitter));
+            }
+        }
+        let delay = dag_delays.get(release_count).copied().unwrap_or(0);
+        if !(0..=jitter).contains(&delay) {
+            panic!(
+                "The release delay of DAG {} exceeds the jitter {}: {}",
+                dag_id, jitter, delay
+            );
+        }
+        delay
+    }
+}
+
+/// The jitter of the DAG, or 0 if the source node has no `jitter`.
+pub fn get_jitter(dag: &Graph<NodeData, i64>) -> i64 {
+    dag[dag.get_source_nodes()[0]]
+        .params
+        .get("jitter")
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Return an error if the jitter of a DAG is not shorter than its period, since the next job would arrive before the release.
+pub fn validate_jitters(dag_set: &[Graph<NodeData, i64>]) -> Result<()> {
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let jitter = get_jitter(dag);
+        if jitter < 0 || jitter >= dag.get_head_period().unwrap() {
+            return Err(SchedSimError::Validation(format!(
+                "The jitter of DAG {} must be in [0, period): {}",
+                dag_id, jitter
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graph::NodeIndex;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag_with_period(period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", 1));
+        dag.add_param(source, "period", period);
+        dag
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_normal() {
+        let arrival_trace =
+            ArrivalTrace::from_yaml_file("tests/sample_arrival_traces/sporadic.yaml").unwrap();
+        assert_eq!(arrival_trace.get_arrival_time(0, 1), Some(120));
+        assert_eq!(arrival_trace.get_arrival_time(1, 0), Some(10));
+        assert_eq!(arrival_trace.get_arrival_time(0, 3), None);
+        assert_eq!(arrival_trace.get_arrival_time(2, 0), None);
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_from_yaml_file_no_yaml() {
+        assert!(matches!(
+            ArrivalTrace::from_yaml_file("tests/sample_dags/no_yaml.tex"),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_generate_normal() {
+        let dag_set = vec![create_dag_with_period(10), create_dag_with_period(30)];
+        let distribution = InterArrivalDistribution::Uniform { max_delay: 5 };
+        let arrival_trace = ArrivalTrace::generate(&dag_set, 100, &distribution, 0);
+
+        assert_eq!(
+            arrival_trace,
+            ArrivalTrace::generate(&dag_set, 100, &distribution, 0)
+        );
+        arrival_trace.validate(&dag_set).unwrap();
+        for (dag_id, period) in [(0, 10), (1, 30)] {
+            let dag_arrival_times = &arrival_trace.arrival_times[&dag_id];
+            assert_eq!(dag_arrival_times[0], 0);
+            assert!(*dag_arrival_times.last().unwrap() < 100);
+            for arrivals in dag_arrival_times.windows(2) {
+                assert!((period..=period + 5).contains(&(arrivals[1] - arrivals[0])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_exponential() {
+        let dag_set = vec![create_dag_with_period(10)];
+        let distribution = InterArrivalDistribution::Exponential { mean_delay: 2.0 };
+        let arrival_trace = ArrivalTrace::generate(&dag_set, 10000, &distribution, 0);
+
+        arrival_trace.validate(&dag_set).unwrap();
+        let num_arrivals = arrival_trace.arrival_times[&0].len() as f64;
+        // The mean inter-arrival time is about 12.
+        assert!((10000.0 / num_arrivals - 12.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_validate_shorter_than_period() {
+        let dag_set = vec![create_dag_with_period(100)];
+        assert!(ArrivalTrace::new(BTreeMap::from([(0, vec![0, 99])]))
+            .validate(&dag_set)
+            .is_err());
+    }
+
+    #[test]
+    fn test_release_jitter_from_seed() {
+        let mut release_jitter = ReleaseJitter::from_seed(0);
+        let delays: Vec<i64> = (0..100)
+            .map(|release_count| release_jitter.get_delay(0, release_count, 5))
+            .collect();
+
+        assert!(delays.iter().all(|delay| (0..=5).contains(delay)));
+        assert!(delays.contains(&0));
+        assert!(delays.contains(&5));
+        // The delay of a job is drawn only once.
+        assert_eq!(release_jitter.get_delay(0, 10, 5), delays[10]);
+    }
+
+    #[test]
+    fn test_release_jitter_independent_of_draw_order() {
+        let mut release_jitter = ReleaseJitter::from_seed(0);
+        let mut other_release_jitter = ReleaseJitter::from_seed(0);
+        for release_count in 0..10 {
+            other_release_jitter.get_delay(1, release_count, 5);
+        }
+        assert_eq!(
+            release_jitter.get_delay(0, 3, 5),
+            other_release_jitter.get_delay(0, 3, 5)
+        );
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_release_jitter_from_yaml_file() {
+        let mut release_jitter =
+            ReleaseJitter::from_yaml_file("tests/sample_arrival_traces/jitter.yaml").unwrap();
+        assert_eq!(release_jitter.get_delay(0, 1, 5), 0);
+        assert_eq!(release_jitter.get_delay(1, 2, 5), 3);
+        assert_eq!(release_jitter.get_delay(1, 3, 5), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_release_jitter_trace_exceeds_jitter() {
+        let mut release_jitter = ReleaseJitter::from_trace(BTreeMap::from([(0, vec![6])]));
+        release_jitter.get_delay(0, 0, 5);
+    }
+
+    #[test]
+    fn test_validate_jitters_not_shorter_than_period() {
+        let mut dag = create_dag_with_period(10);
+        dag.add_param(NodeIndex::new(0), "jitter", 10);
+        assert!(validate_jitters(&[dag]).is_err());
+    }
+}
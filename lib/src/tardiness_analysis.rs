@@ -0,0 +1,121 @@
+//! Bounded tardiness analysis for soft real-time preemptive G-EDF.
+//! The bound of Devi & Anderson for sporadic tasks is adapted to DAG tasks.
+//! The volume of a DAG is used as the execution cost, and its critical path length replaces the execution cost of the analyzed task,
+//! because the nodes of a DAG job can run in parallel once the interference of the other DAGs is over.
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::Graph;
+
+struct DAGTaskParams {
+    volume: i64,
+    critical_path_length: i64,
+    utilization: f64,
+}
+
+impl DAGTaskParams {
+    fn new(dag: &Graph<NodeData, i64>) -> Self {
+        let volume = dag.get_volume();
+        let critical_path = dag.get_critical_path();
+        Self {
+            volume,
+            critical_path_length: dag.get_total_wcet_from_nodes(&critical_path),
+            utilization: volume as f64 / dag.get_head_period().unwrap() as f64,
+        }
+    }
+}
+
+fn sum_of_largest<T: Copy + PartialOrd + std::iter::Sum>(mut values: Vec<T>, count: usize) -> T {
+    values.sort_by(|a, b| b.partial_cmp(a).unwrap());
+    values.into_iter().take(count).sum()
+}
+
+/// Calculate the tardiness bound of each DAG relative to its end-to-end deadline on `number_of_cores` cores.
+/// Returns None if the total utilization exceeds the number of cores, in which case the tardiness is unbounded.
+///
+/// For m cores, the bound of DAG k is x + L_k, where
+/// x = (E_L - C_min) / (m - U_L),
+/// E_L is the sum of the m - 1 largest volumes, U_L is the sum of the m - 2 largest utilizations,
+/// C_min is the smallest volume, and L_k is the critical path length of DAG k.
+pub fn calculate_tardiness_bounds(
+    dag_set: &[Graph<NodeData, i64>],
+    number_of_cores: usize,
+) -> Option<Vec<f64>> {
+    let dag_task_params: Vec<DAGTaskParams> = dag_set.iter().map(DAGTaskParams::new).collect();
+    let total_utilization: f64 = dag_task_params
+        .iter()
+        .map(|params| params.utilization)
+        .sum();
+    if dag_task_params.is_empty() || total_utilization > number_of_cores as f64 {
+        return None;
+    }
+
+    let volumes: Vec<i64> = dag_task_params.iter().map(|params| params.volume).collect();
+    let utilizations: Vec<f64> = dag_task_params
+        .iter()
+        .map(|params| params.utilization)
+        .collect();
+    let min_volume = *volumes.iter().min().unwrap();
+    let largest_volumes_sum = sum_of_largest(volumes, number_of_cores.saturating_sub(1));
+    let largest_utilizations_sum = sum_of_largest(utilizations, number_of_cores.saturating_sub(2));
+
+    let interference = (largest_volumes_sum - min_volume).max(0) as f64
+        / (number_of_cores as f64 - largest_utilizations_sum);
+    Some(
+        dag_task_params
+            .iter()
+            .map(|params| interference + params.critical_path_length as f64)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    fn create_dag(execution_times: &[i64], period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let source = dag.add_node(create_node(0, "execution_time", execution_times[0]));
+        dag.add_param(source, "period", period);
+        dag.add_param(source, "end_to_end_deadline", period);
+        for (id, execution_time) in execution_times.iter().enumerate().skip(1) {
+            let node = dag.add_node(create_node(id as i64, "execution_time", *execution_time));
+            dag.add_edge(source, node, 0);
+        }
+        dag
+    }
+
+    #[test]
+    fn test_calculate_tardiness_bounds_normal() {
+        // Volume 70 and critical path length 30, volume 60 and critical path length 40.
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 150),
+            create_dag(&[20, 20, 20], 100),
+        ];
+
+        // x = (70 - 60) / (2 - 0)
+        assert_eq!(
+            calculate_tardiness_bounds(&dag_set, 2),
+            Some(vec![35.0, 45.0])
+        );
+
+        // x = (130 - 60) / (4 - 1.0666667)
+        let bounds = calculate_tardiness_bounds(&dag_set, 4).unwrap();
+        assert!((bounds[0] - 53.863636).abs() < 1e-4);
+        assert!((bounds[1] - 63.863636).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_calculate_tardiness_bounds_over_utilization() {
+        let dag_set = vec![
+            create_dag(&[10, 20, 20, 20], 150),
+            create_dag(&[20, 20, 20], 100),
+        ];
+        assert_eq!(calculate_tardiness_bounds(&dag_set, 1), None);
+    }
+}
@@ -0,0 +1,630 @@
+//! Transformations of a DAG reused by the schedulers, currently the decomposition of the TPDS 2014 paper.
+//! The decomposition divides the DAG into the segments between the earliest finish times of its nodes,
+//! gives each segment a share of the period chosen by a SegmentationStrategy,
+//! and gives each node the deadlines of the segments it spans after the deadlines of its predecessors.
+//! The results are the node params scaled by DEADLINE_FACTOR, both relative to the release of the job:
+//! `int_scaled_node_offset`, the release of the node, and `int_scaled_node_relative_deadline`, the deadline of the node.
+//! The global EDF scheduler uses the latter instead of the end-to-end deadline, and so can a partitioned one.
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::{graph::Graph, visit::Topo};
+
+/// The scale of the node deadlines and offsets to integers.
+pub const DEADLINE_FACTOR: f32 = 100000.0;
+
+pub enum SegmentClassification {
+    Heavy,
+    Light,
+}
+
+pub enum DAGClassification {
+    Heavy,
+    Light,
+    Mixture,
+}
+
+pub struct Segment {
+    pub nodes: Vec<NodeData>,
+    pub begin_range: i64,
+    pub end_range: i64,
+    pub deadline: f32,
+    pub classification: Option<SegmentClassification>,
+    pub execution_requirement: i64, // end_range - begin_range
+    pub parallel_degree: i64,       // number of nodes in the segment
+    pub volume: i64,                // execution_requirement * nodes.len()
+}
+
+pub fn create_segments(dag: &mut Graph<NodeData, i64>) -> Vec<Segment> {
+    dag.calculate_earliest_finish_times();
+
+    let mut earliest_finish_times = Vec::new();
+    for node in dag.node_weights_mut() {
+        earliest_finish_times.push(node.params["earliest_finish_time"]);
+    }
+
+    earliest_finish_times.dedup();
+    earliest_finish_times.sort();
+
+    let mut segments: Vec<Segment> = Vec::with_capacity(earliest_finish_times.len());
+    for i in 0..earliest_finish_times.len() {
+        let begin_range = if i == 0 {
+            0
+        } else {
+            earliest_finish_times[i - 1]
+        };
+        let end_range = earliest_finish_times[i];
+
+        let segment = Segment {
+            nodes: Vec::new(),
+            begin_range,
+            end_range,
+            deadline: 0.0,
+            classification: None,
+            execution_requirement: end_range - begin_range,
+            parallel_degree: 0,
+            volume: 0,
+        };
+
+        segments.push(segment);
+    }
+
+    for node in dag.node_weights() {
+        for segment in &mut segments {
+            if node.params["earliest_start_time"] <= segment.begin_range
+                && segment.end_range <= node.params["earliest_finish_time"]
+            {
+                segment.nodes.push(node.clone());
+                segment.parallel_degree += 1;
+                segment.volume += segment.execution_requirement;
+            }
+        }
+    }
+
+    segments
+}
+
+fn classify_segment(volume: f32, period: f32, crit_path_len: f32, segment: &mut Segment) {
+    assert!(!segment.nodes.is_empty());
+
+    segment.classification =
+        if segment.parallel_degree as f32 > volume / ((2.0 * period) - crit_path_len) {
+            Some(SegmentClassification::Heavy)
+        } else {
+            Some(SegmentClassification::Light)
+        };
+}
+
+fn classify_dag(
+    volume: f32,
+    period: f32,
+    crit_path_len: f32,
+    segments: &mut [Segment],
+) -> DAGClassification {
+    for segment in segments.iter_mut() {
+        classify_segment(volume, period, crit_path_len, segment);
+    }
+
+    let (mut heavy_count, mut light_count) = (0, 0);
+    for segment in segments {
+        match segment.classification {
+            Some(SegmentClassification::Heavy) => heavy_count += 1,
+            Some(SegmentClassification::Light) => light_count += 1,
+            _ => unreachable!("Segment classification error"),
+        }
+    }
+
+    match (heavy_count > 0, light_count > 0) {
+        (true, true) => DAGClassification::Mixture,
+        (true, false) => DAGClassification::Heavy,
+        (false, true) => DAGClassification::Light,
+        _ => unreachable!("Segments classification error"),
+    }
+}
+
+pub fn calculate_segments_deadline(dag: &mut Graph<NodeData, i64>, segments: &mut [Segment]) {
+    let volume = dag.get_volume() as f32;
+    let period = dag.get_head_period().unwrap() as f32;
+    let crit_path = dag.get_critical_path();
+    let crit_path_len = dag.get_total_wcet_from_nodes(&crit_path) as f32;
+
+    let classification = classify_dag(volume, period, crit_path_len, segments);
+    match classification {
+        DAGClassification::Heavy => {
+            for segment in segments {
+                segment.deadline = (period / volume) * segment.volume as f32;
+            }
+        }
+        DAGClassification::Light => {
+            for segment in segments {
+                segment.deadline = (period / crit_path_len) * segment.execution_requirement as f32;
+            }
+        }
+        DAGClassification::Mixture => {
+            let (mut heavy_segment_volume, mut light_segment_length) = (0, 0);
+            for segment in segments.iter() {
+                match segment.classification {
+                    Some(SegmentClassification::Heavy) => {
+                        heavy_segment_volume += segment.volume;
+                    }
+                    Some(SegmentClassification::Light) => {
+                        light_segment_length += segment.execution_requirement;
+                    }
+                    _ => unreachable!("Segment classification error"),
+                }
+            }
+
+            let light_segment_period = crit_path_len / 2.0;
+            for segment in segments {
+                match segment.classification {
+                    Some(SegmentClassification::Heavy) => {
+                        segment.deadline = (period - light_segment_period)
+                            / heavy_segment_volume as f32
+                            * segment.volume as f32;
+                    }
+                    Some(SegmentClassification::Light) => {
+                        segment.deadline = light_segment_period / light_segment_length as f32
+                            * segment.execution_requirement as f32;
+                    }
+                    _ => unreachable!("Segment classification error"),
+                }
+            }
+        }
+    }
+}
+
+/// How the period of a DAG is divided into the deadlines of its segments.
+pub trait SegmentationStrategy {
+    /// The name selecting the strategy on the command line, which is also recorded in the log.
+    fn get_name(&self) -> &'static str;
+    fn calculate_segments_deadline(&self, dag: &mut Graph<NodeData, i64>, segments: &mut [Segment]);
+}
+
+/// The segmentation of the TPDS 2014 paper by the classification of the segments into heavy and light ones.
+pub struct BasicSegmentation;
+
+impl SegmentationStrategy for BasicSegmentation {
+    fn get_name(&self) -> &'static str {
+        "basic"
+    }
+
+    fn calculate_segments_deadline(
+        &self,
+        dag: &mut Graph<NodeData, i64>,
+        segments: &mut [Segment],
+    ) {
+        calculate_segments_deadline(dag, segments);
+    }
+}
+
+/// Every segment has the same deadline regardless of its length.
+pub struct EvenSegmentation;
+
+impl SegmentationStrategy for EvenSegmentation {
+    fn get_name(&self) -> &'static str {
+        "even"
+    }
+
+    fn calculate_segments_deadline(
+        &self,
+        dag: &mut Graph<NodeData, i64>,
+        segments: &mut [Segment],
+    ) {
+        let period = dag.get_head_period().unwrap() as f32;
+        let number_of_segments = segments.len() as f32;
+        for segment in segments {
+            segment.deadline = period / number_of_segments;
+        }
+    }
+}
+
+/// Each segment has its length and the slack of the period over the critical path in proportion to its volume.
+pub struct ProportionalToSlackSegmentation;
+
+impl SegmentationStrategy for ProportionalToSlackSegmentation {
+    fn get_name(&self) -> &'static str {
+        "proportional_to_slack"
+    }
+
+    fn calculate_segments_deadline(
+        &self,
+        dag: &mut Graph<NodeData, i64>,
+        segments: &mut [Segment],
+    ) {
+        distribute_slack(dag, segments, |segment| segment.volume);
+    }
+}
+
+/// Each segment has its length and the slack of the period over the critical path in proportion to
+/// the volume running in parallel with the critical path, which is the volume other than the critical node.
+pub struct CriticalPathAwareSegmentation;
+
+impl SegmentationStrategy for CriticalPathAwareSegmentation {
+    fn get_name(&self) -> &'static str {
+        "critical_path_aware"
+    }
+
+    fn calculate_segments_deadline(
+        &self,
+        dag: &mut Graph<NodeData, i64>,
+        segments: &mut [Segment],
+    ) {
+        distribute_slack(dag, segments, |segment| {
+            segment.volume - segment.execution_requirement
+        });
+    }
+}
+
+pub fn create_segmentation_strategy(name: &str) -> Box<dyn SegmentationStrategy> {
+    match name {
+        "basic" => Box::new(BasicSegmentation),
+        "even" => Box::new(EvenSegmentation),
+        "proportional_to_slack" => Box::new(ProportionalToSlackSegmentation),
+        "critical_path_aware" => Box::new(CriticalPathAwareSegmentation),
+        _ => panic!("Unknown segmentation strategy: {}", name),
+    }
+}
+
+/// The segments span the critical path, so the slack is the period minus the total length of the segments.
+/// The slack is distributed by the lengths of the segments if all the weights are zero, as in a chain.
+fn distribute_slack(
+    dag: &Graph<NodeData, i64>,
+    segments: &mut [Segment],
+    get_weight: impl Fn(&Segment) -> i64,
+) {
+    let period = dag.get_head_period().unwrap() as f32;
+    let total_length: i64 = segments
+        .iter()
+        .map(|segment| segment.execution_requirement)
+        .sum();
+    let slack = period - total_length as f32;
+    let mut weights: Vec<i64> = segments.iter().map(&get_weight).collect();
+    if weights.iter().all(|weight| *weight == 0) {
+        weights = segments
+            .iter()
+            .map(|segment| segment.execution_requirement)
+            .collect();
+    }
+    let total_weight: i64 = weights.iter().sum();
+    for (segment, weight) in segments.iter_mut().zip(weights) {
+        segment.deadline =
+            segment.execution_requirement as f32 + slack * weight as f32 / total_weight as f32;
+    }
+}
+
+/// Decompose the DAG with the basic segmentation of the TPDS 2014 paper.
+pub fn decompose(dag: &mut Graph<NodeData, i64>) {
+    decompose_with_strategy(dag, &BasicSegmentation);
+}
+
+/// Decompose the DAG with the deadlines of the segments given by the strategy instead of the basic one.
+pub fn decompose_with_strategy(
+    dag: &mut Graph<NodeData, i64>,
+    segmentation_strategy: &dyn SegmentationStrategy,
+) {
+    let mut segments = create_segments(dag);
+    segmentation_strategy.calculate_segments_deadline(dag, &mut segments);
+
+    // The fifth decimal place is truncated.
+    let mut int_scaled_deadline = vec![0; dag.node_count()];
+    for segment in segments.iter() {
+        segment.nodes.iter().for_each(|node| {
+            int_scaled_deadline[node.id as usize] += (segment.deadline * DEADLINE_FACTOR) as i64;
+        });
+    }
+    let int_scaled_offset = calc_int_scaled_offsets(dag, &int_scaled_deadline);
+
+    for node_i in dag.node_indices() {
+        dag.add_param(
+            node_i,
+            "int_scaled_node_offset",
+            int_scaled_offset[node_i.index()],
+        );
+        dag.add_param(
+            node_i,
+            "int_scaled_node_relative_deadline",
+            int_scaled_deadline[node_i.index()] + int_scaled_offset[node_i.index()],
+        );
+    }
+}
+
+fn calc_int_scaled_offsets(dag: &Graph<NodeData, i64>, deadlines: &[i64]) -> Vec<i64> {
+    let mut int_scaled_offsets = vec![0; dag.node_count()];
+
+    // Sort because offsets need to be calculated in the order of execution.
+    let mut topo_order = Topo::new(dag);
+    while let Some(node_i) = topo_order.next(dag) {
+        if let Some(pre_nodes) = dag.get_pre_nodes(node_i) {
+            // offset = maximum of offset + deadline of predecessor nodes.
+            let max_offset = pre_nodes
+                .iter()
+                .map(|pre_node_i| {
+                    let pre_idx = pre_node_i.index();
+                    int_scaled_offsets[pre_idx] + deadlines[pre_idx]
+                })
+                .max()
+                .unwrap_or(0);
+            int_scaled_offsets[node_i.index()] = max_offset;
+        }
+    }
+
+    int_scaled_offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "file_io")]
+    use crate::{
+        dag_creator::create_dag_set_from_dir,
+        dag_set_scheduler::{DAGSetSchedulerBase, PreemptiveType},
+        global_edf_scheduler::GlobalEDFScheduler,
+        golden::assert_matches_golden,
+        homogeneous::HomogeneousProcessor,
+        processor::ProcessorBase,
+        util::adjust_to_implicit_deadline,
+    };
+    use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::fs::remove_file;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+    fn create_sample_dag(period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 55));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_param(n0, "period", period);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+
+        dag
+    }
+
+    fn create_duplicates_dag(period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 4));
+        let n1 = dag.add_node(create_node(1, "execution_time", 7));
+        let n2 = dag.add_node(create_node(2, "execution_time", 7));
+        let n3 = dag.add_node(create_node(3, "execution_time", 36));
+        let n4 = dag.add_node(create_node(4, "execution_time", 54));
+        dag.add_param(n0, "period", period);
+        dag.add_edge(n0, n1, 1);
+        dag.add_edge(n0, n2, 1);
+        dag.add_edge(n1, n3, 1);
+        dag.add_edge(n2, n4, 1);
+
+        dag
+    }
+
+    #[test]
+    fn test_decompose_normal_float() {
+        let mut dag = create_sample_dag(120);
+        decompose(&mut dag);
+
+        let expect_relative_deadline = [322857, 1356578, 7641428, 6672857, 11999999];
+        for node_i in dag.node_indices() {
+            assert_eq!(
+                dag[node_i].params["int_scaled_node_relative_deadline"],
+                expect_relative_deadline[node_i.index()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_decompose_offsets() {
+        let mut dag = create_sample_dag(120);
+        decompose_with_strategy(&mut dag, &EvenSegmentation);
+
+        // A node is released when all its predecessors reach their deadlines.
+        let expect_offset = [0, 2400000, 2400000, 4800000, 9600000];
+        for node_i in dag.node_indices() {
+            assert_eq!(
+                dag[node_i].params["int_scaled_node_offset"],
+                expect_offset[node_i.index()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_decompose_with_strategy_even() {
+        let mut dag = create_sample_dag(120);
+        decompose_with_strategy(&mut dag, &EvenSegmentation);
+
+        // Each segment has the deadline of 24, and the nodes span 1, 1, 3, 1 and 1 segments.
+        let expect_relative_deadline = [2400000, 4800000, 9600000, 7200000, 12000000];
+        for node_i in dag.node_indices() {
+            assert_eq!(
+                dag[node_i].params["int_scaled_node_relative_deadline"],
+                expect_relative_deadline[node_i.index()]
+            );
+        }
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_decompose_golden_log() {
+        let mut dag_set = create_dag_set_from_dir("../lib/tests/golden_logs/dag_set").unwrap();
+        adjust_to_implicit_deadline(&mut dag_set);
+        for dag in dag_set.iter_mut() {
+            decompose(dag);
+        }
+        let mut gedf_scheduler = GlobalEDFScheduler::new(&dag_set, &HomogeneousProcessor::new(4));
+        gedf_scheduler
+            .schedule(PreemptiveType::Preemptive {
+                key: "int_scaled_node_relative_deadline".to_string(),
+            })
+            .unwrap();
+        let file_path = gedf_scheduler.dump_log("../lib/tests", "golden_decomp_gedf_preemptive");
+
+        assert_matches_golden(
+            &file_path,
+            "../lib/tests/golden_logs/golden_decomp_gedf_preemptive.yaml",
+        );
+        remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_create_segment_normal() {
+        let mut dag = create_sample_dag(120);
+        let segments = create_segments(&mut dag);
+
+        assert_eq!(segments.len(), 5);
+
+        assert_eq!(segments[0].nodes.len(), 1);
+        assert_eq!(segments[1].nodes.len(), 2);
+        assert_eq!(segments[2].nodes.len(), 2);
+        assert_eq!(segments[3].nodes.len(), 1);
+        assert_eq!(segments[4].nodes.len(), 1);
+
+        assert_eq!(segments[0].begin_range, 0);
+        assert_eq!(segments[0].end_range, 4);
+        assert_eq!(segments[1].begin_range, 4);
+        assert_eq!(segments[1].end_range, 11);
+        assert_eq!(segments[2].begin_range, 11);
+        assert_eq!(segments[2].end_range, 47);
+        assert_eq!(segments[3].begin_range, 47);
+        assert_eq!(segments[3].end_range, 59);
+        assert_eq!(segments[4].begin_range, 59);
+        assert_eq!(segments[4].end_range, 113);
+    }
+
+    #[test]
+    fn test_create_segment_duplicates() {
+        let mut dag = create_duplicates_dag(120);
+        let segments = create_segments(&mut dag);
+
+        assert_eq!(segments.len(), 4);
+
+        assert_eq!(segments[0].nodes.len(), 1);
+        assert_eq!(segments[1].nodes.len(), 2);
+        assert_eq!(segments[2].nodes.len(), 2);
+        assert_eq!(segments[3].nodes.len(), 1);
+
+        assert_eq!(segments[0].begin_range, 0);
+        assert_eq!(segments[0].end_range, 4);
+        assert_eq!(segments[1].begin_range, 4);
+        assert_eq!(segments[1].end_range, 11);
+        assert_eq!(segments[2].begin_range, 11);
+        assert_eq!(segments[2].end_range, 47);
+        assert_eq!(segments[3].begin_range, 47);
+        assert_eq!(segments[3].end_range, 65);
+    }
+
+    #[test]
+    fn test_calculate_segments_deadline_normal_heavy() {
+        let mut dag = create_sample_dag(150);
+        let mut segments = create_segments(&mut dag);
+        calculate_segments_deadline(&mut dag, &mut segments);
+
+        assert_eq!(segments[0].deadline, 3.8461537);
+        assert_eq!(segments[1].deadline, 13.461538);
+        assert_eq!(segments[2].deadline, 69.23077);
+        assert_eq!(segments[3].deadline, 11.538462);
+        assert_eq!(segments[4].deadline, 51.923077);
+    }
+
+    #[test]
+    fn test_calculate_segments_deadline_normal_light() {
+        let mut dag = create_sample_dag(65);
+        let mut segments = create_segments(&mut dag);
+        calculate_segments_deadline(&mut dag, &mut segments);
+
+        assert_eq!(segments[0].deadline, 2.300885);
+        assert_eq!(segments[1].deadline, 4.026549);
+        assert_eq!(segments[2].deadline, 20.707964);
+        assert_eq!(segments[3].deadline, 6.9026546);
+        assert_eq!(segments[4].deadline, 31.061947);
+    }
+
+    #[test]
+    fn test_calculate_segments_deadline_normal_mixture() {
+        let mut dag = create_sample_dag(120);
+        let mut segments = create_segments(&mut dag);
+        calculate_segments_deadline(&mut dag, &mut segments);
+
+        assert_eq!(segments[0].deadline, 3.2285714);
+        assert_eq!(segments[1].deadline, 10.33721);
+        assert_eq!(segments[2].deadline, 53.16279);
+        assert_eq!(segments[3].deadline, 9.685715);
+        assert_eq!(segments[4].deadline, 43.585712);
+    }
+
+    #[test]
+    fn test_even_segmentation_normal() {
+        let mut dag = create_sample_dag(120);
+        let mut segments = create_segments(&mut dag);
+        EvenSegmentation.calculate_segments_deadline(&mut dag, &mut segments);
+
+        for segment in segments.iter() {
+            assert_eq!(segment.deadline, 24.0);
+        }
+    }
+
+    #[test]
+    fn test_proportional_to_slack_segmentation_normal() {
+        let mut dag = create_sample_dag(120);
+        let mut segments = create_segments(&mut dag);
+        ProportionalToSlackSegmentation.calculate_segments_deadline(&mut dag, &mut segments);
+
+        // The slack of 120 - 113 is distributed by the volumes 4, 14, 72, 12 and 54.
+        assert_eq!(segments[0].deadline, 4.179487);
+        assert_eq!(segments[1].deadline, 7.6282053);
+        assert_eq!(segments[2].deadline, 39.23077);
+        assert_eq!(segments[3].deadline, 12.538462);
+        assert_eq!(segments[4].deadline, 56.423077);
+    }
+
+    #[test]
+    fn test_critical_path_aware_segmentation_normal() {
+        let mut dag = create_sample_dag(120);
+        let mut segments = create_segments(&mut dag);
+        CriticalPathAwareSegmentation.calculate_segments_deadline(&mut dag, &mut segments);
+
+        // Only the second and the third segments run nodes in parallel with the critical path.
+        assert_eq!(segments[0].deadline, 4.0);
+        assert_eq!(segments[1].deadline, 8.139535);
+        assert_eq!(segments[2].deadline, 41.860466);
+        assert_eq!(segments[3].deadline, 12.0);
+        assert_eq!(segments[4].deadline, 54.0);
+    }
+
+    #[test]
+    fn test_critical_path_aware_segmentation_chain() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        let n1 = dag.add_node(create_node(1, "execution_time", 30));
+        dag.add_param(n0, "period", 80);
+        dag.add_edge(n0, n1, 1);
+        let mut segments = create_segments(&mut dag);
+        CriticalPathAwareSegmentation.calculate_segments_deadline(&mut dag, &mut segments);
+
+        // No node runs in parallel, so the slack is distributed by the lengths.
+        assert_eq!(segments[0].deadline, 20.0);
+        assert_eq!(segments[1].deadline, 60.0);
+    }
+
+    #[test]
+    fn test_create_segmentation_strategy_normal() {
+        for name in [
+            "basic",
+            "even",
+            "proportional_to_slack",
+            "critical_path_aware",
+        ] {
+            assert_eq!(create_segmentation_strategy(name).get_name(), name);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown segmentation strategy: unknown")]
+    fn test_create_segmentation_strategy_unknown() {
+        create_segmentation_strategy("unknown");
+    }
+}
@@ -1,36 +1,57 @@
+use crate::error::{Result, SchedSimError};
 use crate::{
     core::ProcessResult,
+    diagnostics::{report, DiagnosticKind},
     graph_extension::{GraphExtension, NodeData},
+    multi_rate::is_unrolled_multi_rate_dag,
 };
-use chrono::{DateTime, Utc};
-use log::{info, warn};
-use num_integer::lcm;
+use num_integer::gcd;
 use petgraph::graph::Graph;
-use std::{
-    fs::{self, OpenOptions},
-    io::Write,
+#[cfg(feature = "file_io")]
+use {
+    chrono::{DateTime, Utc},
+    log::{info, warn},
+    serde::de::DeserializeOwned,
+    std::{
+        fs::{self, OpenOptions},
+        io::{self, ErrorKind, Read, Write},
+    },
+    yaml_rust::YamlLoader,
 };
-use yaml_rust::YamlLoader;
 
-pub fn get_hyper_period(dag_set: &[Graph<NodeData, i32>]) -> i32 {
-    let mut hyper_period = 1;
-    for dag in dag_set {
-        let dag_period = dag.get_head_period().unwrap();
-        hyper_period = lcm(hyper_period, dag_period);
+/// Returns an error if a DAG has no period or the hyper period overflows i64, instead of silently wrapping around.
+pub fn get_hyper_period(dag_set: &[Graph<NodeData, i64>]) -> Result<i64> {
+    let mut hyper_period: i64 = 1;
+    for (dag_id, dag) in dag_set.iter().enumerate() {
+        let dag_period = dag
+            .get_head_period()
+            .ok_or_else(|| SchedSimError::Validation(format!("DAG {} has no period.", dag_id)))?;
+        hyper_period = (hyper_period / gcd(hyper_period, dag_period))
+            .checked_mul(dag_period)
+            .ok_or_else(|| {
+                SchedSimError::Validation(format!(
+                    "The hyper period overflows. hyper period: {}, period: {}",
+                    hyper_period, dag_period
+                ))
+            })?;
     }
-    hyper_period
+    Ok(hyper_period)
 }
 
-pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i32>]) {
-    for dag in dag_set.iter_mut() {
+/// The deadlines of an unrolled multi-rate DAG are of its instances, which are already implicit if not given.
+pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i64>]) {
+    for dag in dag_set
+        .iter_mut()
+        .filter(|dag| !is_unrolled_multi_rate_dag(dag))
+    {
         let period = dag.get_head_period();
         let end_to_end_deadline = dag.get_end_to_end_deadline();
         match (period, end_to_end_deadline) {
             (Some(period_value), Some(_)) => {
                 if end_to_end_deadline != period {
-                    warn!("In this algorithm, the period and the end-to-end deadline must be equal. Therefore, the end-to-end deadline is overridden by the period.");
+                    report(DiagnosticKind::DeadlineOverridden, "In this algorithm, the period and the end-to-end deadline must be equal. Therefore, the end-to-end deadline is overridden by the period.");
                     dag.get_sink_nodes().iter().for_each(|&sink_i| {
-                        if dag[sink_i].params.get("end_to_end_deadline").is_some() {
+                        if dag[sink_i].params.contains_key("end_to_end_deadline") {
                             dag.update_param(sink_i, "end_to_end_deadline", period_value);
                         }
                     });
@@ -49,21 +70,174 @@ pub fn adjust_to_implicit_deadline(dag_set: &mut [Graph<NodeData, i32>]) {
     }
 }
 
-pub fn load_yaml(file_path: &str) -> Vec<yaml_rust::Yaml> {
+/// Impose the constrained deadline `ratio_deadline_to_period` times the period on the DAGs without an end-to-end deadline,
+/// and keep the given deadlines. A DAG without a period is released at its end-to-end deadline.
+pub fn adjust_to_constrained_deadline(
+    dag_set: &mut [Graph<NodeData, i64>],
+    ratio_deadline_to_period: f32,
+) {
+    for dag in dag_set
+        .iter_mut()
+        .filter(|dag| !is_unrolled_multi_rate_dag(dag))
+    {
+        match (dag.get_head_period(), dag.get_end_to_end_deadline()) {
+            (Some(_), Some(_)) => {}
+            (None, Some(deadline_value)) => {
+                dag.add_param(dag.get_source_nodes()[0], "period", deadline_value);
+            }
+            (Some(period_value), None) => {
+                dag.add_param(
+                    dag.get_sink_nodes()[0],
+                    "end_to_end_deadline",
+                    (period_value as f32 * ratio_deadline_to_period) as i64,
+                );
+            }
+            (None, None) => {
+                panic!("Either an period or end-to-end deadline is required for the schedule.");
+            }
+        }
+    }
+}
+
+/// Write the rank of each DAG by `get_key` as its `dag_priority` DAG param, where the smaller value has the higher priority.
+/// The DAGs of the same key are ranked in the order of the set.
+/// Returns an error without writing any priority if `get_key` has no key for a DAG.
+fn assign_dag_priorities_by_key(
+    dag_set: &mut [Graph<NodeData, i64>],
+    get_key: impl Fn(usize, &Graph<NodeData, i64>) -> Result<i64>,
+) -> Result<()> {
+    let keys = dag_set
+        .iter()
+        .enumerate()
+        .map(|(dag_i, dag)| get_key(dag_i, dag))
+        .collect::<Result<Vec<i64>>>()?;
+    let mut dag_indices: Vec<usize> = (0..dag_set.len()).collect();
+    dag_indices.sort_by_key(|dag_i| keys[*dag_i]);
+    for (dag_priority, dag_i) in dag_indices.into_iter().enumerate() {
+        dag_set[dag_i].set_dag_param("dag_priority", dag_priority as i64);
+    }
+    Ok(())
+}
+
+/// Deadline-monotonic priorities. A DAG without an end-to-end deadline has the implicit one,
+/// and a DAG with multiple sink deadlines is ranked by the shortest one.
+pub fn assign_dm_priorities(dag_set: &mut [Graph<NodeData, i64>]) -> Result<()> {
+    assign_dag_priorities_by_key(dag_set, |dag_i, dag| {
+        dag.get_deadlines_per_sink()
+            .into_iter()
+            .map(|(_, end_to_end_deadline)| end_to_end_deadline)
+            .min()
+            .or(dag.get_head_period())
+            .ok_or_else(|| {
+                SchedSimError::Validation(format!(
+                    "DAG {} has neither a period nor an end-to-end deadline.",
+                    dag_i
+                ))
+            })
+    })
+}
+
+/// Rate-monotonic priorities.
+pub fn assign_rm_priorities(dag_set: &mut [Graph<NodeData, i64>]) -> Result<()> {
+    assign_dag_priorities_by_key(dag_set, |dag_i, dag| {
+        dag.get_head_period()
+            .ok_or_else(|| SchedSimError::Validation(format!("DAG {} has no period.", dag_i)))
+    })
+}
+
+/// Replicate each DAG `num_copies` times to build a larger workload from a few templates.
+/// The i-th copy is released `i * phase_shift` later than the template (modulo its period).
+/// dag_ids are renumbered over the whole set.
+/// Each copy keeps `template_dag_id` and `copy_index` as DAG params to trace it back to its template.
+pub fn replicate_dag_set(
+    dag_set: &[Graph<NodeData, i64>],
+    num_copies: usize,
+    phase_shift: i64,
+) -> Vec<Graph<NodeData, i64>> {
+    let mut replicated_dag_set = Vec::with_capacity(dag_set.len() * num_copies);
+    for (template_dag_id, template_dag) in dag_set.iter().enumerate() {
+        let period = template_dag.get_head_period().unwrap();
+        let template_offset = template_dag.get_head_offset();
+        for copy_index in 0..num_copies {
+            let mut dag = template_dag.clone();
+            let offset = (template_offset + copy_index as i64 * phase_shift) % period;
+            for source_i in dag.get_source_nodes() {
+                dag[source_i].params.insert("offset".to_string(), offset);
+            }
+            dag.set_dag_param("dag_id", replicated_dag_set.len() as i64);
+            dag.set_dag_param("template_dag_id", template_dag_id as i64);
+            dag.set_dag_param("copy_index", copy_index as i64);
+            replicated_dag_set.push(dag);
+        }
+    }
+    replicated_dag_set
+}
+
+/// Multiply the execution times of all nodes by `utilization_scale` to sweep the utilization of a DAG set.
+/// bcet, acet and the execution time samples are scaled together, and every positive time is kept at least one time unit.
+pub fn scale_execution_times(dag_set: &mut [Graph<NodeData, i64>], utilization_scale: f64) {
+    if utilization_scale <= 0.0 {
+        panic!(
+            "The utilization scale must be positive: {}",
+            utilization_scale
+        );
+    }
+    for dag in dag_set.iter_mut() {
+        for node in dag.node_weights_mut() {
+            for (key, value) in node.params.iter_mut() {
+                if *value > 0
+                    && (key == "execution_time"
+                        || key == "bcet"
+                        || key == "acet"
+                        || key.starts_with("execution_time_sample_"))
+                {
+                    *value = ((*value as f64 * utilization_scale).round() as i64).max(1);
+                }
+            }
+        }
+    }
+}
+
+/// The file path that reads the YAML from the standard input, e.g., `-f -` to pipe a DAG from a generator.
+#[cfg(feature = "file_io")]
+pub const STDIN_FILE_PATH: &str = "-";
+
+#[cfg(feature = "file_io")]
+pub fn load_yaml(file_path: &str) -> Result<Vec<yaml_rust::Yaml>> {
+    if file_path == STDIN_FILE_PATH {
+        return load_yaml_from_reader(file_path, io::stdin().lock());
+    }
+    if !file_path.ends_with(".yaml") && !file_path.ends_with(".yml") {
+        return Err(SchedSimError::parse(file_path, "Invalid file type"));
+    }
+    let file = fs::File::open(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+    load_yaml_from_reader(file_path, file)
+}
+
+/// Load a struct from a YAML file, e.g., an event script or a dumped log.
+#[cfg(feature = "file_io")]
+pub fn load_yaml_struct<T: DeserializeOwned>(file_path: &str) -> Result<T> {
     if !file_path.ends_with(".yaml") && !file_path.ends_with(".yml") {
-        panic!("Invalid file type: {}", file_path);
+        return Err(SchedSimError::parse(file_path, "Invalid file type"));
     }
-    let file_content = fs::read_to_string(file_path).unwrap();
-    YamlLoader::load_from_str(&file_content).unwrap()
+    let file_content =
+        fs::read_to_string(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+    serde_yaml::from_str(&file_content).map_err(|err| SchedSimError::parse(file_path, err))
 }
 
+/// Load the YAML documents read from `reader`. `name` is the name of the input in the errors, as the path of a file.
+#[cfg(feature = "file_io")]
+pub fn load_yaml_from_reader(name: &str, mut reader: impl Read) -> Result<Vec<yaml_rust::Yaml>> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|err| SchedSimError::io(name, err))?;
+    YamlLoader::load_from_str(&content).map_err(|err| SchedSimError::parse(name, err))
+}
+
+#[cfg(feature = "file_io")]
 pub fn append_info_to_yaml(file_path: &str, info: &str) {
-    if let Ok(mut file) = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .create(true)
-        .open(file_path)
-    {
+    if let Ok(mut file) = OpenOptions::new().append(true).create(true).open(file_path) {
         if let Err(err) = file.write_all(info.as_bytes()) {
             eprintln!("Failed to write to file: {}", err);
         }
@@ -72,6 +246,7 @@ pub fn append_info_to_yaml(file_path: &str, info: &str) {
     }
 }
 
+#[cfg(feature = "file_io")]
 pub fn create_yaml(folder_path: &str, file_name: &str) -> String {
     if fs::metadata(folder_path).is_err() {
         let _ = fs::create_dir_all(folder_path);
@@ -84,11 +259,66 @@ pub fn create_yaml(folder_path: &str, file_name: &str) -> String {
     file_path
 }
 
+/// The log file is named after its creation time. Runs in parallel may start in the same millisecond,
+/// so `_1`, `_2`, ... is appended to the time until the file is newly created rather than overwritten.
+#[cfg(feature = "file_io")]
 pub fn create_scheduler_log_yaml(dir_path: &str, alg_name: &str) -> String {
     let now: DateTime<Utc> = Utc::now();
     let date = now.format("%Y-%m-%d-%H-%M-%S-%3f").to_string();
-    let file_name = format!("{}-{}-log", date, alg_name);
-    create_yaml(dir_path, &file_name)
+    if fs::metadata(dir_path).is_err() {
+        let _ = fs::create_dir_all(dir_path);
+        info!("Created folder: {}", dir_path);
+    }
+    let mut suffix_index = 0;
+    loop {
+        let file_name = if suffix_index == 0 {
+            format!("{}-{}-log", date, alg_name)
+        } else {
+            format!("{}_{}-{}-log", date, suffix_index, alg_name)
+        };
+        let file_path = format!("{}/{}.yaml", dir_path, file_name);
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&file_path)
+        {
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => suffix_index += 1,
+            Err(err) => {
+                warn!("Failed to create file: {}", err);
+                return file_path;
+            }
+            Ok(_) => return file_path,
+        }
+    }
+}
+
+/// Write the YAML file to a JSON file of the same name, and return the path of the JSON file.
+/// The sections appended to the YAML file by the binaries are included.
+#[cfg(feature = "file_io")]
+pub fn convert_yaml_to_json(yaml_file_path: &str) -> String {
+    let Some(file_stem) = yaml_file_path
+        .strip_suffix(".yaml")
+        .or_else(|| yaml_file_path.strip_suffix(".yml"))
+    else {
+        panic!("Invalid file type: {}", yaml_file_path);
+    };
+    let file_content = fs::read_to_string(yaml_file_path).unwrap();
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(&file_content)
+        .unwrap_or_else(|err| panic!("Failed to parse the YAML file: {}", err));
+    let json_file_path = format!("{}.json", file_stem);
+    let json = serde_json::to_string_pretty(&yaml_value).expect("Failed to serialize.");
+    if let Err(err) = fs::write(&json_file_path, json) {
+        warn!("Failed to write to file: {}", err);
+    }
+    json_file_path
+}
+
+pub fn get_overhead_core_indices(process_result: &[ProcessResult]) -> Vec<usize> {
+    process_result
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| (*result == ProcessResult::Overhead).then_some(index))
+        .collect()
 }
 
 pub fn get_process_core_indices(process_result: &[ProcessResult]) -> Vec<usize> {
@@ -108,10 +338,13 @@ pub fn get_process_core_indices(process_result: &[ProcessResult]) -> Vec<usize>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use petgraph::graph::NodeIndex;
     use std::collections::BTreeMap;
+    #[cfg(feature = "file_io")]
+    use std::collections::BTreeSet;
 
-    fn create_dag() -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_dag() -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 4);
         dag.add_node(NodeData { id: 0, params });
@@ -119,8 +352,8 @@ mod tests {
         dag
     }
 
-    fn create_dag_with_period(period: i32) -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_dag_with_period(period: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 4);
         params.insert("period".to_owned(), period);
@@ -135,8 +368,8 @@ mod tests {
         dag
     }
 
-    fn create_dag_with_deadline(deadline: i32) -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_dag_with_deadline(deadline: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 4);
         let n0 = dag.add_node(NodeData { id: 0, params });
@@ -151,8 +384,8 @@ mod tests {
         dag
     }
 
-    fn create_dag_with_period_and_deadline(period: i32, deadline: i32) -> Graph<NodeData, i32> {
-        let mut dag = Graph::<NodeData, i32>::new();
+    fn create_dag_with_period_and_deadline(period: i64, deadline: i64) -> Graph<NodeData, i64> {
+        let mut dag = Graph::<NodeData, i64>::new();
         let mut params = BTreeMap::new();
         params.insert("execution_time".to_owned(), 4);
         params.insert("period".to_owned(), period);
@@ -176,7 +409,40 @@ mod tests {
             create_dag_with_period(30),
             create_dag_with_period(40),
         ];
-        assert_eq!(get_hyper_period(&dag_set), 120);
+        assert_eq!(get_hyper_period(&dag_set).unwrap(), 120);
+    }
+
+    #[test]
+    fn test_get_hyper_period_beyond_i32() {
+        let dag_set = vec![
+            create_dag_with_period(1_000_000_007),
+            create_dag_with_period(1_000_000_009),
+        ];
+        assert_eq!(
+            get_hyper_period(&dag_set).unwrap(),
+            1_000_000_016_000_000_063
+        );
+    }
+
+    #[test]
+    fn test_get_hyper_period_overflow() {
+        let dag_set = vec![
+            create_dag_with_period(1_000_000_007),
+            create_dag_with_period(1_000_000_009),
+            create_dag_with_period(1_000_000_021),
+        ];
+        assert!(matches!(
+            get_hyper_period(&dag_set),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_hyper_period_no_period() {
+        assert!(matches!(
+            get_hyper_period(&[create_dag()]),
+            Err(SchedSimError::Validation(_))
+        ));
     }
 
     #[test]
@@ -218,9 +484,126 @@ mod tests {
         adjust_to_implicit_deadline(&mut dag_set);
     }
 
+    #[test]
+    fn test_adjust_to_constrained_deadline_normal() {
+        let mut dag_set = vec![
+            create_dag_with_period(20),
+            create_dag_with_period_and_deadline(20, 10),
+            create_dag_with_deadline(30),
+        ];
+        adjust_to_constrained_deadline(&mut dag_set, 0.5);
+        assert_eq!(
+            dag_set
+                .iter()
+                .map(|dag| (
+                    dag.get_head_period().unwrap(),
+                    dag.get_end_to_end_deadline().unwrap()
+                ))
+                .collect::<Vec<_>>(),
+            vec![(20, 10), (20, 10), (30, 30)]
+        );
+    }
+
+    fn get_dag_priorities(dag_set: &[Graph<NodeData, i64>]) -> Vec<i64> {
+        dag_set
+            .iter()
+            .map(|dag| dag.get_dag_param("dag_priority"))
+            .collect()
+    }
+
+    #[test]
+    fn test_assign_dm_priorities_normal() {
+        let mut dag_set = vec![
+            create_dag_with_period(20),
+            create_dag_with_period_and_deadline(30, 10),
+            create_dag_with_deadline(20),
+        ];
+        assign_dm_priorities(&mut dag_set).unwrap();
+        // The implicit deadline of the first DAG ties with the third one, so the first one comes first.
+        assert_eq!(get_dag_priorities(&dag_set), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_assign_rm_priorities_normal() {
+        let mut dag_set = vec![
+            create_dag_with_period(20),
+            create_dag_with_period_and_deadline(30, 10),
+            create_dag_with_period(10),
+        ];
+        assign_rm_priorities(&mut dag_set).unwrap();
+        assert_eq!(get_dag_priorities(&dag_set), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_assign_rm_priorities_no_period() {
+        let mut dag_set = vec![create_dag_with_period(10), create_dag_with_deadline(20)];
+        assert!(assign_rm_priorities(&mut dag_set).is_err());
+        assert!(!dag_set[0][NodeIndex::new(0)]
+            .params
+            .contains_key("dag_priority"));
+    }
+
+    #[test]
+    fn test_get_overhead_core_indices_normal() {
+        let process_result = vec![
+            ProcessResult::Overhead,
+            ProcessResult::Continue,
+            ProcessResult::Idle,
+            ProcessResult::Overhead,
+            ProcessResult::ObservationDelay,
+        ];
+        assert_eq!(get_overhead_core_indices(&process_result), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_replicate_dag_set_normal() {
+        let dag_set = vec![create_dag_with_period(10), create_dag_with_period(20)];
+        let replicated_dag_set = replicate_dag_set(&dag_set, 3, 4);
+
+        assert_eq!(replicated_dag_set.len(), 6);
+        let offsets: Vec<i64> = replicated_dag_set
+            .iter()
+            .map(|dag| dag.get_head_offset())
+            .collect();
+        assert_eq!(offsets, vec![0, 4, 8, 0, 4, 8]);
+        for (dag_id, dag) in replicated_dag_set.iter().enumerate() {
+            assert_eq!(dag.get_dag_param("dag_id"), dag_id as i64);
+            assert_eq!(dag.get_dag_param("template_dag_id"), dag_id as i64 / 3);
+            assert_eq!(dag.get_dag_param("copy_index"), dag_id as i64 % 3);
+        }
+        assert_eq!(get_hyper_period(&replicated_dag_set).unwrap(), 20);
+    }
+
+    #[test]
+    fn test_replicate_dag_set_wrap_around() {
+        let dag_set = vec![create_dag_with_period(10)];
+        let replicated_dag_set = replicate_dag_set(&dag_set, 3, 6);
+
+        assert_eq!(replicated_dag_set[2].get_head_offset(), 2);
+    }
+
+    #[test]
+    fn test_scale_execution_times_normal() {
+        let mut dag_set = vec![create_dag_with_period(10)];
+        dag_set[0][NodeIndex::new(1)]
+            .params
+            .insert("bcet".to_string(), 1);
+        scale_execution_times(&mut dag_set, 1.5);
+
+        assert_eq!(dag_set[0][NodeIndex::new(0)].params["execution_time"], 6);
+        assert_eq!(dag_set[0][NodeIndex::new(1)].params["execution_time"], 6);
+        assert_eq!(dag_set[0][NodeIndex::new(1)].params["bcet"], 2);
+        assert_eq!(dag_set[0].get_head_period(), Some(10));
+
+        // Kept at least one time unit
+        scale_execution_times(&mut dag_set, 0.1);
+        assert_eq!(dag_set[0][NodeIndex::new(1)].params["execution_time"], 1);
+        assert_eq!(dag_set[0][NodeIndex::new(1)].params["bcet"], 1);
+    }
+
     #[test]
     fn test_get_process_core_indices_normal() {
-        fn create_node(id: i32, key: &str, value: i32) -> NodeData {
+        fn create_node(id: i64, key: &str, value: i64) -> NodeData {
             let mut params = BTreeMap::new();
             params.insert(key.to_string(), value);
             NodeData { id, params }
@@ -233,4 +616,39 @@ mod tests {
         ];
         assert_eq!(get_process_core_indices(&process_result), vec![0, 3]);
     }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_create_scheduler_log_yaml_same_time() {
+        let dir_path = "../lib/tests/create_scheduler_log_yaml_test";
+        let file_paths: BTreeSet<String> = (0..10)
+            .map(|_| create_scheduler_log_yaml(dir_path, "same_time"))
+            .collect();
+
+        assert_eq!(file_paths.len(), 10);
+        assert_eq!(fs::read_dir(dir_path).unwrap().count(), 10);
+        assert!(file_paths
+            .iter()
+            .all(|file_path| file_path.ends_with("-same_time-log.yaml")));
+
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_convert_yaml_to_json_normal() {
+        let yaml_file_path = create_yaml("../lib/tests", "convert_yaml_to_json_test");
+        append_info_to_yaml(&yaml_file_path, "dag_set_log:\n- dag_id: 0\n");
+        append_info_to_yaml(&yaml_file_path, "result: true\n");
+        let json_file_path = convert_yaml_to_json(&yaml_file_path);
+        assert!(json_file_path.ends_with("convert_yaml_to_json_test.json"));
+
+        let json_value: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&json_file_path).unwrap()).unwrap();
+        assert_eq!(json_value["dag_set_log"][0]["dag_id"], 0);
+        assert_eq!(json_value["result"], true);
+
+        fs::remove_file(yaml_file_path).unwrap();
+        fs::remove_file(json_file_path).unwrap();
+    }
 }
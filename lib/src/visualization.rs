@@ -0,0 +1,131 @@
+//! Gantt chart rendering of simulated schedules.
+//! Each core is a row, and each execution slice of a job is a bar colored by its DAG,
+//! so that a schedule can be inspected without plotting the YAML log by hand.
+use crate::log::DAGSetSchedulerLog;
+use std::fmt::Write;
+#[cfg(feature = "file_io")]
+use {log::warn, std::fs};
+
+const CHART_WIDTH: f64 = 1200.0;
+const LABEL_WIDTH: f64 = 80.0;
+const ROW_HEIGHT: f64 = 30.0;
+const BAR_HEIGHT: f64 = 20.0;
+const AXIS_HEIGHT: f64 = 30.0;
+const NUM_TICKS: i64 = 10;
+
+/// Spread the hues by the golden angle so that neighboring DAG ids get distinct colors.
+fn get_dag_color(dag_id: usize) -> String {
+    format!("hsl({}, 65%, 55%)", (dag_id * 137) % 360)
+}
+
+/// Render the execution slices of the log as an SVG Gantt chart.
+pub fn render_gantt_svg(log: &DAGSetSchedulerLog) -> String {
+    let execution_slices = log.get_execution_slices();
+    let number_of_cores = log.get_number_of_cores();
+    let end_time = execution_slices
+        .iter()
+        .map(|slice| slice.end_time)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let time_scale = CHART_WIDTH / end_time as f64;
+    let width = LABEL_WIDTH + CHART_WIDTH;
+    let height = ROW_HEIGHT * number_of_cores as f64 + AXIS_HEIGHT;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="sans-serif" font-size="12">"#
+    )
+    .unwrap();
+    for core_id in 0..number_of_cores {
+        let y = ROW_HEIGHT * core_id as f64;
+        writeln!(
+            svg,
+            r#"<text x="4" y="{}">Core {core_id}</text>"#,
+            y + ROW_HEIGHT / 2.0 + 4.0
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r##"<line x1="{LABEL_WIDTH}" y1="{0}" x2="{width}" y2="{0}" stroke="#ccc"/>"##,
+            y + ROW_HEIGHT
+        )
+        .unwrap();
+    }
+    for slice in execution_slices.iter() {
+        let x = LABEL_WIDTH + slice.start_time as f64 * time_scale;
+        let y = ROW_HEIGHT * slice.core_id as f64 + (ROW_HEIGHT - BAR_HEIGHT) / 2.0;
+        writeln!(
+            svg,
+            r#"<rect x="{x:.2}" y="{y}" width="{:.2}" height="{BAR_HEIGHT}" fill="{}" stroke="black" stroke-width="0.5"><title>DAG {} node {} job {}: {}-{}</title></rect>"#,
+            (slice.end_time - slice.start_time) as f64 * time_scale,
+            get_dag_color(slice.dag_id),
+            slice.dag_id,
+            slice.node_id,
+            slice.job_id,
+            slice.start_time,
+            slice.end_time
+        )
+        .unwrap();
+    }
+    let axis_y = ROW_HEIGHT * number_of_cores as f64;
+    for tick_i in 0..=NUM_TICKS {
+        let time = end_time * tick_i / NUM_TICKS;
+        writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{}" text-anchor="middle">{time}</text>"#,
+            LABEL_WIDTH + time as f64 * time_scale,
+            axis_y + AXIS_HEIGHT / 2.0 + 4.0
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(feature = "file_io")]
+pub fn dump_gantt_svg(log: &DAGSetSchedulerLog, file_path: &str) {
+    if let Err(err) = fs::write(file_path, render_gantt_svg(log)) {
+        warn!("Failed to write to file: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph_extension::{GraphExtension, NodeData},
+        log::JobEventTimes,
+    };
+    use petgraph::Graph;
+    use std::collections::BTreeMap;
+
+    fn create_node(id: i64, key: &str, value: i64) -> NodeData {
+        let mut params = BTreeMap::new();
+        params.insert(key.to_string(), value);
+        NodeData { id, params }
+    }
+
+    #[test]
+    fn test_render_gantt_svg_normal() {
+        let mut dag = Graph::<NodeData, i64>::new();
+        let n0 = dag.add_node(create_node(0, "execution_time", 10));
+        dag.add_param(n0, "period", 100);
+        dag.set_dag_param("dag_id", 0);
+        let mut log = DAGSetSchedulerLog::new(&[dag.clone()], 2);
+
+        let node_data = &dag[n0];
+        log.write_job_event(node_data, 1, 0, JobEventTimes::StartTime(0));
+        log.write_job_event(node_data, 1, 0, JobEventTimes::PreemptedTime(4));
+        log.write_job_event(node_data, 0, 0, JobEventTimes::ResumeTime(6));
+        log.write_job_event(node_data, 0, 0, JobEventTimes::FinishTime(12));
+
+        let svg = render_gantt_svg(&log);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Core 1"));
+        // The preempted job is drawn as two bars.
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains("DAG 0 node 0 job 0: 6-12"));
+    }
+}
@@ -0,0 +1,131 @@
+//! JavaScript bindings for an in-browser demo of the schedulers, enabled by the `wasm` feature.
+//! The browser has no file system, so the DAGs are given as the contents of their YAML files
+//! and the results are returned as JSON strings. Build the module without the default `file_io` feature:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg ../target/wasm32-unknown-unknown/release/lib.wasm
+//! ```
+//!
+//! ```js
+//! import init, { schedule_dag_set } from "./pkg/lib.js";
+//! await init();
+//! const result = JSON.parse(schedule_dag_set("global_edf", [dagYaml0, dagYaml1], 4, undefined, 1));
+//! document.body.innerHTML = result.gantt_svg;
+//! ```
+use crate::{
+    dag_creator::create_dag_set_from_yaml_strs, dag_set_scheduler::PreemptiveType, error::Result,
+    log::DAGSetSchedulerLog, scheduler_factory::SchedulerFactory, visualization::render_gantt_svg,
+};
+use serde_derive::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[derive(Serialize)]
+struct ScheduleResult {
+    schedule_length: i64,
+    response_times: Vec<Vec<i64>>,
+    worst_response_times: Vec<i64>,
+    deadline_miss_flags: Vec<bool>,
+    log: DAGSetSchedulerLog,
+    gantt_svg: String,
+}
+
+/// The names of the schedulers of `schedule_dag_set`.
+#[wasm_bindgen]
+pub fn get_scheduler_names() -> Vec<String> {
+    SchedulerFactory::default()
+        .get_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn schedule_dag_set_to_json(
+    scheduler_name: &str,
+    dag_yamls: &[String],
+    number_of_cores: usize,
+    preemptive_key: Option<String>,
+    num_hyper_periods: u32,
+) -> Result<String> {
+    let dag_set = create_dag_set_from_yaml_strs(dag_yamls)?;
+    let preemptive_type = match preemptive_key {
+        Some(key) => PreemptiveType::Preemptive { key },
+        None => PreemptiveType::NonPreemptive,
+    };
+    let schedule_outcome = SchedulerFactory::default().simulate(
+        scheduler_name,
+        &dag_set,
+        number_of_cores,
+        preemptive_type,
+        num_hyper_periods as i64,
+    )?;
+    let schedule_result = ScheduleResult {
+        schedule_length: schedule_outcome.schedule_length,
+        response_times: schedule_outcome.response_times,
+        worst_response_times: schedule_outcome.worst_response_times,
+        deadline_miss_flags: schedule_outcome.deadline_miss_flags,
+        gantt_svg: render_gantt_svg(&schedule_outcome.log),
+        log: schedule_outcome.log,
+    };
+    Ok(serde_json::to_string(&schedule_result).expect("Failed to serialize."))
+}
+
+/// Simulate the DAG set given as the YAML strings of its DAGs, which are numbered in their order.
+/// The scheduling is preemptive by the node parameter `preemptive_key` if it is given.
+/// Returns the JSON of the response times, the log and the Gantt chart as SVG,
+/// or throws the error of an invalid DAG or an unknown scheduler.
+#[wasm_bindgen]
+pub fn schedule_dag_set(
+    scheduler_name: &str,
+    dag_yamls: Vec<String>,
+    number_of_cores: usize,
+    preemptive_key: Option<String>,
+    num_hyper_periods: u32,
+) -> std::result::Result<String, JsError> {
+    schedule_dag_set_to_json(
+        scheduler_name,
+        &dag_yamls,
+        number_of_cores,
+        preemptive_key,
+        num_hyper_periods,
+    )
+    .map_err(|err| JsError::new(&err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SchedSimError;
+
+    const DAG_YAML: &str = "nodes: [{id: 0, execution_time: 10, period: 100}, {id: 1, execution_time: 5}]\nlinks: [{source: 0, target: 1}]";
+
+    #[test]
+    fn test_schedule_dag_set_to_json_normal() {
+        let json = schedule_dag_set_to_json(
+            "global_edf",
+            &[DAG_YAML.to_string(), DAG_YAML.to_string()],
+            2,
+            None,
+            1,
+        )
+        .unwrap();
+        let schedule_result: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            schedule_result["worst_response_times"],
+            serde_json::json!([15, 15])
+        );
+        assert!(schedule_result["log"]["dag_set_log"].is_array());
+        assert!(schedule_result["gantt_svg"]
+            .as_str()
+            .unwrap()
+            .starts_with("<svg"));
+    }
+
+    #[test]
+    fn test_schedule_dag_set_to_json_invalid_yaml() {
+        assert!(matches!(
+            schedule_dag_set_to_json("global_edf", &["nodes: [".to_string()], 2, None, 1),
+            Err(SchedSimError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,431 @@
+//! Calibration of synthetic workloads against a target acceptance ratio.
+//! A DAG set consists of G(n, p) DAGs whose utilizations are drawn by UUniFast.
+//! The generator parameters are searched until the acceptance ratio of a reference algorithm over the generated DAG sets
+//! approaches the target, so that evaluations of different algorithms can use workloads of comparable hardness.
+//!
+//! The search tries each pair of the candidate numbers of nodes and edge probabilities,
+//! and bisects the total utilization for each pair because the acceptance ratio decreases with it.
+//!
+//! Small or dense G(n, p) DAGs are often drawn more than once, which would bias the acceptance ratio toward them.
+//! Therefore, the DAG sets structurally identical to an earlier one are removed from the corpus before it is evaluated or dumped.
+use crate::graph_extension::{GraphExtension, NodeData};
+use petgraph::{algo::is_isomorphic_matching, graph::NodeIndex, Graph};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "file_io")]
+use {
+    crate::{
+        dag_creator::dump_dag_to_yaml,
+        error::{Result, SchedSimError},
+    },
+    std::fs,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorParams {
+    pub number_of_dags: usize,
+    pub total_utilization: f64,
+    pub number_of_nodes: usize,
+    // Probability of an edge from each node to each later node
+    pub edge_probability: f64,
+    // The period of each DAG is drawn from them, and the deadline is implicit.
+    pub periods: Vec<i64>,
+}
+
+/// Split the total utilization into `count` utilizations uniformly (UUniFast).
+fn draw_utilizations(total_utilization: f64, count: usize, rng: &mut StdRng) -> Vec<f64> {
+    let mut utilizations = Vec::with_capacity(count);
+    let mut remaining_utilization = total_utilization;
+    for i in 1..count {
+        let next_remaining_utilization =
+            remaining_utilization * rng.gen::<f64>().powf(1.0 / (count - i) as f64);
+        utilizations.push(remaining_utilization - next_remaining_utilization);
+        remaining_utilization = next_remaining_utilization;
+    }
+    utilizations.push(remaining_utilization);
+    utilizations
+}
+
+/// Returns whether the DAGs are isomorphic with the same execution times and communication times,
+/// and have the same period and end-to-end deadline. The node ids are ignored.
+pub fn is_duplicate_dag(dag: &Graph<NodeData, i64>, other_dag: &Graph<NodeData, i64>) -> bool {
+    let get_sorted_wcets = |dag: &Graph<NodeData, i64>| {
+        let mut wcets: Vec<i64> = dag
+            .node_weights()
+            .map(|node| node.params["execution_time"])
+            .collect();
+        wcets.sort_unstable();
+        wcets
+    };
+    // The WCET multisets are compared first because they differ for most pairs.
+    dag.edge_count() == other_dag.edge_count()
+        && dag.get_head_period() == other_dag.get_head_period()
+        && dag.get_end_to_end_deadline() == other_dag.get_end_to_end_deadline()
+        && get_sorted_wcets(dag) == get_sorted_wcets(other_dag)
+        && is_isomorphic_matching(
+            dag,
+            other_dag,
+            |node, other_node| node.params["execution_time"] == other_node.params["execution_time"],
+            |edge, other_edge| edge == other_edge,
+        )
+}
+
+/// A DAG set is a duplicate if its DAGs pair up with the duplicate DAGs of the other DAG set in any order.
+fn is_duplicate_dag_set(
+    dag_set: &[Graph<NodeData, i64>],
+    other_dag_set: &[Graph<NodeData, i64>],
+) -> bool {
+    if dag_set.len() != other_dag_set.len() {
+        return false;
+    }
+    // Being a duplicate is an equivalence relation, so pairing greedily finds a pairing if one exists.
+    let mut is_paired = vec![false; other_dag_set.len()];
+    dag_set.iter().all(|dag| {
+        match (0..other_dag_set.len())
+            .find(|&i| !is_paired[i] && is_duplicate_dag(dag, &other_dag_set[i]))
+        {
+            Some(i) => {
+                is_paired[i] = true;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Remove the DAG sets that are duplicates of an earlier DAG set, and return the rest with the number of removed DAG sets.
+pub fn deduplicate_dag_sets(
+    dag_sets: Vec<Vec<Graph<NodeData, i64>>>,
+) -> (Vec<Vec<Graph<NodeData, i64>>>, usize) {
+    let mut unique_dag_sets: Vec<Vec<Graph<NodeData, i64>>> = Vec::with_capacity(dag_sets.len());
+    let mut duplicate_count = 0;
+    for dag_set in dag_sets {
+        if unique_dag_sets
+            .iter()
+            .any(|unique_dag_set| is_duplicate_dag_set(&dag_set, unique_dag_set))
+        {
+            duplicate_count += 1;
+        } else {
+            unique_dag_sets.push(dag_set);
+        }
+    }
+    (unique_dag_sets, duplicate_count)
+}
+
+impl GeneratorParams {
+    /// The same `seed` always yields the same DAG set.
+    pub fn generate_dag_set(&self, seed: u64) -> Vec<Graph<NodeData, i64>> {
+        if self.number_of_nodes == 0 || self.periods.is_empty() {
+            panic!("A DAG requires at least one node and one period.");
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        draw_utilizations(self.total_utilization, self.number_of_dags, &mut rng)
+            .into_iter()
+            .enumerate()
+            .map(|(dag_id, utilization)| {
+                let mut dag = self.generate_dag(utilization, &mut rng);
+                dag.set_dag_param("dag_id", dag_id as i64);
+                dag
+            })
+            .collect()
+    }
+
+    /// Node 0 is the only source, which has the period and the end-to-end deadline.
+    /// The volume is split among the nodes at random, and each node executes for at least 1.
+    fn generate_dag(&self, utilization: f64, rng: &mut StdRng) -> Graph<NodeData, i64> {
+        let period = *self.periods.choose(rng).unwrap();
+        let volume =
+            ((utilization * period as f64).round() as i64).max(self.number_of_nodes as i64);
+        let weights: Vec<f64> = (0..self.number_of_nodes)
+            .map(|_| rng.gen_range(f64::EPSILON..1.0))
+            .collect();
+        let total_weight: f64 = weights.iter().sum();
+
+        let mut dag = Graph::<NodeData, i64>::new();
+        for (id, weight) in weights.iter().enumerate() {
+            let execution_time = ((volume as f64 * weight / total_weight).round() as i64).max(1);
+            let node_i = dag.add_node(NodeData::new(id as i64, Default::default()));
+            dag.add_param(node_i, "execution_time", execution_time);
+        }
+        let source = NodeIndex::new(0);
+        dag.add_param(source, "period", period);
+        dag.add_param(source, "end_to_end_deadline", period);
+
+        for to in 1..self.number_of_nodes {
+            for from in 1..to {
+                if rng.gen_bool(self.edge_probability) {
+                    dag.add_edge(NodeIndex::new(from), NodeIndex::new(to), 0);
+                }
+            }
+            if dag.get_pre_nodes(NodeIndex::new(to)).is_none() {
+                dag.add_edge(source, NodeIndex::new(to), 0);
+            }
+        }
+        dag
+    }
+
+    /// Generate `number_of_dag_sets` DAG sets, the k-th from `seed + k`, and remove the duplicates.
+    /// Returns the unique DAG sets and the number of removed DAG sets.
+    pub fn generate_corpus(
+        &self,
+        number_of_dag_sets: usize,
+        seed: u64,
+    ) -> (Vec<Vec<Graph<NodeData, i64>>>, usize) {
+        deduplicate_dag_sets(
+            (0..number_of_dag_sets)
+                .map(|k| self.generate_dag_set(seed + k as u64))
+                .collect(),
+        )
+    }
+
+    /// Returns the ratio of the unique DAG sets in the corpus accepted by the reference algorithm.
+    pub fn calculate_acceptance_ratio(
+        &self,
+        number_of_dag_sets: usize,
+        seed: u64,
+        is_accepted: &impl Fn(&[Graph<NodeData, i64>]) -> bool,
+    ) -> f64 {
+        let (dag_sets, _) = self.generate_corpus(number_of_dag_sets, seed);
+        let accepted_count = dag_sets
+            .iter()
+            .filter(|dag_set| is_accepted(dag_set))
+            .count();
+        accepted_count as f64 / dag_sets.len() as f64
+    }
+
+    /// Write the k-th unique DAG set to `dir_path/dag_set_k/dag_i.yaml`, which create_dag_set_from_dir reads.
+    /// Returns the number of removed duplicates.
+    #[cfg(feature = "file_io")]
+    pub fn dump_corpus(
+        &self,
+        dir_path: &str,
+        number_of_dag_sets: usize,
+        seed: u64,
+    ) -> Result<usize> {
+        let (dag_sets, duplicate_count) = self.generate_corpus(number_of_dag_sets, seed);
+        for (k, dag_set) in dag_sets.iter().enumerate() {
+            let dag_set_dir_path = format!("{}/dag_set_{}", dir_path, k);
+            fs::create_dir_all(&dag_set_dir_path)
+                .map_err(|err| SchedSimError::io(&dag_set_dir_path, err))?;
+            for (dag_id, dag) in dag_set.iter().enumerate() {
+                dump_dag_to_yaml(dag, &format!("{}/dag_{}.yaml", dag_set_dir_path, dag_id))?;
+            }
+        }
+        Ok(duplicate_count)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationConfig {
+    pub target_acceptance_ratio: f64,
+    // The search stops once the acceptance ratio is within the tolerance of the target.
+    pub tolerance: f64,
+    pub number_of_dag_sets: usize,
+    pub max_bisection_steps: usize,
+    pub utilization_range: (f64, f64),
+    pub numbers_of_nodes: Vec<usize>,
+    pub edge_probabilities: Vec<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationResult {
+    pub generator_params: GeneratorParams,
+    pub acceptance_ratio: f64,
+}
+
+/// Search the generator parameters whose acceptance ratio is the closest to the target.
+/// The parameters not searched, such as the number of DAGs and the periods, are taken from `base_params`.
+/// The same seeds are used for every candidate, so that the candidates are compared on the same random draws.
+pub fn calibrate(
+    base_params: &GeneratorParams,
+    config: &CalibrationConfig,
+    seed: u64,
+    is_accepted: impl Fn(&[Graph<NodeData, i64>]) -> bool,
+) -> CalibrationResult {
+    let mut best_result: Option<CalibrationResult> = None;
+    for number_of_nodes in config.numbers_of_nodes.iter() {
+        for edge_probability in config.edge_probabilities.iter() {
+            let (mut lower_utilization, mut upper_utilization) = config.utilization_range;
+            for _ in 0..config.max_bisection_steps {
+                let generator_params = GeneratorParams {
+                    total_utilization: (lower_utilization + upper_utilization) / 2.0,
+                    number_of_nodes: *number_of_nodes,
+                    edge_probability: *edge_probability,
+                    ..base_params.clone()
+                };
+                let acceptance_ratio = generator_params.calculate_acceptance_ratio(
+                    config.number_of_dag_sets,
+                    seed,
+                    &is_accepted,
+                );
+                // A higher acceptance ratio than the target calls for a heavier workload.
+                if acceptance_ratio > config.target_acceptance_ratio {
+                    lower_utilization = generator_params.total_utilization;
+                } else {
+                    upper_utilization = generator_params.total_utilization;
+                }
+
+                let error = (acceptance_ratio - config.target_acceptance_ratio).abs();
+                if best_result.as_ref().is_none_or(|best_result| {
+                    error < (best_result.acceptance_ratio - config.target_acceptance_ratio).abs()
+                }) {
+                    best_result = Some(CalibrationResult {
+                        generator_params,
+                        acceptance_ratio,
+                    });
+                }
+                if error <= config.tolerance {
+                    return best_result.unwrap();
+                }
+            }
+        }
+    }
+    best_result.expect("No candidate of the generator parameters is given.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "file_io")]
+    use crate::dag_creator::create_dag_set_from_dir;
+    use crate::schedulability_test::test_federated;
+    use petgraph::{algo::is_cyclic_directed, visit::EdgeRef};
+
+    // Nodes and edges of each DAG
+    type DAGSummary = (Vec<NodeData>, Vec<(usize, usize)>);
+
+    fn summarize(dag_set: &[Graph<NodeData, i64>]) -> Vec<DAGSummary> {
+        dag_set
+            .iter()
+            .map(|dag| {
+                (
+                    dag.node_weights().cloned().collect(),
+                    dag.edge_references()
+                        .map(|edge| (edge.source().index(), edge.target().index()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn create_generator_params() -> GeneratorParams {
+        GeneratorParams {
+            number_of_dags: 4,
+            total_utilization: 2.0,
+            number_of_nodes: 10,
+            edge_probability: 0.3,
+            periods: vec![100, 200, 400],
+        }
+    }
+
+    #[test]
+    fn test_generate_dag_set_normal() {
+        let generator_params = create_generator_params();
+        let dag_set = generator_params.generate_dag_set(0);
+        assert_eq!(
+            summarize(&dag_set),
+            summarize(&generator_params.generate_dag_set(0))
+        );
+        assert_eq!(dag_set.len(), 4);
+
+        let mut total_utilization = 0.0;
+        for dag in dag_set.iter() {
+            assert_eq!(dag.node_count(), 10);
+            assert!(!is_cyclic_directed(dag));
+            assert_eq!(dag.get_source_nodes(), vec![NodeIndex::new(0)]);
+            total_utilization += dag.get_volume() as f64 / dag.get_head_period().unwrap() as f64;
+        }
+        // The execution times are rounded to integers.
+        assert!((total_utilization - 2.0).abs() < 0.1);
+    }
+
+    #[cfg(feature = "file_io")]
+    #[test]
+    fn test_dump_corpus_normal() {
+        let dir_path = "../lib/tests/calibration_corpus_test";
+        let generator_params = create_generator_params();
+        assert_eq!(generator_params.dump_corpus(dir_path, 2, 0).unwrap(), 0);
+
+        let dag_set = create_dag_set_from_dir(&format!("{}/dag_set_1", dir_path)).unwrap();
+        assert_eq!(
+            summarize(&dag_set),
+            summarize(&generator_params.generate_dag_set(1))
+        );
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_is_duplicate_dag_ignores_node_ids() {
+        let dag = create_generator_params().generate_dag_set(0).remove(0);
+        // Reverse the node order, which renumbers the nodes.
+        let mut reversed_dag = Graph::<NodeData, i64>::new();
+        for node_i in dag.node_indices().rev() {
+            reversed_dag.add_node(dag[node_i].clone());
+        }
+        let last_index = dag.node_count() - 1;
+        for edge in dag.edge_references() {
+            reversed_dag.add_edge(
+                NodeIndex::new(last_index - edge.source().index()),
+                NodeIndex::new(last_index - edge.target().index()),
+                *edge.weight(),
+            );
+        }
+        assert!(is_duplicate_dag(&dag, &reversed_dag));
+
+        let mut other_wcet_dag = reversed_dag.clone();
+        let node_i = NodeIndex::new(1);
+        let execution_time = other_wcet_dag[node_i].params["execution_time"];
+        other_wcet_dag.update_param(node_i, "execution_time", execution_time + 1);
+        assert!(!is_duplicate_dag(&dag, &other_wcet_dag));
+    }
+
+    #[test]
+    fn test_generate_corpus_removes_duplicates() {
+        // Every DAG set is a single DAG of one node with the same execution time.
+        let generator_params = GeneratorParams {
+            number_of_dags: 1,
+            total_utilization: 0.5,
+            number_of_nodes: 1,
+            edge_probability: 0.0,
+            periods: vec![100],
+        };
+        let (dag_sets, duplicate_count) = generator_params.generate_corpus(10, 0);
+        assert_eq!((dag_sets.len(), duplicate_count), (1, 9));
+
+        let dag_set = create_generator_params().generate_dag_set(0);
+        let mut reversed_dag_set = dag_set.clone();
+        reversed_dag_set.reverse();
+        let (unique_dag_sets, duplicate_count) = deduplicate_dag_sets(vec![
+            dag_set.clone(),
+            create_generator_params().generate_dag_set(1),
+            reversed_dag_set,
+        ]);
+        assert_eq!(duplicate_count, 1);
+        assert_eq!(summarize(&unique_dag_sets[0]), summarize(&dag_set));
+        assert_eq!(unique_dag_sets.len(), 2);
+    }
+
+    #[test]
+    fn test_calibrate_normal() {
+        let config = CalibrationConfig {
+            target_acceptance_ratio: 0.5,
+            tolerance: 0.1,
+            number_of_dag_sets: 20,
+            max_bisection_steps: 8,
+            utilization_range: (0.5, 4.0),
+            numbers_of_nodes: vec![10, 20],
+            edge_probabilities: vec![0.3],
+        };
+        let is_accepted =
+            |dag_set: &[Graph<NodeData, i64>]| test_federated(dag_set, 4).is_schedulable;
+
+        let calibration_result = calibrate(&create_generator_params(), &config, 0, is_accepted);
+        assert!((calibration_result.acceptance_ratio - 0.5).abs() <= 0.1);
+        assert_eq!(
+            calibration_result
+                .generator_params
+                .calculate_acceptance_ratio(20, 0, &is_accepted),
+            calibration_result.acceptance_ratio
+        );
+    }
+}
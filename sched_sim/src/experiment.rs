@@ -0,0 +1,245 @@
+//! Experiment spec running every combination of the DAG sets, the algorithms, the numbers of cores
+//! and the preemption modes from one invocation.
+//! Each combination writes its logs to `<output_dir_path>/<DAG set directory name>/<number of cores>_cores`,
+//! so that the logs of the same algorithm on different DAG sets and cores are not mixed.
+//!
+//! ```yaml
+//! dag_dir_paths: [../dags/set_0, ../dags/set_1]
+//! algorithms: [global_edf, decomp_gedf, dynfed, federated]
+//! numbers_of_cores: [4, 8]
+//! enable_preemption: [false, true]
+//! ```
+use crate::{
+    default_dag_format, default_segmentation_strategy, Command, CommonArgs, DagSetArgs,
+    SimulationArgs,
+};
+use clap::ValueEnum;
+use lib::error::{Result, SchedSimError};
+use serde_derive::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum Algorithm {
+    GlobalEdf,
+    RoundRobin,
+    ProportionalShare,
+    DecompGedf,
+    Dynfed,
+    Federated,
+}
+
+impl Algorithm {
+    /// Only the G-EDF based algorithms have a preemptive mode. The others are run once.
+    pub fn is_preemptable(&self) -> bool {
+        matches!(self, Self::GlobalEdf | Self::DecompGedf)
+    }
+
+    pub fn create_command(
+        &self,
+        common: CommonArgs,
+        simulation: SimulationArgs,
+        enable_preemption: bool,
+        enable_task_splitting: bool,
+    ) -> Command {
+        match self {
+            Self::GlobalEdf => Command::GlobalEdf {
+                common,
+                simulation,
+                enable_preemption,
+            },
+            Self::RoundRobin => Command::RoundRobin { common, simulation },
+            Self::ProportionalShare => Command::ProportionalShare { common, simulation },
+            Self::DecompGedf => Command::DecompGedf {
+                common,
+                simulation,
+                enable_preemption,
+                segmentation_strategy: default_segmentation_strategy(),
+            },
+            Self::Dynfed => Command::Dynfed { common, simulation },
+            Self::Federated => Command::Federated {
+                common,
+                dag_set: simulation.dag_set,
+                enable_task_splitting,
+                simulate: false,
+            },
+        }
+    }
+}
+
+/// The name of the output directory of the DAG set, which is the name of its directory.
+pub fn get_dag_set_name(dag_dir_path: &str) -> String {
+    Path::new(dag_dir_path)
+        .file_name()
+        .map_or(dag_dir_path.to_string(), |name| {
+            name.to_string_lossy().into_owned()
+        })
+}
+
+fn default_enable_preemption() -> Vec<bool> {
+    vec![false]
+}
+
+fn default_output_dir_path() -> String {
+    "../outputs".to_string()
+}
+
+fn default_output_format() -> String {
+    "yaml".to_string()
+}
+
+fn default_num_hyper_periods() -> i64 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentSpec {
+    dag_dir_paths: Vec<String>,
+    algorithms: Vec<Algorithm>,
+    numbers_of_cores: Vec<usize>,
+    #[serde(default = "default_enable_preemption")]
+    enable_preemption: Vec<bool>,
+    #[serde(default = "default_output_dir_path")]
+    output_dir_path: String,
+    #[serde(default = "default_output_format")]
+    output_format: String,
+    #[serde(default = "default_num_hyper_periods")]
+    num_hyper_periods: i64,
+    #[serde(default)]
+    warm_up_time: i64,
+    #[serde(default)]
+    render_gantt: bool,
+    #[serde(default)]
+    export_parquet: bool,
+    #[serde(default)]
+    enable_task_splitting: bool,
+    #[serde(default = "default_dag_format")]
+    dag_format: String,
+}
+
+impl ExperimentSpec {
+    pub fn from_yaml_file(file_path: &str) -> Result<Self> {
+        if !file_path.ends_with(".yaml") && !file_path.ends_with(".yml") {
+            return Err(SchedSimError::parse(file_path, "Invalid file type"));
+        }
+        let file_content =
+            fs::read_to_string(file_path).map_err(|err| SchedSimError::io(file_path, err))?;
+        let experiment_spec: Self = serde_yaml::from_str(&file_content)
+            .map_err(|err| SchedSimError::parse(file_path, err))?;
+        experiment_spec.validate()?;
+        Ok(experiment_spec)
+    }
+
+    /// Return an error if any dimension of the matrix is empty, which would silently run nothing.
+    fn validate(&self) -> Result<()> {
+        for (name, is_empty) in [
+            ("dag_dir_paths", self.dag_dir_paths.is_empty()),
+            ("algorithms", self.algorithms.is_empty()),
+            ("numbers_of_cores", self.numbers_of_cores.is_empty()),
+            ("enable_preemption", self.enable_preemption.is_empty()),
+        ] {
+            if is_empty {
+                return Err(SchedSimError::Validation(format!(
+                    "{} of the experiment is empty.",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The commands of all the combinations, in the order of the DAG sets, the numbers of cores,
+    /// the algorithms and the preemption modes.
+    pub fn create_commands(&self) -> Vec<Command> {
+        let mut commands = Vec::new();
+        for dag_dir_path in self.dag_dir_paths.iter() {
+            let dag_set_name = get_dag_set_name(dag_dir_path);
+            for number_of_cores in self.numbers_of_cores.iter() {
+                let output_dir_path = format!(
+                    "{}/{}/{}_cores",
+                    self.output_dir_path, dag_set_name, number_of_cores
+                );
+                for algorithm in self.algorithms.iter() {
+                    let enable_preemption = if algorithm.is_preemptable() {
+                        self.enable_preemption.clone()
+                    } else {
+                        vec![false]
+                    };
+                    for enable_preemption in enable_preemption {
+                        let common = CommonArgs {
+                            number_of_cores: *number_of_cores,
+                            output_dir_path: output_dir_path.clone(),
+                            output_format: self.output_format.clone(),
+                        };
+                        let simulation = SimulationArgs {
+                            dag_set: DagSetArgs {
+                                dag_dir_path: dag_dir_path.clone(),
+                                utilization_scale: 1.0,
+                                dag_format: self.dag_format.clone(),
+                            },
+                            num_hyper_periods: self.num_hyper_periods,
+                            warm_up_time: self.warm_up_time,
+                            render_gantt: self.render_gantt,
+                            export_parquet: self.export_parquet,
+                        };
+                        commands.push(algorithm.create_command(
+                            common,
+                            simulation,
+                            enable_preemption,
+                            self.enable_task_splitting,
+                        ));
+                    }
+                }
+            }
+        }
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_commands_normal() {
+        let experiment_spec =
+            ExperimentSpec::from_yaml_file("tests/sample_experiments/matrix.yaml").unwrap();
+        let commands = experiment_spec.create_commands();
+
+        // 2 numbers of cores * (G-EDF with 2 preemption modes + DynFed)
+        assert_eq!(commands.len(), 6);
+        match &commands[1] {
+            Command::GlobalEdf {
+                common,
+                simulation,
+                enable_preemption,
+            } => {
+                assert_eq!(common.number_of_cores, 2);
+                assert_eq!(common.output_dir_path, "../outputs/multiple_yaml/2_cores");
+                assert_eq!(
+                    simulation.dag_set.dag_dir_path,
+                    "../lib/tests/sample_dags/multiple_yaml"
+                );
+                assert_eq!(simulation.num_hyper_periods, 2);
+                assert!(*enable_preemption);
+            }
+            _ => panic!("The second command must be preemptive G-EDF."),
+        }
+        assert!(
+            matches!(&commands[2], Command::Dynfed { common, .. } if common.number_of_cores == 2)
+        );
+        assert!(
+            matches!(&commands[5], Command::Dynfed { common, .. } if common.number_of_cores == 4)
+        );
+    }
+
+    #[test]
+    fn test_from_yaml_file_empty_algorithms() {
+        assert!(matches!(
+            ExperimentSpec::from_yaml_file("tests/sample_experiments/empty_algorithms.yaml"),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,731 @@
+mod experiment;
+#[cfg(feature = "server")]
+mod server;
+mod sweep;
+mod watch;
+
+use clap::{Args, Parser, Subcommand};
+use ecrts_federated::driver::run_federated;
+use experiment::ExperimentSpec;
+use lib::{
+    dag_creator::{
+        create_dag_from_yaml, create_dag_set_from_dir_in_format,
+        create_dag_set_from_yaml_in_format, dump_dag_to_yaml, DagFormat,
+    },
+    dag_set_scheduler::PreemptiveType,
+    error::{Result, SchedSimError},
+    fixed_priority_scheduler::FixedPriorityScheduler,
+    graph_extension::NodeData,
+    homogeneous::HomogeneousProcessor,
+    hyper_period_unrolling::unroll_dag_set_over_hyper_period,
+    log::{
+        dump_dag_set_scheduler_result_to_yaml, dump_run_time_to_yaml, print_result_summary,
+        DAGSetSchedulerLog, OutputFormat, RunTimeLog,
+    },
+    multi_rate::unroll_multi_rate_dag_set,
+    processor::ProcessorBase,
+    run_metadata::{replay, RunMetadata},
+    scheduler_factory::{create_boxed_scheduler, SchedulerFactory},
+    simulation_horizon::{HorizonEnd, SimulationHorizon},
+    transformations::{create_segmentation_strategy, decompose_with_strategy},
+    util::{adjust_to_implicit_deadline, scale_execution_times, STDIN_FILE_PATH},
+    visualization::dump_gantt_svg,
+};
+use log::error;
+use petgraph::Graph;
+use rayon::{prelude::*, ThreadPoolBuilder};
+use rtcsa_dynfed::dynfed::DynamicFederatedScheduler;
+use rtss_cpc::driver::{run_cpc_dag, run_cpc_dag_set};
+use rtss_intra_task_priority::driver::run_intra_task_priority;
+use serde_derive::{Deserialize, Serialize};
+use std::{env, path::Path};
+use sweep::{run_sweep, SweepArgs};
+use watch::{get_total_utilization, watch, WatchedRun};
+
+#[derive(Parser)]
+#[clap(
+    name = "sched_sim",
+    version = "1.0",
+    about = "About:
+    sched_sim runs each algorithm of this repository with the same arguments.
+    The algorithms on a DAG set operate on the same assumption of period and end_to_end_deadline.
+    Therefore, the period shall be considered as the end_to_end_deadline.
+    If there is no period, the end_to_end_deadline shall be obtained."
+)]
+struct ArgParser {
+    #[command(subcommand)]
+    command: Command,
+    ///Number of threads running the independent runs of an experiment or a sweep in parallel. All the CPUs by default.
+    ///The run times in the logs include the interference between the runs unless it is 1.
+    #[clap(short = 'j', long = "jobs", global = true)]
+    jobs: Option<usize>,
+    ///Re-run the command whenever the files of its DAG set or DAG file change, appending the results
+    ///to watch-summary.csv in the output directory, until the process is terminated.
+    #[clap(long = "watch", global = true, default_value = "false")]
+    watch: bool,
+}
+
+/// Arguments shared by all the algorithms.
+#[derive(Args, Serialize, Deserialize)]
+struct CommonArgs {
+    ///Number of processing cores.
+    #[clap(short = 'c', long = "number_of_cores", required = true)]
+    number_of_cores: usize,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Format of the summary printed on stdout: yaml (nothing is printed) or json.
+    #[clap(
+        long = "output_format",
+        alias = "output-format",
+        default_value = "yaml"
+    )]
+    output_format: String,
+}
+
+/// Arguments of the input DAG set.
+#[derive(Args, Serialize, Deserialize)]
+struct DagSetArgs {
+    ///Path to DAGSet directory, or to a YAML file with one DAG per document.
+    #[clap(short = 'd', long = "dag_dir_path", required = true)]
+    dag_dir_path: String,
+    ///Multiplier of the execution times of all nodes to scale the utilization of the DAG set.
+    #[clap(
+        long = "utilization_scale",
+        alias = "utilization-scale",
+        default_value = "1.0"
+    )]
+    utilization_scale: f64,
+    ///Format of the DAG files: base or rd_gen (the output of RD-Gen).
+    #[clap(long = "dag_format", alias = "dag-format", default_value = "base")]
+    #[serde(default = "default_dag_format")]
+    dag_format: String,
+}
+
+fn default_dag_format() -> String {
+    "base".to_string()
+}
+
+fn default_segmentation_strategy() -> String {
+    "basic".to_string()
+}
+
+/// Arguments shared by the algorithms simulating a DAG set.
+#[derive(Args, Serialize, Deserialize)]
+struct SimulationArgs {
+    #[command(flatten)]
+    dag_set: DagSetArgs,
+    ///Number of hyper periods to simulate.
+    #[clap(short = 'n', long = "num_hyper_periods", default_value = "1")]
+    num_hyper_periods: i64,
+    ///Time before which released jobs are excluded from the response time statistics.
+    #[clap(short = 'w', long = "warm_up_time", default_value = "0")]
+    warm_up_time: i64,
+    ///Render the schedule as an SVG Gantt chart next to the log.
+    #[clap(long = "render_gantt", alias = "render-gantt", default_value = "false")]
+    render_gantt: bool,
+    ///Write the job events and the DAG summaries as Parquet files next to the log. Requires the arrow feature.
+    #[clap(
+        long = "export_parquet",
+        alias = "export-parquet",
+        default_value = "false"
+    )]
+    #[serde(default)]
+    export_parquet: bool,
+}
+
+/// The command is serialized as the config of the run metadata to replay the run.
+#[derive(Subcommand, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Command {
+    ///Global EDF (ECRTS 2013).
+    GlobalEdf {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        simulation: SimulationArgs,
+        ///Enable or disable preemptive scheduling.
+        #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+        enable_preemption: bool,
+    },
+    ///Global round robin, a baseline for fairness comparisons.
+    RoundRobin {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        simulation: SimulationArgs,
+    },
+    ///Quantum-based proportional share approximating Pfair.
+    ProportionalShare {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        simulation: SimulationArgs,
+    },
+    ///Global EDF of the decomposed DAGs (TPDS 2014).
+    DecompGedf {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        simulation: SimulationArgs,
+        ///Enable or disable preemptive scheduling.
+        #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+        enable_preemption: bool,
+        ///Strategy dividing the period into the deadlines of the segments:
+        ///basic, even, proportional_to_slack or critical_path_aware.
+        #[clap(short = 's', long = "segmentation_strategy", default_value = "basic")]
+        #[serde(default = "default_segmentation_strategy")]
+        segmentation_strategy: String,
+    },
+    ///Dynamic federated scheduling (RTCSA 2021).
+    Dynfed {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        simulation: SimulationArgs,
+    },
+    ///Federated scheduling analysis (ECRTS 2014).
+    Federated {
+        #[command(flatten)]
+        common: CommonArgs,
+        #[command(flatten)]
+        dag_set: DagSetArgs,
+        ///Split the low-utilization DAGs that fit on no remaining core over two cores.
+        #[clap(short = 's', long = "enable_task_splitting", default_value = "false")]
+        enable_task_splitting: bool,
+        ///Simulate a schedulable result by preemptive EDF on its core assignment, with the job logs of all the DAGs.
+        #[clap(long = "simulate", default_value = "false")]
+        #[serde(default)]
+        simulate: bool,
+    },
+    ///Fixed-priority scheduling of a DAG prioritized by the CPC model (RTSS 2020).
+    Cpc {
+        #[command(flatten)]
+        common: CommonArgs,
+        ///Path to DAG file, or `-` to read it from the standard input.
+        #[clap(
+            short = 'f',
+            long = "dag_file_path",
+            required_unless_present = "dag_dir_path",
+            conflicts_with = "dag_dir_path"
+        )]
+        dag_file_path: Option<String>,
+        ///Path to DAGSet directory. The DAGs are prioritized by the CPC model one by one,
+        ///and the set is scheduled by global EDF, whose nodes of the same deadline are ordered by the priorities.
+        #[clap(short = 'd', long = "dag_dir_path")]
+        #[serde(default)]
+        dag_dir_path: Option<String>,
+        ///Multiplier to compute constrained deadlines
+        #[clap(short = 'r', long = "ratio_deadline_to_period", default_value = "1.0")]
+        ratio_deadline_to_period: f32,
+        ///Preempt the nodes by their absolute deadlines and then their CPC priorities, instead of the non-preemptive execution of the CPC model.
+        #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+        #[serde(default)]
+        enable_preemption: bool,
+    },
+    ///Fixed-priority scheduling of a DAG prioritized path by path by the intra-task priority assignment (RTSS 2019).
+    IntraTaskPriority {
+        #[command(flatten)]
+        common: CommonArgs,
+        ///Path to DAG file, or `-` to read it from the standard input.
+        #[clap(short = 'f', long = "dag_file_path", required = true)]
+        dag_file_path: String,
+        ///Multiplier to compute constrained deadlines
+        #[clap(short = 'r', long = "ratio_deadline_to_period", default_value = "1.0")]
+        ratio_deadline_to_period: f32,
+    },
+    ///Run the matrix of the DAG sets, the algorithms, the numbers of cores and the preemption modes in a spec file.
+    Experiment {
+        ///Path to experiment spec file.
+        #[clap(short = 'f', long = "experiment_file_path", required = true)]
+        experiment_file_path: String,
+    },
+    ///Sweep the number of cores and the utilization scale, and aggregate the schedulability of each point.
+    Sweep {
+        #[command(flatten)]
+        sweep: SweepArgs,
+    },
+    ///Unroll the DAG set into a super-DAG of its jobs in one hyper period for offline schedulers.
+    Unroll {
+        #[command(flatten)]
+        dag_set: DagSetArgs,
+        ///Path to the super-DAG file to write.
+        #[clap(short = 'o', long = "output_file_path", required = true)]
+        output_file_path: String,
+    },
+    ///Re-run the run of a log from the run metadata in its header.
+    Replay {
+        ///Path to log file.
+        #[clap(short = 'f', long = "log_file_path", required = true)]
+        log_file_path: String,
+    },
+    ///Serve the schedulers over HTTP, caching the submitted DAG sets between the requests.
+    #[cfg(feature = "server")]
+    Serve {
+        ///Address to listen on.
+        #[clap(short = 'a', long = "address", default_value = "127.0.0.1:8080")]
+        address: String,
+    },
+}
+
+fn create_scheduler_factory() -> SchedulerFactory {
+    let mut factory = SchedulerFactory::default();
+    factory.register(
+        "dynfed",
+        create_boxed_scheduler::<
+            DynamicFederatedScheduler<FixedPriorityScheduler<HomogeneousProcessor>>,
+        >,
+    );
+    factory
+}
+
+/// The preemptive type by the key of the priority and the name of the log file.
+fn get_preemptive_type(
+    enable_preemption: bool,
+    key: &str,
+    alg_name: &str,
+) -> (PreemptiveType, String) {
+    if enable_preemption {
+        (
+            PreemptiveType::Preemptive {
+                key: key.to_string(),
+            },
+            format!("{}_preemptive", alg_name),
+        )
+    } else {
+        (
+            PreemptiveType::NonPreemptive,
+            format!("{}_non_preemptive", alg_name),
+        )
+    }
+}
+
+/// The run metadata of the command, which records the command itself as the config to replay it.
+fn create_run_metadata(
+    command: &Command,
+    algorithm: &str,
+    number_of_cores: usize,
+    input_path: &str,
+) -> Result<RunMetadata> {
+    let mut run_metadata = RunMetadata::new(algorithm, number_of_cores);
+    run_metadata.config =
+        Some(serde_yaml::to_value(command).expect("The command is always serializable to YAML."));
+    if Path::new(input_path).is_dir() {
+        run_metadata.add_input_dir(input_path)?;
+    } else {
+        run_metadata.add_input_file(input_path)?;
+    }
+    Ok(run_metadata)
+}
+
+fn create_dag_set(dag_set_args: &DagSetArgs) -> Result<Vec<Graph<NodeData, i64>>> {
+    let dag_format = DagFormat::from_name(&dag_set_args.dag_format)?;
+    let mut dag_set = if Path::new(&dag_set_args.dag_dir_path).is_file() {
+        create_dag_set_from_yaml_in_format(&dag_set_args.dag_dir_path, dag_format)?
+    } else {
+        create_dag_set_from_dir_in_format(&dag_set_args.dag_dir_path, dag_format)?
+    };
+    if dag_set_args.utilization_scale != 1.0 {
+        scale_execution_times(&mut dag_set, dag_set_args.utilization_scale);
+    }
+    // The multi-rate DAGs are scheduled as their unrolled single-rate DAGs.
+    unroll_multi_rate_dag_set(&mut dag_set)?;
+    Ok(dag_set)
+}
+
+/// Write `<log>-jobs.parquet` and `<log>-dags.parquet` next to the log at `file_path`.
+#[cfg(feature = "arrow")]
+fn export_parquet(log: &DAGSetSchedulerLog, file_path: &str) -> Result<()> {
+    log.dump_job_logs_to_parquet(&file_path.replace(".yaml", "-jobs.parquet"))?;
+    log.dump_dag_summaries_to_parquet(&file_path.replace(".yaml", "-dags.parquet"))
+}
+
+#[cfg(not(feature = "arrow"))]
+fn export_parquet(_log: &DAGSetSchedulerLog, _file_path: &str) -> Result<()> {
+    Err(SchedSimError::Validation(
+        "--export_parquet requires sched_sim built with the arrow feature.".to_string(),
+    ))
+}
+
+/// Simulate the DAG set with the scheduler registered as `scheduler_name`, and dump the log and the result.
+#[allow(clippy::too_many_arguments)]
+fn simulate_dag_set(
+    common: &CommonArgs,
+    simulation: &SimulationArgs,
+    run_metadata: RunMetadata,
+    dag_set: &[Graph<NodeData, i64>],
+    mut run_time_log: RunTimeLog,
+    scheduler_name: &str,
+    preemptive_type: PreemptiveType,
+    file_name: &str,
+) -> Result<bool> {
+    let homogeneous_processor = HomogeneousProcessor::new(common.number_of_cores);
+    let mut scheduler =
+        create_scheduler_factory().create(scheduler_name, dag_set, &homogeneous_processor)?;
+    scheduler.set_simulation_horizon(SimulationHorizon::new(
+        HorizonEnd::HyperPeriods(simulation.num_hyper_periods),
+        simulation.warm_up_time,
+    ));
+
+    let schedule_outcome =
+        run_time_log.measure_simulation(|| scheduler.schedule(preemptive_type))?;
+    // The minimum-core search of DynFed is done inside the scheduling.
+    run_time_log.move_to_preprocessing(schedule_outcome.preprocessing_time);
+    scheduler.get_log_mut().write_run_metadata(run_metadata);
+    let file_path = scheduler.dump_log(&common.output_dir_path, file_name);
+    if simulation.render_gantt {
+        dump_gantt_svg(&schedule_outcome.log, &file_path.replace(".yaml", ".svg"));
+    }
+    if simulation.export_parquet {
+        export_parquet(&schedule_outcome.log, &file_path)?;
+    }
+
+    // Check the result
+    let result = !schedule_outcome.has_deadline_miss();
+
+    dump_dag_set_scheduler_result_to_yaml(&file_path, result);
+    dump_run_time_to_yaml(&file_path, &run_time_log);
+    print_result_summary(
+        OutputFormat::from_name(&common.output_format)?,
+        &file_path,
+        result,
+        Some(schedule_outcome.schedule_length),
+        &schedule_outcome.worst_response_times,
+    );
+    Ok(result)
+}
+
+fn run_dag_set_scheduler(
+    common: &CommonArgs,
+    simulation: &SimulationArgs,
+    run_metadata: RunMetadata,
+    scheduler_name: &str,
+    preemptive_type: PreemptiveType,
+    file_name: &str,
+) -> Result<bool> {
+    let mut run_time_log = RunTimeLog::default();
+    let mut dag_set = create_dag_set(&simulation.dag_set)?;
+    run_time_log.measure_preprocessing(|| adjust_to_implicit_deadline(&mut dag_set));
+    simulate_dag_set(
+        common,
+        simulation,
+        run_metadata,
+        &dag_set,
+        run_time_log,
+        scheduler_name,
+        preemptive_type,
+        file_name,
+    )
+}
+
+fn run_decomp_gedf(
+    common: &CommonArgs,
+    simulation: &SimulationArgs,
+    run_metadata: RunMetadata,
+    enable_preemption: bool,
+    segmentation_strategy: &str,
+) -> Result<bool> {
+    // The strategy is recorded in the log as a part of the command in the run metadata.
+    let segmentation_strategy = create_segmentation_strategy(segmentation_strategy);
+    let mut run_time_log = RunTimeLog::default();
+    let mut dag_set = create_dag_set(&simulation.dag_set)?;
+    run_time_log.measure_preprocessing(|| {
+        adjust_to_implicit_deadline(&mut dag_set);
+        // Decompose DAGs
+        for dag in dag_set.iter_mut() {
+            decompose_with_strategy(dag, segmentation_strategy.as_ref());
+        }
+    });
+    let (preemptive_type, file_name) = get_preemptive_type(
+        enable_preemption,
+        "int_scaled_node_relative_deadline",
+        "decomp_gedf",
+    );
+    simulate_dag_set(
+        common,
+        simulation,
+        run_metadata,
+        &dag_set,
+        run_time_log,
+        "global_edf",
+        preemptive_type,
+        &file_name,
+    )
+}
+
+/// Run the command, and return whether the DAG set is schedulable.
+/// An experiment or a sweep of many runs is schedulable if all its runs are.
+fn run_command(command: &Command) -> Result<bool> {
+    match command {
+        Command::GlobalEdf {
+            common,
+            simulation,
+            enable_preemption,
+        } => {
+            let (preemptive_type, file_name) =
+                get_preemptive_type(*enable_preemption, "node_absolute_deadline", "gedf");
+            let run_metadata = create_run_metadata(
+                command,
+                "global_edf",
+                common.number_of_cores,
+                &simulation.dag_set.dag_dir_path,
+            )?;
+            run_dag_set_scheduler(
+                common,
+                simulation,
+                run_metadata,
+                "global_edf",
+                preemptive_type,
+                &file_name,
+            )
+        }
+        // Round robin and proportional share slice the time by themselves regardless of the preemptive type.
+        Command::RoundRobin { common, simulation } => run_dag_set_scheduler(
+            common,
+            simulation,
+            create_run_metadata(
+                command,
+                "round_robin",
+                common.number_of_cores,
+                &simulation.dag_set.dag_dir_path,
+            )?,
+            "round_robin",
+            PreemptiveType::NonPreemptive,
+            "round_robin",
+        ),
+        Command::ProportionalShare { common, simulation } => run_dag_set_scheduler(
+            common,
+            simulation,
+            create_run_metadata(
+                command,
+                "proportional_share",
+                common.number_of_cores,
+                &simulation.dag_set.dag_dir_path,
+            )?,
+            "proportional_share",
+            PreemptiveType::NonPreemptive,
+            "proportional_share",
+        ),
+        Command::DecompGedf {
+            common,
+            simulation,
+            enable_preemption,
+            segmentation_strategy,
+        } => run_decomp_gedf(
+            common,
+            simulation,
+            create_run_metadata(
+                command,
+                "decomp_gedf",
+                common.number_of_cores,
+                &simulation.dag_set.dag_dir_path,
+            )?,
+            *enable_preemption,
+            segmentation_strategy,
+        ),
+        // DynFed schedules each DAG non-preemptively with the fixed-priority scheduler.
+        Command::Dynfed { common, simulation } => run_dag_set_scheduler(
+            common,
+            simulation,
+            create_run_metadata(
+                command,
+                "dynfed",
+                common.number_of_cores,
+                &simulation.dag_set.dag_dir_path,
+            )?,
+            "dynfed",
+            PreemptiveType::NonPreemptive,
+            "FixedPriority",
+        ),
+        Command::Federated {
+            common,
+            dag_set,
+            enable_task_splitting,
+            simulate,
+        } => run_federated(
+            create_dag_set(dag_set)?,
+            common.number_of_cores,
+            &common.output_dir_path,
+            OutputFormat::from_name(&common.output_format)?,
+            create_run_metadata(
+                command,
+                "federated",
+                common.number_of_cores,
+                &dag_set.dag_dir_path,
+            )?,
+            *enable_task_splitting,
+            *simulate,
+        ),
+        Command::Cpc {
+            common,
+            dag_file_path: Some(dag_file_path),
+            ratio_deadline_to_period,
+            enable_preemption,
+            ..
+        } => run_cpc_dag(
+            create_dag_from_yaml(dag_file_path, false)?,
+            common.number_of_cores,
+            &common.output_dir_path,
+            OutputFormat::from_name(&common.output_format)?,
+            create_run_metadata(command, "cpc", common.number_of_cores, dag_file_path)?,
+            *ratio_deadline_to_period,
+            *enable_preemption,
+        ),
+        Command::Cpc {
+            common,
+            dag_dir_path: Some(dag_dir_path),
+            ratio_deadline_to_period,
+            enable_preemption,
+            ..
+        } => run_cpc_dag_set(
+            create_dag_set_from_dir_in_format(dag_dir_path, DagFormat::Base)?,
+            common.number_of_cores,
+            &common.output_dir_path,
+            OutputFormat::from_name(&common.output_format)?,
+            create_run_metadata(command, "cpc", common.number_of_cores, dag_dir_path)?,
+            *ratio_deadline_to_period,
+            *enable_preemption,
+        ),
+        Command::Cpc { .. } => Err(SchedSimError::Validation(
+            "cpc requires either --dag_file_path or --dag_dir_path.".to_string(),
+        )),
+        Command::IntraTaskPriority {
+            common,
+            dag_file_path,
+            ratio_deadline_to_period,
+        } => run_intra_task_priority(
+            create_dag_from_yaml(dag_file_path, false)?,
+            common.number_of_cores,
+            &common.output_dir_path,
+            OutputFormat::from_name(&common.output_format)?,
+            create_run_metadata(
+                command,
+                "intra_task_priority",
+                common.number_of_cores,
+                dag_file_path,
+            )?,
+            *ratio_deadline_to_period,
+        ),
+        Command::Experiment {
+            experiment_file_path,
+        } => {
+            // The runs share no state, so they run in parallel.
+            ExperimentSpec::from_yaml_file(experiment_file_path)?
+                .create_commands()
+                .par_iter()
+                .map(run_command)
+                .try_reduce(|| true, |a, b| Ok(a && b))
+        }
+        Command::Sweep { sweep } => run_sweep(sweep),
+        // The unrolling has no schedulability to return.
+        Command::Unroll {
+            dag_set,
+            output_file_path,
+        } => {
+            let super_dag = unroll_dag_set_over_hyper_period(&create_dag_set(dag_set)?)?;
+            dump_dag_to_yaml(&super_dag, output_file_path)?;
+            Ok(true)
+        }
+        // Each run of an experiment or a sweep records its own command, so the replayed command is a single run.
+        Command::Replay { log_file_path } => replay(log_file_path, |run_metadata| {
+            let config = run_metadata.config.clone().ok_or_else(|| {
+                SchedSimError::Validation(format!(
+                    "The run metadata has no config to replay: {}",
+                    log_file_path
+                ))
+            })?;
+            let command: Command = serde_yaml::from_value(config)
+                .map_err(|err| SchedSimError::parse(log_file_path, err))?;
+            // The relative paths in the command are relative to the working directory of the run.
+            env::set_current_dir(&run_metadata.working_dir_path)
+                .map_err(|err| SchedSimError::io(&run_metadata.working_dir_path, err))?;
+            run_command(&command)
+        }),
+        // The server runs until the process is terminated, so it has no result.
+        #[cfg(feature = "server")]
+        Command::Serve { address } => server::run_server(address).map(|()| true),
+    }
+}
+
+/// Watch the input of a command of a single DAG set or DAG file. See watch.rs.
+fn watch_command(command: &Command) -> Result<()> {
+    // The DAG file or the DAG set directory of CPC and the DAG file of the intra-task priority have no DAG set arguments.
+    let (input_path, common, dag_set_args) = match command {
+        Command::GlobalEdf {
+            common, simulation, ..
+        }
+        | Command::RoundRobin { common, simulation }
+        | Command::ProportionalShare { common, simulation }
+        | Command::DecompGedf {
+            common, simulation, ..
+        }
+        | Command::Dynfed { common, simulation } => (
+            &simulation.dag_set.dag_dir_path,
+            common,
+            Some(&simulation.dag_set),
+        ),
+        Command::Federated {
+            common, dag_set, ..
+        } => (&dag_set.dag_dir_path, common, Some(dag_set)),
+        Command::Cpc {
+            common,
+            dag_file_path,
+            dag_dir_path,
+            ..
+        } => match dag_file_path.as_ref().or(dag_dir_path.as_ref()) {
+            Some(input_path) => (input_path, common, None),
+            None => {
+                return Err(SchedSimError::Validation(
+                    "cpc requires either --dag_file_path or --dag_dir_path.".to_string(),
+                ))
+            }
+        },
+        Command::IntraTaskPriority {
+            common,
+            dag_file_path,
+            ..
+        } => (dag_file_path, common, None),
+        _ => {
+            return Err(SchedSimError::Validation(
+                "--watch requires a command of a DAG set or a DAG file.".to_string(),
+            ))
+        }
+    };
+    if input_path == STDIN_FILE_PATH {
+        return Err(SchedSimError::Validation(
+            "--watch cannot watch the standard input.".to_string(),
+        ));
+    }
+    watch(input_path, &common.output_dir_path, || {
+        // The utilization is of the DAG set as the command loads it, e.g., scaled by utilization_scale.
+        let dag_set = match dag_set_args {
+            Some(dag_set_args) => create_dag_set(dag_set_args)?,
+            None if Path::new(input_path).is_dir() => {
+                create_dag_set_from_dir_in_format(input_path, DagFormat::Base)?
+            }
+            None => vec![create_dag_from_yaml(input_path, false)?],
+        };
+        Ok(WatchedRun {
+            total_utilization: get_total_utilization(&dag_set),
+            is_schedulable: run_command(command)?,
+        })
+    })
+}
+
+fn main() -> Result<()> {
+    let arg: ArgParser = ArgParser::parse();
+    // Without the panic handler, a panic in a task spawned on the pool, e.g., a request of the server, aborts the process.
+    let mut thread_pool_builder =
+        ThreadPoolBuilder::new().panic_handler(|_| error!("A task on the thread pool panicked."));
+    if let Some(jobs) = arg.jobs {
+        thread_pool_builder = thread_pool_builder.num_threads(jobs);
+    }
+    thread_pool_builder
+        .build_global()
+        .expect("Failed to build the thread pool.");
+    if arg.watch {
+        return watch_command(&arg.command);
+    }
+    run_command(&arg.command)?;
+    Ok(())
+}
@@ -0,0 +1,485 @@
+//! Service mode of sched_sim, enabled by the `server` feature, for a pipeline that submits many DAG sets.
+//! A DAG set is submitted once as the contents of its YAML files and cached by its id, and then scheduled
+//! by any scheduler and number of cores without being parsed again. The ids are the hashes of the contents,
+//! so submitting the same DAG set again returns the cached one.
+//!
+//! | Request | Body | Response |
+//! | --- | --- | --- |
+//! | `GET /schedulers` | | the scheduler names |
+//! | `POST /dag_sets` | `{"dag_yamls": [...], "dag_format"}` | `{"dag_set_id", "number_of_dags"}` |
+//! | `GET /dag_sets/<id>` | | `{"dag_set_id", "number_of_dags"}` |
+//! | `DELETE /dag_sets/<id>` | | |
+//! | `POST /dag_sets/<id>/schedule` | `{"scheduler_name", "number_of_cores", "preemptive_key", "num_hyper_periods", "include_log"}` | the schedule result |
+//!
+//! The bodies are JSON, and an error is returned as `{"error": <message>}` with a 4xx or 5xx status.
+use crate::create_scheduler_factory;
+use lib::{
+    dag_creator::{create_dag_set_from_yaml_strs_in_format, DagFormat},
+    dag_set_scheduler::PreemptiveType,
+    error::{Result, SchedSimError},
+    graph_extension::{GraphExtension, NodeData},
+    log::DAGSetSchedulerLog,
+};
+use log::{error, info};
+use petgraph::Graph;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+type DagSet = Vec<Graph<NodeData, i64>>;
+
+#[derive(Deserialize)]
+struct SubmitRequest {
+    dag_yamls: Vec<String>,
+    #[serde(default)]
+    dag_format: DagFormat,
+}
+
+#[derive(Serialize)]
+struct DagSetInfo {
+    dag_set_id: String,
+    number_of_dags: usize,
+}
+
+fn default_num_hyper_periods() -> i64 {
+    1
+}
+
+#[derive(Deserialize)]
+struct ScheduleRequest {
+    scheduler_name: String,
+    number_of_cores: usize,
+    /// The scheduling is preemptive by the node parameter if it is given.
+    #[serde(default)]
+    preemptive_key: Option<String>,
+    #[serde(default = "default_num_hyper_periods")]
+    num_hyper_periods: i64,
+    /// The log is large, so it is returned only on request.
+    #[serde(default)]
+    include_log: bool,
+}
+
+#[derive(Serialize)]
+struct ScheduleResponse {
+    schedulable: bool,
+    schedule_length: i64,
+    worst_response_times: Vec<i64>,
+    deadline_miss_flags: Vec<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    log: Option<DAGSetSchedulerLog>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+struct ApiError {
+    status_code: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status_code: u16, message: String) -> Self {
+        Self {
+            status_code,
+            message,
+        }
+    }
+}
+
+impl From<SchedSimError> for ApiError {
+    fn from(err: SchedSimError) -> Self {
+        let status_code = match err {
+            SchedSimError::Io { .. } => 500,
+            _ => 400,
+        };
+        Self::new(status_code, err.to_string())
+    }
+}
+
+/// The status code and the JSON body of a response.
+#[derive(Debug, PartialEq)]
+pub struct ApiResponse {
+    pub status_code: u16,
+    pub body: String,
+}
+
+impl ApiResponse {
+    fn json<T: Serialize>(status_code: u16, body: &T) -> Self {
+        Self {
+            status_code,
+            body: serde_json::to_string(body)
+                .expect("The response is always serializable to JSON."),
+        }
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &str) -> std::result::Result<T, ApiError> {
+    serde_json::from_str(body)
+        .map_err(|err| ApiError::new(400, format!("Failed to parse the request body: {}", err)))
+}
+
+fn calculate_dag_set_id(dag_yamls: &[String], dag_format: DagFormat) -> String {
+    let mut hasher = DefaultHasher::new();
+    dag_yamls.hash(&mut hasher);
+    dag_format.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The parsed DAG sets shared by the requests. The lock is held only to look up or insert a DAG set,
+/// so that the requests are scheduled in parallel.
+#[derive(Default)]
+pub struct DagSetCache {
+    dag_sets: Mutex<HashMap<String, Arc<DagSet>>>,
+}
+
+impl DagSetCache {
+    /// A request that panicked holds the lock only briefly, so the DAG sets are still consistent.
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Arc<DagSet>>> {
+        self.dag_sets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn get(&self, dag_set_id: &str) -> std::result::Result<Arc<DagSet>, ApiError> {
+        self.lock()
+            .get(dag_set_id)
+            .cloned()
+            .ok_or_else(|| ApiError::new(404, format!("Unknown DAG set: {}", dag_set_id)))
+    }
+
+    fn submit(&self, body: &str) -> std::result::Result<ApiResponse, ApiError> {
+        let submit_request: SubmitRequest = parse_body(body)?;
+        let dag_set_id = calculate_dag_set_id(&submit_request.dag_yamls, submit_request.dag_format);
+        if let Ok(dag_set) = self.get(&dag_set_id) {
+            return Ok(ApiResponse::json(
+                200,
+                &DagSetInfo {
+                    dag_set_id,
+                    number_of_dags: dag_set.len(),
+                },
+            ));
+        }
+        let dag_set = create_dag_set_from_yaml_strs_in_format(
+            &submit_request.dag_yamls,
+            submit_request.dag_format,
+        )?;
+        // The schedulers take the period from the end-to-end deadline if it is missing.
+        if let Some(dag_i) = dag_set.iter().position(|dag| {
+            dag.get_head_period().is_none() && dag.get_end_to_end_deadline().is_none()
+        }) {
+            return Err(ApiError::new(
+                400,
+                format!(
+                    "DAG {} has neither a period nor an end-to-end deadline.",
+                    dag_i
+                ),
+            ));
+        }
+        let number_of_dags = dag_set.len();
+        self.lock().insert(dag_set_id.clone(), Arc::new(dag_set));
+        Ok(ApiResponse::json(
+            201,
+            &DagSetInfo {
+                dag_set_id,
+                number_of_dags,
+            },
+        ))
+    }
+
+    fn schedule(&self, dag_set_id: &str, body: &str) -> std::result::Result<ApiResponse, ApiError> {
+        let schedule_request: ScheduleRequest = parse_body(body)?;
+        if schedule_request.num_hyper_periods <= 0 {
+            return Err(ApiError::new(
+                400,
+                format!(
+                    "The number of hyper periods must be positive: {}",
+                    schedule_request.num_hyper_periods
+                ),
+            ));
+        }
+        if schedule_request.number_of_cores == 0 {
+            return Err(ApiError::new(
+                400,
+                "The number of cores must be positive.".to_string(),
+            ));
+        }
+        let dag_set = self.get(dag_set_id)?;
+        let preemptive_type = match schedule_request.preemptive_key {
+            Some(key) => PreemptiveType::Preemptive { key },
+            None => PreemptiveType::NonPreemptive,
+        };
+        let schedule_outcome = create_scheduler_factory().simulate(
+            &schedule_request.scheduler_name,
+            &dag_set,
+            schedule_request.number_of_cores,
+            preemptive_type,
+            schedule_request.num_hyper_periods,
+        )?;
+        Ok(ApiResponse::json(
+            200,
+            &ScheduleResponse {
+                schedulable: !schedule_outcome.has_deadline_miss(),
+                schedule_length: schedule_outcome.schedule_length,
+                worst_response_times: schedule_outcome.worst_response_times,
+                deadline_miss_flags: schedule_outcome.deadline_miss_flags,
+                log: schedule_request.include_log.then_some(schedule_outcome.log),
+            },
+        ))
+    }
+
+    fn route(
+        &self,
+        method: &Method,
+        url: &str,
+        body: &str,
+    ) -> std::result::Result<ApiResponse, ApiError> {
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            (Method::Get, ["schedulers"]) => Ok(ApiResponse::json(
+                200,
+                &create_scheduler_factory().get_names(),
+            )),
+            (Method::Post, ["dag_sets"]) => self.submit(body),
+            (Method::Get, ["dag_sets", dag_set_id]) => Ok(ApiResponse::json(
+                200,
+                &DagSetInfo {
+                    dag_set_id: dag_set_id.to_string(),
+                    number_of_dags: self.get(dag_set_id)?.len(),
+                },
+            )),
+            (Method::Delete, ["dag_sets", dag_set_id]) => {
+                self.lock().remove(*dag_set_id).ok_or_else(|| {
+                    ApiError::new(404, format!("Unknown DAG set: {}", dag_set_id))
+                })?;
+                Ok(ApiResponse {
+                    status_code: 204,
+                    body: String::new(),
+                })
+            }
+            (Method::Post, ["dag_sets", dag_set_id, "schedule"]) => self.schedule(dag_set_id, body),
+            _ => Err(ApiError::new(
+                404,
+                format!("Unknown request: {} {}", method, path),
+            )),
+        }
+    }
+
+    /// Handle a request, and return its error as the body of the response.
+    pub fn handle_request(&self, method: &Method, url: &str, body: &str) -> ApiResponse {
+        self.route(method, url, body).unwrap_or_else(|err| {
+            ApiResponse::json(err.status_code, &ErrorResponse { error: err.message })
+        })
+    }
+}
+
+/// A request that panics is answered with 500, so that neither the client waits forever
+/// nor the panic aborts the server on the thread pool.
+fn respond(dag_set_cache: &DagSetCache, mut request: Request) {
+    let mut body = String::new();
+    let api_response = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => panic::catch_unwind(AssertUnwindSafe(|| {
+            dag_set_cache.handle_request(request.method(), request.url(), &body)
+        }))
+        .unwrap_or_else(|_| {
+            error!("{} {} panicked", request.method(), request.url());
+            ApiResponse::json(
+                500,
+                &ErrorResponse {
+                    error: "The request panicked.".to_string(),
+                },
+            )
+        }),
+        Err(err) => ApiResponse::json(
+            400,
+            &ErrorResponse {
+                error: format!("Failed to read the request body: {}", err),
+            },
+        ),
+    };
+    info!(
+        "{} {} {}",
+        request.method(),
+        request.url(),
+        api_response.status_code
+    );
+    let response = Response::from_string(api_response.body)
+        .with_status_code(api_response.status_code)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("The header is valid."),
+        );
+    // The client may have gone, which does not affect the other requests.
+    let _ = request.respond(response);
+}
+
+/// Serve the requests until the process is terminated. The requests are handled in parallel
+/// on the thread pool, whose size is given by `--jobs`.
+pub fn run_server(address: &str) -> Result<()> {
+    let server =
+        Server::http(address).map_err(|err| SchedSimError::io(address, io::Error::other(err)))?;
+    info!("sched_sim is serving on {}", address);
+    let dag_set_cache = Arc::new(DagSetCache::default());
+    for request in server.incoming_requests() {
+        let dag_set_cache = Arc::clone(&dag_set_cache);
+        rayon::spawn(move || respond(&dag_set_cache, request));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn create_submit_body() -> String {
+        let dag_yamls: Vec<String> = ["dag_0.yaml", "dag_1.yaml"]
+            .iter()
+            .map(|file_name| {
+                fs::read_to_string(format!(
+                    "../lib/tests/sample_dags/multiple_yaml/{}",
+                    file_name
+                ))
+                .unwrap()
+            })
+            .collect();
+        serde_json::json!({ "dag_yamls": dag_yamls }).to_string()
+    }
+
+    fn parse_response(api_response: &ApiResponse) -> serde_json::Value {
+        serde_json::from_str(&api_response.body).unwrap()
+    }
+
+    #[test]
+    fn test_handle_request_normal() {
+        let dag_set_cache = DagSetCache::default();
+        let submit_response =
+            dag_set_cache.handle_request(&Method::Post, "/dag_sets", &create_submit_body());
+        assert_eq!(submit_response.status_code, 201);
+        let dag_set_id = parse_response(&submit_response)["dag_set_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(parse_response(&submit_response)["number_of_dags"], 2);
+
+        // The same DAG set is returned from the cache.
+        let resubmit_response =
+            dag_set_cache.handle_request(&Method::Post, "/dag_sets", &create_submit_body());
+        assert_eq!(resubmit_response.status_code, 200);
+        assert_eq!(parse_response(&resubmit_response)["dag_set_id"], dag_set_id);
+
+        let schedule_url = format!("/dag_sets/{}/schedule", dag_set_id);
+        let schedule_response = dag_set_cache.handle_request(
+            &Method::Post,
+            &schedule_url,
+            r#"{"scheduler_name": "global_edf", "number_of_cores": 4}"#,
+        );
+        assert_eq!(schedule_response.status_code, 200);
+        let schedule_result = parse_response(&schedule_response);
+        assert_eq!(schedule_result["schedulable"], true);
+        assert_eq!(
+            schedule_result["worst_response_times"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(schedule_result.get("log").is_none());
+
+        let schedule_response = dag_set_cache.handle_request(
+            &Method::Post,
+            &schedule_url,
+            r#"{"scheduler_name": "round_robin", "number_of_cores": 4, "include_log": true}"#,
+        );
+        assert!(parse_response(&schedule_response)["log"]["dag_set_log"].is_array());
+
+        let dag_set_url = format!("/dag_sets/{}", dag_set_id);
+        assert_eq!(
+            dag_set_cache
+                .handle_request(&Method::Delete, &dag_set_url, "")
+                .status_code,
+            204
+        );
+        assert_eq!(
+            dag_set_cache
+                .handle_request(&Method::Get, &dag_set_url, "")
+                .status_code,
+            404
+        );
+    }
+
+    #[test]
+    fn test_handle_request_invalid() {
+        let dag_set_cache = DagSetCache::default();
+        let invalid_yaml_response = dag_set_cache.handle_request(
+            &Method::Post,
+            "/dag_sets",
+            r#"{"dag_yamls": ["nodes: ["]}"#,
+        );
+        assert_eq!(invalid_yaml_response.status_code, 400);
+        assert!(parse_response(&invalid_yaml_response)["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to parse"));
+
+        let submit_response =
+            dag_set_cache.handle_request(&Method::Post, "/dag_sets", &create_submit_body());
+        let dag_set_id = parse_response(&submit_response)["dag_set_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let unknown_scheduler_response = dag_set_cache.handle_request(
+            &Method::Post,
+            &format!("/dag_sets/{}/schedule", dag_set_id),
+            r#"{"scheduler_name": "unknown", "number_of_cores": 4}"#,
+        );
+        assert_eq!(unknown_scheduler_response.status_code, 400);
+        for schedule_body in [
+            r#"{"scheduler_name": "global_edf", "number_of_cores": 4, "num_hyper_periods": 0}"#,
+            r#"{"scheduler_name": "global_edf", "number_of_cores": 0}"#,
+        ] {
+            assert_eq!(
+                dag_set_cache
+                    .handle_request(
+                        &Method::Post,
+                        &format!("/dag_sets/{}/schedule", dag_set_id),
+                        schedule_body,
+                    )
+                    .status_code,
+                400
+            );
+        }
+
+        let no_period_response = dag_set_cache.handle_request(
+            &Method::Post,
+            "/dag_sets",
+            r#"{"dag_yamls": ["nodes: [{id: 0, execution_time: 1}]\nlinks: []"]}"#,
+        );
+        assert_eq!(no_period_response.status_code, 400);
+
+        assert_eq!(
+            dag_set_cache
+                .handle_request(&Method::Put, "/schedulers", "")
+                .status_code,
+            404
+        );
+    }
+
+    #[test]
+    fn test_handle_request_schedulers() {
+        let schedulers_response =
+            DagSetCache::default().handle_request(&Method::Get, "/schedulers", "");
+        assert_eq!(schedulers_response.status_code, 200);
+        assert!(parse_response(&schedulers_response)
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("dynfed")));
+    }
+}
@@ -0,0 +1,323 @@
+//! Sweep of the number of cores and the utilization scale for an algorithm.
+//! Each point runs the algorithm on every DAG set, and its schedulability ratio is the fraction of
+//! the schedulable DAG sets. The points are aggregated into a single YAML or CSV file in output_dir_path,
+//! and the log of each run is written to `<output_dir_path>/<DAG set directory name>/<number of cores>_cores_<utilization scale>_scale`.
+use crate::{
+    experiment::{get_dag_set_name, Algorithm},
+    run_command, CommonArgs, DagSetArgs, SimulationArgs,
+};
+use chrono::{DateTime, Utc};
+use clap::{Args, ValueEnum};
+use lib::{
+    error::{Result, SchedSimError},
+    log::dump_struct,
+    util::create_yaml,
+};
+use rayon::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepFormat {
+    Yaml,
+    Csv,
+}
+
+#[derive(Args, Serialize, Deserialize)]
+pub struct SweepArgs {
+    ///Paths to DAGSet directories, separated by commas. The schedulability ratio of each point is over them.
+    #[clap(
+        short = 'd',
+        long = "dag_dir_paths",
+        required = true,
+        value_delimiter = ','
+    )]
+    dag_dir_paths: Vec<String>,
+    ///Algorithm to run at each point.
+    #[clap(short = 'a', long = "algorithm", required = true)]
+    algorithm: Algorithm,
+    ///Smallest number of cores of the sweep.
+    #[clap(long = "min_cores", alias = "min-cores", required = true)]
+    min_cores: usize,
+    ///Largest number of cores of the sweep.
+    #[clap(long = "max_cores", alias = "max-cores", required = true)]
+    max_cores: usize,
+    ///Step of the number of cores.
+    #[clap(long = "core_step", alias = "core-step", default_value = "1")]
+    core_step: usize,
+    ///Multipliers of the execution times of all nodes, separated by commas.
+    #[clap(
+        long = "utilization_scales",
+        alias = "utilization-scales",
+        default_value = "1.0",
+        value_delimiter = ','
+    )]
+    utilization_scales: Vec<f64>,
+    ///Enable or disable preemptive scheduling. Ignored by the algorithms without a preemptive mode.
+    #[clap(short = 'p', long = "enable_preemption", default_value = "false")]
+    enable_preemption: bool,
+    ///Split the low-utilization DAGs that fit on no remaining core over two cores in federated scheduling.
+    #[clap(short = 's', long = "enable_task_splitting", default_value = "false")]
+    enable_task_splitting: bool,
+    ///Number of hyper periods to simulate.
+    #[clap(short = 'n', long = "num_hyper_periods", default_value = "1")]
+    num_hyper_periods: i64,
+    ///Path to output directory.
+    #[clap(short = 'o', long = "output_dir_path", default_value = "../outputs")]
+    output_dir_path: String,
+    ///Format of the aggregated schedulability results.
+    #[clap(
+        long = "sweep_format",
+        alias = "sweep-format",
+        value_enum,
+        default_value = "yaml"
+    )]
+    sweep_format: SweepFormat,
+    ///Format of the DAG files: base or rd_gen (the output of RD-Gen).
+    #[clap(long = "dag_format", alias = "dag-format", default_value = "base")]
+    dag_format: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SweepPoint {
+    pub number_of_cores: usize,
+    pub utilization_scale: f64,
+    pub number_of_dag_sets: usize,
+    pub number_of_schedulable_dag_sets: usize,
+    pub schedulability_ratio: f64,
+}
+
+#[derive(Serialize)]
+struct SweepResult {
+    algorithm: Algorithm,
+    enable_preemption: bool,
+    sweep_points: Vec<SweepPoint>,
+}
+
+impl SweepArgs {
+    /// Return an error if the sweep has no point.
+    fn validate(&self) -> Result<()> {
+        if self.core_step == 0 || self.min_cores == 0 || self.min_cores > self.max_cores {
+            return Err(SchedSimError::Validation(format!(
+                "The cores of the sweep must satisfy 0 < min_cores <= max_cores and 0 < core_step: {}, {}, {}",
+                self.min_cores, self.max_cores, self.core_step
+            )));
+        }
+        if let Some(utilization_scale) = self
+            .utilization_scales
+            .iter()
+            .find(|utilization_scale| **utilization_scale <= 0.0)
+        {
+            return Err(SchedSimError::Validation(format!(
+                "The utilization scale must be positive: {}",
+                utilization_scale
+            )));
+        }
+        Ok(())
+    }
+
+    fn run_point(&self, number_of_cores: usize, utilization_scale: f64) -> Result<SweepPoint> {
+        let results = self
+            .dag_dir_paths
+            .par_iter()
+            .map(|dag_dir_path| {
+                let common = CommonArgs {
+                    number_of_cores,
+                    output_dir_path: format!(
+                        "{}/{}/{}_cores_{}_scale",
+                        self.output_dir_path,
+                        get_dag_set_name(dag_dir_path),
+                        number_of_cores,
+                        utilization_scale
+                    ),
+                    output_format: "yaml".to_string(),
+                };
+                let simulation = SimulationArgs {
+                    dag_set: DagSetArgs {
+                        dag_dir_path: dag_dir_path.clone(),
+                        utilization_scale,
+                        dag_format: self.dag_format.clone(),
+                    },
+                    num_hyper_periods: self.num_hyper_periods,
+                    warm_up_time: 0,
+                    render_gantt: false,
+                    export_parquet: false,
+                };
+                let command = self.algorithm.create_command(
+                    common,
+                    simulation,
+                    self.enable_preemption,
+                    self.enable_task_splitting,
+                );
+                run_command(&command)
+            })
+            .collect::<Result<Vec<bool>>>()?;
+        let number_of_schedulable_dag_sets = results.iter().filter(|result| **result).count();
+        Ok(SweepPoint {
+            number_of_cores,
+            utilization_scale,
+            number_of_dag_sets: self.dag_dir_paths.len(),
+            number_of_schedulable_dag_sets,
+            schedulability_ratio: number_of_schedulable_dag_sets as f64
+                / self.dag_dir_paths.len() as f64,
+        })
+    }
+}
+
+pub fn convert_sweep_points_to_csv(sweep_points: &[SweepPoint]) -> String {
+    let mut csv = "number_of_cores,utilization_scale,number_of_dag_sets,number_of_schedulable_dag_sets,schedulability_ratio\n".to_string();
+    for sweep_point in sweep_points {
+        csv += &format!(
+            "{},{},{},{},{}\n",
+            sweep_point.number_of_cores,
+            sweep_point.utilization_scale,
+            sweep_point.number_of_dag_sets,
+            sweep_point.number_of_schedulable_dag_sets,
+            sweep_point.schedulability_ratio
+        );
+    }
+    csv
+}
+
+/// Run all the points, which are ordered by the utilization scales and then the numbers of cores,
+/// and return whether every DAG set is schedulable at every point.
+pub fn run_sweep(sweep_args: &SweepArgs) -> Result<bool> {
+    sweep_args.validate()?;
+    let points: Vec<(f64, usize)> = sweep_args
+        .utilization_scales
+        .iter()
+        .flat_map(|utilization_scale| {
+            (sweep_args.min_cores..=sweep_args.max_cores)
+                .step_by(sweep_args.core_step)
+                .map(|number_of_cores| (*utilization_scale, number_of_cores))
+        })
+        .collect();
+    // The points and the DAG sets are independent, so they run in parallel in any order,
+    // and the sweep points are collected in the order of the points.
+    let sweep_points = points
+        .par_iter()
+        .map(|(utilization_scale, number_of_cores)| {
+            sweep_args.run_point(*number_of_cores, *utilization_scale)
+        })
+        .collect::<Result<Vec<SweepPoint>>>()?;
+    let is_all_schedulable = sweep_points.iter().all(|sweep_point| {
+        sweep_point.number_of_schedulable_dag_sets == sweep_point.number_of_dag_sets
+    });
+
+    let now: DateTime<Utc> = Utc::now();
+    let file_name = format!(
+        "{}-{}-sweep",
+        now.format("%Y-%m-%d-%H-%M-%S-%3f"),
+        sweep_args
+            .algorithm
+            .to_possible_value()
+            .expect("Every algorithm has a name.")
+            .get_name()
+    );
+    match sweep_args.sweep_format {
+        SweepFormat::Yaml => {
+            let file_path = create_yaml(&sweep_args.output_dir_path, &file_name);
+            dump_struct(
+                &file_path,
+                &SweepResult {
+                    algorithm: sweep_args.algorithm,
+                    enable_preemption: sweep_args.enable_preemption,
+                    sweep_points,
+                },
+            );
+        }
+        SweepFormat::Csv => {
+            fs::create_dir_all(&sweep_args.output_dir_path)
+                .map_err(|err| SchedSimError::io(&sweep_args.output_dir_path, err))?;
+            let file_path = format!("{}/{}.csv", sweep_args.output_dir_path, file_name);
+            fs::write(&file_path, convert_sweep_points_to_csv(&sweep_points))
+                .map_err(|err| SchedSimError::io(&file_path, err))?;
+        }
+    }
+    Ok(is_all_schedulable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestArgParser {
+        #[command(flatten)]
+        sweep: SweepArgs,
+    }
+
+    #[test]
+    fn test_run_sweep_normal() {
+        let output_dir_path = "../lib/tests/sweep_test";
+        let arg = TestArgParser::parse_from([
+            "sweep",
+            "-d",
+            "../lib/tests/sample_dags/multiple_yaml",
+            "-a",
+            "global_edf",
+            "--min_cores",
+            "1",
+            "--max_cores",
+            "3",
+            "--core_step",
+            "2",
+            "--utilization_scales",
+            "1.0,2.0",
+            "-o",
+            output_dir_path,
+            "--sweep_format",
+            "csv",
+        ]);
+        assert!(run_sweep(&arg.sweep).unwrap());
+
+        let csv_file_path = fs::read_dir(output_dir_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.extension().is_some_and(|extension| extension == "csv"))
+            .unwrap();
+        let csv = fs::read_to_string(csv_file_path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "1,1,1,1,1");
+        assert_eq!(lines[4], "3,2,1,1,1");
+
+        fs::remove_dir_all(output_dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_run_sweep_no_point() {
+        let arg = TestArgParser::parse_from([
+            "sweep",
+            "-d",
+            "../lib/tests/sample_dags/multiple_yaml",
+            "-a",
+            "dynfed",
+            "--min_cores",
+            "4",
+            "--max_cores",
+            "2",
+        ]);
+        assert!(matches!(
+            run_sweep(&arg.sweep),
+            Err(SchedSimError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_convert_sweep_points_to_csv_normal() {
+        let sweep_points = vec![SweepPoint {
+            number_of_cores: 2,
+            utilization_scale: 1.5,
+            number_of_dag_sets: 4,
+            number_of_schedulable_dag_sets: 1,
+            schedulability_ratio: 0.25,
+        }];
+        assert_eq!(
+            convert_sweep_points_to_csv(&sweep_points),
+            "number_of_cores,utilization_scale,number_of_dag_sets,number_of_schedulable_dag_sets,schedulability_ratio\n2,1.5,4,1,0.25\n"
+        );
+    }
+}
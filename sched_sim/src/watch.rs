@@ -0,0 +1,213 @@
+//! Watch mode, which re-runs a command whenever the DAG files of its input change,
+//! e.g., to hand-tune the execution times and the periods of a DAG set to a target utilization.
+//! The files are polled by their modification times and sizes rather than by file system events,
+//! so that it works on every platform and on network file systems.
+//! Each run appends a row of its time, the total utilization of the DAG set and its result
+//! to `<output_dir_path>/watch-summary.csv`, so that the runs of a tuning session are compared at a glance.
+use chrono::{DateTime, Utc};
+use lib::{
+    error::{Result, SchedSimError},
+    graph_extension::{GraphExtension, NodeData},
+};
+use petgraph::Graph;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const SUMMARY_FILE_NAME: &str = "watch-summary.csv";
+const SUMMARY_HEADER: &str = "time,total_utilization,result\n";
+
+/// The outcome of a run of the watched command.
+pub struct WatchedRun {
+    pub total_utilization: f64,
+    pub is_schedulable: bool,
+}
+
+/// The modification time and the size of each YAML file of the input, ordered by the paths.
+/// A file that disappears while it is saved has no metadata, which is also a change.
+type Fingerprint = Vec<(String, Option<(SystemTime, u64)>)>;
+
+fn get_fingerprint(input_path: &str) -> Fingerprint {
+    let mut file_paths = if Path::new(input_path).is_dir() {
+        fs::read_dir(input_path)
+            .map(|dir_entries| {
+                dir_entries
+                    .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+                    .filter(|path| {
+                        path.extension()
+                            .is_some_and(|extension| extension == "yaml" || extension == "yml")
+                    })
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        vec![input_path.to_string()]
+    };
+    file_paths.sort();
+    file_paths
+        .into_iter()
+        .map(|file_path| {
+            let metadata = fs::metadata(&file_path)
+                .ok()
+                .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            (file_path, metadata)
+        })
+        .collect()
+}
+
+/// Block until the input changes and then stays unchanged for a poll interval,
+/// because an editor may write a file in several steps.
+fn wait_for_change(input_path: &str, fingerprint: &Fingerprint) -> Fingerprint {
+    let mut current_fingerprint = fingerprint.clone();
+    while current_fingerprint == *fingerprint {
+        thread::sleep(POLL_INTERVAL);
+        current_fingerprint = get_fingerprint(input_path);
+    }
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let next_fingerprint = get_fingerprint(input_path);
+        if next_fingerprint == current_fingerprint {
+            return current_fingerprint;
+        }
+        current_fingerprint = next_fingerprint;
+    }
+}
+
+/// The sum of the utilizations of the DAGs. The period of a DAG without one is its end-to-end deadline,
+/// as the binaries consider, and a DAG with neither is not counted.
+pub fn get_total_utilization(dag_set: &[Graph<NodeData, i64>]) -> f64 {
+    dag_set
+        .iter()
+        .filter_map(|dag| {
+            let period = dag
+                .get_head_period()
+                .or_else(|| dag.get_end_to_end_deadline())?;
+            Some(dag.get_volume() as f64 / period as f64)
+        })
+        .sum()
+}
+
+/// The row of a run. A failed run, e.g., of a DAG file saved in the middle of an edit, is recorded as an error.
+fn convert_watched_run_to_csv_row(time: DateTime<Utc>, watched_run: &Result<WatchedRun>) -> String {
+    let time = time.format("%Y-%m-%d %H:%M:%S");
+    match watched_run {
+        Ok(watched_run) => format!(
+            "{},{:.4},{}\n",
+            time,
+            watched_run.total_utilization,
+            if watched_run.is_schedulable {
+                "schedulable"
+            } else {
+                "unschedulable"
+            }
+        ),
+        Err(_) => format!("{},,error\n", time),
+    }
+}
+
+fn append_to_summary(summary_file_path: &str, row: &str) -> Result<()> {
+    let is_new = !Path::new(summary_file_path).exists();
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(summary_file_path)
+        .map_err(|err| SchedSimError::io(summary_file_path, err))?;
+    let content = if is_new {
+        format!("{}{}", SUMMARY_HEADER, row)
+    } else {
+        row.to_string()
+    };
+    file.write_all(content.as_bytes())
+        .map_err(|err| SchedSimError::io(summary_file_path, err))
+}
+
+/// Run `run` now and whenever the files of `input_path`, a DAG file or a DAG set directory, change,
+/// until the process is terminated. A failed run is reported and waits for the next change.
+pub fn watch(
+    input_path: &str,
+    output_dir_path: &str,
+    mut run: impl FnMut() -> Result<WatchedRun>,
+) -> Result<()> {
+    fs::create_dir_all(output_dir_path).map_err(|err| SchedSimError::io(output_dir_path, err))?;
+    let summary_file_path = format!("{}/{}", output_dir_path, SUMMARY_FILE_NAME);
+    let mut fingerprint = get_fingerprint(input_path);
+    loop {
+        let watched_run = run();
+        if let Err(err) = &watched_run {
+            eprintln!("{}", err);
+        }
+        let row = convert_watched_run_to_csv_row(Utc::now(), &watched_run);
+        eprint!("{}", row);
+        append_to_summary(&summary_file_path, &row)?;
+        fingerprint = wait_for_change(input_path, &fingerprint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib::dag_creator::create_dag_from_yaml_str;
+
+    #[test]
+    fn test_get_fingerprint_changed() {
+        let dir_path = "../outputs/watch_fingerprint_test";
+        fs::create_dir_all(dir_path).unwrap();
+        let file_path = format!("{}/dag_0.yaml", dir_path);
+        fs::write(&file_path, "nodes: []\n").unwrap();
+        fs::write(format!("{}/notes.txt", dir_path), "").unwrap();
+        let fingerprint = get_fingerprint(dir_path);
+        assert_eq!(fingerprint.len(), 1);
+        assert_eq!(get_fingerprint(dir_path), fingerprint);
+
+        fs::write(&file_path, "nodes: [{id: 0}]\n").unwrap();
+        assert_ne!(get_fingerprint(dir_path), fingerprint);
+        fs::remove_file(&file_path).unwrap();
+        assert!(get_fingerprint(dir_path).is_empty());
+
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+
+    #[test]
+    fn test_get_total_utilization_normal() {
+        let dag_set = [
+            "nodes: [{id: 0, execution_time: 10, period: 100}, {id: 1, execution_time: 15}]\nlinks: [{source: 0, target: 1}]",
+            "nodes: [{id: 0, execution_time: 10}, {id: 1, execution_time: 10, end_to_end_deadline: 40}]\nlinks: [{source: 0, target: 1}]",
+            "nodes: [{id: 0, execution_time: 10}]\nlinks: []",
+        ]
+        .map(|yaml_str| create_dag_from_yaml_str(yaml_str, false).unwrap());
+        assert_eq!(get_total_utilization(&dag_set), 0.75);
+    }
+
+    #[test]
+    fn test_append_to_summary_normal() {
+        let dir_path = "../outputs/watch_summary_test";
+        fs::create_dir_all(dir_path).unwrap();
+        let summary_file_path = format!("{}/{}", dir_path, SUMMARY_FILE_NAME);
+        let time = DateTime::from_timestamp(0, 0).unwrap();
+        for watched_run in [
+            Ok(WatchedRun {
+                total_utilization: 0.5,
+                is_schedulable: true,
+            }),
+            Err(SchedSimError::Validation("invalid".to_string())),
+        ] {
+            append_to_summary(
+                &summary_file_path,
+                &convert_watched_run_to_csv_row(time, &watched_run),
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(&summary_file_path).unwrap(),
+            "time,total_utilization,result\n1970-01-01 00:00:00,0.5000,schedulable\n1970-01-01 00:00:00,,error\n"
+        );
+
+        fs::remove_dir_all(dir_path).unwrap();
+    }
+}